@@ -1,4 +1,7 @@
-use clap::{Parser, Subcommand};
+use crate::confirm::ConfirmPolicy;
+use crate::crypto::KdfAlgorithm;
+use crate::storage::BackendKind;
+use clap::{Parser, Subcommand, ValueEnum};
 
 #[derive(Parser)]
 #[command(
@@ -10,6 +13,38 @@ use clap::{Parser, Subcommand};
 pub struct CliArgs {
     #[command(subcommand)]
     pub command: Option<Commands>,
+
+    /// Confirmation policy for destructive operations (overrides config)
+    #[arg(long, global = true, value_enum)]
+    pub confirm_destructive: Option<ConfirmPolicy>,
+
+    /// Assume "yes" for destructive operation confirmations (for scripts; the master password is still required)
+    #[arg(short = 'y', long, global = true, default_value_t = false)]
+    pub yes: bool,
+
+    /// Warn on unlock if the vault hasn't been modified in this many days (overrides config)
+    #[arg(long, global = true, value_name = "DAYS")]
+    pub max_age: Option<u32>,
+
+    /// Storage backend for the vault (overrides config)
+    #[arg(long, global = true, value_enum)]
+    pub backend: Option<BackendKind>,
+
+    /// Named vault to use, each with its own master password (default: "default")
+    #[arg(long, global = true, value_name = "NAME")]
+    pub vault: Option<String>,
+
+    /// Fsync and read back the database file after every save (also enabled by config)
+    #[arg(long, global = true, default_value_t = false)]
+    pub safe_save: bool,
+
+    /// Print machine-readable JSON instead of decorated text (list, get, import, export --diff, errors)
+    #[arg(long, global = true, default_value_t = false)]
+    pub json: bool,
+
+    /// Print how long key derivation, database load/save and encryption batches take (stderr)
+    #[arg(long, global = true, default_value_t = false)]
+    pub verbose: bool,
 }
 
 #[derive(Subcommand)]
@@ -19,6 +54,26 @@ pub enum Commands {
         /// Master password
         #[arg(short, long)]
         master: Option<String>,
+
+        /// Populate the new vault from an encrypted .ik backup
+        #[arg(long, value_name = "FILE")]
+        import: Option<std::path::PathBuf>,
+
+        /// Password of the .ik backup (prompted if omitted)
+        #[arg(long, requires = "import")]
+        import_password: Option<String>,
+
+        /// Record this machine in the vault and warn when it's opened elsewhere
+        #[arg(long, default_value_t = false)]
+        bind_machine: bool,
+
+        /// Key derivation function for the new master key (argon2 = Argon2id, 64 MiB, 3 passes; default: pbkdf2, or kdf in config)
+        #[arg(long, value_enum)]
+        kdf: Option<KdfAlgorithm>,
+
+        /// PBKDF2 iterations for the new master key (at least 100000, the default)
+        #[arg(long, value_name = "N")]
+        iterations: Option<u32>,
     },
 
     /// Creates a new entry
@@ -30,6 +85,18 @@ pub enum Commands {
         /// Value for the entry (if not provided, will prompt securely)
         #[arg(short, long)]
         value: Option<String>,
+
+        /// Read the value from stdin (one trailing newline is dropped)
+        #[arg(long, default_value_t = false, conflicts_with = "value")]
+        stdin: bool,
+
+        /// Fail if the entry already exists (default)
+        #[arg(long, default_value_t = false, conflicts_with = "ignore_if_exists")]
+        fail_if_exists: bool,
+
+        /// Do nothing if the entry already exists
+        #[arg(long, default_value_t = false, conflicts_with = "fail_if_exists")]
+        ignore_if_exists: bool,
     },
 
     /// Gets an entry by name
@@ -46,9 +113,98 @@ pub enum Commands {
         #[arg(long, default_value_t = false)]
         no_clear: bool,
 
-        /// Timeout in seconds before auto-clearing clipboard (default: 30)
-        #[arg(short, long, default_value_t = 30)]
-        timeout: u64,
+        /// Timeout in seconds before auto-clearing clipboard (default: 30, or clipboard_timeout in config)
+        #[arg(short, long)]
+        timeout: Option<u64>,
+
+        /// Stop waiting as soon as the clipboard holds something else (still clears on --timeout)
+        #[arg(long, default_value_t = false, requires = "copy", conflicts_with_all = ["no_clear", "then_run"])]
+        clear_on_change: bool,
+
+        /// Run a command after copying and clear the clipboard when it exits (requires --copy)
+        #[arg(
+            long,
+            num_args = 1..,
+            value_name = "CMD",
+            allow_hyphen_values = true,
+            requires = "copy",
+            conflicts_with = "no_clear"
+        )]
+        then_run: Vec<String>,
+
+        /// Which field to show (`label` is readable even when the entry is locked)
+        #[arg(long, value_enum, default_value_t = EntryField::Value, conflicts_with = "copy")]
+        field: EntryField,
+
+        /// Read a locked entry once after re-entering the master password (it stays locked)
+        #[arg(long, default_value_t = false)]
+        unlock_once: bool,
+
+        /// Show the value as a QR code (on the alternate screen, never printed as text)
+        #[arg(long, default_value_t = false, conflicts_with_all = ["copy", "field"])]
+        qr: bool,
+
+        /// Print only the value, without the "Value:" prefix or a trailing newline (for piping)
+        #[arg(short = 'n', long, default_value_t = false, conflicts_with_all = ["copy", "qr", "field"])]
+        no_newline: bool,
+
+        /// Also set the X11/Wayland primary selection (middle-click paste; Linux and BSD only)
+        #[arg(long, default_value_t = false, requires = "copy")]
+        primary: bool,
+
+        /// Copy through the terminal with OSC 52 (automatic over SSH without a forwarded display)
+        #[arg(long, default_value_t = false, requires = "copy", conflicts_with_all = ["primary", "then_run"])]
+        osc52: bool,
+    },
+
+    /// Copies an entry's value to the clipboard without printing it
+    Copy {
+        /// Entry name
+        #[arg(short, long)]
+        key: String,
+
+        /// Timeout in seconds before auto-clearing clipboard (default: 30, or clipboard_timeout in config)
+        #[arg(short, long)]
+        timeout: Option<u64>,
+
+        /// Also set the X11/Wayland primary selection (middle-click paste; Linux and BSD only)
+        #[arg(long, default_value_t = false)]
+        primary: bool,
+
+        /// Copy through the terminal with OSC 52 (automatic over SSH without a forwarded display)
+        #[arg(long, default_value_t = false, conflicts_with = "primary")]
+        osc52: bool,
+    },
+
+    /// Clears the clipboard now (no master password needed)
+    ClearClipboard,
+
+    /// Prints the current TOTP code of an entry holding a base32 secret or otpauth:// URI
+    Totp {
+        /// Entry name
+        #[arg(short, long)]
+        key: String,
+
+        /// Copy the code to the clipboard instead of displaying it
+        #[arg(short, long, default_value_t = false)]
+        copy: bool,
+
+        /// Timeout in seconds before auto-clearing clipboard (default: 30, or clipboard_timeout in config)
+        #[arg(short, long, requires = "copy")]
+        timeout: Option<u64>,
+
+        /// Also set the X11/Wayland primary selection (middle-click paste; Linux and BSD only)
+        #[arg(long, default_value_t = false, requires = "copy")]
+        primary: bool,
+
+        /// Copy through the terminal with OSC 52 (automatic over SSH without a forwarded display)
+        #[arg(
+            long,
+            default_value_t = false,
+            requires = "copy",
+            conflicts_with = "primary"
+        )]
+        osc52: bool,
     },
 
     /// Updates an existing entry
@@ -60,14 +216,34 @@ pub enum Commands {
         /// New value for the entry (if not provided, will prompt securely)
         #[arg(short, long)]
         value: Option<String>,
+
+        /// Read the new value from stdin (one trailing newline is dropped)
+        #[arg(long, default_value_t = false, conflicts_with = "value")]
+        stdin: bool,
+
+        /// Fail if the entry doesn't exist (default)
+        #[arg(long, default_value_t = false, conflicts_with = "create_if_missing")]
+        fail_if_missing: bool,
+
+        /// Create the entry if it doesn't exist (upsert)
+        #[arg(long, default_value_t = false, conflicts_with = "fail_if_missing")]
+        create_if_missing: bool,
     },
 
     /// List all entries with optional search and filter
     List {
         /// Search for entries by name (case-insensitive, partial match)
-        #[arg(short, long)]
+        #[arg(short, long, conflicts_with_all = ["glob", "regex"])]
         search: Option<String>,
 
+        /// Show entries whose whole key matches a glob (e.g. 'aws/*/prod'; `*` stops at `/`)
+        #[arg(long, value_name = "PATTERN", conflicts_with = "regex")]
+        glob: Option<String>,
+
+        /// Show entries whose key matches a regular expression (e.g. '^aws/.*/prod$')
+        #[arg(long, value_name = "PATTERN")]
+        regex: Option<String>,
+
         /// Show only locked entries
         #[arg(long, conflicts_with = "unlocked")]
         locked: bool,
@@ -75,27 +251,223 @@ pub enum Commands {
         /// Show only unlocked entries
         #[arg(long, conflicts_with = "locked")]
         unlocked: bool,
+
+        /// Show only entries with this tag
+        #[arg(short, long)]
+        tag: Option<String>,
+
+        /// Sort order (key: alphabetical, accessed: most accessed first)
+        #[arg(long, value_enum, default_value_t = ListSort::Key)]
+        sort: ListSort,
+
+        /// Show metadata columns (like `ls -l`)
+        #[arg(short = 'l', long, default_value_t = false)]
+        long: bool,
+
+        /// Include a masked value preview in long mode (unlocked entries only)
+        #[arg(long, default_value_t = false, requires = "long")]
+        preview: bool,
+
+        /// Print only the number of matching entries (for scripts)
+        #[arg(long, default_value_t = false, conflicts_with_all = ["long", "watch"])]
+        count_only: bool,
+
+        /// Keep the list open and re-render whenever the database changes
+        #[arg(short, long, default_value_t = false)]
+        watch: bool,
+
+        /// Seconds before --watch locks the vault and exits (default: 300)
+        #[arg(
+            long,
+            value_name = "SECONDS",
+            default_value_t = 300,
+            requires = "watch"
+        )]
+        lock_after: u64,
+    },
+
+    /// Find entries whose value contains some text (prints keys only, never values)
+    Search {
+        /// Text to look for in the decrypted values
+        #[arg(short, long)]
+        value: String,
+
+        /// Match regardless of case
+        #[arg(short, long, default_value_t = false)]
+        ignore_case: bool,
+    },
+
+    /// Show vault statistics (entry counts, most accessed entries)
+    Stats,
+
+    /// Rewrite the database file with sorted keys (for reproducible diffs)
+    Normalize,
+
+    /// Restore a corrupted database from its backup (.bak) or staging (.tmp) copy
+    RecoverDb,
+
+    /// Check that every entry still decrypts (lists damaged keys, never values)
+    Doctor,
+
+    /// Audit the vault for weak practices (never prints values)
+    Audit {
+        /// Report entries that share the same value (the default check)
+        #[arg(long, default_value_t = false)]
+        duplicates: bool,
+    },
+
+    /// Restore the database from one of its rotating backups (.bak.1 is the newest)
+    Restore {
+        /// Backup number (1 = state before the last save)
+        #[arg(long, value_name = "N")]
+        backup: usize,
+    },
+
+    /// Permanently delete the vault (e.g. a broken one that blocks `ik init`)
+    Reset {
+        /// Required: acknowledge that every entry will be lost
+        #[arg(long, default_value_t = false)]
+        force: bool,
+    },
+
+    /// Lists or deletes named vaults (select one with --vault)
+    Vault {
+        #[command(subcommand)]
+        action: VaultCommand,
+    },
+
+    /// Changes the master password, re-encrypting every entry
+    ChangeMaster {
+        /// Only report what would be re-encrypted and how long it should take
+        #[arg(long, default_value_t = false)]
+        plan: bool,
     },
 
     /// Deletes an entry
     Delete {
+        /// Entry name
+        #[arg(
+            short,
+            long,
+            required_unless_present = "search",
+            conflicts_with = "search"
+        )]
+        key: Option<String>,
+
+        /// Delete every unlocked entry whose key contains this text (case-insensitive; requires --yes)
+        #[arg(long, value_name = "PATTERN")]
+        search: Option<String>,
+    },
+
+    /// Locks or unlocks an entry (requires master password to unlock)
+    Lock {
         /// Entry name
         #[arg(short, long)]
         key: String,
+
+        /// Lock for a period (e.g. 90s, 30m, 1h, 2d), after which the entry unlocks by itself
+        #[arg(long, value_name = "DURATION", value_parser = parse_lock_duration)]
+        timer: Option<chrono::Duration>,
+
+        /// Also require a lock passphrase (prompted) to unlock the entry
+        #[arg(long, default_value_t = false, conflicts_with = "timer")]
+        password: bool,
     },
 
-    /// Locks an entry (requires master password to unlock)
-    Lock {
+    /// Renames an entry (--from) or every entry whose key starts with a prefix (--prefix)
+    Rename {
+        /// Entry to rename (works on locked entries; they stay locked)
+        #[arg(long, required_unless_present = "prefix", conflicts_with = "prefix")]
+        from: Option<String>,
+
+        /// Prefix to replace (e.g. old_project_)
+        #[arg(long)]
+        prefix: Option<String>,
+
+        /// New key, or replacement prefix with --prefix (e.g. new_project_)
+        #[arg(long)]
+        to: String,
+    },
+
+    /// Shows an entry's metadata: lock status, label, reads and when it was created and last updated
+    Info {
         /// Entry name
         #[arg(short, long)]
         key: String,
     },
 
+    /// Lists an entry's previous values (masked unless --reveal)
+    History {
+        /// Entry name
+        #[arg(short, long)]
+        key: String,
+
+        /// Show the previous values in full
+        #[arg(long, default_value_t = false)]
+        reveal: bool,
+    },
+
+    /// Restores a previous value from an entry's history
+    Revert {
+        /// Entry name
+        #[arg(short, long)]
+        key: String,
+
+        /// Version to restore, as numbered by `ik history` (1 = most recent)
+        #[arg(long)]
+        version: usize,
+    },
+
+    /// Shows, sets or clears an entry's plaintext label (works on locked entries)
+    Label {
+        /// Entry name
+        #[arg(short, long)]
+        key: String,
+
+        /// New label text (omit to show the current label)
+        #[arg(short, long)]
+        text: Option<String>,
+
+        /// Remove the label
+        #[arg(long, default_value_t = false, conflicts_with = "text")]
+        clear: bool,
+    },
+
+    /// Shows, adds or removes an entry's tags (works on locked entries)
+    Tag {
+        /// Entry name
+        #[arg(short, long)]
+        key: String,
+
+        /// Tag to add
+        #[arg(short, long, conflicts_with = "remove")]
+        add: Option<String>,
+
+        /// Tag to remove
+        #[arg(short, long)]
+        remove: Option<String>,
+    },
+
+    /// Shows or sets an entry's encrypted notes
+    Note {
+        /// Entry name
+        #[arg(short, long)]
+        key: String,
+
+        /// New notes text (an empty string removes the notes)
+        #[arg(long, conflicts_with = "get", required_unless_present = "get")]
+        set: Option<String>,
+
+        /// Show the current notes
+        #[arg(long, default_value_t = false)]
+        get: bool,
+    },
+
     /// Generates a random secure password
     Generate {
-        /// Length of password (default: 16)
-        #[arg(short, long, default_value_t = 16)]
-        length: usize,
+        /// Length of password (default: 16, or default_password_length in config)
+        #[arg(short, long)]
+        length: Option<usize>,
 
         /// Exclude lowercase letters (a-z)
         #[arg(long, default_value_t = false)]
@@ -113,13 +485,46 @@ pub enum Commands {
         #[arg(long, default_value_t = false)]
         no_symbols: bool,
 
+        /// Pronounceable consonant/vowel syllables plus digits (easier to remember, weaker)
+        #[arg(long, default_value_t = false, conflicts_with_all = ["no_lowercase", "no_uppercase", "no_symbols"])]
+        pronounceable: bool,
+
+        /// Leave out easily confused characters (l1IO0oB8S5Z2)
+        #[arg(long, default_value_t = false, conflicts_with = "pronounceable")]
+        exclude_ambiguous: bool,
+
+        /// Symbols to use instead of the built-in set (printable ASCII, e.g. '!@#$%')
+        #[arg(long, value_name = "SET", conflicts_with_all = ["no_symbols", "pronounceable"])]
+        symbols: Option<String>,
+
+        /// Allow passwords that miss a selected character type (by default each appears at least once)
+        #[arg(long, default_value_t = false, conflicts_with = "pronounceable")]
+        no_enforce_classes: bool,
+
         /// Copies to clipboard instead of displaying
         #[arg(short, long, default_value_t = false)]
         copy: bool,
 
+        /// Also set the X11/Wayland primary selection (middle-click paste; Linux and BSD only)
+        #[arg(long, default_value_t = false, requires = "copy")]
+        primary: bool,
+
+        /// Copy through the terminal with OSC 52 (automatic over SSH without a forwarded display)
+        #[arg(
+            long,
+            default_value_t = false,
+            requires = "copy",
+            conflicts_with = "primary"
+        )]
+        osc52: bool,
+
         /// Saves to vault with this key name
         #[arg(short, long)]
         key: Option<String>,
+
+        /// Replace the value if --key already exists (rotates the credential)
+        #[arg(long, default_value_t = false, requires = "key")]
+        rotate: bool,
     },
 
     /// Export vault to encrypted .ik file
@@ -139,6 +544,50 @@ pub enum Commands {
         /// List all available exports in default folder
         #[arg(short, long, default_value_t = false)]
         list: bool,
+
+        /// Print the export as a single base64 line on stdout (for text-only transport)
+        #[arg(long, default_value_t = false, conflicts_with_all = ["output", "name", "list", "force"])]
+        stdout_base64: bool,
+
+        /// Store identical values once to shrink the export
+        #[arg(long, default_value_t = false, conflicts_with_all = ["list", "stdout_base64"])]
+        dedup: bool,
+
+        /// Gzip the entries before encrypting them (smaller files; needs this ironkey version to import)
+        #[arg(long, default_value_t = false, conflicts_with_all = ["list", "stdout_base64"])]
+        compress: bool,
+
+        /// Encrypt the export with the master password instead of a separate export password
+        #[arg(long, visible_alias = "encrypt-to-master", default_value_t = false, conflicts_with_all = ["list", "stdout_base64"])]
+        same_password: bool,
+
+        /// Omit the ironkey version and export time from the file
+        #[arg(long, visible_alias = "no-metadata", default_value_t = false, conflicts_with_all = ["list", "stdout_base64"])]
+        minimal: bool,
+
+        /// Only export this entry (repeat for several)
+        #[arg(short, long = "key", value_name = "NAME", conflicts_with_all = ["list", "stdout_base64"])]
+        keys: Vec<String>,
+
+        /// Show which entries would be exported without writing a file (dry-run)
+        #[arg(short, long, default_value_t = false, conflicts_with_all = ["list", "stdout_base64", "to_dir", "format", "tar"])]
+        diff: bool,
+
+        /// Write each unlocked entry's value to its own file in DIR, plus a lock-status manifest.json (unencrypted)
+        #[arg(long, value_name = "DIR", conflicts_with_all = ["output", "name", "list", "stdout_base64", "dedup", "compress", "same_password", "minimal", "keys"])]
+        to_dir: Option<std::path::PathBuf>,
+
+        /// Also pack the --to-dir output into `<DIR>.tar.gz`
+        #[arg(long, default_value_t = false)]
+        tar: bool,
+
+        /// Output format (toml/yaml/csv/dir write unencrypted secrets; csv includes locked entries)
+        #[arg(long, value_enum, default_value_t = ExportFormat::Ik, conflicts_with_all = ["list", "stdout_base64", "dedup", "compress", "same_password", "minimal", "to_dir", "keys"])]
+        format: ExportFormat,
+
+        /// Acknowledge that --to-dir or --format toml/yaml/csv/dir write unencrypted secrets to disk
+        #[arg(long, alias = "i-understand-plaintext", default_value_t = false)]
+        plaintext: bool,
     },
 
     /// Import vault from encrypted .ik file
@@ -159,8 +608,186 @@ pub enum Commands {
         #[arg(short, long, conflicts_with = "merge")]
         replace: bool,
 
+        /// Mirror: Replace, then delete entries missing from the backup (exact snapshot restore)
+        #[arg(long, conflicts_with_all = ["merge", "replace", "stdin_base64"])]
+        mirror: bool,
+
+        /// Only import this entry from the .ik file (repeat for several)
+        #[arg(short, long = "key", value_name = "NAME", conflicts_with_all = ["mirror", "stdin_base64"])]
+        keys: Vec<String>,
+
+        /// Show what would be imported without applying changes (dry-run)
+        #[arg(short, long, default_value_t = false)]
+        diff: bool,
+
+        /// Read a base64 export (from `ik export --stdout-base64`) from stdin
+        #[arg(long, default_value_t = false, conflicts_with_all = ["input", "name"])]
+        stdin_base64: bool,
+
+        /// Input format (json-plain reads an unencrypted `[{key, value, locked}]` array, csv a `key,value[,locked]` table)
+        #[arg(long, value_enum, default_value_t = ImportFormat::Ik)]
+        format: ImportFormat,
+
+        /// Acknowledge that the input file contains unencrypted secrets (required for json-plain and csv)
+        #[arg(long, alias = "i-understand-plaintext", default_value_t = false)]
+        plaintext: bool,
+    },
+
+    /// Import entries from a plaintext dotenv-style KEY=VALUE file
+    ImportEnv {
+        /// Path to the .env file
+        #[arg(short, long)]
+        input: std::path::PathBuf,
+
+        /// Merge: Add new entries, skip existing (default)
+        #[arg(short, long, conflicts_with = "replace")]
+        merge: bool,
+
+        /// Replace: Overwrite existing entries with the file's values
+        #[arg(short, long, conflicts_with = "merge")]
+        replace: bool,
+
         /// Show what would be imported without applying changes (dry-run)
         #[arg(short, long, default_value_t = false)]
         diff: bool,
+
+        /// Acknowledge that the input file contains unencrypted secrets (required)
+        #[arg(long, alias = "i-understand-plaintext", default_value_t = false)]
+        plaintext: bool,
     },
+
+    /// Unlock once and run several commands at an `ik>` prompt (exit or Ctrl-D to leave)
+    Shell {
+        /// Seconds of inactivity before the shell locks and asks for the master password again
+        #[arg(long, value_name = "SECONDS", default_value_t = 300)]
+        lock_after: u64,
+    },
+}
+
+impl Commands {
+    /// Whether the command line carries a secret (e.g. `create KEY VALUE`) and must stay out of shell history
+    pub fn has_secret_argument(&self) -> bool {
+        match self {
+            Commands::Create { value, .. } | Commands::Update { value, .. } => value.is_some(),
+            Commands::Note { set, .. } => set.is_some(),
+            // Part of a value is still a secret
+            Commands::Search { .. } => true,
+            Commands::Init { master, .. } => master.is_some(),
+            _ => false,
+        }
+    }
+
+    /// Whether the command can run inside `ik shell` against the already-unlocked vault
+    pub fn runs_in_shell(&self) -> bool {
+        matches!(
+            self,
+            Commands::Create { .. }
+                | Commands::Get { .. }
+                | Commands::Copy { .. }
+                | Commands::ClearClipboard
+                | Commands::Totp { .. }
+                | Commands::Update { .. }
+                | Commands::List { .. }
+                | Commands::Search { .. }
+                | Commands::Stats
+                | Commands::Audit { .. }
+                | Commands::Delete { .. }
+                | Commands::Lock { .. }
+                | Commands::Rename { .. }
+                | Commands::Info { .. }
+                | Commands::History { .. }
+                | Commands::Revert { .. }
+                | Commands::Label { .. }
+                | Commands::Tag { .. }
+                | Commands::Note { .. }
+                | Commands::Generate { .. }
+        )
+    }
+}
+
+/// Subcommands of `ik vault`
+#[derive(Subcommand)]
+pub enum VaultCommand {
+    /// Lists the vaults that have a database (the selected one is marked)
+    List,
+
+    /// Permanently deletes a named vault, its backups and every entry in it
+    Delete {
+        /// Vault name
+        name: String,
+    },
+}
+
+/// One line typed at the `ik shell` prompt (a subcommand without the `ik` prefix)
+#[derive(Parser)]
+#[command(name = "ik", no_binary_name = true, disable_version_flag = true)]
+pub struct ShellLine {
+    #[command(subcommand)]
+    pub command: Commands,
+}
+
+/// Entry field shown by `ik get --field`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum EntryField {
+    /// Decrypted secret value
+    Value,
+    /// Plaintext label
+    Label,
+}
+
+/// Output format for `ik export`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ExportFormat {
+    /// Encrypted .ik export file
+    Ik,
+    /// Plaintext TOML table
+    Toml,
+    /// Plaintext YAML map
+    Yaml,
+    /// Plaintext `key,value,locked` CSV (locked entries included)
+    Csv,
+    /// Same as --to-dir, writing into the --output directory
+    Dir,
+}
+
+/// Input format for `ik import`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ImportFormat {
+    /// Encrypted .ik export file
+    Ik,
+    /// Plaintext JSON array of entries
+    JsonPlain,
+    /// Plaintext CSV with `key`, `value` and optional `locked` columns
+    Csv,
+}
+
+/// Parse a lock period like `90s`, `30m`, `1h` or `2d` for `ik lock --timer`
+pub fn parse_lock_duration(s: &str) -> Result<chrono::Duration, String> {
+    let s = s.trim();
+    let split = s.len() - s.chars().last().map_or(0, |c| c.len_utf8());
+    let (amount, unit) = s.split_at(split);
+    let amount: i64 = amount
+        .parse()
+        .map_err(|_| format!("invalid duration '{s}' (expected e.g. 90s, 30m, 1h, 2d)"))?;
+    if amount <= 0 {
+        return Err("duration must be greater than zero".to_string());
+    }
+
+    let duration = match unit {
+        "s" => chrono::Duration::try_seconds(amount),
+        "m" => chrono::Duration::try_minutes(amount),
+        "h" => chrono::Duration::try_hours(amount),
+        "d" => chrono::Duration::try_days(amount),
+        _ => return Err(format!("unknown unit in '{s}' (use s, m, h or d)")),
+    };
+    duration.ok_or_else(|| format!("duration '{s}' is too long"))
+}
+
+/// Sort order for `ik list`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ListSort {
+    /// Alphabetical by key
+    Key,
+    /// Most accessed first (requires access tracking)
+    Accessed,
 }
@@ -1,7 +1,12 @@
 use crate::error::{Error, Result};
+use crate::secret::SecretString;
 use arboard::Clipboard;
+use base64::{Engine as _, engine::general_purpose};
+use std::io::Write;
+use std::process::{Command, ExitStatus};
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
+use zeroize::{Zeroize, Zeroizing};
 
 /// Copy text to the system clipboard
 pub fn copy_to_clipboard(text: &str) -> Result<()> {
@@ -15,6 +20,87 @@ pub fn copy_to_clipboard(text: &str) -> Result<()> {
     Ok(())
 }
 
+/// Copy text to the primary selection (what middle-click pastes) on Linux and BSD
+///
+/// Some apps paste from PRIMARY rather than the clipboard. This goes through X11
+/// (XWayland on Wayland); compositors without primary selection support return an error.
+#[cfg(all(unix, not(target_os = "macos")))]
+pub fn copy_to_primary(text: &str) -> Result<()> {
+    use arboard::{LinuxClipboardKind, SetExtLinux};
+
+    let mut clipboard =
+        Clipboard::new().map_err(|e| Error::Io(format!("Failed to access clipboard: {e}")))?;
+
+    clipboard
+        .set()
+        .clipboard(LinuxClipboardKind::Primary)
+        .text(text.to_string())
+        .map_err(|e| Error::Io(format!("Failed to copy to primary selection: {e}")))
+}
+
+/// The primary selection only exists on Linux and BSD
+#[cfg(not(all(unix, not(target_os = "macos"))))]
+pub fn copy_to_primary(_text: &str) -> Result<()> {
+    Err(Error::Io(
+        "The primary selection is only available on Linux and BSD".to_string(),
+    ))
+}
+
+/// Build the OSC 52 escape sequence that asks the terminal to set its clipboard to `text`
+///
+/// Inside tmux (`tmux` = true) the sequence is wrapped in a DCS passthrough so it
+/// reaches the outer terminal (needs `set -g allow-passthrough on` in tmux 3.3+).
+pub fn osc52_sequence(text: &str, tmux: bool) -> Zeroizing<String> {
+    let encoded = Zeroizing::new(general_purpose::STANDARD.encode(text));
+
+    if tmux {
+        Zeroizing::new(format!(
+            "\x1bPtmux;\x1b\x1b]52;c;{}\x07\x1b\\",
+            encoded.as_str()
+        ))
+    } else {
+        Zeroizing::new(format!("\x1b]52;c;{}\x07", encoded.as_str()))
+    }
+}
+
+/// Copy text to the local terminal's clipboard with the OSC 52 escape sequence
+///
+/// Works over SSH, where the system clipboard belongs to the remote machine. The
+/// sequence goes to the controlling terminal (`/dev/tty`) so it still works when
+/// stdout is redirected, falling back to stdout if there is none.
+///
+/// # Security Note
+/// The terminal (and anything logging it, e.g. tmux scrollback) sees the value,
+/// and nothing can clear it again afterwards.
+pub fn copy_via_osc52(text: &str) -> Result<()> {
+    let sequence = osc52_sequence(text, std::env::var_os("TMUX").is_some());
+
+    let written = match std::fs::OpenOptions::new().write(true).open("/dev/tty") {
+        Ok(mut tty) => tty
+            .write_all(sequence.as_bytes())
+            .and_then(|()| tty.flush()),
+        Err(_) => {
+            let mut stdout = std::io::stdout();
+            stdout
+                .write_all(sequence.as_bytes())
+                .and_then(|()| stdout.flush())
+        }
+    };
+
+    written.map_err(|e| Error::Io(format!("Failed to write OSC 52 sequence: {e}")))
+}
+
+/// Whether copying should go through OSC 52 instead of the system clipboard
+///
+/// Either requested (`--osc52`) or over SSH (`SSH_TTY` set) with no forwarded
+/// X11/Wayland display for the system clipboard to use.
+pub fn prefers_osc52(requested: bool) -> bool {
+    requested
+        || (std::env::var_os("SSH_TTY").is_some()
+            && std::env::var_os("DISPLAY").is_none()
+            && std::env::var_os("WAYLAND_DISPLAY").is_none())
+}
+
 /// Get text from the system clipboard
 ///
 /// **Note**: Currently used for testing clipboard functionality.
@@ -29,6 +115,26 @@ pub fn get_from_clipboard() -> Result<String> {
         .map_err(|e| Error::Io(format!("Failed to read from clipboard: {e}")))
 }
 
+/// Clear the system clipboard, whatever it contains
+///
+/// An already-empty clipboard is left alone.
+pub fn clear_clipboard() -> Result<()> {
+    let mut clipboard =
+        Clipboard::new().map_err(|e| Error::Io(format!("Failed to access clipboard: {e}")))?;
+
+    if let Ok(mut current) = clipboard.get_text() {
+        let empty = current.is_empty();
+        current.zeroize();
+        if empty {
+            return Ok(());
+        }
+    }
+
+    clipboard
+        .set_text(String::new())
+        .map_err(|e| Error::Io(format!("Failed to clear clipboard: {e}")))
+}
+
 /// Auto-clear clipboard after timeout if it still contains the expected value
 ///
 /// This function spawns a background thread that waits for the specified duration,
@@ -42,7 +148,7 @@ pub fn get_from_clipboard() -> Result<String> {
 /// # Security Note
 /// This prevents clipboard persistence of sensitive data while respecting user's clipboard usage.
 pub fn auto_clear_clipboard(expected_value: &str, timeout: Duration) -> Result<()> {
-    let expected = expected_value.to_string();
+    let expected = SecretString::from(expected_value.to_string());
 
     // Spawn background thread to clear after timeout
     thread::spawn(move || {
@@ -50,15 +156,171 @@ pub fn auto_clear_clipboard(expected_value: &str, timeout: Duration) -> Result<(
         thread::sleep(timeout);
 
         // Only clear if clipboard still contains our value
-        if let Ok(mut clipboard) = Clipboard::new() {
-            if let Ok(current_value) = clipboard.get_text() {
-                if current_value == expected {
-                    // Clear clipboard by setting empty string
-                    let _ = clipboard.set_text(String::new());
-                }
+        clear_if_unchanged(&expected);
+    });
+
+    Ok(())
+}
+
+/// How often `auto_clear_clipboard_on_change` checks the clipboard
+const CHANGE_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Why waiting to clear the clipboard ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClearTrigger {
+    /// The timeout elapsed with our value still (or possibly) on the clipboard
+    Timeout,
+    /// The clipboard no longer holds our value (the user copied something else)
+    Changed,
+}
+
+/// Wait until the clipboard stops holding `expected` or `timeout` elapses, whichever is first
+///
+/// `read` returns the current clipboard text; `None` (unreadable) counts as unchanged.
+pub fn wait_for_clear<F>(
+    expected: &str,
+    timeout: Duration,
+    poll_interval: Duration,
+    mut read: F,
+) -> ClearTrigger
+where
+    F: FnMut() -> Option<String>,
+{
+    let started = Instant::now();
+
+    loop {
+        let remaining = timeout.saturating_sub(started.elapsed());
+        if remaining.is_zero() {
+            return ClearTrigger::Timeout;
+        }
+        thread::sleep(poll_interval.min(remaining));
+
+        if let Some(mut current) = read() {
+            let changed = current != expected;
+            current.zeroize();
+            if changed {
+                return ClearTrigger::Changed;
             }
         }
+    }
+}
+
+/// Like `auto_clear_clipboard`, but stop as soon as the clipboard holds something else
+///
+/// Approximates "clear after first paste": once the user has moved on and copied
+/// something else, there's nothing left to clear and their new content is kept.
+/// Otherwise the clipboard is cleared after `timeout` as usual.
+pub fn auto_clear_clipboard_on_change(expected_value: &str, timeout: Duration) -> Result<()> {
+    let mut expected = expected_value.to_string();
+
+    thread::spawn(move || {
+        let trigger = wait_for_clear(&expected, timeout, CHANGE_POLL_INTERVAL, || {
+            Clipboard::new().ok()?.get_text().ok()
+        });
+
+        if trigger == ClearTrigger::Timeout {
+            clear_if_unchanged(&expected);
+        }
+        expected.zeroize();
     });
 
     Ok(())
 }
+
+/// Clears the clipboard when dropped, if it still contains the copied value
+///
+/// Call `cancel` to leave the clipboard untouched.
+pub struct ClearGuard {
+    expected: Option<String>,
+}
+
+impl ClearGuard {
+    /// Guard the given value (already copied to the clipboard)
+    pub fn new(expected_value: &str) -> Self {
+        Self {
+            expected: Some(expected_value.to_string()),
+        }
+    }
+
+    /// Don't clear the clipboard on drop
+    #[allow(dead_code)] // Public API - may be used by external consumers
+    pub fn cancel(&mut self) {
+        if let Some(mut expected) = self.expected.take() {
+            expected.zeroize();
+        }
+    }
+}
+
+impl Drop for ClearGuard {
+    fn drop(&mut self) {
+        if let Some(mut expected) = self.expected.take() {
+            clear_if_unchanged(&expected);
+            expected.zeroize();
+        }
+    }
+}
+
+/// Copy a value, run a command, and clear the clipboard once the command exits
+///
+/// The clipboard is cleared (if unchanged) whether the command succeeds, fails
+/// or can't be started.
+///
+/// # Arguments
+/// * `value` - Value to copy
+/// * `command` - Program followed by its arguments
+///
+/// # Returns
+/// The command's exit status (non-zero exits are not an error here)
+pub fn copy_and_run(value: &str, command: &[String]) -> Result<ExitStatus> {
+    let (program, args) = command
+        .split_first()
+        .filter(|(program, _)| !program.trim().is_empty())
+        .ok_or_else(|| Error::InvalidInput("--then-run requires a command".to_string()))?;
+
+    copy_to_clipboard(value)?;
+    let _guard = ClearGuard::new(value);
+
+    Command::new(program)
+        .args(args)
+        .status()
+        .map_err(|e| Error::Io(format!("Failed to run '{program}': {e}")))
+}
+
+/// Clear the clipboard only if it still contains `expected`
+///
+/// The primary selection is cleared too if it holds `expected` (see `copy_to_primary`).
+fn clear_if_unchanged(expected: &str) {
+    if let Ok(mut clipboard) = Clipboard::new()
+        && let Ok(current_value) = clipboard.get_text()
+        && current_value == expected
+    {
+        // Clear clipboard by setting empty string
+        let _ = clipboard.set_text(String::new());
+    }
+
+    clear_primary_if_unchanged(expected);
+}
+
+/// Clear the primary selection only if it still contains `expected`
+#[cfg(all(unix, not(target_os = "macos")))]
+fn clear_primary_if_unchanged(expected: &str) {
+    use arboard::{GetExtLinux, LinuxClipboardKind, SetExtLinux};
+
+    if let Ok(mut clipboard) = Clipboard::new()
+        && let Ok(mut current_value) = clipboard
+            .get()
+            .clipboard(LinuxClipboardKind::Primary)
+            .text()
+    {
+        if current_value == expected {
+            let _ = clipboard
+                .set()
+                .clipboard(LinuxClipboardKind::Primary)
+                .text(String::new());
+        }
+        current_value.zeroize();
+    }
+}
+
+#[cfg(not(all(unix, not(target_os = "macos"))))]
+fn clear_primary_if_unchanged(_expected: &str) {}
@@ -0,0 +1,95 @@
+//! Configuration Module
+//!
+//! Loads optional user preferences from `config.toml` in the ironkey config
+//! directory. A missing file means all built-in defaults apply.
+
+use crate::confirm::ConfirmPolicy;
+use crate::crypto::KdfAlgorithm;
+use crate::error::{Error, Result};
+use crate::storage::BackendKind;
+use serde::Deserialize;
+use std::fs;
+use std::path::PathBuf;
+
+/// Seconds before a copied value is cleared from the clipboard, unless configured
+pub const DEFAULT_CLIPBOARD_TIMEOUT: u64 = 30;
+
+/// Length of `ik generate` passwords, unless configured
+pub const DEFAULT_PASSWORD_LENGTH: usize = 16;
+
+/// User configuration (all keys optional)
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// Default confirmation policy for destructive operations
+    pub confirm_destructive: Option<ConfirmPolicy>,
+
+    /// Record access counts and timestamps when entries are read
+    pub track_access: bool,
+
+    /// Warn on unlock if the vault hasn't been modified in this many days
+    pub max_age_days: Option<u32>,
+
+    /// Storage backend for the vault (`json` or `sqlite`)
+    pub backend: Option<BackendKind>,
+
+    /// Fsync and read back the database file after every save
+    pub safe_save: bool,
+
+    /// Seconds before copied values are cleared from the clipboard
+    pub clipboard_timeout: Option<u64>,
+
+    /// Length of `ik generate` passwords
+    pub default_password_length: Option<usize>,
+
+    /// Key derivation function for `ik init` (`pbkdf2` or `argon2`)
+    pub kdf: Option<KdfAlgorithm>,
+}
+
+impl Config {
+    /// Load the config file, falling back to defaults if it doesn't exist
+    pub fn load_or_default() -> Result<Self> {
+        let path = get_config_path()?;
+
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(&path)
+            .map_err(|e| Error::Io(format!("Failed to read config file: {e}")))?;
+
+        Self::from_toml_str(&content)
+    }
+
+    /// Parse a config from TOML text
+    pub fn from_toml_str(content: &str) -> Result<Self> {
+        toml::from_str(content)
+            .map_err(|e| Error::InvalidInput(format!("Invalid config file: {e}")))
+    }
+
+    /// Clipboard auto-clear timeout: the `--timeout` flag, then the config, then the default
+    pub fn effective_clipboard_timeout(&self, flag: Option<u64>) -> u64 {
+        flag.or(self.clipboard_timeout)
+            .unwrap_or(DEFAULT_CLIPBOARD_TIMEOUT)
+    }
+
+    /// Generated password length: the `--length` flag, then the config, then the default
+    pub fn effective_password_length(&self, flag: Option<usize>) -> usize {
+        flag.or(self.default_password_length)
+            .unwrap_or(DEFAULT_PASSWORD_LENGTH)
+    }
+
+    /// Key derivation function for a new vault: the `--kdf` flag, then the config, then PBKDF2
+    pub fn effective_kdf(&self, flag: Option<KdfAlgorithm>) -> KdfAlgorithm {
+        flag.or(self.kdf).unwrap_or_default()
+    }
+}
+
+/// Get the config file path
+pub fn get_config_path() -> Result<PathBuf> {
+    let config_dir = dirs::config_dir()
+        .ok_or_else(|| Error::Io("Could not find config directory".to_string()))?
+        .join("ironkey");
+
+    Ok(config_dir.join("config.toml"))
+}
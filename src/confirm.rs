@@ -0,0 +1,93 @@
+//! Confirmation Module
+//!
+//! Decides whether destructive operations (delete, replace-mode import, ...)
+//! should prompt the user, proceed silently, or be refused.
+
+use clap::ValueEnum;
+use serde::Deserialize;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Policy controlling confirmation prompts for destructive operations
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConfirmPolicy {
+    /// Prompt before every destructive operation (default)
+    #[default]
+    Always,
+    /// Prompt before the first destructive operation, then proceed
+    Once,
+    /// Never prompt; destructive operations require --yes
+    Never,
+}
+
+/// Outcome of the confirmation decision
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfirmDecision {
+    /// Proceed without asking
+    Proceed,
+    /// Ask the user to confirm
+    Prompt,
+    /// Refuse the operation (policy forbids prompting and --yes was not given)
+    Refuse,
+}
+
+/// Decide how to handle a destructive operation
+///
+/// # Arguments
+/// * `policy` - Active confirmation policy
+/// * `assume_yes` - Whether `--yes` was passed
+/// * `already_confirmed` - Whether the user already confirmed an earlier operation
+pub fn decide(policy: ConfirmPolicy, assume_yes: bool, already_confirmed: bool) -> ConfirmDecision {
+    if assume_yes {
+        return ConfirmDecision::Proceed;
+    }
+
+    match policy {
+        ConfirmPolicy::Always => ConfirmDecision::Prompt,
+        ConfirmPolicy::Once if already_confirmed => ConfirmDecision::Proceed,
+        ConfirmPolicy::Once => ConfirmDecision::Prompt,
+        ConfirmPolicy::Never => ConfirmDecision::Refuse,
+    }
+}
+
+/// Tracks confirmation state for destructive operations within one process
+pub struct DestructiveConfirm {
+    policy: ConfirmPolicy,
+    assume_yes: bool,
+    confirmed: AtomicBool,
+}
+
+impl DestructiveConfirm {
+    /// Create a new confirmation tracker
+    pub fn new(policy: ConfirmPolicy, assume_yes: bool) -> Self {
+        Self {
+            policy,
+            assume_yes,
+            confirmed: AtomicBool::new(false),
+        }
+    }
+
+    /// Get the active policy
+    pub fn policy(&self) -> ConfirmPolicy {
+        self.policy
+    }
+
+    /// Whether `--yes` was passed
+    pub fn assume_yes(&self) -> bool {
+        self.assume_yes
+    }
+
+    /// Decide how to handle the next destructive operation
+    pub fn decide(&self) -> ConfirmDecision {
+        decide(
+            self.policy,
+            self.assume_yes,
+            self.confirmed.load(Ordering::SeqCst),
+        )
+    }
+
+    /// Record that the user confirmed a destructive operation
+    pub fn mark_confirmed(&self) {
+        self.confirmed.store(true, Ordering::SeqCst);
+    }
+}
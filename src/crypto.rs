@@ -1,11 +1,27 @@
 use crate::error::{Error, Result};
+use clap::ValueEnum;
 use ring::rand::SecureRandom;
-use ring::{aead, pbkdf2, rand};
+use ring::{aead, hkdf, hmac, pbkdf2, rand};
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+use subtle::ConstantTimeEq;
+use zeroize::Zeroize;
 
 const PBKDF2_ITERATIONS: u32 = 100_000;
+const ARGON2_MEMORY_KIB: u32 = 64 * 1024;
+const ARGON2_ITERATIONS: u32 = 3;
+const ARGON2_PARALLELISM: u32 = 4;
 const NONCE_LENGTH: usize = 12;
 const SALT_LENGTH: usize = 32;
 const KEY_LENGTH: usize = 32;
+const ENTRY_KEY_INFO: &[u8] = b"ironkey-entry-key-v1";
+const DATABASE_MAC_INFO: &[u8] = b"ironkey-database-mac-v1";
+
+/// Fewest PBKDF2 iterations accepted for a new vault (also the default)
+pub const MIN_PBKDF2_ITERATIONS: u32 = PBKDF2_ITERATIONS;
+
+/// Longest accepted password, in bytes (keeps key derivation time bounded)
+pub const MAX_PASSWORD_LENGTH: usize = 1024;
 
 /// Encrypted data with its nonce
 #[derive(Debug, Clone)]
@@ -14,6 +30,84 @@ pub struct EncryptedData {
     pub nonce: Vec<u8>,
 }
 
+/// Password-based key derivation function for the master key
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum KdfAlgorithm {
+    /// PBKDF2-HMAC-SHA256 (vaults created before Argon2 support)
+    #[default]
+    Pbkdf2,
+    /// Argon2id, memory-hard and much costlier to attack with GPUs
+    #[value(name = "argon2", alias = "argon2id")]
+    #[serde(alias = "argon2")]
+    Argon2id,
+}
+
+impl KdfAlgorithm {
+    pub fn is_pbkdf2(&self) -> bool {
+        *self == KdfAlgorithm::Pbkdf2
+    }
+}
+
+/// Which KDF to run and with what cost
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KdfParams {
+    pub algorithm: KdfAlgorithm,
+    /// PBKDF2 iterations, or the Argon2 time cost (passes over memory)
+    pub iterations: u32,
+    /// Argon2 memory cost in KiB (unused by PBKDF2)
+    pub memory_kib: u32,
+    /// Argon2 lanes (unused by PBKDF2)
+    pub parallelism: u32,
+}
+
+impl KdfParams {
+    /// PBKDF2 with the given iteration count
+    pub fn pbkdf2(iterations: u32) -> Self {
+        Self {
+            algorithm: KdfAlgorithm::Pbkdf2,
+            iterations,
+            memory_kib: 0,
+            parallelism: 0,
+        }
+    }
+
+    /// Argon2id with the given memory (KiB), time cost and parallelism
+    pub fn argon2id(memory_kib: u32, iterations: u32, parallelism: u32) -> Self {
+        Self {
+            algorithm: KdfAlgorithm::Argon2id,
+            iterations,
+            memory_kib,
+            parallelism,
+        }
+    }
+
+    /// Check these settings are strong enough for a new vault
+    ///
+    /// PBKDF2 needs at least `MIN_PBKDF2_ITERATIONS`. Argon2 parameters are
+    /// checked by the argon2 crate when the key is derived.
+    pub fn validate(&self) -> Result<()> {
+        if self.algorithm == KdfAlgorithm::Pbkdf2 && self.iterations < MIN_PBKDF2_ITERATIONS {
+            return Err(Error::InvalidInput(format!(
+                "PBKDF2 iterations must be at least {MIN_PBKDF2_ITERATIONS} (got {})",
+                self.iterations
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Defaults for new vaults: 100,000 PBKDF2 iterations, or Argon2id with 64 MiB, 3 passes, 4 lanes
+    pub fn default_for(algorithm: KdfAlgorithm) -> Self {
+        match algorithm {
+            KdfAlgorithm::Pbkdf2 => Self::pbkdf2(PBKDF2_ITERATIONS),
+            KdfAlgorithm::Argon2id => {
+                Self::argon2id(ARGON2_MEMORY_KIB, ARGON2_ITERATIONS, ARGON2_PARALLELISM)
+            }
+        }
+    }
+}
+
 /// Generate a random salt for key derivation
 pub fn generate_salt() -> Result<Vec<u8>> {
     let rng = rand::SystemRandom::new();
@@ -25,44 +119,226 @@ pub fn generate_salt() -> Result<Vec<u8>> {
 
 /// Derive an encryption key from a password using PBKDF2
 pub fn derive_key(password: &str, salt: &[u8], iterations: u32) -> Result<Vec<u8>> {
+    derive_key_with(password, salt, &KdfParams::pbkdf2(iterations))
+}
+
+/// Derive an encryption key from a password with the given KDF
+pub fn derive_key_with(password: &str, salt: &[u8], params: &KdfParams) -> Result<Vec<u8>> {
+    let mut key = vec![0u8; KEY_LENGTH];
+    derive_key_into_with(password, salt, params, &mut key)?;
+    Ok(key)
+}
+
+/// Derive an encryption key into a caller-provided buffer (must be 32 bytes)
+///
+/// Lets bulk operations reuse (and deterministically zeroize) one key buffer.
+#[allow(dead_code)] // Public API - may be used by external consumers
+pub fn derive_key_into(password: &str, salt: &[u8], iterations: u32, out: &mut [u8]) -> Result<()> {
+    derive_key_into_with(password, salt, &KdfParams::pbkdf2(iterations), out)
+}
+
+/// Same as `derive_key_into`, with the given KDF
+pub fn derive_key_into_with(
+    password: &str,
+    salt: &[u8],
+    params: &KdfParams,
+    out: &mut [u8],
+) -> Result<()> {
     if password.is_empty() {
         return Err(Error::EmptyPassword);
     }
+    check_password_length(password)?;
+
+    if out.len() != KEY_LENGTH {
+        return Err(Error::KeyDerivationFailed(format!(
+            "Invalid output length: expected {}, got {}",
+            KEY_LENGTH,
+            out.len()
+        )));
+    }
+
+    run_kdf(password, salt, params, out)
+}
+
+/// Run the KDF itself (password length and output size already checked)
+fn run_kdf(password: &str, salt: &[u8], params: &KdfParams, out: &mut [u8]) -> Result<()> {
+    match params.algorithm {
+        KdfAlgorithm::Pbkdf2 => {
+            pbkdf2::derive(
+                pbkdf2::PBKDF2_HMAC_SHA256,
+                std::num::NonZeroU32::new(params.iterations)
+                    .ok_or_else(|| Error::KeyDerivationFailed("Invalid iterations".to_string()))?,
+                salt,
+                password.as_bytes(),
+                out,
+            );
+            Ok(())
+        }
+        KdfAlgorithm::Argon2id => {
+            let argon2_params = argon2::Params::new(
+                params.memory_kib,
+                params.iterations,
+                params.parallelism,
+                Some(out.len()),
+            )
+            .map_err(|e| Error::KeyDerivationFailed(format!("Invalid Argon2 parameters: {e}")))?;
+
+            argon2::Argon2::new(
+                argon2::Algorithm::Argon2id,
+                argon2::Version::V0x13,
+                argon2_params,
+            )
+            .hash_password_into(password.as_bytes(), salt, out)
+            .map_err(|e| Error::KeyDerivationFailed(format!("Argon2 failed: {e}")))
+        }
+    }
+}
+
+/// Reject passwords over `MAX_PASSWORD_LENGTH` bytes before they reach the KDF
+fn check_password_length(password: &str) -> Result<()> {
+    if password.len() > MAX_PASSWORD_LENGTH {
+        return Err(Error::InvalidInput(format!(
+            "Password is too long: {} bytes (maximum is {MAX_PASSWORD_LENGTH})",
+            password.len()
+        )));
+    }
+
+    Ok(())
+}
+
+/// Derive a per-entry encryption key from the master key using HKDF-SHA256
+///
+/// The entry's key name is the HKDF salt, so every entry is encrypted under its own sub-key.
+pub fn derive_entry_key(master_key: &[u8], entry_key: &str) -> Vec<u8> {
+    hkdf_sha256(master_key, entry_key.as_bytes(), ENTRY_KEY_INFO)
+}
+
+/// Derive the key for the database MAC from the master key using HKDF-SHA256
+///
+/// Uses its own HKDF info string, so it never coincides with an entry encryption key.
+pub fn derive_mac_key(master_key: &[u8]) -> Vec<u8> {
+    hkdf_sha256(master_key, &[], DATABASE_MAC_INFO)
+}
 
+fn hkdf_sha256(master_key: &[u8], salt: &[u8], info: &[u8]) -> Vec<u8> {
+    let prk = hkdf::Salt::new(hkdf::HKDF_SHA256, salt).extract(master_key);
     let mut key = vec![0u8; KEY_LENGTH];
-    pbkdf2::derive(
-        pbkdf2::PBKDF2_HMAC_SHA256,
-        std::num::NonZeroU32::new(iterations)
-            .ok_or_else(|| Error::KeyDerivationFailed("Invalid iterations".to_string()))?,
-        salt,
-        password.as_bytes(),
-        &mut key,
-    );
 
-    Ok(key)
+    // HKDF_SHA256 always yields exactly KEY_LENGTH bytes, so neither step can fail
+    let info = [info];
+    prk.expand(&info, hkdf::HKDF_SHA256)
+        .and_then(|okm| okm.fill(&mut key))
+        .expect("HKDF output length matches the key length");
+
+    key
+}
+
+/// Compute an HMAC-SHA256 tag over `data`
+pub fn compute_mac(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let key = hmac::Key::new(hmac::HMAC_SHA256, key);
+    hmac::sign(&key, data).as_ref().to_vec()
+}
+
+/// Check an HMAC-SHA256 tag over `data` in constant time
+pub fn verify_mac(key: &[u8], data: &[u8], tag: &[u8]) -> bool {
+    let key = hmac::Key::new(hmac::HMAC_SHA256, key);
+    hmac::verify(&key, data, tag).is_ok()
 }
 
 /// Hash a password for verification (same as derive_key, but semantically different)
+#[allow(dead_code)] // Public API - may be used by external consumers
 pub fn hash_password(password: &str, salt: &[u8], iterations: u32) -> Result<Vec<u8>> {
     derive_key(password, salt, iterations)
 }
 
+/// Hash a password for verification with the given KDF
+pub fn hash_password_with(password: &str, salt: &[u8], params: &KdfParams) -> Result<Vec<u8>> {
+    derive_key_with(password, salt, params)
+}
+
 /// Verify a password against a stored hash
+#[allow(dead_code)] // Public API - may be used by external consumers
 pub fn verify_password(password: &str, salt: &[u8], hash: &[u8], iterations: u32) -> Result<bool> {
-    let result = pbkdf2::verify(
-        pbkdf2::PBKDF2_HMAC_SHA256,
-        std::num::NonZeroU32::new(iterations)
-            .ok_or_else(|| Error::KeyDerivationFailed("Invalid iterations".to_string()))?,
-        salt,
-        password.as_bytes(),
-        hash,
-    );
+    verify_password_with(password, salt, hash, &KdfParams::pbkdf2(iterations))
+}
+
+/// Verify a password against a stored hash made with the given KDF
+pub fn verify_password_with(
+    password: &str,
+    salt: &[u8],
+    hash: &[u8],
+    params: &KdfParams,
+) -> Result<bool> {
+    check_password_length(password)?;
+
+    let mut candidate = [0u8; KEY_LENGTH];
+    run_kdf(password, salt, params, &mut candidate)?;
 
-    Ok(result.is_ok())
+    let result = verify_derived(hash, &candidate);
+    candidate.zeroize();
+
+    Ok(result)
+}
+
+/// Derive the key for `password` and check it against the stored hash in one KDF run
+///
+/// The stored hash is the derived key itself, so unlocking doesn't need a separate
+/// `verify_password_with` call. The comparison is constant time.
+///
+/// # Returns
+/// The derived key if the password matches, `None` otherwise (the candidate is zeroized)
+pub fn derive_verified_key_with(
+    password: &str,
+    salt: &[u8],
+    hash: &[u8],
+    params: &KdfParams,
+) -> Result<Option<Vec<u8>>> {
+    check_password_length(password)?;
+
+    let mut key = vec![0u8; KEY_LENGTH];
+    run_kdf(password, salt, params, &mut key)?;
+
+    if verify_derived(hash, &key) {
+        Ok(Some(key))
+    } else {
+        key.zeroize();
+        Ok(None)
+    }
+}
+
+/// Compare a stored derived key/hash with a freshly derived one in constant time
+///
+/// KDF-agnostic: every password verification path should end here rather than
+/// in a KDF-specific verify function. Slices of different lengths never match.
+pub fn verify_derived(expected: &[u8], candidate: &[u8]) -> bool {
+    constant_time_eq(expected, candidate)
+}
+
+/// Compare two byte slices without leaking where they differ through timing
+///
+/// Use for anything derived from a secret (keys, hashes, MACs). Only the length
+/// check short-circuits, and lengths aren't secret here. Built on `subtle`, since
+/// ring's `constant_time::verify_slices_are_equal` is deprecated.
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    bool::from(a.ct_eq(b))
 }
 
 /// Encrypt data using AES-256-GCM
 pub fn encrypt(plaintext: &[u8], key: &[u8]) -> Result<EncryptedData> {
+    let mut ciphertext = Vec::new();
+    let nonce = encrypt_into(plaintext, key, &mut ciphertext)?;
+
+    Ok(EncryptedData {
+        ciphertext,
+        nonce: nonce.to_vec(),
+    })
+}
+
+/// Encrypt data using AES-256-GCM into a caller-provided buffer
+///
+/// `out` is cleared and receives ciphertext + tag (its capacity is reused).
+/// Returns the random nonce used.
+pub fn encrypt_into(plaintext: &[u8], key: &[u8], out: &mut Vec<u8>) -> Result<[u8; NONCE_LENGTH]> {
     if key.len() != KEY_LENGTH {
         return Err(Error::EncryptionFailed(format!(
             "Invalid key length: expected {}, got {}",
@@ -78,30 +354,33 @@ pub fn encrypt(plaintext: &[u8], key: &[u8]) -> Result<EncryptedData> {
 
     // Generate random nonce
     let rng = rand::SystemRandom::new();
-    let mut nonce_bytes = vec![0u8; NONCE_LENGTH];
+    let mut nonce_bytes = [0u8; NONCE_LENGTH];
     rng.fill(&mut nonce_bytes)
         .map_err(|e| Error::EncryptionFailed(format!("Failed to generate nonce: {e:?}")))?;
-    let nonce = aead::Nonce::assume_unique_for_key(
-        nonce_bytes
-            .clone()
-            .try_into()
-            .map_err(|_| Error::EncryptionFailed("Invalid nonce length".to_string()))?,
-    );
+    let nonce = aead::Nonce::assume_unique_for_key(nonce_bytes);
 
     // Encrypt the data
-    let mut ciphertext = plaintext.to_vec();
+    out.zeroize();
+    out.extend_from_slice(plaintext);
     sealing_key
-        .seal_in_place_append_tag(nonce, aead::Aad::empty(), &mut ciphertext)
+        .seal_in_place_append_tag(nonce, aead::Aad::empty(), out)
         .map_err(|e| Error::EncryptionFailed(format!("Encryption failed: {e:?}")))?;
 
-    Ok(EncryptedData {
-        ciphertext,
-        nonce: nonce_bytes,
-    })
+    Ok(nonce_bytes)
 }
 
 /// Decrypt data using AES-256-GCM
 pub fn decrypt(encrypted: &EncryptedData, key: &[u8]) -> Result<Vec<u8>> {
+    let mut plaintext = Vec::new();
+    decrypt_into(encrypted, key, &mut plaintext)?;
+    Ok(plaintext)
+}
+
+/// Decrypt data using AES-256-GCM into a caller-provided buffer
+///
+/// `out` is cleared and receives the plaintext (its capacity is reused).
+/// On failure `out` is left zeroized and empty.
+pub fn decrypt_into(encrypted: &EncryptedData, key: &[u8], out: &mut Vec<u8>) -> Result<()> {
     if key.len() != KEY_LENGTH {
         return Err(Error::DecryptionFailed(format!(
             "Invalid key length: expected {}, got {}",
@@ -124,24 +403,49 @@ pub fn decrypt(encrypted: &EncryptedData, key: &[u8]) -> Result<Vec<u8>> {
     let opening_key = aead::LessSafeKey::new(unbound_key);
 
     // Create nonce
-    let nonce = aead::Nonce::assume_unique_for_key(
-        encrypted
-            .nonce
-            .clone()
-            .try_into()
-            .map_err(|_| Error::DecryptionFailed("Invalid nonce length".to_string()))?,
-    );
+    let nonce = aead::Nonce::try_assume_unique_for_key(&encrypted.nonce)
+        .map_err(|_| Error::DecryptionFailed("Invalid nonce length".to_string()))?;
 
-    // Decrypt the data
-    let mut ciphertext = encrypted.ciphertext.clone();
-    let plaintext = opening_key
-        .open_in_place(nonce, aead::Aad::empty(), &mut ciphertext)
-        .map_err(|e| Error::DecryptionFailed(format!("Decryption failed: {e:?}")))?;
+    // Decrypt the data in place
+    out.zeroize();
+    out.extend_from_slice(&encrypted.ciphertext);
+    let plaintext_len = match opening_key.open_in_place(nonce, aead::Aad::empty(), out) {
+        Ok(plaintext) => plaintext.len(),
+        Err(e) => {
+            out.zeroize();
+            return Err(Error::DecryptionFailed(format!("Decryption failed: {e:?}")));
+        }
+    };
 
-    Ok(plaintext.to_vec())
+    // Drop the authentication tag, wiping it first
+    out[plaintext_len..].zeroize();
+    out.truncate(plaintext_len);
+
+    Ok(())
 }
 
 /// Get the default number of PBKDF2 iterations
 pub fn default_iterations() -> u32 {
     PBKDF2_ITERATIONS
 }
+
+/// Measure how long one key derivation takes with the given iteration count
+///
+/// Uses a throwaway password and salt; useful for estimating bulk re-encryption time.
+#[allow(dead_code)] // Public API - may be used by external consumers
+pub fn time_kdf(iterations: u32) -> Result<Duration> {
+    time_kdf_with(&KdfParams::pbkdf2(iterations))
+}
+
+/// Measure how long one key derivation takes with the given KDF
+pub fn time_kdf_with(params: &KdfParams) -> Result<Duration> {
+    let salt = generate_salt()?;
+    let mut key = [0u8; KEY_LENGTH];
+
+    let started = Instant::now();
+    derive_key_into_with("ironkey-kdf-timing", &salt, params, &mut key)?;
+    let elapsed = started.elapsed();
+
+    key.zeroize();
+    Ok(elapsed)
+}
@@ -0,0 +1,278 @@
+//! Display Module
+//!
+//! Formatting helpers for CLI output that are independent of the vault
+//! (table layout for `ik list --long`, masked value previews, the list summary
+//! line, `--json` output and errors, `ik get --qr` codes).
+
+use crate::error::{Error, Result};
+use crate::vault::EntrySummary;
+use qrcode::QrCode;
+use qrcode::render::unicode::Dense1x2;
+
+/// Character used to mask hidden parts of a value
+const MASK_CHAR: char = '•';
+
+/// Maximum number of mask characters shown in a preview
+const MASK_MAX: usize = 6;
+
+/// Maximum width of the key column before truncation
+const KEY_COLUMN_MAX: usize = 40;
+
+/// Terminal width used when it can't be detected (e.g. output is piped)
+pub const DEFAULT_TERMINAL_WIDTH: usize = 100;
+
+/// Mask a secret value for preview, revealing at most the first two characters
+///
+/// Values of 4 characters or fewer are fully masked. The mask length is capped
+/// so the preview doesn't reveal the value's length.
+pub fn mask_value(value: &str) -> String {
+    let char_count = value.chars().count();
+    let revealed = if char_count > 4 { 2 } else { 0 };
+
+    let mut masked: String = value.chars().take(revealed).collect();
+    masked.extend(std::iter::repeat_n(MASK_CHAR, MASK_MAX));
+    masked
+}
+
+/// Columns available in `ik list --long`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Column {
+    Key,
+    Locked,
+    Accessed,
+    LastAccessed,
+    Created,
+    Updated,
+    Tags,
+    Preview,
+}
+
+impl Column {
+    /// Header label for the column
+    pub fn header(&self) -> &'static str {
+        match self {
+            Column::Key => "KEY",
+            Column::Locked => "LOCKED",
+            Column::Accessed => "READS",
+            Column::LastAccessed => "LAST READ",
+            Column::Created => "CREATED",
+            Column::Updated => "UPDATED",
+            Column::Tags => "TAGS",
+            Column::Preview => "PREVIEW",
+        }
+    }
+
+    /// Fixed display width (the key column is sized to its content)
+    fn width(&self) -> usize {
+        match self {
+            Column::Key => KEY_COLUMN_MAX,
+            Column::Locked => 6,
+            Column::Accessed => 5,
+            Column::LastAccessed => 16,
+            Column::Created => 16,
+            Column::Updated => 16,
+            Column::Tags => 4,
+            Column::Preview => 8,
+        }
+    }
+}
+
+/// Pick which columns fit in the given terminal width
+///
+/// Optional columns are dropped (preview first, then tags, timestamps and
+/// access metadata) until the row fits; key and lock status are always shown.
+pub fn long_columns(terminal_width: usize, key_width: usize, with_preview: bool) -> Vec<Column> {
+    let mut columns = vec![
+        Column::Key,
+        Column::Locked,
+        Column::Accessed,
+        Column::LastAccessed,
+        Column::Created,
+        Column::Updated,
+        Column::Tags,
+    ];
+    if with_preview {
+        columns.push(Column::Preview);
+    }
+
+    let row_width = |cols: &[Column]| -> usize {
+        cols.iter()
+            .map(|c| {
+                if *c == Column::Key {
+                    key_width
+                } else {
+                    c.width()
+                }
+            })
+            .sum::<usize>()
+            + 2 * (cols.len() + 1)
+    };
+
+    for optional in [
+        Column::Preview,
+        Column::Tags,
+        Column::Created,
+        Column::Updated,
+        Column::LastAccessed,
+        Column::Accessed,
+    ] {
+        if row_width(&columns) <= terminal_width {
+            break;
+        }
+        columns.retain(|c| *c != optional);
+    }
+
+    columns
+}
+
+/// Width of the key column for the given summaries
+pub fn key_column_width(summaries: &[EntrySummary]) -> usize {
+    summaries
+        .iter()
+        .map(|s| s.key.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max(Column::Key.header().len())
+        .min(KEY_COLUMN_MAX)
+}
+
+/// Format the header row for `ik list --long`
+pub fn format_long_header(columns: &[Column], key_width: usize) -> String {
+    let cells: Vec<String> = columns
+        .iter()
+        .map(|c| pad(c.header(), cell_width(c, key_width)))
+        .collect();
+
+    format!("  {}", cells.join("  ")).trim_end().to_string()
+}
+
+/// Format a single entry row for `ik list --long`
+///
+/// The entry's label, if any, is appended after the last column.
+///
+/// # Arguments
+/// * `summary` - Entry metadata
+/// * `preview` - Masked value preview (None for locked entries or when not requested)
+/// * `columns` - Columns to render (see `long_columns`)
+/// * `key_width` - Width of the key column (see `key_column_width`)
+pub fn format_long_row(
+    summary: &EntrySummary,
+    preview: Option<&str>,
+    columns: &[Column],
+    key_width: usize,
+) -> String {
+    let cells: Vec<String> = columns
+        .iter()
+        .map(|c| {
+            let value = match c {
+                Column::Key => truncate(&summary.key, key_width),
+                Column::Locked => if summary.is_locked { "yes" } else { "no" }.to_string(),
+                Column::Accessed => summary.access_count.to_string(),
+                Column::LastAccessed => format_timestamp(summary.last_accessed.as_deref()),
+                Column::Created => format_timestamp(summary.created_at.as_deref()),
+                Column::Updated => format_timestamp(summary.updated_at.as_deref()),
+                Column::Tags => summary.tag_count.to_string(),
+                Column::Preview => preview.unwrap_or("-").to_string(),
+            };
+            pad(&value, cell_width(c, key_width))
+        })
+        .collect();
+
+    let row = format!("  {}", cells.join("  ")).trim_end().to_string();
+    match &summary.label {
+        Some(label) => format!("{row}  — {label}"),
+        None => row,
+    }
+}
+
+/// Format an RFC 3339 timestamp as local "YYYY-MM-DD HH:MM", or "-" if absent/invalid
+pub fn format_timestamp(timestamp: Option<&str>) -> String {
+    timestamp
+        .and_then(|ts| chrono::DateTime::parse_from_rfc3339(ts).ok())
+        .map(|ts| {
+            ts.with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M")
+                .to_string()
+        })
+        .unwrap_or_else(|| "-".to_string())
+}
+
+/// Trailing `ik list` line: "3 entries (1 locked)"
+pub fn list_summary(summaries: &[EntrySummary]) -> String {
+    let total = summaries.len();
+    let locked = summaries.iter().filter(|summary| summary.is_locked).count();
+    let noun = if total == 1 { "entry" } else { "entries" };
+    format!("{total} {noun} ({locked} locked)")
+}
+
+/// `ik list --json` output: an array of `{"key": ..., "locked": ...}` objects
+pub fn list_json(summaries: &[EntrySummary]) -> String {
+    let items: Vec<serde_json::Value> = summaries
+        .iter()
+        .map(|summary| serde_json::json!({ "key": summary.key, "locked": summary.is_locked }))
+        .collect();
+    serde_json::Value::Array(items).to_string()
+}
+
+/// `ik get --json` output: `{"key": ..., "value": ...}`
+pub fn value_json(key: &str, value: &str) -> String {
+    serde_json::json!({ "key": key, "value": value }).to_string()
+}
+
+/// Error output with `--json`: `{"error": <kind>, "message": ...}`
+///
+/// `error` is `Error::kind_str`, which scripts can match on; `message` is the
+/// human-readable Display text.
+pub fn error_json(error: &Error) -> String {
+    serde_json::json!({ "error": error.kind_str(), "message": error.to_string() }).to_string()
+}
+
+/// Render a value as a QR code for `ik get --qr`
+///
+/// Two modules per character using half-block characters, drawn light-on-dark
+/// with a quiet zone so phones can scan it from a dark terminal.
+pub fn render_qr(value: &str) -> Result<String> {
+    let code = QrCode::new(value.as_bytes())
+        .map_err(|e| Error::InvalidInput(format!("✘ Value can't be shown as a QR code: {e}")))?;
+
+    Ok(code
+        .render::<Dense1x2>()
+        .dark_color(Dense1x2::Light)
+        .light_color(Dense1x2::Dark)
+        .quiet_zone(true)
+        .build())
+}
+
+/// Detect the terminal width, falling back to `DEFAULT_TERMINAL_WIDTH`
+pub fn terminal_width() -> usize {
+    terminal_size::terminal_size()
+        .map(|(terminal_size::Width(w), _)| w as usize)
+        .unwrap_or(DEFAULT_TERMINAL_WIDTH)
+}
+
+fn cell_width(column: &Column, key_width: usize) -> usize {
+    if *column == Column::Key {
+        key_width
+    } else {
+        column.width()
+    }
+}
+
+fn pad(value: &str, width: usize) -> String {
+    let len = value.chars().count();
+    if len >= width {
+        value.to_string()
+    } else {
+        format!("{value}{}", " ".repeat(width - len))
+    }
+}
+
+fn truncate(value: &str, width: usize) -> String {
+    if value.chars().count() <= width {
+        value.to_string()
+    } else {
+        let mut truncated: String = value.chars().take(width.saturating_sub(1)).collect();
+        truncated.push('…');
+        truncated
+    }
+}
@@ -13,6 +13,7 @@ pub enum Error {
     MasterKeyNotInitialized,
     MasterKeyAlreadyExists,
     EmptyPassword,
+    KeyMismatch,
 
     // Crypto errors
     EncryptionFailed(String),
@@ -23,6 +24,7 @@ pub enum Error {
     DatabaseNotFound,
     DatabaseLoadFailed(String),
     DatabaseSaveFailed(String),
+    IntegrityCheckFailed,
 
     // I/O errors
     Io(String),
@@ -46,6 +48,10 @@ impl fmt::Display for Error {
                 write!(f, "✦ Master key already exists. Use 'ik init' to verify")
             }
             Error::EmptyPassword => write!(f, "◆ Password cannot be empty"),
+            Error::KeyMismatch => write!(
+                f,
+                "◆ Master password verified, but entries failed to decrypt (database may be corrupted or tampered with)"
+            ),
 
             Error::EncryptionFailed(msg) => write!(f, "◆ Encryption failed: {msg}"),
             Error::DecryptionFailed(msg) => write!(f, "◆ Decryption failed: {msg}"),
@@ -54,6 +60,10 @@ impl fmt::Display for Error {
             Error::DatabaseNotFound => write!(f, "◆ Database not found. Run 'ik init' first"),
             Error::DatabaseLoadFailed(msg) => write!(f, "◆ Failed to load database: {msg}"),
             Error::DatabaseSaveFailed(msg) => write!(f, "◆ Failed to save database: {msg}"),
+            Error::IntegrityCheckFailed => write!(
+                f,
+                "◆ Database integrity check failed: entries were modified outside ironkey (run 'ik doctor', or restore a backup)"
+            ),
 
             Error::Io(msg) => write!(f, "I/O error: {msg}"),
             Error::InvalidInput(msg) => write!(f, "◆ Invalid input: {msg}"),
@@ -61,6 +71,117 @@ impl fmt::Display for Error {
     }
 }
 
+impl Error {
+    /// Whether retrying the same operation might succeed
+    ///
+    /// True for transient failures such as I/O (including clipboard access)
+    /// and failed database writes. Missing entries, bad passwords, corrupt
+    /// data and invalid input will fail the same way again.
+    #[allow(dead_code)] // Public API - may be used by external consumers
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Error::Io(_) | Error::DatabaseSaveFailed(_) => true,
+
+            Error::EntryNotFound(_)
+            | Error::EntryAlreadyExists(_)
+            | Error::EntryLocked(_)
+            | Error::InvalidMasterPassword
+            | Error::MasterKeyNotInitialized
+            | Error::MasterKeyAlreadyExists
+            | Error::EmptyPassword
+            | Error::KeyMismatch
+            | Error::EncryptionFailed(_)
+            | Error::DecryptionFailed(_)
+            | Error::KeyDerivationFailed(_)
+            | Error::DatabaseNotFound
+            | Error::DatabaseLoadFailed(_)
+            | Error::IntegrityCheckFailed
+            | Error::InvalidInput(_) => false,
+        }
+    }
+
+    /// Whether the error was caused by the user rather than a system failure
+    ///
+    /// User errors (unknown key, wrong password, missing `ik init`, bad input)
+    /// are fixed by changing the command; everything else points at the
+    /// environment or a damaged vault.
+    #[allow(dead_code)] // Public API - may be used by external consumers
+    pub fn is_user_error(&self) -> bool {
+        match self {
+            Error::EntryNotFound(_)
+            | Error::EntryAlreadyExists(_)
+            | Error::EntryLocked(_)
+            | Error::InvalidMasterPassword
+            | Error::MasterKeyNotInitialized
+            | Error::MasterKeyAlreadyExists
+            | Error::EmptyPassword
+            | Error::DatabaseNotFound
+            | Error::InvalidInput(_) => true,
+
+            Error::KeyMismatch
+            | Error::EncryptionFailed(_)
+            | Error::DecryptionFailed(_)
+            | Error::KeyDerivationFailed(_)
+            | Error::DatabaseLoadFailed(_)
+            | Error::DatabaseSaveFailed(_)
+            | Error::IntegrityCheckFailed
+            | Error::Io(_) => false,
+        }
+    }
+
+    /// Process exit code for this error, so scripts can branch on `$?`
+    ///
+    /// Codes are stable: 2 invalid master password, 3 entry not found,
+    /// 4 entry locked, 5 I/O error. Everything else exits with 1.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::InvalidMasterPassword => 2,
+            Error::EntryNotFound(_) => 3,
+            Error::EntryLocked(_) => 4,
+            Error::Io(_) => 5,
+
+            Error::EntryAlreadyExists(_)
+            | Error::MasterKeyNotInitialized
+            | Error::MasterKeyAlreadyExists
+            | Error::EmptyPassword
+            | Error::KeyMismatch
+            | Error::EncryptionFailed(_)
+            | Error::DecryptionFailed(_)
+            | Error::KeyDerivationFailed(_)
+            | Error::DatabaseNotFound
+            | Error::DatabaseLoadFailed(_)
+            | Error::DatabaseSaveFailed(_)
+            | Error::IntegrityCheckFailed
+            | Error::InvalidInput(_) => 1,
+        }
+    }
+
+    /// Stable name of the error kind, for machine-readable (`--json`) errors
+    ///
+    /// Matches the variant name and won't change, unlike the Display message.
+    pub fn kind_str(&self) -> &'static str {
+        match self {
+            Error::EntryNotFound(_) => "EntryNotFound",
+            Error::EntryAlreadyExists(_) => "EntryAlreadyExists",
+            Error::EntryLocked(_) => "EntryLocked",
+            Error::InvalidMasterPassword => "InvalidMasterPassword",
+            Error::MasterKeyNotInitialized => "MasterKeyNotInitialized",
+            Error::MasterKeyAlreadyExists => "MasterKeyAlreadyExists",
+            Error::EmptyPassword => "EmptyPassword",
+            Error::KeyMismatch => "KeyMismatch",
+            Error::EncryptionFailed(_) => "EncryptionFailed",
+            Error::DecryptionFailed(_) => "DecryptionFailed",
+            Error::KeyDerivationFailed(_) => "KeyDerivationFailed",
+            Error::DatabaseNotFound => "DatabaseNotFound",
+            Error::DatabaseLoadFailed(_) => "DatabaseLoadFailed",
+            Error::DatabaseSaveFailed(_) => "DatabaseSaveFailed",
+            Error::IntegrityCheckFailed => "IntegrityCheckFailed",
+            Error::Io(_) => "Io",
+            Error::InvalidInput(_) => "InvalidInput",
+        }
+    }
+}
+
 impl std::error::Error for Error {}
 
 // Conversions from other error types
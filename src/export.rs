@@ -1,15 +1,21 @@
 use crate::crypto;
 use crate::error::{Error, Result};
-use crate::storage::Database;
+use crate::storage::{Database, Entry};
 use base64::{Engine as _, engine::general_purpose};
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
-use std::path::Path;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use zeroize::{Zeroize, Zeroizing};
 
 /// Format version for export files
 pub const EXPORT_FORMAT_VERSION: &str = "1.0.0";
 
+/// Format version for exports whose payload is a `DedupPayload`
+pub const EXPORT_FORMAT_VERSION_DEDUP: &str = "1.1.0";
+
 /// Encryption metadata for export file
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ExportEncryption {
@@ -17,33 +23,190 @@ pub struct ExportEncryption {
     pub salt: String,  // Base64-encoded
     pub nonce: String, // Base64-encoded
     pub iterations: u32,
+    /// The entries JSON was gzip-compressed before encryption (`ik export --compress`)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub compressed: bool,
 }
 
-/// Metadata about the export
-#[derive(Debug, Serialize, Deserialize)]
+/// Metadata about the export (every field is optional, see `ExportOptions::minimal`)
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct ExportMetadata {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub exported_from: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub vault_name: Option<String>, // TODO: Multiple vaults support
-    pub tags: Option<Vec<String>>,  // TODO: Tag filtering support
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>, // Every tag used by the exported entries
+}
+
+impl ExportMetadata {
+    fn is_empty(&self) -> bool {
+        self.exported_from.is_empty() && self.vault_name.is_none() && self.tags.is_none()
+    }
 }
 
 /// Export file structure
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ExportFile {
     pub format_version: String,
-    pub exported_at: String, // ISO 8601 timestamp
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub exported_at: String, // ISO 8601 timestamp (empty in minimal exports)
     pub entry_count: usize,
     pub encryption: ExportEncryption,
     pub encrypted_data: String, // Base64-encoded encrypted entry data
+    #[serde(default, skip_serializing_if = "ExportMetadata::is_empty")]
     pub metadata: ExportMetadata,
 }
 
+/// Optional export behaviours
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ExportOptions {
+    /// Store identical values once (format version `EXPORT_FORMAT_VERSION_DEDUP`)
+    pub dedup: bool,
+    /// Omit metadata that isn't needed to import (ironkey version, export time)
+    pub minimal: bool,
+    /// Gzip the entries before encrypting them (smaller files for large vaults)
+    pub compress: bool,
+}
+
+/// Summary of what an export would contain, without decrypting anything
+///
+/// Serializable for machine-readable (`--json`) output.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ExportPreview {
+    pub format_version: String,
+    pub total: usize,
+    pub locked: usize,
+    /// Entry keys in sorted order
+    pub keys: Vec<String>,
+}
+
+impl ExportPreview {
+    /// Describe the export of `db` with the given options
+    ///
+    /// Passphrase-locked entries are left out, like in the export itself.
+    pub fn new(db: &Database, options: ExportOptions) -> Self {
+        let exported = || {
+            db.entries
+                .iter()
+                .filter(|(_, entry)| !entry.is_passphrase_locked())
+        };
+        let mut keys: Vec<String> = exported().map(|(key, _)| key.clone()).collect();
+        keys.sort();
+
+        Self {
+            format_version: if options.dedup {
+                EXPORT_FORMAT_VERSION_DEDUP
+            } else {
+                EXPORT_FORMAT_VERSION
+            }
+            .to_string(),
+            total: keys.len(),
+            locked: exported().filter(|(_, entry)| entry.is_locked).count(),
+            keys,
+        }
+    }
+
+    /// Describe an export of already collected entries (see `collect_export_entries`)
+    pub fn from_entries(entries: &[ExportEntry], options: ExportOptions) -> Self {
+        let mut keys: Vec<String> = entries.iter().map(|entry| entry.key.clone()).collect();
+        keys.sort();
+
+        Self {
+            format_version: if options.dedup {
+                EXPORT_FORMAT_VERSION_DEDUP
+            } else {
+                EXPORT_FORMAT_VERSION
+            }
+            .to_string(),
+            total: keys.len(),
+            locked: entries.iter().filter(|entry| entry.locked).count(),
+            keys,
+        }
+    }
+}
+
 /// Entry data in the decrypted export (internal)
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ExportEntry {
     pub key: String,
     pub value: String, // Decrypted value
     pub locked: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+}
+
+/// Deduplicated export payload: each distinct value is stored once
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DedupPayload {
+    pub values: Vec<String>,
+    pub entries: Vec<DedupEntry>,
+}
+
+/// Entry in a deduplicated export, referencing a value by index
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DedupEntry {
+    pub key: String,
+    pub value_ref: usize,
+    pub locked: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+}
+
+impl DedupPayload {
+    /// Build the reference table, storing identical values once
+    pub fn from_entries(entries: Vec<ExportEntry>) -> Self {
+        let mut values: Vec<String> = Vec::new();
+        let mut index: HashMap<String, usize> = HashMap::new();
+        let mut dedup_entries = Vec::with_capacity(entries.len());
+
+        for entry in entries {
+            let value_ref = *index.entry(entry.value).or_insert_with_key(|value| {
+                values.push(value.clone());
+                values.len() - 1
+            });
+
+            dedup_entries.push(DedupEntry {
+                key: entry.key,
+                value_ref,
+                locked: entry.locked,
+                label: entry.label,
+                tags: entry.tags,
+            });
+        }
+
+        Self {
+            values,
+            entries: dedup_entries,
+        }
+    }
+
+    /// Expand references back into full entries
+    pub fn into_entries(self) -> Result<Vec<ExportEntry>> {
+        self.entries
+            .into_iter()
+            .map(|entry| {
+                let value = self.values.get(entry.value_ref).ok_or_else(|| {
+                    Error::Io(format!(
+                        "Invalid value reference {} for entry '{}'",
+                        entry.value_ref, entry.key
+                    ))
+                })?;
+
+                Ok(ExportEntry {
+                    key: entry.key,
+                    value: value.clone(),
+                    locked: entry.locked,
+                    label: entry.label,
+                    tags: entry.tags,
+                })
+            })
+            .collect()
+    }
 }
 
 /// Export vault entries to encrypted file
@@ -54,6 +217,11 @@ pub struct ExportEntry {
 /// * `output_path` - Path where export file will be written
 /// * `export_password` - Password to encrypt the export file
 /// * `force` - Whether to overwrite existing file
+/// * `options` - Dedup/minimal export options
+/// * `keys` - Only export these entries (None exports every entry)
+///
+/// # Errors
+/// `Error::EntryNotFound` if a requested key isn't in the vault
 ///
 /// # Security
 /// - Uses same PBKDF2 + AES-256-GCM as vault
@@ -65,6 +233,8 @@ pub fn export_vault(
     output_path: &Path,
     export_password: String,
     force: bool,
+    options: ExportOptions,
+    keys: Option<&[String]>,
 ) -> Result<()> {
     // Check if file exists (unless force is true)
     if !force && output_path.exists() {
@@ -74,10 +244,64 @@ pub fn export_vault(
         )));
     }
 
-    // Decrypt all entries from the vault
+    let mut buffer = Vec::new();
+    export_vault_to_writer(db, master_key, &mut buffer, export_password, options, keys)?;
+
+    write_private_file(output_path, &buffer)?;
+
+    Ok(())
+}
+
+/// Decrypt the entries an export would contain
+///
+/// This is the collection phase of `export_vault`, shared with `ik export --diff`.
+///
+/// # Arguments
+/// * `keys` - Only collect these entries (None collects every entry)
+///
+/// Passphrase-locked entries (`ik lock --password`) are never exported: they are
+/// left out of a full export, and requesting one by key is an error.
+///
+/// # Errors
+/// `Error::EntryNotFound` if a requested key isn't in the vault, and
+/// `Error::EntryLocked` if it is passphrase-locked
+pub fn collect_export_entries(
+    db: &Database,
+    master_key: &[u8],
+    keys: Option<&[String]>,
+) -> Result<Vec<ExportEntry>> {
+    // Pick the entries to export, checking every requested key up front
+    let selected: Vec<(&String, &Entry)> = match keys {
+        Some(keys) => {
+            let mut selected = keys
+                .iter()
+                .map(|key| {
+                    let selected = db
+                        .entries
+                        .get_key_value(key)
+                        .ok_or_else(|| Error::EntryNotFound(key.clone()))?;
+                    if selected.1.is_passphrase_locked() {
+                        return Err(Error::EntryLocked(key.clone()));
+                    }
+                    Ok(selected)
+                })
+                .collect::<Result<Vec<_>>>()?;
+            selected.sort_by(|a, b| a.0.cmp(b.0));
+            selected.dedup_by(|a, b| a.0 == b.0);
+            selected
+        }
+        None => db
+            .entries
+            .iter()
+            .filter(|(_, entry)| !entry.is_passphrase_locked())
+            .collect(),
+    };
+
+    // Decrypt the selected entries, reusing one scratch buffer
     let mut export_entries: Vec<ExportEntry> = Vec::new();
+    let mut scratch = Vec::new();
 
-    for (key, entry) in &db.entries {
+    for (key, entry) in selected {
         // Decrypt the entry value using master key
         let encrypted_value = entry.get_encrypted_value()?;
         let nonce_bytes = entry.get_nonce()?;
@@ -88,20 +312,59 @@ pub fn export_vault(
             nonce: nonce_bytes,
         };
 
-        let decrypted_value = crypto::decrypt(&encrypted_data, master_key)?;
-        let value = String::from_utf8(decrypted_value)
-            .map_err(|e| Error::DecryptionFailed(format!("Invalid UTF-8: {e}")))?;
+        let entry_key = db.entry_encryption_key(master_key, key);
+        crypto::decrypt_into(&encrypted_data, &entry_key, &mut scratch)?;
+        let value = std::str::from_utf8(&scratch)
+            .map_err(|e| Error::DecryptionFailed(format!("Invalid UTF-8: {e}")))?
+            .to_string();
 
         export_entries.push(ExportEntry {
             key: key.clone(),
             value,
             locked: entry.is_locked,
+            label: entry.label.clone(),
+            tags: entry.tags.clone(),
         });
     }
+    scratch.zeroize();
+
+    Ok(export_entries)
+}
+
+/// Export vault entries as encrypted .ik JSON to any writer
+///
+/// Produces exactly the same format as `export_vault`, without touching the filesystem.
+pub fn export_vault_to_writer<W: Write>(
+    db: &Database,
+    master_key: &[u8],
+    mut writer: W,
+    export_password: String,
+    options: ExportOptions,
+    keys: Option<&[String]>,
+) -> Result<()> {
+    let export_password = Zeroizing::new(export_password);
+    let export_entries = collect_export_entries(db, master_key, keys)?;
+
+    let entry_count = export_entries.len();
+    let mut all_tags: Vec<String> = export_entries
+        .iter()
+        .flat_map(|entry| entry.tags.iter().cloned())
+        .collect();
+    all_tags.sort();
+    all_tags.dedup();
 
     // Serialize entries to JSON
-    let entries_json = serde_json::to_string(&export_entries)
-        .map_err(|e| Error::Io(format!("Failed to serialize entries: {e}")))?;
+    let (format_version, entries_json) = if options.dedup {
+        let payload = DedupPayload::from_entries(export_entries);
+        (EXPORT_FORMAT_VERSION_DEDUP, serde_json::to_string(&payload))
+    } else {
+        (
+            EXPORT_FORMAT_VERSION,
+            serde_json::to_string(&export_entries),
+        )
+    };
+    let entries_json =
+        entries_json.map_err(|e| Error::Io(format!("Failed to serialize entries: {e}")))?;
 
     // Generate salt for export encryption
     let export_salt = crypto::generate_salt()?;
@@ -110,39 +373,552 @@ pub fn export_vault(
     // Derive key from export password
     let export_key = crypto::derive_key(&export_password, &export_salt, iterations)?;
 
-    // Encrypt the entries JSON
-    let entries_bytes = entries_json.as_bytes();
-    let encrypted = crypto::encrypt(entries_bytes, &export_key)?;
+    // Encrypt the entries JSON (compressed first if requested: ciphertext doesn't compress)
+    let encrypted = if options.compress {
+        let mut compressed = gzip(entries_json.as_bytes())?;
+        let encrypted = crypto::encrypt(&compressed, &export_key);
+        compressed.zeroize();
+        encrypted?
+    } else {
+        crypto::encrypt(entries_json.as_bytes(), &export_key)?
+    };
 
     // Create export file structure
+    let (exported_at, metadata) = if options.minimal {
+        (String::new(), ExportMetadata::default())
+    } else {
+        (
+            Utc::now().to_rfc3339(),
+            ExportMetadata {
+                exported_from: format!("ironkey v{}", env!("CARGO_PKG_VERSION")),
+                vault_name: None, // TODO: Multiple vaults
+                tags: (!all_tags.is_empty()).then_some(all_tags),
+            },
+        )
+    };
+
     let export_file = ExportFile {
-        format_version: EXPORT_FORMAT_VERSION.to_string(),
-        exported_at: Utc::now().to_rfc3339(),
-        entry_count: export_entries.len(),
+        format_version: format_version.to_string(),
+        exported_at,
+        entry_count,
         encryption: ExportEncryption {
             algorithm: "AES-256-GCM".to_string(),
             salt: general_purpose::STANDARD.encode(&export_salt),
             nonce: general_purpose::STANDARD.encode(&encrypted.nonce),
             iterations,
+            compressed: options.compress,
         },
         encrypted_data: general_purpose::STANDARD.encode(&encrypted.ciphertext),
-        metadata: ExportMetadata {
-            exported_from: format!("ironkey v{}", env!("CARGO_PKG_VERSION")),
-            vault_name: None, // TODO: Multiple vaults
-            tags: None,       // TODO: Tag filtering
-        },
+        metadata,
     };
 
-    // Serialize to JSON and write to file
+    // Serialize to JSON and write out
     let export_json = serde_json::to_string_pretty(&export_file)
         .map_err(|e| Error::Io(format!("Failed to serialize export file: {e}")))?;
 
-    fs::write(output_path, export_json)
-        .map_err(|e| Error::Io(format!("Failed to write export file: {e}")))?;
+    writer
+        .write_all(export_json.as_bytes())
+        .map_err(|e| Error::Io(format!("Failed to write export data: {e}")))?;
+
+    Ok(())
+}
+
+/// Gzip an export payload before it is encrypted
+fn gzip(data: &[u8]) -> Result<Vec<u8>> {
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder
+        .write_all(data)
+        .and_then(|()| encoder.finish())
+        .map_err(|e| Error::Io(format!("Failed to compress entries: {e}")))
+}
+
+/// Export vault entries as a single base64 line (for text-only transport)
+///
+/// Base64 is NOT encryption: the wrapped .ik JSON is already encrypted
+/// with the export password, base64 only makes it copy/paste friendly.
+pub fn export_vault_to_base64(
+    db: &Database,
+    master_key: &[u8],
+    export_password: String,
+) -> Result<String> {
+    let mut buffer = Vec::new();
+    export_vault_to_writer(
+        db,
+        master_key,
+        &mut buffer,
+        export_password,
+        ExportOptions::default(),
+        None,
+    )?;
+
+    Ok(general_purpose::STANDARD.encode(buffer))
+}
+
+/// Outcome of `export_vault_to_dir`
+#[derive(Debug, Default)]
+pub struct DirExportResult {
+    /// Entries written, as (key, file path), sorted by key
+    pub written: Vec<(String, PathBuf)>,
+    /// Locked entries that were not written
+    pub skipped_locked: Vec<String>,
+}
+
+/// Turn an entry key into a safe file name
+///
+/// Anything outside `[A-Za-z0-9._-]` becomes `_`, and names that would be
+/// hidden or special (`.`, `..`, leading dot, empty) get a `_` prefix.
+pub fn sanitize_filename(key: &str) -> String {
+    let name: String = key
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+
+    if name.is_empty() || name.starts_with('.') {
+        format!("_{name}")
+    } else {
+        name
+    }
+}
+
+/// File name of the lock-status sidecar written by `export_vault_to_dir`
+pub const DIR_MANIFEST_FILE: &str = "manifest.json";
+
+/// Contents of `manifest.json` in a `--to-dir` export
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DirManifest {
+    pub entries: Vec<DirManifestEntry>,
+}
+
+/// One entry of a `DirManifest`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DirManifestEntry {
+    pub key: String,
+    /// File holding the value (None for locked entries, which aren't written)
+    pub file: Option<String>,
+    pub locked: bool,
+}
+
+/// Write each unlocked entry's decrypted value to `<dir>/<sanitized-key>`, plus a `manifest.json`
+///
+/// Files are created with 0600 permissions (and a new directory with 0700) on Unix.
+/// Nothing is written if two keys sanitize to the same file name (or to the
+/// manifest's), or if a target file already exists and `force` is false. The
+/// manifest maps every key (locked ones included) to its file and lock status,
+/// so nothing is lost to sanitization.
+///
+/// # Security
+/// The values are written UNENCRYPTED. Callers must get explicit consent first.
+pub fn export_vault_to_dir(
+    db: &Database,
+    master_key: &[u8],
+    dir: &Path,
+    force: bool,
+) -> Result<DirExportResult> {
+    let result = plan_dir_export(db, dir, force)?;
+
+    let manifest_path = dir.join(DIR_MANIFEST_FILE);
+    if !force && manifest_path.exists() {
+        return Err(Error::Io(format!(
+            "File '{}' already exists. Use --force to overwrite",
+            manifest_path.display()
+        )));
+    }
+
+    let file_name = |path: &PathBuf| {
+        path.file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+    };
+    let mut entries: Vec<DirManifestEntry> = result
+        .written
+        .iter()
+        .map(|(key, path)| DirManifestEntry {
+            key: key.clone(),
+            file: file_name(path),
+            locked: false,
+        })
+        .chain(result.skipped_locked.iter().map(|key| DirManifestEntry {
+            key: key.clone(),
+            file: None,
+            locked: true,
+        }))
+        .collect();
+    entries.sort_by(|a, b| a.key.cmp(&b.key));
+
+    let manifest = serde_json::to_string_pretty(&DirManifest { entries })
+        .map_err(|e| Error::Io(format!("Failed to serialize manifest: {e}")))?;
+
+    write_dir_export(db, master_key, dir, &result)?;
+    write_private_file(&manifest_path, manifest.as_bytes())?;
+
+    Ok(result)
+}
+
+/// Pack `dir` into a gzipped tarball at `archive` (0600 on Unix)
+///
+/// Entries are stored relative to the directory's own name, e.g. `out/github`.
+pub fn archive_directory(dir: &Path, archive: &Path, force: bool) -> Result<()> {
+    if !force && archive.exists() {
+        return Err(Error::Io(format!(
+            "File '{}' already exists. Use --force to overwrite",
+            archive.display()
+        )));
+    }
+
+    let root = dir
+        .file_name()
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("export"));
+
+    let mut builder = tar::Builder::new(flate2::write::GzEncoder::new(
+        Vec::new(),
+        flate2::Compression::default(),
+    ));
+    builder
+        .append_dir_all(&root, dir)
+        .map_err(|e| Error::Io(format!("Failed to archive '{}': {e}", dir.display())))?;
+    let mut compressed = builder
+        .into_inner()
+        .and_then(|encoder| encoder.finish())
+        .map_err(|e| Error::Io(format!("Failed to archive '{}': {e}", dir.display())))?;
+
+    let written = write_private_file(archive, &compressed);
+    compressed.zeroize();
+    written
+}
+
+/// Work out every target file before anything is written
+///
+/// Fails on sanitized-name collisions and (without `force`) existing files.
+fn plan_dir_export(db: &Database, dir: &Path, force: bool) -> Result<DirExportResult> {
+    let mut result = DirExportResult::default();
+    let mut targets: HashMap<String, &str> = HashMap::new();
+
+    let mut keys: Vec<&String> = db.entries.keys().collect();
+    keys.sort();
+
+    for key in keys {
+        if db.entries[key].is_locked {
+            result.skipped_locked.push(key.clone());
+            continue;
+        }
+
+        let name = sanitize_filename(key);
+        if name == DIR_MANIFEST_FILE {
+            return Err(Error::InvalidInput(format!(
+                "Entry '{key}' would overwrite the export's {DIR_MANIFEST_FILE}"
+            )));
+        }
+        if let Some(other) = targets.insert(name.clone(), key) {
+            return Err(Error::InvalidInput(format!(
+                "Entries '{other}' and '{key}' would both be written to '{name}'"
+            )));
+        }
+
+        let path = dir.join(&name);
+        if !force && path.exists() {
+            return Err(Error::Io(format!(
+                "File '{}' already exists. Use --force to overwrite",
+                path.display()
+            )));
+        }
+        result.written.push((key.clone(), path));
+    }
+
+    Ok(result)
+}
+
+/// Decrypt and write the files planned by `plan_dir_export`
+fn write_dir_export(
+    db: &Database,
+    master_key: &[u8],
+    dir: &Path,
+    planned: &DirExportResult,
+) -> Result<()> {
+    if !dir.exists() {
+        create_private_dir(dir)?;
+    }
+
+    let mut scratch = Vec::new();
+    for (key, path) in &planned.written {
+        let entry = &db.entries[key];
+        let encrypted_data = crypto::EncryptedData {
+            ciphertext: entry.get_encrypted_value()?,
+            nonce: entry.get_nonce()?,
+        };
+
+        let entry_key = db.entry_encryption_key(master_key, key);
+        crypto::decrypt_into(&encrypted_data, &entry_key, &mut scratch)?;
+        write_private_file(path, &scratch)?;
+    }
+    scratch.zeroize();
 
     Ok(())
 }
 
+/// Plaintext config-style export formats (`ik export --format toml|yaml`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlainFormat {
+    Toml,
+    Yaml,
+}
+
+impl PlainFormat {
+    /// File extension for this format
+    pub fn extension(self) -> &'static str {
+        match self {
+            PlainFormat::Toml => "toml",
+            PlainFormat::Yaml => "yaml",
+        }
+    }
+}
+
+/// Outcome of `export_vault_plain`
+#[derive(Debug, Default)]
+pub struct PlainExportResult {
+    /// Number of entries written
+    pub written: usize,
+    /// Keys that had to be sanitized, as (original key, written key)
+    pub renamed: Vec<(String, String)>,
+    /// Locked entries that were not written
+    pub skipped_locked: Vec<String>,
+}
+
+/// Turn an entry key into a plain identifier for config-style formats
+///
+/// TOML bare keys allow `[A-Za-z0-9_-]`; for YAML we stick to `[A-Za-z0-9_]`
+/// so keys never need quoting. Anything else becomes `_`, and a key that
+/// would start with a digit (or be empty) gets a `_` prefix.
+pub fn sanitize_identifier(key: &str, format: PlainFormat) -> String {
+    let name: String = key
+        .chars()
+        .map(|c| match c {
+            c if c.is_ascii_alphanumeric() || c == '_' => c,
+            '-' if format == PlainFormat::Toml => c,
+            _ => '_',
+        })
+        .collect();
+
+    if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("_{name}")
+    } else {
+        name
+    }
+}
+
+/// Render every unlocked entry as a flat `key = value` TOML table or `key: value` YAML map
+///
+/// Values are escaped by the format's serializer. Fails without rendering anything
+/// if two keys sanitize to the same identifier.
+///
+/// # Security
+/// The returned text contains the values UNENCRYPTED.
+pub fn export_vault_plain(
+    db: &Database,
+    master_key: &[u8],
+    format: PlainFormat,
+) -> Result<(String, PlainExportResult)> {
+    let mut result = PlainExportResult::default();
+    let mut values: BTreeMap<String, String> = BTreeMap::new();
+    let mut sources: HashMap<String, &str> = HashMap::new();
+
+    let mut keys: Vec<&String> = db.entries.keys().collect();
+    keys.sort();
+
+    let mut scratch = Vec::new();
+    for key in keys {
+        let entry = &db.entries[key];
+        if entry.is_locked {
+            result.skipped_locked.push(key.clone());
+            continue;
+        }
+
+        let name = sanitize_identifier(key, format);
+        if let Some(other) = sources.insert(name.clone(), key) {
+            return Err(Error::InvalidInput(format!(
+                "Entries '{other}' and '{key}' would both be exported as '{name}'"
+            )));
+        }
+        if &name != key {
+            result.renamed.push((key.clone(), name.clone()));
+        }
+
+        let encrypted_data = crypto::EncryptedData {
+            ciphertext: entry.get_encrypted_value()?,
+            nonce: entry.get_nonce()?,
+        };
+        let entry_key = db.entry_encryption_key(master_key, key);
+        crypto::decrypt_into(&encrypted_data, &entry_key, &mut scratch)?;
+        let value = std::str::from_utf8(&scratch)
+            .map_err(|e| Error::DecryptionFailed(format!("Invalid UTF-8: {e}")))?
+            .to_string();
+        values.insert(name, value);
+    }
+    scratch.zeroize();
+    result.written = values.len();
+
+    let rendered = match format {
+        PlainFormat::Toml => toml::to_string(&values).map_err(|e| e.to_string()),
+        PlainFormat::Yaml => serde_yaml::to_string(&values).map_err(|e| e.to_string()),
+    };
+    for value in values.values_mut() {
+        value.zeroize();
+    }
+
+    let rendered = rendered.map_err(|e| Error::Io(format!("Failed to serialize entries: {e}")))?;
+    Ok((rendered, result))
+}
+
+/// Write `export_vault_plain` output to a file readable only by the owner
+pub fn export_vault_plain_to_file(
+    db: &Database,
+    master_key: &[u8],
+    output_path: &Path,
+    format: PlainFormat,
+    force: bool,
+) -> Result<PlainExportResult> {
+    if !force && output_path.exists() {
+        return Err(Error::Io(format!(
+            "File '{}' already exists. Use --force to overwrite",
+            output_path.display()
+        )));
+    }
+
+    let (mut rendered, result) = export_vault_plain(db, master_key, format)?;
+    let written = write_private_file(output_path, rendered.as_bytes());
+    rendered.zeroize();
+    written?;
+
+    Ok(result)
+}
+
+/// Quote a CSV field per RFC 4180 when it contains a comma, quote or line break
+///
+/// Embedded quotes are doubled.
+pub fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Render every entry as `key,value,locked` CSV rows (RFC 4180, CRLF line endings)
+///
+/// Locked entries are included with `locked` set to `true`; the lock column lets
+/// another tool (or a later import) keep them locked. Passphrase-locked entries
+/// are left out entirely.
+///
+/// # Security
+/// The returned text contains the values UNENCRYPTED.
+///
+/// # Returns
+/// The CSV text and the number of entries written
+pub fn export_vault_csv(db: &Database, master_key: &[u8]) -> Result<(String, usize)> {
+    let mut keys: Vec<&String> = db
+        .entries
+        .iter()
+        .filter(|(_, entry)| !entry.is_passphrase_locked())
+        .map(|(key, _)| key)
+        .collect();
+    keys.sort();
+
+    let mut rendered = String::from("key,value,locked\r\n");
+    let mut scratch = Vec::new();
+    for key in &keys {
+        let entry = &db.entries[*key];
+        let encrypted_data = crypto::EncryptedData {
+            ciphertext: entry.get_encrypted_value()?,
+            nonce: entry.get_nonce()?,
+        };
+        let entry_key = db.entry_encryption_key(master_key, key);
+        crypto::decrypt_into(&encrypted_data, &entry_key, &mut scratch)?;
+        let value = std::str::from_utf8(&scratch)
+            .map_err(|e| Error::DecryptionFailed(format!("Invalid UTF-8: {e}")))?;
+
+        let mut field = csv_field(value);
+        rendered.push_str(&csv_field(key));
+        rendered.push(',');
+        rendered.push_str(&field);
+        rendered.push(',');
+        rendered.push_str(if entry.is_locked { "true" } else { "false" });
+        rendered.push_str("\r\n");
+        field.zeroize();
+    }
+    scratch.zeroize();
+
+    Ok((rendered, keys.len()))
+}
+
+/// Write `export_vault_csv` output to a file readable only by the owner
+///
+/// # Returns
+/// The number of entries written
+pub fn export_plaintext_csv(
+    db: &Database,
+    master_key: &[u8],
+    output_path: &Path,
+    force: bool,
+) -> Result<usize> {
+    if !force && output_path.exists() {
+        return Err(Error::Io(format!(
+            "File '{}' already exists. Use --force to overwrite",
+            output_path.display()
+        )));
+    }
+
+    let (mut rendered, written) = export_vault_csv(db, master_key)?;
+    let result = write_private_file(output_path, rendered.as_bytes());
+    rendered.zeroize();
+    result?;
+
+    Ok(written)
+}
+
+#[cfg(unix)]
+fn create_private_dir(dir: &Path) -> Result<()> {
+    use std::os::unix::fs::DirBuilderExt;
+
+    fs::DirBuilder::new()
+        .recursive(true)
+        .mode(0o700)
+        .create(dir)
+        .map_err(|e| Error::Io(format!("Failed to create '{}': {e}", dir.display())))
+}
+
+#[cfg(not(unix))]
+fn create_private_dir(dir: &Path) -> Result<()> {
+    fs::create_dir_all(dir)
+        .map_err(|e| Error::Io(format!("Failed to create '{}': {e}", dir.display())))
+}
+
+/// Write a file readable only by the owner (permissions are reset if it already existed)
+fn write_private_file(path: &Path, contents: &[u8]) -> Result<()> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+
+        options.mode(0o600);
+        if path.exists() {
+            fs::set_permissions(path, fs::Permissions::from_mode(0o600))?;
+        }
+    }
+
+    let mut file = options
+        .open(path)
+        .map_err(|e| Error::Io(format!("Failed to write '{}': {e}", path.display())))?;
+    file.write_all(contents)
+        .map_err(|e| Error::Io(format!("Failed to write '{}': {e}", path.display())))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -158,6 +934,8 @@ mod tests {
             key: "test".to_string(),
             value: "password123".to_string(),
             locked: false,
+            label: None,
+            tags: Vec::new(),
         };
 
         let json = serde_json::to_string(&entry).unwrap();
@@ -179,6 +957,7 @@ mod tests {
                 salt: "dGVzdHNhbHQ=".to_string(),
                 nonce: "dGVzdG5vbmNl".to_string(),
                 iterations: 100000,
+                compressed: false,
             },
             encrypted_data: "ZW5jcnlwdGVkZGF0YQ==".to_string(),
             metadata: ExportMetadata {
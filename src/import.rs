@@ -1,23 +1,39 @@
 //! Import Module
 //!
-//! Handles importing vault entries from encrypted .ik export files.
+//! Handles importing vault entries from encrypted .ik export files
+//! (or plaintext JSON entry lists, CSV and dotenv files when explicitly requested).
 //! Supports merge, replace, and diff (dry-run) strategies.
 
 use crate::crypto::{self, EncryptedData};
 use crate::error::{Error, Result};
-use crate::export::{EXPORT_FORMAT_VERSION, ExportEntry, ExportFile};
+use crate::export::{
+    DedupPayload, EXPORT_FORMAT_VERSION, EXPORT_FORMAT_VERSION_DEDUP, ExportEntry, ExportFile,
+};
 use crate::storage::{Database, Entry};
 use base64::{Engine, engine::general_purpose::STANDARD as BASE64};
+use rayon::prelude::*;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
 use std::fs;
+use std::io::Read;
 use std::path::Path;
+use zeroize::Zeroize;
 
 /// Import Strategy Result
 /// Contains information about what happened during import
-#[derive(Debug)]
+///
+/// Serializes as the `ik import --json` output.
+#[derive(Debug, Serialize)]
 pub struct ImportResult {
     pub added: Vec<String>,
     pub updated: Vec<String>,
     pub skipped: Vec<String>,
+    /// Entries removed because they were missing from the export (mirror mode only)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub deleted: Vec<String>,
+    /// Requested keys (`--key`) that the export doesn't contain
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub not_found: Vec<String>,
     pub total_in_export: usize,
 }
 
@@ -27,6 +43,8 @@ impl ImportResult {
             added: Vec::new(),
             updated: Vec::new(),
             skipped: Vec::new(),
+            deleted: Vec::new(),
+            not_found: Vec::new(),
             total_in_export,
         }
     }
@@ -42,10 +60,13 @@ impl ImportResult {
 /// * `merge` - If true, add new entries but skip existing ones
 /// * `replace` - If true, overwrite existing entries with imported ones
 /// * `diff` - If true, dry-run mode (show what would be imported without making changes)
+/// * `keys` - Only import these entries (None imports every entry); requested keys
+///   missing from the export are listed in `ImportResult::not_found`
 ///
 /// # Returns
 /// * `Ok(ImportResult)` - Information about what was imported
 /// * `Err(Error)` - If import fails
+#[allow(clippy::too_many_arguments)] // One flag per `ik import` option
 pub fn import_vault(
     import_path: &Path,
     import_password: String,
@@ -54,21 +75,136 @@ pub fn import_vault(
     merge: bool,
     replace: bool,
     diff: bool,
+    keys: Option<&[String]>,
 ) -> Result<ImportResult> {
-    // Read and parse the export file
-    let export_data = fs::read_to_string(import_path)
+    let file = fs::File::open(import_path)
+        .map_err(|e| Error::Io(format!("Failed to read import file: {e}")))?;
+
+    import_vault_from_reader_with_keys(
+        file,
+        import_password,
+        current_db,
+        master_key,
+        merge,
+        replace,
+        diff,
+        keys,
+    )
+}
+
+/// Import vault entries from .ik JSON read from any reader, optionally only some keys
+///
+/// Same semantics as `import_vault` (which wraps it), without touching the filesystem.
+#[allow(clippy::too_many_arguments)] // One flag per `ik import` option
+pub fn import_vault_from_reader_with_keys<R: Read>(
+    reader: R,
+    import_password: String,
+    current_db: &mut Database,
+    master_key: &[u8],
+    merge: bool,
+    replace: bool,
+    diff: bool,
+    keys: Option<&[String]>,
+) -> Result<ImportResult> {
+    let entries = decrypt_export(reader, import_password)?;
+    let Some(keys) = keys else {
+        return apply_entries(entries, current_db, master_key, merge, replace, diff);
+    };
+
+    let total_in_export = entries.len();
+    let (entries, not_found) = select_entries(entries, keys);
+    let mut result = apply_entries(entries, current_db, master_key, merge, replace, diff)?;
+    result.total_in_export = total_in_export;
+    result.not_found = not_found;
+
+    Ok(result)
+}
+
+/// Keep only the entries named in `keys`
+///
+/// # Returns
+/// The selected entries and the requested keys that weren't found (sorted, unique)
+fn select_entries(entries: Vec<ExportEntry>, keys: &[String]) -> (Vec<ExportEntry>, Vec<String>) {
+    let wanted: HashSet<&str> = keys.iter().map(String::as_str).collect();
+    let selected: Vec<ExportEntry> = entries
+        .into_iter()
+        .filter(|entry| wanted.contains(entry.key.as_str()))
+        .collect();
+
+    let found: HashSet<&str> = selected.iter().map(|entry| entry.key.as_str()).collect();
+    let mut not_found: Vec<String> = wanted
+        .difference(&found)
+        .map(|key| key.to_string())
+        .collect();
+    not_found.sort();
+
+    (selected, not_found)
+}
+
+/// Import vault entries from a base64-wrapped .ik payload
+///
+/// Counterpart of `export::export_vault_to_base64`. Surrounding whitespace is ignored.
+pub fn import_vault_from_base64(
+    encoded: &str,
+    import_password: String,
+    current_db: &mut Database,
+    master_key: &[u8],
+    merge: bool,
+    replace: bool,
+    diff: bool,
+) -> Result<ImportResult> {
+    let decoded = BASE64
+        .decode(encoded.trim())
+        .map_err(|e| Error::Io(format!("Failed to decode base64 import data: {e}")))?;
+
+    import_vault_from_reader(
+        decoded.as_slice(),
+        import_password,
+        current_db,
+        master_key,
+        merge,
+        replace,
+        diff,
+    )
+}
+
+/// Import vault entries from .ik JSON read from any reader
+///
+/// Same semantics as `import_vault`, without touching the filesystem.
+pub fn import_vault_from_reader<R: Read>(
+    reader: R,
+    import_password: String,
+    current_db: &mut Database,
+    master_key: &[u8],
+    merge: bool,
+    replace: bool,
+    diff: bool,
+) -> Result<ImportResult> {
+    import_vault_from_reader_with_keys(
+        reader,
+        import_password,
+        current_db,
+        master_key,
+        merge,
+        replace,
+        diff,
+        None,
+    )
+}
+
+/// Read .ik JSON and decrypt its entries with the import password
+fn decrypt_export<R: Read>(mut reader: R, import_password: String) -> Result<Vec<ExportEntry>> {
+    // Read and parse the export data
+    let mut export_data = String::new();
+    reader
+        .read_to_string(&mut export_data)
         .map_err(|e| Error::Io(format!("Failed to read import file: {e}")))?;
 
     let export_file: ExportFile = serde_json::from_str(&export_data)
         .map_err(|e| Error::Io(format!("Failed to parse import file: {e}")))?;
 
     // Validate format version
-    if export_file.format_version != EXPORT_FORMAT_VERSION {
-        return Err(Error::Io(format!(
-            "Unsupported export format version: {} (expected {})",
-            export_file.format_version, EXPORT_FORMAT_VERSION
-        )));
-    }
+    let dedup = check_format_version(&export_file.format_version)?;
 
     // Derive encryption key from import password
     let salt = BASE64
@@ -89,26 +225,373 @@ pub fn import_vault(
 
     let encrypted_data = EncryptedData { ciphertext, nonce };
 
-    let decrypted_bytes = crypto::decrypt(&encrypted_data, &import_key)
+    let mut decrypted_bytes = crypto::decrypt(&encrypted_data, &import_key)
         .map_err(|_| Error::Io("Failed to decrypt import file (wrong password?)".to_string()))?;
 
+    if export_file.encryption.compressed {
+        let mut compressed = decrypted_bytes;
+        decrypted_bytes = Vec::new();
+        let decompressed =
+            flate2::read::GzDecoder::new(compressed.as_slice()).read_to_end(&mut decrypted_bytes);
+        compressed.zeroize();
+        decompressed.map_err(|e| Error::Io(format!("Failed to decompress entries: {e}")))?;
+    }
+
     // Parse the decrypted entries
     let decrypted_str = String::from_utf8(decrypted_bytes)
         .map_err(|e| Error::Io(format!("Failed to decode decrypted data: {e}")))?;
 
-    let entries: Vec<ExportEntry> = serde_json::from_str(&decrypted_str)
-        .map_err(|e| Error::Io(format!("Failed to parse decrypted entries: {e}")))?;
+    let entries: Vec<ExportEntry> = if dedup {
+        serde_json::from_str::<DedupPayload>(&decrypted_str)
+            .map_err(|e| Error::Io(format!("Failed to parse decrypted entries: {e}")))?
+            .into_entries()?
+    } else {
+        serde_json::from_str(&decrypted_str)
+            .map_err(|e| Error::Io(format!("Failed to parse decrypted entries: {e}")))?
+    };
+
+    Ok(entries)
+}
+
+/// Check that an export's format version can be read
+///
+/// Any version with the same major as `EXPORT_FORMAT_VERSION` is accepted, so
+/// files from newer minor or patch releases still import.
+///
+/// # Returns
+/// Whether the payload is a `DedupPayload` (`EXPORT_FORMAT_VERSION_DEDUP` and later)
+fn check_format_version(format_version: &str) -> Result<bool> {
+    let parse = |version: &str| {
+        semver::Version::parse(version.trim()).map_err(|e| {
+            Error::Io(format!(
+                "Unsupported export format version: {version} ({e})"
+            ))
+        })
+    };
+    let version = parse(format_version)?;
+    let current = parse(EXPORT_FORMAT_VERSION)?;
+
+    if version.major != current.major {
+        return Err(Error::Io(format!(
+            "Unsupported export format version: {format_version} (this version of ironkey reads {}.x, e.g. {EXPORT_FORMAT_VERSION})",
+            current.major
+        )));
+    }
 
+    Ok(version >= parse(EXPORT_FORMAT_VERSION_DEDUP)?)
+}
+
+/// Import entries from a plaintext JSON array of `ExportEntry` objects
+///
+/// Expects `[{"key": ..., "value": ..., "locked": ...}]`, i.e. the decrypted
+/// payload of a .ik file. No import password is involved.
+pub fn import_plain_json_from_reader<R: Read>(
+    mut reader: R,
+    current_db: &mut Database,
+    master_key: &[u8],
+    merge: bool,
+    replace: bool,
+    diff: bool,
+) -> Result<ImportResult> {
+    let mut content = String::new();
+    reader
+        .read_to_string(&mut content)
+        .map_err(|e| Error::Io(format!("Failed to read import file: {e}")))?;
+
+    let entries: Vec<ExportEntry> = serde_json::from_str(&content)
+        .map_err(|e| Error::Io(format!("Failed to parse plaintext entries: {e}")))?;
+
+    apply_entries(entries, current_db, master_key, merge, replace, diff)
+}
+
+/// Import entries from a plaintext CSV file (e.g. exported from another password manager)
+///
+/// The header row must name `key` and `value` columns; a `locked` column
+/// (`true`/`false`, `yes`/`no`, `1`/`0`, empty = unlocked) is optional and any
+/// other columns are ignored. Fields follow RFC 4180 quoting, and fully blank
+/// lines are skipped. See `parse_csv_entries` for error reporting.
+pub fn import_plaintext_csv(
+    import_path: &Path,
+    current_db: &mut Database,
+    master_key: &[u8],
+    merge: bool,
+    replace: bool,
+    diff: bool,
+) -> Result<ImportResult> {
+    let content = fs::read_to_string(import_path)
+        .map_err(|e| Error::Io(format!("Failed to read import file: {e}")))?;
+
+    let entries = parse_csv_entries(&content)?;
+    apply_entries(entries, current_db, master_key, merge, replace, diff)
+}
+
+/// Parse plaintext CSV into entries (see `import_plaintext_csv` for the layout)
+///
+/// Nothing is imported from a file with errors: every malformed row (wrong
+/// field count, empty or duplicate key, bad `locked` value) is reported with
+/// its line number in a single `Error::InvalidInput`.
+pub fn parse_csv_entries(content: &str) -> Result<Vec<ExportEntry>> {
+    let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+    let mut records = parse_csv_records(content)?.into_iter();
+
+    let Some((_, header)) = records.next() else {
+        return Err(Error::InvalidInput("CSV file is empty".to_string()));
+    };
+    let column = |name: &str| {
+        header
+            .iter()
+            .position(|h| h.trim().eq_ignore_ascii_case(name))
+    };
+    let (Some(key_col), Some(value_col)) = (column("key"), column("value")) else {
+        return Err(Error::InvalidInput(
+            "CSV header must contain 'key' and 'value' columns".to_string(),
+        ));
+    };
+    let locked_col = column("locked");
+
+    let mut entries = Vec::new();
+    let mut errors = Vec::new();
+    let mut seen: HashMap<String, usize> = HashMap::new();
+
+    for (line, fields) in records {
+        if fields.len() != header.len() {
+            errors.push(format!(
+                "line {line}: expected {} fields, found {}",
+                header.len(),
+                fields.len()
+            ));
+            continue;
+        }
+
+        let key = fields[key_col].trim();
+        if key.is_empty() {
+            errors.push(format!("line {line}: key is empty"));
+            continue;
+        }
+        if let Some(first) = seen.insert(key.to_string(), line) {
+            errors.push(format!(
+                "line {line}: duplicate key '{key}' (first on line {first})"
+            ));
+            continue;
+        }
+
+        let locked = match locked_col.map(|col| fields[col].trim().to_ascii_lowercase()) {
+            None => false,
+            Some(flag) => match flag.as_str() {
+                "" | "false" | "no" | "0" => false,
+                "true" | "yes" | "1" => true,
+                other => {
+                    errors.push(format!("line {line}: invalid locked value '{other}'"));
+                    continue;
+                }
+            },
+        };
+
+        entries.push(ExportEntry {
+            key: key.to_string(),
+            value: fields[value_col].clone(),
+            locked,
+            label: None,
+            tags: Vec::new(),
+        });
+    }
+
+    if !errors.is_empty() {
+        return Err(Error::InvalidInput(format!(
+            "Malformed CSV:\n  {}",
+            errors.join("\n  ")
+        )));
+    }
+
+    Ok(entries)
+}
+
+/// Split CSV text into records (RFC 4180), each with the line it starts on
+///
+/// Fully blank lines are dropped. Quoting errors stop parsing, since the
+/// rest of the file can't be split reliably after them.
+fn parse_csv_records(content: &str) -> Result<Vec<(usize, Vec<String>)>> {
+    let mut records = Vec::new();
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut line = 1;
+    let mut record_line = 1;
+    let mut chars = content.chars().peekable();
+
+    let malformed = |line: usize, what: &str| {
+        Error::InvalidInput(format!("Malformed CSV:\n  line {line}: {what}"))
+    };
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if field.is_empty() => {
+                // Quoted field: runs until a quote that isn't doubled
+                let start = line;
+                loop {
+                    match chars.next() {
+                        Some('"') if chars.peek() == Some(&'"') => {
+                            chars.next();
+                            field.push('"');
+                        }
+                        Some('"') => break,
+                        Some(c) => {
+                            if c == '\n' {
+                                line += 1;
+                            }
+                            field.push(c);
+                        }
+                        None => return Err(malformed(start, "unterminated quoted field")),
+                    }
+                }
+                if !matches!(chars.peek(), None | Some(',' | '\r' | '\n')) {
+                    return Err(malformed(line, "unexpected text after closing quote"));
+                }
+            }
+            '"' => return Err(malformed(line, "quote inside an unquoted field")),
+            ',' => fields.push(std::mem::take(&mut field)),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' => {
+                fields.push(std::mem::take(&mut field));
+                push_record(&mut records, record_line, std::mem::take(&mut fields));
+                line += 1;
+                record_line = line;
+            }
+            c => field.push(c),
+        }
+    }
+    if !field.is_empty() || !fields.is_empty() {
+        fields.push(field);
+        push_record(&mut records, record_line, fields);
+    }
+
+    Ok(records)
+}
+
+fn push_record(records: &mut Vec<(usize, Vec<String>)>, line: usize, fields: Vec<String>) {
+    let blank = fields.len() == 1 && fields[0].trim().is_empty();
+    if !blank {
+        records.push((line, fields));
+    }
+}
+
+/// Import entries from a plaintext dotenv-style file of `KEY=VALUE` lines
+///
+/// See `parse_env_entries` for the accepted syntax and error reporting.
+pub fn import_plaintext_env(
+    import_path: &Path,
+    current_db: &mut Database,
+    master_key: &[u8],
+    merge: bool,
+    replace: bool,
+    diff: bool,
+) -> Result<ImportResult> {
+    let content = fs::read_to_string(import_path)
+        .map_err(|e| Error::Io(format!("Failed to read import file: {e}")))?;
+
+    let entries = parse_env_entries(&content)?;
+    apply_entries(entries, current_db, master_key, merge, replace, diff)
+}
+
+/// Parse dotenv-style `KEY=VALUE` lines into (unlocked) entries
+///
+/// Blank lines and `#` comments are skipped and an optional `export ` prefix
+/// is allowed. Lines split on the first `=`, so values may contain `=`; the
+/// value is trimmed and one pair of matching surrounding quotes is removed.
+/// Like `parse_csv_entries`, nothing is imported from a file with errors:
+/// every bad line (no `=`, empty or duplicate key) is reported with its line
+/// number in a single `Error::InvalidInput`.
+pub fn parse_env_entries(content: &str) -> Result<Vec<ExportEntry>> {
+    let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+
+    let mut entries = Vec::new();
+    let mut errors = Vec::new();
+    let mut seen: HashMap<String, usize> = HashMap::new();
+
+    for (index, raw) in content.lines().enumerate() {
+        let line = index + 1;
+        let text = raw.trim();
+        if text.is_empty() || text.starts_with('#') {
+            continue;
+        }
+        let text = text.strip_prefix("export ").unwrap_or(text);
+
+        let Some((key, value)) = text.split_once('=') else {
+            errors.push(format!("line {line}: expected KEY=VALUE"));
+            continue;
+        };
+
+        let key = key.trim();
+        if key.is_empty() {
+            errors.push(format!("line {line}: key is empty"));
+            continue;
+        }
+        if let Some(first) = seen.insert(key.to_string(), line) {
+            errors.push(format!(
+                "line {line}: duplicate key '{key}' (first on line {first})"
+            ));
+            continue;
+        }
+
+        let value = value.trim();
+        let value = ['"', '\'']
+            .iter()
+            .find_map(|&quote| {
+                value
+                    .strip_prefix(quote)
+                    .and_then(|rest| rest.strip_suffix(quote))
+            })
+            .unwrap_or(value);
+
+        entries.push(ExportEntry {
+            key: key.to_string(),
+            value: value.to_string(),
+            locked: false,
+            label: None,
+            tags: Vec::new(),
+        });
+    }
+
+    if !errors.is_empty() {
+        return Err(Error::InvalidInput(format!(
+            "Malformed env file:\n  {}",
+            errors.join("\n  ")
+        )));
+    }
+
+    Ok(entries)
+}
+
+/// Apply decrypted entries to the database using the merge/replace/diff strategy
+///
+/// Shared by every import format once entries are in plaintext form.
+pub fn apply_entries(
+    entries: Vec<ExportEntry>,
+    current_db: &mut Database,
+    master_key: &[u8],
+    merge: bool,
+    replace: bool,
+    diff: bool,
+) -> Result<ImportResult> {
     // Initialize import result
     let mut result = ImportResult::new(entries.len());
 
-    // Process each entry based on strategy
+    // Decide what happens to each entry in export order first, so a key that
+    // appears twice resolves exactly as if entries were inserted one at a time
+    let mut to_insert = Vec::new();
+    let mut pending: HashSet<String> = HashSet::new();
     for entry in entries {
-        let key_exists = current_db.entries.contains_key(&entry.key);
+        let key_exists =
+            current_db.entries.contains_key(&entry.key) || pending.contains(&entry.key);
+
+        let passphrase_locked = current_db
+            .entries
+            .get(&entry.key)
+            .is_some_and(Entry::is_passphrase_locked);
 
         if key_exists {
-            if merge {
-                // Merge mode: skip existing entries
+            if merge || passphrase_locked {
+                // Merge mode skips existing entries; a passphrase lock is never
+                // overwritten, since that would drop the lock without its passphrase
                 result.skipped.push(entry.key.clone());
                 continue;
             } else if replace {
@@ -125,27 +608,85 @@ pub fn import_vault(
             continue; // Skip the actual encryption and insertion
         }
 
-        // Encrypt the value with the destination vault's master key
-        let encrypted_data = crypto::encrypt(entry.value.as_bytes(), master_key)?;
+        pending.insert(entry.key.clone());
+        to_insert.push(entry);
+    }
 
-        // Encode to base64 for storage
-        let encrypted_value_b64 = BASE64.encode(&encrypted_data.ciphertext);
-        let nonce_b64 = BASE64.encode(&encrypted_data.nonce);
+    // Each value is encrypted independently, so the batch runs in parallel.
+    // Nothing is inserted unless every entry encrypts.
+    let db: &Database = current_db;
+    let encrypted: Vec<(ExportEntry, EncryptedData)> = to_insert
+        .into_par_iter()
+        .map(|entry| {
+            // Encrypt the value with the destination vault's key for this entry
+            let entry_key = db.entry_encryption_key(master_key, &entry.key);
+            let encrypted_data = crypto::encrypt(entry.value.as_bytes(), &entry_key)?;
+            Ok((entry, encrypted_data))
+        })
+        .collect::<Result<_>>()?;
 
-        // Create the entry
-        let db_entry = Entry {
-            encrypted_value: encrypted_value_b64,
-            nonce: nonce_b64,
-            is_locked: entry.locked,
+    // Insert or update in export order (a repeated key keeps its last value).
+    // Replacing works like `Vault::update_entry`: the old value goes into history
+    // and notes, usage counters and creation time are kept. Added entries are
+    // created (and last updated) now.
+    let now = chrono::Utc::now().to_rfc3339();
+    for (entry, encrypted_data) in encrypted {
+        let mut db_entry = match current_db.entries.get(&entry.key) {
+            Some(existing) => existing.with_replaced_value(encrypted_data, &now),
+            None => {
+                let mut added = Entry::new(encrypted_data.ciphertext, encrypted_data.nonce, false);
+                added.created_at = now.clone();
+                added.updated_at = now.clone();
+                added
+            }
         };
-
-        // Insert or update the entry
+        db_entry.is_locked = entry.locked;
+        db_entry.label = entry.label;
+        db_entry.tags = entry.tags;
         current_db.entries.insert(entry.key, db_entry);
     }
 
+    result.added.sort();
+    result.updated.sort();
+    result.skipped.sort();
+
     Ok(result)
 }
 
+/// Remove entries that weren't part of an import, making the database mirror the export
+///
+/// Call after importing in replace mode: every key in the export is then listed in
+/// `result.added`/`result.updated`. Locked entries are never deleted; they are
+/// reported in `result.skipped` instead. In diff mode nothing is removed.
+pub fn remove_missing(current_db: &mut Database, result: &mut ImportResult, diff: bool) {
+    let imported: HashSet<&String> = result
+        .added
+        .iter()
+        .chain(&result.updated)
+        .chain(&result.skipped)
+        .collect();
+
+    let mut missing: Vec<String> = current_db
+        .entries
+        .keys()
+        .filter(|key| !imported.contains(key))
+        .cloned()
+        .collect();
+    missing.sort();
+
+    for key in missing {
+        if current_db.entries[&key].is_locked {
+            result.skipped.push(key);
+            continue;
+        }
+
+        if !diff {
+            current_db.entries.remove(&key);
+        }
+        result.deleted.push(key);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -157,6 +698,7 @@ mod tests {
         assert_eq!(result.added.len(), 0);
         assert_eq!(result.updated.len(), 0);
         assert_eq!(result.skipped.len(), 0);
+        assert_eq!(result.deleted.len(), 0);
     }
 
     #[test]
@@ -164,4 +706,16 @@ mod tests {
         // This will be tested in integration tests
         // as it requires actual file I/O
     }
+
+    #[test]
+    fn test_check_format_version_is_semver_aware() {
+        assert!(!check_format_version("1.0.0").unwrap());
+        assert!(!check_format_version("1.0.5").unwrap());
+        assert!(check_format_version(EXPORT_FORMAT_VERSION_DEDUP).unwrap());
+        assert!(check_format_version("1.2.0").unwrap());
+
+        let message = check_format_version("2.0.0").unwrap_err().to_string();
+        assert!(message.contains("2.0.0") && message.contains(EXPORT_FORMAT_VERSION));
+        assert!(check_format_version("one").is_err());
+    }
 }
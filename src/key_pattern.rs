@@ -0,0 +1,101 @@
+//! Key Patterns
+//!
+//! How `ik list` matches entry keys: a case-insensitive substring (`--search`),
+//! a glob (`--glob 'aws/*/prod'`) or a regular expression (`--regex`).
+//! Patterns are compiled once and only ever see keys, never values.
+
+use crate::error::{Error, Result};
+
+/// How a `KeyPattern` is interpreted
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MatchMode {
+    /// Case-insensitive partial match (the `--search` behaviour)
+    #[default]
+    Substring,
+    /// Whole-key glob; `*` and `?` don't cross `/`, `**` does (case-sensitive)
+    Glob,
+    /// Regular expression matched anywhere in the key (case-sensitive unless `(?i)`)
+    Regex,
+}
+
+/// A compiled key pattern
+#[derive(Debug, Clone)]
+pub struct KeyPattern {
+    pattern: String,
+    matcher: Matcher,
+}
+
+#[derive(Debug, Clone)]
+enum Matcher {
+    /// Lowercased search term
+    Substring(String),
+    Glob(globset::GlobMatcher),
+    Regex(regex::Regex),
+}
+
+impl KeyPattern {
+    /// Compile `pattern` in the given mode
+    ///
+    /// # Errors
+    /// `Error::InvalidInput` with the compiler's message if the glob or regex is invalid
+    pub fn new(pattern: &str, mode: MatchMode) -> Result<Self> {
+        let matcher = match mode {
+            MatchMode::Substring => Matcher::Substring(pattern.to_lowercase()),
+            MatchMode::Glob => globset::GlobBuilder::new(pattern)
+                .literal_separator(true)
+                .build()
+                .map(|glob| Matcher::Glob(glob.compile_matcher()))
+                .map_err(|e| {
+                    Error::InvalidInput(format!("✘ Invalid glob pattern '{pattern}': {e}"))
+                })?,
+            MatchMode::Regex => regex::Regex::new(pattern)
+                .map(Matcher::Regex)
+                .map_err(|e| {
+                    Error::InvalidInput(format!("✘ Invalid regex pattern '{pattern}': {e}"))
+                })?,
+        };
+
+        Ok(Self {
+            pattern: pattern.to_string(),
+            matcher,
+        })
+    }
+
+    /// A case-insensitive substring pattern (never fails to compile)
+    pub fn substring(pattern: &str) -> Self {
+        Self {
+            pattern: pattern.to_string(),
+            matcher: Matcher::Substring(pattern.to_lowercase()),
+        }
+    }
+
+    /// The pattern as typed
+    pub fn as_str(&self) -> &str {
+        &self.pattern
+    }
+
+    pub fn mode(&self) -> MatchMode {
+        match self.matcher {
+            Matcher::Substring(_) => MatchMode::Substring,
+            Matcher::Glob(_) => MatchMode::Glob,
+            Matcher::Regex(_) => MatchMode::Regex,
+        }
+    }
+
+    /// Whether `key` matches the pattern
+    pub fn is_match(&self, key: &str) -> bool {
+        match &self.matcher {
+            Matcher::Substring(term) => key.to_lowercase().contains(term.as_str()),
+            Matcher::Glob(glob) => glob.is_match(key),
+            Matcher::Regex(regex) => regex.is_match(key),
+        }
+    }
+
+    /// Lowercased search term, for the vault's cached lowercase key index
+    pub(crate) fn substring_term(&self) -> Option<&str> {
+        match &self.matcher {
+            Matcher::Substring(term) => Some(term),
+            _ => None,
+        }
+    }
+}
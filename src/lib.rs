@@ -2,10 +2,20 @@
 
 pub mod cli;
 pub mod clipboard;
+pub mod config;
+pub mod confirm;
 pub mod crypto;
+pub mod display;
 pub mod error;
 pub mod export;
 pub mod import;
+pub mod key_pattern;
+pub mod machine;
 pub mod password_generator;
+pub mod secret;
+pub mod sqlite_backend;
 pub mod storage;
+pub mod timing;
+pub mod totp;
 pub mod vault;
+pub mod watch;
@@ -0,0 +1,94 @@
+//! Machine Binding
+//!
+//! Optional tamper-evidence: a vault created with `ik init --bind-machine` records
+//! which machine it belongs to, and unlocking it elsewhere prints a warning.
+//! This is NOT a security boundary — the identity is stored in plaintext and
+//! the entries stay encrypted either way.
+
+use crate::error::{Error, Result};
+use ring::rand::{SecureRandom, SystemRandom};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+const MACHINE_ID_LENGTH: usize = 16;
+
+/// Identity of the machine a vault is bound to (stored non-secretly in the database)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MachineBinding {
+    /// Shown in the mismatch warning only; hostnames change and aren't unique
+    pub hostname: String,
+    /// Random id generated once per machine (hex)
+    pub machine_id: String,
+}
+
+impl MachineBinding {
+    /// Identity of the current machine, creating its random id on first use
+    pub fn current() -> Result<Self> {
+        Ok(Self {
+            hostname: current_hostname(),
+            machine_id: load_or_create_machine_id()?,
+        })
+    }
+}
+
+/// Get the path of the per-machine id file
+///
+/// Kept in the local data directory rather than next to the database, so
+/// copying the vault's folder to another machine doesn't copy the id with it.
+pub fn get_machine_id_path() -> Result<PathBuf> {
+    let data_dir = dirs::data_local_dir()
+        .ok_or_else(|| Error::Io("Could not find local data directory".to_string()))?
+        .join("ironkey");
+
+    Ok(data_dir.join("machine-id"))
+}
+
+fn load_or_create_machine_id() -> Result<String> {
+    let path = get_machine_id_path()?;
+
+    if let Ok(existing) = fs::read_to_string(&path) {
+        let existing = existing.trim();
+        if !existing.is_empty() {
+            return Ok(existing.to_string());
+        }
+    }
+
+    let mut bytes = [0u8; MACHINE_ID_LENGTH];
+    SystemRandom::new()
+        .fill(&mut bytes)
+        .map_err(|e| Error::Io(format!("Failed to generate machine id: {e:?}")))?;
+    let machine_id: String = bytes.iter().map(|b| format!("{b:02x}")).collect();
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&path, &machine_id)?;
+
+    Ok(machine_id)
+}
+
+/// Best-effort hostname lookup without extra dependencies
+fn current_hostname() -> String {
+    ["HOSTNAME", "COMPUTERNAME"]
+        .iter()
+        .find_map(|var| std::env::var(var).ok())
+        .or_else(|| fs::read_to_string("/etc/hostname").ok())
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+/// Warning to show when a vault bound to `bound` is opened on `current`, if they differ
+///
+/// Only the machine id is compared; a renamed host is still the same machine.
+pub fn mismatch_warning(bound: &MachineBinding, current: &MachineBinding) -> Option<String> {
+    if bound.machine_id == current.machine_id {
+        return None;
+    }
+
+    Some(format!(
+        "This vault was bound to another machine ('{}'), it may have been copied here",
+        bound.hostname
+    ))
+}
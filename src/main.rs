@@ -1,80 +1,584 @@
-use crate::cli::{CliArgs, Commands};
-use clap::Parser;
+use crate::cli::{
+    CliArgs, Commands, EntryField, ExportFormat, ImportFormat, ListSort, VaultCommand,
+};
+use clap::{Parser, ValueEnum};
 use figlet_rs::FIGfont;
 use std::path::Path;
+use std::sync::OnceLock;
+use zeroize::Zeroizing;
 
 mod cli;
 mod clipboard;
+mod config;
+mod confirm;
 mod crypto;
+mod display;
 mod error;
 mod export;
 mod import;
+mod key_pattern;
+mod machine;
 mod password_generator;
+mod secret;
+mod sqlite_backend;
 mod storage;
+mod timing;
+mod totp;
 mod vault;
+mod watch;
 
+use confirm::{ConfirmDecision, DestructiveConfirm};
 use error::Result;
-use vault::Vault;
+use key_pattern::{KeyPattern, MatchMode};
+use secret::SecretString;
+use storage::StorageBackend;
+use vault::{OnExists, OnMissing, Vault, VaultBuilder, WriteOutcome};
 
 fn main() {
     let cli = CliArgs::parse();
 
-    let result = match cli.command {
+    let config = config::Config::load_or_default().unwrap_or_else(|e| {
+        eprintln!("⚠   {e} (using defaults)");
+        config::Config::default()
+    });
+
+    // CLI flag overrides config, which overrides the built-in default
+    let confirm_policy = cli
+        .confirm_destructive
+        .or(config.confirm_destructive)
+        .unwrap_or_default();
+    let confirm = DestructiveConfirm::new(confirm_policy, cli.yes);
+
+    if let Some(days) = cli.max_age.or(config.max_age_days) {
+        let _ = MAX_AGE_DAYS.set(days);
+    }
+    let _ = BACKEND.set(cli.backend.or(config.backend).unwrap_or_default());
+    let _ = SAFE_SAVE.set(cli.safe_save || config.safe_save);
+    let _ = JSON_OUTPUT.set(cli.json);
+    let _ = VERBOSE.set(cli.verbose);
+
+    // Every backend opened from here on uses the selected vault's files
+    let selected = cli.vault.as_deref().map_or(Ok(()), storage::select_vault);
+
+    let result = selected.and_then(|()| match cli.command {
         None => {
             show_welcome();
             Ok(())
         }
-        Some(Commands::Init { master }) => handle_init(master),
-        Some(Commands::Create { key, value }) => handle_create(key, value),
-        Some(Commands::Get {
+        Some(command) => run_command(command, &config, &confirm, None),
+    });
+
+    if let Err(e) = result {
+        print_error(&e);
+        std::process::exit(e.exit_code());
+    }
+}
+
+/// Report a failed command on stderr, as `{"error": ..., "message": ...}` with `--json`
+fn print_error(e: &error::Error) {
+    if json_output() {
+        eprintln!("{}", display::error_json(e));
+    } else {
+        eprintln!("Error: {e}");
+    }
+}
+
+/// Run one subcommand
+///
+/// `open` is the vault kept unlocked by `ik shell`; without it, commands that
+/// need the vault unlock it themselves.
+fn run_command(
+    command: Commands,
+    config: &config::Config,
+    confirm: &DestructiveConfirm,
+    open: Option<&mut Vault>,
+) -> Result<()> {
+    match command {
+        Commands::Init {
+            master,
+            import,
+            import_password,
+            bind_machine,
+            kdf,
+            iterations,
+        } => handle_init(
+            master,
+            import,
+            import_password,
+            bind_machine,
+            config.effective_kdf(kdf),
+            iterations,
+        ),
+        Commands::Create {
+            key,
+            value,
+            stdin,
+            fail_if_exists: _,
+            ignore_if_exists,
+        } => with_vault(open, "Enter master password: ", |vault| {
+            handle_create(vault, key, value, stdin, ignore_if_exists)
+        }),
+        Commands::Get {
             key,
             copy,
             no_clear,
             timeout,
-        }) => handle_get(key, copy, no_clear, timeout),
-        Some(Commands::Update { key, value }) => handle_update(key, value),
-        Some(Commands::List {
+            clear_on_change,
+            then_run,
+            field,
+            unlock_once,
+            qr,
+            no_newline,
+            primary,
+            osc52,
+        } => {
+            if json_output() && (copy || qr || no_newline) {
+                let flag = if copy {
+                    "--copy"
+                } else if qr {
+                    "--qr"
+                } else {
+                    "--no-newline"
+                };
+                return Err(error::Error::InvalidInput(format!(
+                    "✘ --json prints the value; it can't be combined with {flag}"
+                )));
+            }
+
+            with_vault(open, "Enter master password: ", |vault| {
+                if field == EntryField::Label {
+                    handle_get_label(vault, key)
+                } else {
+                    handle_get(
+                        vault,
+                        key,
+                        copy,
+                        no_clear,
+                        config.effective_clipboard_timeout(timeout),
+                        clear_on_change,
+                        then_run,
+                        config.track_access,
+                        unlock_once,
+                        qr,
+                        no_newline,
+                        primary,
+                        osc52,
+                    )
+                }
+            })
+        }
+        Commands::Copy {
+            key,
+            timeout,
+            primary,
+            osc52,
+        } => with_vault(open, "Enter master password: ", |vault| {
+            handle_copy(
+                vault,
+                key,
+                config.effective_clipboard_timeout(timeout),
+                config.track_access,
+                primary,
+                osc52,
+            )
+        }),
+        Commands::ClearClipboard => handle_clear_clipboard(),
+        Commands::Totp {
+            key,
+            copy,
+            timeout,
+            primary,
+            osc52,
+        } => with_vault(open, "Enter master password: ", |vault| {
+            handle_totp(
+                vault,
+                key,
+                copy,
+                config.effective_clipboard_timeout(timeout),
+                config.track_access,
+                primary,
+                osc52,
+            )
+        }),
+        Commands::Update {
+            key,
+            value,
+            stdin,
+            fail_if_missing: _,
+            create_if_missing,
+        } => with_vault(open, "Enter master password: ", |vault| {
+            handle_update(vault, key, value, stdin, create_if_missing)
+        }),
+        Commands::List {
             search,
+            glob,
+            regex,
             locked,
             unlocked,
-        }) => handle_list(search, locked, unlocked),
-        Some(Commands::Delete { key }) => handle_delete(key),
-        Some(Commands::Lock { key }) => handle_lock(key),
-        Some(Commands::Generate {
+            tag,
+            sort,
+            long,
+            preview,
+            count_only,
+            watch,
+            lock_after,
+        } => {
+            if json_output() && watch {
+                return Err(error::Error::InvalidInput(
+                    "✘ --json can't be combined with --watch".to_string(),
+                ));
+            }
+
+            // Compile the pattern before prompting, so a typo fails fast
+            let search = match (search, glob, regex) {
+                (_, Some(glob), _) => Some(KeyPattern::new(&glob, MatchMode::Glob)?),
+                (_, _, Some(regex)) => Some(KeyPattern::new(&regex, MatchMode::Regex)?),
+                (search, ..) => search.as_deref().map(KeyPattern::substring),
+            };
+
+            with_vault(open, "Enter master password: ", |vault| {
+                handle_list(
+                    vault,
+                    ListFilter {
+                        search,
+                        locked,
+                        unlocked,
+                        tag,
+                    },
+                    sort,
+                    long,
+                    preview,
+                    count_only,
+                    watch.then_some(lock_after),
+                )
+            })
+        }
+        Commands::Search { value, ignore_case } => {
+            with_vault(open, "Enter master password: ", |vault| {
+                handle_search(vault, value, ignore_case)
+            })
+        }
+        Commands::Stats => with_vault(open, "Enter master password: ", |vault| {
+            handle_stats(vault, config.track_access)
+        }),
+        Commands::Normalize => handle_normalize(),
+        Commands::RecoverDb => handle_recover_db(confirm),
+        Commands::Doctor => handle_doctor(),
+        Commands::Audit { duplicates: _ } => with_vault(open, "Enter master password: ", |vault| {
+            handle_audit_duplicates(vault)
+        }),
+        Commands::Restore { backup } => handle_restore(backup, confirm),
+        Commands::Reset { force } => handle_reset(force, confirm),
+        Commands::Vault { action } => match action {
+            VaultCommand::List => handle_vault_list(),
+            VaultCommand::Delete { name } => handle_vault_delete(&name, confirm),
+        },
+        Commands::ChangeMaster { plan } => handle_change_master(plan),
+        Commands::Delete { key, search } => match (key, search) {
+            (Some(key), _) => with_vault(
+                open,
+                "Enter master password to confirm deletion: ",
+                |vault| handle_delete(vault, key, confirm),
+            ),
+            // clap requires --search whenever --key is absent
+            (None, search) => {
+                let pattern = search.unwrap_or_default();
+
+                // Bulk deletes never prompt: they need --yes on the command line
+                if !confirm.assume_yes() {
+                    return Err(error::Error::InvalidInput(format!(
+                        "This will permanently delete every unlocked entry matching '{pattern}'. Pass --yes to confirm"
+                    )));
+                }
+
+                with_vault(
+                    open,
+                    "Enter master password to confirm deletion: ",
+                    |vault| handle_delete_matching(vault, pattern),
+                )
+            }
+        },
+        Commands::Lock {
+            key,
+            timer,
+            password,
+        } => with_vault(open, "Enter master password to toggle lock: ", |vault| {
+            handle_lock(vault, key, timer, password)
+        }),
+        Commands::Rename { from, prefix, to } => {
+            with_vault(open, "Enter master password: ", |vault| {
+                handle_rename(vault, from, prefix, to)
+            })
+        }
+        Commands::Info { key } => with_vault(open, "Enter master password: ", |vault| {
+            handle_info(vault, key)
+        }),
+        Commands::History { key, reveal } => with_vault(open, "Enter master password: ", |vault| {
+            handle_history(vault, key, reveal)
+        }),
+        Commands::Revert { key, version } => with_vault(open, "Enter master password: ", |vault| {
+            handle_revert(vault, key, version)
+        }),
+        Commands::Label { key, text, clear } => {
+            with_vault(open, "Enter master password: ", |vault| {
+                handle_label(vault, key, text, clear)
+            })
+        }
+        Commands::Tag { key, add, remove } => {
+            with_vault(open, "Enter master password: ", |vault| {
+                handle_tag(vault, key, add, remove)
+            })
+        }
+        Commands::Note { key, set, .. } => with_vault(open, "Enter master password: ", |vault| {
+            handle_note(vault, key, set)
+        }),
+        Commands::Generate {
             length,
             no_lowercase,
             no_uppercase,
             no_numbers,
             no_symbols,
+            pronounceable,
+            exclude_ambiguous,
+            symbols,
+            no_enforce_classes,
             copy,
             key,
-        }) => handle_generate(
-            length,
-            !no_lowercase,
-            !no_uppercase,
-            !no_numbers,
-            !no_symbols,
-            copy,
-            key,
-        ),
-        Some(Commands::Export {
+            rotate,
+            primary,
+            osc52,
+        } => {
+            let style = if pronounceable {
+                PasswordStyle::Pronounceable {
+                    digits: !no_numbers,
+                }
+            } else {
+                PasswordStyle::Random {
+                    lowercase: !no_lowercase,
+                    uppercase: !no_uppercase,
+                    numbers: !no_numbers,
+                    symbols: !no_symbols,
+                    exclude_ambiguous,
+                    custom_symbols: symbols,
+                    enforce_classes: !no_enforce_classes,
+                }
+            };
+            handle_generate(
+                open,
+                config.effective_password_length(length),
+                style,
+                copy,
+                config.effective_clipboard_timeout(None),
+                key,
+                rotate,
+                primary,
+                osc52,
+            )
+        }
+        Commands::Export {
+            force,
+            to_dir: Some(dir),
+            tar,
+            plaintext,
+            ..
+        } => handle_export_to_dir(&dir, force, tar, plaintext),
+        // `--format dir --output DIR` is another spelling of `--to-dir DIR`
+        Commands::Export {
+            output,
+            name,
+            force,
+            tar,
+            format: ExportFormat::Dir,
+            plaintext,
+            ..
+        } => match output.filter(|_| name.is_none()) {
+            Some(dir) => handle_export_to_dir(&dir, force, tar, plaintext),
+            None => Err(error::Error::InvalidInput(
+                "✘ --format dir requires --output <dir>".to_string(),
+            )),
+        },
+        Commands::Export { tar: true, .. } => Err(error::Error::InvalidInput(
+            "✘ --tar only applies to --to-dir (or --format dir)".to_string(),
+        )),
+        Commands::Export {
             output,
             name,
             force,
-            list,
-        }) => handle_export(output, name, force, list),
-        Some(Commands::Import {
+            format: format @ (ExportFormat::Toml | ExportFormat::Yaml | ExportFormat::Csv),
+            plaintext,
+            ..
+        } => handle_export_plain(output, name, force, format, plaintext),
+        Commands::Export { list: true, .. } => list_exports(),
+        Commands::Export {
+            output,
+            name,
+            force,
+            stdout_base64,
+            dedup,
+            compress,
+            same_password,
+            minimal,
+            keys,
+            diff,
+            to_dir: None,
+            ..
+        } => {
+            let options = export::ExportOptions {
+                dedup,
+                minimal,
+                compress,
+            };
+            let keys = (!keys.is_empty()).then_some(keys);
+            if diff {
+                handle_export_diff(output, name, force, options, keys)
+            } else {
+                handle_export(
+                    output,
+                    name,
+                    force,
+                    stdout_base64,
+                    same_password,
+                    options,
+                    keys,
+                )
+            }
+        }
+        Commands::Import {
             input,
             name,
-            merge,
+            merge: _,
             replace,
+            mirror,
+            keys,
             diff,
-        }) => handle_import(input, name, merge, replace, diff),
-    };
+            stdin_base64,
+            format,
+            plaintext,
+        } => {
+            resolve_import_source(input, name, stdin_base64, format, plaintext).and_then(|source| {
+                handle_import(source, replace, mirror, keys, diff, json_output(), confirm)
+            })
+        }
+        Commands::ImportEnv {
+            input,
+            merge: _,
+            replace,
+            diff,
+            plaintext,
+        } => resolve_env_import_source(input, plaintext).and_then(|source| {
+            handle_import(
+                source,
+                replace,
+                false,
+                Vec::new(),
+                diff,
+                json_output(),
+                confirm,
+            )
+        }),
+        Commands::Shell { lock_after } => handle_shell(config, confirm, lock_after),
+    }
+}
 
-    if let Err(e) = result {
-        eprintln!("Error: {e}");
-        std::process::exit(1);
+/// Stale-vault threshold in days (unset unless configured)
+static MAX_AGE_DAYS: OnceLock<u32> = OnceLock::new();
+
+/// Storage backend selected via `--backend` or config
+static BACKEND: OnceLock<storage::BackendKind> = OnceLock::new();
+
+/// Whether saves are verified by reading them back (`--safe-save` or config)
+static SAFE_SAVE: OnceLock<bool> = OnceLock::new();
+
+/// Whether output should be JSON instead of decorated text (`--json`)
+static JSON_OUTPUT: OnceLock<bool> = OnceLock::new();
+
+fn json_output() -> bool {
+    JSON_OUTPUT.get().copied().unwrap_or_default()
+}
+
+/// Whether to report timings of slow operations on stderr (`--verbose`)
+static VERBOSE: OnceLock<bool> = OnceLock::new();
+
+fn verbosity() -> timing::Verbosity {
+    timing::Verbosity::from_flag(VERBOSE.get().copied().unwrap_or_default())
+}
+
+/// Open the selected storage backend
+fn open_backend() -> Result<Box<dyn storage::StorageBackend>> {
+    storage::open_backend(
+        BACKEND.get().copied().unwrap_or_default(),
+        SAFE_SAVE.get().copied().unwrap_or_default(),
+    )
+}
+
+/// Unlock the vault, warning if it hasn't been modified within `--max-age` days
+fn unlock_vault(master_password: String) -> Result<Vault> {
+    let vault = VaultBuilder::new()
+        .backend(open_backend()?)
+        .verbosity(verbosity())
+        .unlock(master_password)?;
+
+    if let (Some(&max_age_days), Some(last_modified)) = (MAX_AGE_DAYS.get(), vault.last_modified())
+        && vault::is_stale(last_modified, chrono::Utc::now(), max_age_days)
+    {
+        let days = (chrono::Utc::now() - last_modified).num_days();
+        eprintln!("⚠   Vault hasn't been modified in {days} days.");
+        eprintln!("   Consider reviewing your entries and taking a backup (ik export).");
+    }
+
+    if let Some(bound) = vault.bound_machine()
+        && let Ok(current) = machine::MachineBinding::current()
+        && let Some(warning) = machine::mismatch_warning(bound, &current)
+    {
+        eprintln!("⚠   {warning}.");
+        eprintln!("   Machine binding is tamper-evidence only; your entries are still encrypted.");
+    }
+
+    Ok(vault)
+}
+
+/// Prompt for the master password and unlock the vault, retrying a wrong password
+///
+/// Interactive prompts get `vault::MAX_UNLOCK_ATTEMPTS` tries, waiting a little
+/// longer after each failure. A password from `IRONKEY_MASTER_PASSWORD` fails
+/// fast, since retrying would only reuse it.
+fn unlock_with_retries(prompt: &str) -> Result<Vault> {
+    if let Some(password) = master_password_from_env() {
+        return unlock_vault(password);
+    }
+
+    let mut failed_attempts = 0;
+    loop {
+        match unlock_vault(prompt_password(prompt)?) {
+            Err(error::Error::InvalidMasterPassword)
+                if failed_attempts + 1 < vault::MAX_UNLOCK_ATTEMPTS =>
+            {
+                failed_attempts += 1;
+                let remaining = vault::MAX_UNLOCK_ATTEMPTS - failed_attempts;
+                std::thread::sleep(vault::unlock_retry_delay(failed_attempts));
+                eprintln!(
+                    "✘ Invalid master password ({remaining} {} left)",
+                    if remaining == 1 {
+                        "attempt"
+                    } else {
+                        "attempts"
+                    }
+                );
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Run `run` against the vault `ik shell` keeps open, or unlock one for this command
+fn with_vault<T>(
+    open: Option<&mut Vault>,
+    prompt: &str,
+    run: impl FnOnce(&mut Vault) -> Result<T>,
+) -> Result<T> {
+    match open {
+        Some(vault) => run(vault),
+        None => run(&mut unlock_with_retries(prompt)?),
     }
 }
 
@@ -98,7 +602,7 @@ fn show_welcome() {
     println!("   Version: 0.0.2-beta\n");
 
     // Check if vault is initialized
-    match storage::exists() {
+    match open_backend().and_then(|backend| backend.exists()) {
         Ok(true) => {
             println!("  Vault is initialized");
             println!("\nAvailable commands:");
@@ -131,16 +635,27 @@ fn get_exports_directory() -> Result<std::path::PathBuf> {
     Ok(exports_dir)
 }
 
-fn handle_init(master_password: Option<String>) -> Result<()> {
+fn handle_init(
+    master_password: Option<String>,
+    import: Option<std::path::PathBuf>,
+    import_password: Option<String>,
+    bind_machine: bool,
+    kdf: crypto::KdfAlgorithm,
+    iterations: Option<u32>,
+) -> Result<()> {
     // Check if database already exists
-    if storage::exists()? {
+    if open_backend()?.exists()? {
+        if import.is_some() {
+            return Err(error::Error::MasterKeyAlreadyExists);
+        }
+
         println!("Master key already exists. Please verify your password:");
         let password = match master_password {
             Some(p) => p,
-            None => prompt_password("Enter master password: ")?,
+            None => get_master_password("Enter master password: ")?,
         };
 
-        let is_valid = Vault::verify_master_password(password)?;
+        let is_valid = Vault::verify_master_password_with_backend(open_backend()?, password)?;
 
         if is_valid {
             println!("Master password verified successfully!");
@@ -149,6 +664,23 @@ fn handle_init(master_password: Option<String>) -> Result<()> {
             Err(error::Error::InvalidMasterPassword)
         }
     } else {
+        // Validate the backup path before asking for any passwords
+        let import = import
+            .map(|input| resolve_import_path(Some(input), None))
+            .transpose()?;
+
+        // Likewise for the KDF settings
+        let mut kdf_params = crypto::KdfParams::default_for(kdf);
+        if let Some(iterations) = iterations {
+            if kdf != crypto::KdfAlgorithm::Pbkdf2 {
+                return Err(error::Error::InvalidInput(
+                    "--iterations only applies to --kdf pbkdf2".to_string(),
+                ));
+            }
+            kdf_params.iterations = iterations;
+        }
+        kdf_params.validate()?;
+
         println!("No master key found. Creating a new one...");
         println!("\n⚠ IMPORTANT SECURITY WARNING:");
         println!("   • There is NO password recovery mechanism! for now");
@@ -157,83 +689,470 @@ fn handle_init(master_password: Option<String>) -> Result<()> {
 
         let password = match master_password {
             Some(p) => p,
-            None => prompt_password("Enter new master password: ")?,
+            None => get_master_password("Enter new master password: ")?,
         };
 
         if password.trim().is_empty() {
             return Err(error::Error::EmptyPassword);
         }
 
-        let _vault = Vault::init(password)?;
+        // Optionally bootstrap from the backup (replace mode)
+        if let Some(input) = import {
+            let import_password = match import_password {
+                Some(p) => p,
+                None => prompt_password("Enter import password: ")?,
+            };
+
+            let (mut vault, result) = VaultBuilder::new()
+                .backend(open_backend()?)
+                .kdf(kdf_params)
+                .verbosity(verbosity())
+                .init_from_backup(password, &input, import_password)?;
+            if bind_machine {
+                vault.bind_machine(machine::MachineBinding::current()?)?;
+            }
+            println!("\n✓ Master key and database created successfully!");
+            println!(
+                "✓ Imported {} {} from {}",
+                result.added.len(),
+                if result.added.len() == 1 {
+                    "entry"
+                } else {
+                    "entries"
+                },
+                input.display()
+            );
+            return Ok(());
+        }
+
+        let mut vault = VaultBuilder::new()
+            .backend(open_backend()?)
+            .kdf(kdf_params)
+            .verbosity(verbosity())
+            .init(password)?;
+        if bind_machine {
+            vault.bind_machine(machine::MachineBinding::current()?)?;
+            println!("✓ Vault bound to this machine (you'll be warned if it's opened elsewhere)");
+        }
         println!("\n✓ Master key and database created successfully!");
         Ok(())
     }
 }
 
-fn handle_create(key: String, value: Option<String>) -> Result<()> {
-    let password = prompt_password("Enter master password: ")?;
-    let mut vault = Vault::unlock(password)?;
-
-    // If value not provided via CLI, prompt securely
+fn handle_create(
+    vault: &mut Vault,
+    key: String,
+    value: Option<String>,
+    stdin: bool,
+    ignore_if_exists: bool,
+) -> Result<()> {
+    // If value not provided via CLI or stdin, prompt securely
     let entry_value = match value {
         Some(v) => v,
+        None if stdin => read_value_from_stdin()?,
         None => {
             println!("      Value will be hidden");
             prompt_password("Enter value: ")?
         }
     };
 
-    vault.create_entry(key.clone(), entry_value)?;
-    println!("✓ Entry '{key}' created successfully!");
+    let on_exists = if ignore_if_exists {
+        OnExists::Ignore
+    } else {
+        OnExists::Fail
+    };
+
+    let strength = password_generator::estimate_strength(&entry_value);
+    match vault.create_entry_guarded(key.clone(), entry_value, on_exists)? {
+        WriteOutcome::Unchanged => println!("✦ Entry '{key}' already exists, left unchanged"),
+        _ => {
+            println!("✓ Entry '{key}' created successfully!");
+            warn_if_weak(&strength);
+        }
+    }
 
     Ok(())
 }
 
-fn handle_get(key: String, copy: bool, no_clear: bool, timeout: u64) -> Result<()> {
-    let password = prompt_password("Enter master password: ")?;
-    let vault = Vault::unlock(password)?;
+#[allow(clippy::too_many_arguments)] // One flag per `ik get` option
+fn handle_get(
+    vault: &mut Vault,
+    key: String,
+    copy: bool,
+    no_clear: bool,
+    timeout: u64,
+    clear_on_change: bool,
+    then_run: Vec<String>,
+    track_access: bool,
+    unlock_once: bool,
+    qr: bool,
+    no_newline: bool,
+    primary: bool,
+    osc52: bool,
+) -> Result<()> {
+    vault.set_access_tracking(track_access);
 
-    let value = vault.get_entry(&key)?;
+    let value = match vault.access_entry_secret(&key) {
+        Err(error::Error::EntryLocked(_)) if unlock_once => {
+            confirm_unlock_once(vault, &key)?;
+            SecretString::from(vault.get_entry_unlocked(&key)?)
+        }
+        result => result?,
+    };
 
-    if copy {
+    if json_output() {
+        println!("{}", display::value_json(&key, &value));
+        return Ok(());
+    }
+
+    if qr {
+        return show_qr(&key, &value);
+    }
+
+    if copy && then_run.is_empty() && use_osc52(osc52, primary) {
+        copy_via_osc52(&value, "Value")?;
+    } else if copy && !then_run.is_empty() {
+        // Clear when the command exits instead of on a timer
+        println!(
+            "✓ Value copied to clipboard! (clearing when '{}' exits)",
+            then_run[0]
+        );
+        if primary {
+            copy_to_primary_or_warn(&value);
+        }
+        let status = clipboard::copy_and_run(&value, &then_run)?;
+        println!("✓ Clipboard cleared");
+
+        if !status.success() {
+            return Err(error::Error::Io(format!(
+                "✘ '{}' exited with {status}",
+                then_run[0]
+            )));
+        }
+    } else if copy {
         clipboard::copy_to_clipboard(&value)?;
+        if primary {
+            copy_to_primary_or_warn(&value);
+        }
 
         if no_clear {
             println!("✓ Value copied to clipboard!");
+        } else if clear_on_change {
+            println!(
+                "✓ Value copied to clipboard! (cleared once you copy something else, or in {timeout}s)"
+            );
+            clipboard::auto_clear_clipboard_on_change(
+                &value,
+                std::time::Duration::from_secs(timeout),
+            )?;
         } else {
             println!("✓ Value copied to clipboard! (auto-clearing in {timeout}s)");
 
             // Start auto-clear in background
             clipboard::auto_clear_clipboard(&value, std::time::Duration::from_secs(timeout))?;
         }
+    } else if no_newline {
+        // Exact bytes for `ik get -k token -n > token.txt`
+        use std::io::Write;
+        let mut stdout = std::io::stdout();
+        stdout.write_all(value.as_bytes())?;
+        stdout.flush()?;
+    } else {
+        println!("Value: {}", value.as_str());
+    }
+
+    Ok(())
+}
+
+/// Show a value as a QR code for `ik get --qr`
+///
+/// On a terminal the code is drawn on the alternate screen and removed once
+/// Enter is pressed, so it doesn't stay in the scrollback.
+fn show_qr(key: &str, value: &str) -> Result<()> {
+    use std::io::{IsTerminal, Write};
+
+    let code = Zeroizing::new(display::render_qr(value)?);
+    let mut stdout = std::io::stdout();
+    if !stdout.is_terminal() {
+        println!("{}", code.as_str());
+        return Ok(());
+    }
+
+    // Enter the alternate screen and home the cursor
+    print!("\x1b[?1049h\x1b[H");
+    println!("{}", code.as_str());
+    println!("Scan '{key}', then press Enter to clear the screen");
+    stdout.flush()?;
+
+    let mut line = String::new();
+    let read = std::io::stdin().read_line(&mut line);
+
+    print!("\x1b[?1049l");
+    stdout.flush()?;
+    read?;
+    Ok(())
+}
+
+/// Re-authenticate before `ik get --unlock-once` reads a locked entry
+///
+/// Passphrase locks ask for their passphrase, like `ik lock` does to lift them.
+fn confirm_unlock_once(vault: &Vault, key: &str) -> Result<()> {
+    if vault.has_lock_password(key)? {
+        let passphrase = prompt_password("Enter lock passphrase: ")?;
+        return vault.check_lock_password(key, &passphrase);
+    }
+
+    let password = get_master_password(&format!(
+        "Entry '{key}' is locked. Re-enter master password to read it once: "
+    ))?;
+    if Vault::verify_master_password_with_backend(open_backend()?, password)? {
+        Ok(())
     } else {
-        println!("Value: {value}");
+        Err(error::Error::InvalidMasterPassword)
+    }
+}
+
+/// Whether `--copy` goes through the terminal (OSC 52) instead of the system clipboard
+///
+/// `--primary` asks for the system's selections, so it turns SSH auto-detection off.
+fn use_osc52(osc52: bool, primary: bool) -> bool {
+    !primary && clipboard::prefers_osc52(osc52)
+}
+
+/// Copy a value with OSC 52, which can't be auto-cleared
+fn copy_via_osc52(value: &str, what: &str) -> Result<()> {
+    clipboard::copy_via_osc52(value)?;
+    println!("✓ {what} sent to your terminal's clipboard (OSC 52)");
+    eprintln!("⚠   OSC 52 hands the value to your terminal; it won't be auto-cleared");
+    Ok(())
+}
+
+/// Also put a copied value on the primary selection for `--primary`
+///
+/// The clipboard copy has already succeeded, so a missing primary selection
+/// (e.g. an unsupported compositor) is only a warning.
+fn copy_to_primary_or_warn(value: &str) {
+    if let Err(e) = clipboard::copy_to_primary(value) {
+        eprintln!("⚠   Primary selection not set, only the clipboard was: {e}");
+    }
+}
+
+fn handle_copy(
+    vault: &mut Vault,
+    key: String,
+    timeout: u64,
+    track_access: bool,
+    primary: bool,
+    osc52: bool,
+) -> Result<()> {
+    vault.set_access_tracking(track_access);
+
+    let value = vault.access_entry_secret(&key)?;
+    if use_osc52(osc52, primary) {
+        return copy_via_osc52(&value, "Value");
+    }
+
+    clipboard::copy_to_clipboard(&value)?;
+    if primary {
+        copy_to_primary_or_warn(&value);
+    }
+
+    println!("✓ Value copied to clipboard! (auto-clearing in {timeout}s)");
+    clipboard::auto_clear_clipboard(&value, std::time::Duration::from_secs(timeout))
+}
+
+fn handle_totp(
+    vault: &mut Vault,
+    key: String,
+    copy: bool,
+    timeout: u64,
+    track_access: bool,
+    primary: bool,
+    osc52: bool,
+) -> Result<()> {
+    vault.set_access_tracking(track_access);
+
+    let value = vault.access_entry_secret(&key)?;
+    let totp = totp::Totp::parse(&value)?;
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_err(|e| error::Error::Io(format!("System clock is before 1970: {e}")))?
+        .as_secs();
+    let code = totp.code_at(now);
+    let remaining = totp.seconds_remaining(now);
+
+    if copy && use_osc52(osc52, primary) {
+        copy_via_osc52(&code, "Code")?;
+        println!("   (valid for {remaining}s)");
+        return Ok(());
+    }
+
+    if copy {
+        clipboard::copy_to_clipboard(&code)?;
+        if primary {
+            copy_to_primary_or_warn(&code);
+        }
+        println!(
+            "✓ Code copied to clipboard! (valid for {remaining}s, auto-clearing in {timeout}s)"
+        );
+        return clipboard::auto_clear_clipboard(&code, std::time::Duration::from_secs(timeout));
     }
 
+    println!("{code} (valid for {remaining}s)");
     Ok(())
 }
 
-fn handle_update(key: String, value: Option<String>) -> Result<()> {
-    let password = prompt_password("Enter master password: ")?;
-    let mut vault = Vault::unlock(password)?;
+fn handle_clear_clipboard() -> Result<()> {
+    clipboard::clear_clipboard()?;
+    println!("✓ Clipboard cleared");
+    Ok(())
+}
+
+fn handle_get_label(vault: &Vault, key: String) -> Result<()> {
+    if json_output() {
+        let label = vault.get_label(&key)?;
+        println!("{}", serde_json::json!({ "key": key, "label": label }));
+        return Ok(());
+    }
+
+    match vault.get_label(&key)? {
+        Some(label) => println!("Label: {label}"),
+        None => println!("✘ Entry '{key}' has no label"),
+    }
+
+    Ok(())
+}
 
-    // If value not provided via CLI, prompt securely
+fn handle_update(
+    vault: &mut Vault,
+    key: String,
+    value: Option<String>,
+    stdin: bool,
+    create_if_missing: bool,
+) -> Result<()> {
+    // If value not provided via CLI or stdin, prompt securely
     let new_value = match value {
         Some(v) => v,
+        None if stdin => read_value_from_stdin()?,
         None => {
             println!("      Value will be hidden");
             prompt_password("Enter new value: ")?
         }
     };
 
-    vault.update_entry(key.clone(), new_value)?;
-    println!("✓ Entry '{key}' updated successfully!");
+    let on_missing = if create_if_missing {
+        OnMissing::Create
+    } else {
+        OnMissing::Fail
+    };
+
+    let strength = password_generator::estimate_strength(&new_value);
+    match vault.update_entry_guarded(key.clone(), new_value, on_missing)? {
+        WriteOutcome::Created => println!("✓ Entry '{key}' created successfully!"),
+        _ => println!("✓ Entry '{key}' updated successfully!"),
+    }
+    warn_if_weak(&strength);
+
+    Ok(())
+}
+
+/// Read an entry value from stdin, dropping a single trailing newline
+fn read_value_from_stdin() -> Result<String> {
+    let mut value = String::new();
+    std::io::Read::read_to_string(&mut std::io::stdin(), &mut value)?;
+
+    if value.ends_with('\n') {
+        value.pop();
+        if value.ends_with('\r') {
+            value.pop();
+        }
+    }
+
+    Ok(value)
+}
+
+/// Print a non-blocking warning when a stored value looks easy to guess
+fn warn_if_weak(strength: &password_generator::StrengthReport) {
+    if !strength.is_weak() {
+        return;
+    }
+
+    println!(
+        "⚠   Weak value (strength {}/4, ~{} to crack offline)",
+        strength.score, strength.crack_time
+    );
+    if let Some(warning) = &strength.warning {
+        println!("⚠   {warning}");
+    }
+}
+
+/// Which entries `ik list` shows
+struct ListFilter {
+    search: Option<KeyPattern>,
+    locked: bool,
+    unlocked: bool,
+    tag: Option<String>,
+}
+
+fn handle_list(
+    vault: &mut Vault,
+    filter: ListFilter,
+    sort: ListSort,
+    long: bool,
+    preview: bool,
+    count_only: bool,
+    watch_lock_after: Option<u64>,
+) -> Result<()> {
+    let Some(lock_after) = watch_lock_after else {
+        return print_list(vault, &filter, sort, long, preview, count_only);
+    };
+
+    // Poll through a second handle; the vault keeps its own for reading
+    let backend = open_backend()?;
+    let exit = watch::watch_changes(
+        backend.as_ref(),
+        watch::DEFAULT_POLL_INTERVAL,
+        std::time::Duration::from_secs(lock_after),
+        || {
+            match vault.reload() {
+                Ok(()) => {}
+                Err(e @ error::Error::InvalidMasterPassword) => return Err(e),
+                Err(e) => {
+                    eprintln!("⚠  Could not re-read database, keeping last view: {e}");
+                    return Ok(std::ops::ControlFlow::Continue(()));
+                }
+            }
+
+            // Clear the screen and move the cursor home
+            print!("\x1B[2J\x1B[H");
+            print_list(vault, &filter, sort, long, preview, false)?;
+            println!(
+                "\n  Watching for changes (locks after {lock_after}s, Ctrl+C to quit) — updated {}",
+                chrono::Local::now().format("%H:%M:%S")
+            );
+            Ok(std::ops::ControlFlow::Continue(()))
+        },
+    )?;
+
+    if exit == watch::WatchExit::TimedOut {
+        vault.lock_session();
+        println!("🔒 Vault locked after {lock_after}s. Run 'ik list --watch' again to continue.");
+    }
 
     Ok(())
 }
 
-fn handle_list(search: Option<String>, locked: bool, unlocked: bool) -> Result<()> {
-    let vault = Vault::unlock(prompt_password("Enter master password: ")?)?;
+fn print_list(
+    vault: &Vault,
+    filter: &ListFilter,
+    sort: ListSort,
+    long: bool,
+    preview: bool,
+    count_only: bool,
+) -> Result<()> {
+    let search = filter.search.as_ref();
+    let tag = filter.tag.as_deref();
+    let (locked, unlocked) = (filter.locked, filter.unlocked);
 
     // Determine lock filter
     let lock_filter = if locked {
@@ -244,10 +1163,25 @@ fn handle_list(search: Option<String>, locked: bool, unlocked: bool) -> Result<(
         None // Show all entries
     };
 
-    let entries = vault.list_entries(search.as_deref(), lock_filter)?;
+    let mut entries = vault.entry_summaries_matching(search, lock_filter, tag)?;
+
+    if sort == ListSort::Accessed {
+        // Stable sort keeps alphabetical order among equal counts
+        entries.sort_by_key(|entry| std::cmp::Reverse(entry.access_count));
+    }
+
+    if count_only {
+        println!("{}", entries.len());
+        return Ok(());
+    }
+
+    if json_output() {
+        println!("{}", display::list_json(&entries));
+        return Ok(());
+    }
 
     if entries.is_empty() {
-        if search.is_some() || locked || unlocked {
+        if search.is_some() || tag.is_some() || locked || unlocked {
             println!("✘ No matching entries found.");
         } else {
             println!("✘ No entries found.");
@@ -256,12 +1190,21 @@ fn handle_list(search: Option<String>, locked: bool, unlocked: bool) -> Result<(
     }
 
     // Print header
-    if let Some(ref search_term) = search {
-        print!("Entries matching '{search_term}'");
+    if let Some(pattern) = search {
+        let kind = match pattern.mode() {
+            MatchMode::Substring => "",
+            MatchMode::Glob => "glob ",
+            MatchMode::Regex => "regex ",
+        };
+        print!("Entries matching {kind}'{}'", pattern.as_str());
     } else {
         print!("Stored entries");
     }
 
+    if let Some(tag) = tag {
+        print!(" tagged '{tag}'");
+    }
+
     if locked {
         print!(" (locked only)");
     } else if unlocked {
@@ -269,28 +1212,494 @@ fn handle_list(search: Option<String>, locked: bool, unlocked: bool) -> Result<(
     }
     println!(":");
 
-    // Print entries
-    for (key, is_locked) in entries {
-        let status = if is_locked { " [LOCKED]" } else { "" };
-        println!("  - {key}{status}");
-    }
-
-    Ok(())
-}
+    if long {
+        let key_width = display::key_column_width(&entries);
+        let columns = display::long_columns(display::terminal_width(), key_width, preview);
 
-fn handle_delete(key: String) -> Result<()> {
-    let password = prompt_password("Enter master password to confirm deletion: ")?;
-    let mut vault = Vault::unlock(password)?;
+        println!("{}", display::format_long_header(&columns, key_width));
+        for entry in &entries {
+            let masked = if preview && !entry.is_locked {
+                Some(display::mask_value(&vault.get_entry_secret(&entry.key)?))
+            } else {
+                None
+            };
+            println!(
+                "{}",
+                display::format_long_row(entry, masked.as_deref(), &columns, key_width)
+            );
+        }
+    } else {
+        for entry in &entries {
+            let status = if entry.is_locked { " [LOCKED]" } else { "" };
+            let label = entry
+                .label
+                .as_deref()
+                .map(|label| format!(" — {label}"))
+                .unwrap_or_default();
+            println!("  - {}{status}{label}", entry.key);
+        }
+    }
 
-    vault.delete_entry(&key)?;
-    println!("Entry '{key}' deleted successfully!");
+    println!("\n{}", display::list_summary(&entries));
 
     Ok(())
 }
 
-fn handle_lock(key: String) -> Result<()> {
-    let password = prompt_password("Enter master password to toggle lock: ")?;
-    let mut vault = Vault::unlock(password)?;
+fn handle_doctor() -> Result<()> {
+    // Open even on a MAC mismatch so damaged entries can still be pinpointed
+    let vault = Vault::unlock_unverified_with_backend(
+        open_backend()?,
+        get_master_password("Enter master password: ")?,
+    )?;
+    let report = vault.verify_integrity()?;
+
+    println!(
+        "Checked {} entries: {} healthy, {} damaged",
+        report.healthy.len() + report.damaged.len(),
+        report.healthy.len(),
+        report.damaged.len()
+    );
+    for key in &report.healthy {
+        println!("  ✓ {key}");
+    }
+    for (key, problem) in &report.damaged {
+        println!("  ✘ {key} — {problem}");
+    }
+    if report.mac_mismatch {
+        println!("  ✘ Database MAC does not match the entries (modified outside ironkey)");
+    }
+
+    if !report.is_healthy() {
+        println!(
+            "\n✦    Restore damaged entries from a backup, e.g. 'ik import --name <backup> --replace --key <name>'"
+        );
+    }
+
+    Ok(())
+}
+
+fn handle_audit_duplicates(vault: &Vault) -> Result<()> {
+    let groups = vault.find_duplicates()?;
+    let locked = vault.list_entries(None, Some(true))?;
+
+    if groups.is_empty() {
+        println!("✓ No reused values found");
+    } else {
+        println!(
+            "⚠   {} {} shared by more than one entry:",
+            groups.len(),
+            if groups.len() == 1 {
+                "value is"
+            } else {
+                "values are"
+            }
+        );
+        for group in &groups {
+            println!("  ✘ {}", group.join(", "));
+        }
+    }
+
+    if !locked.is_empty() {
+        println!("\n  Not checked (locked):");
+        for (key, _) in &locked {
+            println!("    - {key}");
+        }
+    }
+
+    Ok(())
+}
+
+fn handle_search(vault: &Vault, value: String, ignore_case: bool) -> Result<()> {
+    let value = Zeroizing::new(value);
+    let result = vault.search_values(&value, ignore_case)?;
+
+    if json_output() {
+        println!(
+            "{}",
+            serde_json::json!({
+                "matches": result.matches,
+                "skipped_locked": result.skipped_locked,
+            })
+        );
+        return Ok(());
+    }
+
+    if result.matches.is_empty() {
+        println!("✘ No entry values match.");
+    } else {
+        println!("Entries whose value matches:");
+        for key in &result.matches {
+            println!("  - {key}");
+        }
+    }
+
+    if result.skipped_locked > 0 {
+        println!(
+            "\n🔒 {} locked {} not searched",
+            result.skipped_locked,
+            if result.skipped_locked == 1 {
+                "entry"
+            } else {
+                "entries"
+            }
+        );
+    }
+
+    Ok(())
+}
+
+fn handle_stats(vault: &Vault, track_access: bool) -> Result<()> {
+    let total = vault.list_entries(None, None)?.len();
+    let locked = vault.list_entries(None, Some(true))?.len();
+
+    println!("Vault statistics:");
+    println!("  Entries: {total} ({locked} locked)");
+
+    if track_access {
+        println!("  Access tracking: enabled");
+    } else {
+        println!("  Access tracking: disabled (set 'track_access = true' in config.toml)");
+    }
+
+    let accessed: Vec<_> = vault
+        .access_stats()
+        .into_iter()
+        .filter(|(_, count, _)| *count > 0)
+        .take(10)
+        .collect();
+
+    if accessed.is_empty() {
+        println!("\n✘ No recorded accesses yet.");
+        return Ok(());
+    }
+
+    println!("\nMost accessed:");
+    for (i, (key, count, last)) in accessed.iter().enumerate() {
+        let last_str = last
+            .and_then(|ts| chrono::DateTime::parse_from_rfc3339(ts).ok())
+            .and_then(|ts| {
+                (chrono::Utc::now() - ts.with_timezone(&chrono::Utc))
+                    .to_std()
+                    .ok()
+            })
+            .map(|d| format!("last: {} ago", format_time_ago(d)))
+            .unwrap_or_else(|| "last: unknown".to_string());
+
+        println!(
+            "  {}. {:<30} {:>5} {}  ({})",
+            i + 1,
+            key,
+            count,
+            if *count == 1 { "read " } else { "reads" },
+            last_str
+        );
+    }
+
+    Ok(())
+}
+
+fn handle_normalize() -> Result<()> {
+    if BACKEND.get().copied().unwrap_or_default() != storage::BackendKind::Json {
+        return Err(error::Error::InvalidInput(
+            "✘ normalize only applies to the JSON file backend".to_string(),
+        ));
+    }
+
+    let count = storage::normalize()?;
+    println!(
+        "✓ Database normalized ({} {} in sorted order)",
+        count,
+        if count == 1 { "entry" } else { "entries" }
+    );
+
+    Ok(())
+}
+
+fn handle_recover_db(confirm: &DestructiveConfirm) -> Result<()> {
+    if BACKEND.get().copied().unwrap_or_default() != storage::BackendKind::Json {
+        return Err(error::Error::InvalidInput(
+            "✘ recover-db only applies to the JSON file backend".to_string(),
+        ));
+    }
+
+    let backend = storage::JsonFileBackend::default_location()?
+        .with_safe_save(SAFE_SAVE.get().copied().unwrap_or_default());
+    match backend.load() {
+        Ok(_) => {
+            println!("✓ Database is healthy, nothing to recover");
+            return Ok(());
+        }
+        Err(error::Error::DatabaseNotFound) => println!("✘ Database file is missing"),
+        Err(e) => println!("✘ {e}"),
+    }
+
+    let Some((candidate, database)) = backend.find_recoverable() else {
+        return Err(error::Error::Io(format!(
+            "✘ No usable backup (.bak.1 to .bak.{}) or staging (.tmp) copy found",
+            storage::BACKUP_COUNT
+        )));
+    };
+
+    println!(
+        "\n  Found usable copy: {} ({} {})",
+        candidate.display(),
+        database.entries.len(),
+        if database.entries.len() == 1 {
+            "entry"
+        } else {
+            "entries"
+        }
+    );
+
+    if !confirm_destructive(
+        confirm,
+        "This will replace the current database with the copy above!",
+    )? {
+        println!("Recovery cancelled.");
+        return Ok(());
+    }
+
+    let count = backend.recover_from(&candidate)?;
+    println!("✓ Database restored ({count} entries)");
+    println!(
+        "  The unreadable file was kept as {}",
+        backend.sibling_path(".corrupt").display()
+    );
+
+    Ok(())
+}
+
+fn handle_change_master(plan: bool) -> Result<()> {
+    // Unlocking verifies the current password, so it isn't checked again below. The
+    // password moves into the unlock (no copy), which wipes it once the key is derived
+    let old_password = get_master_password("Enter current master password: ")?;
+    let mut vault = unlock_vault(old_password)?;
+
+    if plan {
+        let plan = vault.reencrypt_plan(None)?;
+        println!("Changing the master password would:");
+        println!(
+            "  • re-encrypt {} entries ({} locked)",
+            plan.entry_count, plan.locked_count
+        );
+        println!(
+            "  • derive a new key with {} iterations (~{} ms)",
+            plan.iterations,
+            plan.kdf_time.as_millis()
+        );
+        println!(
+            "  • take about {} ms in total",
+            plan.estimated_time.as_millis()
+        );
+        return Ok(());
+    }
+
+    let new_password = Zeroizing::new(prompt_password("Enter new master password: ")?);
+    let confirm_password = Zeroizing::new(prompt_password("Confirm new master password: ")?);
+    if new_password != confirm_password {
+        return Err(error::Error::InvalidInput(
+            "New passwords do not match".to_string(),
+        ));
+    }
+
+    vault.change_master_password_verified(new_password)?;
+    println!("✓ Master password changed");
+
+    Ok(())
+}
+
+fn handle_restore(index: usize, confirm: &DestructiveConfirm) -> Result<()> {
+    if BACKEND.get().copied().unwrap_or_default() != storage::BackendKind::Json {
+        return Err(error::Error::InvalidInput(
+            "✘ restore only applies to the JSON file backend".to_string(),
+        ));
+    }
+
+    let backend = storage::JsonFileBackend::default_location()?
+        .with_safe_save(SAFE_SAVE.get().copied().unwrap_or_default());
+
+    println!("  Backups (newest first):");
+    for i in 1..=storage::BACKUP_COUNT {
+        let path = backend.backup_path(i);
+        let modified = std::fs::metadata(&path)
+            .and_then(|m| m.modified())
+            .ok()
+            .map(|t| {
+                display::format_timestamp(Some(
+                    &chrono::DateTime::<chrono::Utc>::from(t).to_rfc3339(),
+                ))
+            });
+        match modified {
+            Some(modified) => println!("  {i}. {} ({modified})", path.display()),
+            None => println!("  {i}. (none)"),
+        }
+    }
+
+    if !confirm_destructive(
+        confirm,
+        &format!("This will replace the current database with backup #{index}!"),
+    )? {
+        println!("Restore cancelled.");
+        return Ok(());
+    }
+
+    let count = backend.restore_backup(index)?;
+    println!("✓ Database restored from backup #{index} ({count} entries)");
+    println!(
+        "  The previous database was kept as {}",
+        backend.backup_path(1).display()
+    );
+
+    Ok(())
+}
+
+fn handle_reset(force: bool, confirm: &DestructiveConfirm) -> Result<()> {
+    if !force {
+        return Err(error::Error::InvalidInput(
+            "✘ ik reset permanently deletes the vault; pass --force to continue".to_string(),
+        ));
+    }
+
+    let backend = open_backend()?;
+    if !backend.exists()? {
+        println!("✘ No vault found, nothing to reset");
+        return Ok(());
+    }
+
+    if !confirm_destructive(
+        confirm,
+        "This will permanently DELETE the vault and every entry in it!",
+    )? {
+        println!("Reset cancelled.");
+        return Ok(());
+    }
+
+    backend.remove()?;
+    println!("✓ Vault removed. Run 'ik init' to create a new one.");
+
+    Ok(())
+}
+
+fn handle_vault_list() -> Result<()> {
+    let vaults = storage::list_vaults()?;
+    if vaults.is_empty() {
+        println!("✘ No vaults found. Run 'ik init' (or 'ik --vault NAME init') to create one.");
+        return Ok(());
+    }
+
+    let selected = storage::selected_vault();
+    println!("Vaults:");
+    for name in &vaults {
+        if name == selected {
+            println!("  * {name} (selected)");
+        } else {
+            println!("  - {name}");
+        }
+    }
+
+    Ok(())
+}
+
+fn handle_vault_delete(name: &str, confirm: &DestructiveConfirm) -> Result<()> {
+    storage::validate_vault_name(name)?;
+    if !storage::list_vaults()?.iter().any(|vault| vault == name) {
+        return Err(error::Error::InvalidInput(format!(
+            "✘ No vault named '{name}' (see 'ik vault list')"
+        )));
+    }
+
+    if !confirm_destructive(
+        confirm,
+        &format!("This will permanently DELETE the vault '{name}' and every entry in it!"),
+    )? {
+        println!("Deletion cancelled.");
+        return Ok(());
+    }
+
+    storage::remove_vault(name)?;
+    println!("✓ Vault '{name}' deleted.");
+
+    Ok(())
+}
+
+fn handle_delete(vault: &mut Vault, key: String, confirm: &DestructiveConfirm) -> Result<()> {
+    if !confirm_destructive(confirm, &format!("This will permanently delete '{key}'!"))? {
+        println!("Deletion cancelled.");
+        return Ok(());
+    }
+
+    vault.delete_entry(&key)?;
+    println!("Entry '{key}' deleted successfully!");
+
+    Ok(())
+}
+
+fn handle_delete_matching(vault: &mut Vault, pattern: String) -> Result<()> {
+    let locked: Vec<String> = vault
+        .list_entries(Some(&pattern), Some(true))?
+        .into_iter()
+        .map(|(key, _)| key.clone())
+        .collect();
+    let deleted = vault.delete_matching(&pattern)?;
+
+    if deleted.is_empty() && locked.is_empty() {
+        println!("No entries match '{pattern}'");
+        return Ok(());
+    }
+
+    for key in &deleted {
+        println!("  ✘ {key}");
+    }
+    println!("✓ Deleted {} entries", deleted.len());
+
+    if !locked.is_empty() {
+        println!("\n  Skipped (locked):");
+        for key in &locked {
+            println!("    - {key}");
+        }
+    }
+
+    Ok(())
+}
+
+fn handle_lock(
+    vault: &mut Vault,
+    key: String,
+    timer: Option<chrono::Duration>,
+    with_password: bool,
+) -> Result<()> {
+    if let Some(duration) = timer {
+        let unlock_after = vault.lock_for(&key, duration)?;
+        let local = chrono::DateTime::parse_from_rfc3339(&unlock_after)
+            .map(|t| {
+                t.with_timezone(&chrono::Local)
+                    .format("%Y-%m-%d %H:%M:%S")
+                    .to_string()
+            })
+            .unwrap_or(unlock_after);
+        println!("Entry '{key}' locked until {local}");
+        return Ok(());
+    }
+
+    if with_password {
+        let passphrase = prompt_password("Enter lock passphrase: ")?;
+        let confirm = prompt_password("Confirm lock passphrase: ")?;
+        if passphrase != confirm {
+            return Err(error::Error::InvalidInput(
+                "Passphrases do not match".to_string(),
+            ));
+        }
+        vault.lock_with_password(&key, &passphrase)?;
+        println!("Entry '{key}' locked with a passphrase");
+        return Ok(());
+    }
+
+    if vault.has_lock_password(&key)? {
+        let passphrase = prompt_password("Enter lock passphrase: ")?;
+        vault.unlock_with_password(&key, &passphrase)?;
+        println!("Entry '{key}' unlocked successfully!");
+        return Ok(());
+    }
 
     let is_locked = vault.toggle_lock(&key)?;
     let status = if is_locked { "locked" } else { "unlocked" };
@@ -299,37 +1708,295 @@ fn handle_lock(key: String) -> Result<()> {
     Ok(())
 }
 
+fn handle_rename(
+    vault: &mut Vault,
+    from: Option<String>,
+    prefix: Option<String>,
+    to: String,
+) -> Result<()> {
+    if let Some(from) = from {
+        vault.rename_entry(&from, &to)?;
+        println!("✓ Renamed '{from}' to '{to}'");
+        return Ok(());
+    }
+
+    // clap requires --prefix whenever --from is absent
+    let prefix = prefix.unwrap_or_default();
+    let renamed = vault.rename_prefix(&prefix, &to)?;
+    if renamed.is_empty() {
+        println!("No entries start with '{prefix}'");
+        return Ok(());
+    }
+
+    for (old, new) in &renamed {
+        println!("  {old} → {new}");
+    }
+    println!("✓ Renamed {} entries", renamed.len());
+
+    Ok(())
+}
+
+fn handle_info(vault: &Vault, key: String) -> Result<()> {
+    let info = vault.entry_metadata(&key)?;
+
+    let describe = |timestamp: Option<&str>| -> String {
+        let ago = timestamp
+            .and_then(|ts| chrono::DateTime::parse_from_rfc3339(ts).ok())
+            .and_then(|ts| {
+                (chrono::Utc::now() - ts.with_timezone(&chrono::Utc))
+                    .to_std()
+                    .ok()
+            })
+            .map(|d| match format_time_ago(d).as_str() {
+                "just now" => " (just now)".to_string(),
+                ago => format!(" ({ago} ago)"),
+            })
+            .unwrap_or_default();
+        match timestamp {
+            Some(_) => format!("{}{ago}", display::format_timestamp(timestamp)),
+            None => "unknown".to_string(),
+        }
+    };
+
+    println!("Entry '{}':", info.key);
+    println!(
+        "  Locked:       {}",
+        if info.is_locked { "yes" } else { "no" }
+    );
+    if let Some(label) = &info.label {
+        println!("  Label:        {label}");
+    }
+    if !info.tags.is_empty() {
+        println!("  Tags:         {}", info.tags.join(", "));
+    }
+    println!("  Created:      {}", describe(info.created_at.as_deref()));
+    println!("  Last updated: {}", describe(info.updated_at.as_deref()));
+    println!("  Reads:        {}", info.access_count);
+
+    Ok(())
+}
+
+fn handle_history(vault: &Vault, key: String, reveal: bool) -> Result<()> {
+    let history = vault.get_history(&key)?;
+
+    if history.is_empty() {
+        println!("✦ Entry '{key}' has no previous values");
+        return Ok(());
+    }
+
+    println!("Previous values of '{key}' (newest first):");
+    for item in &history {
+        let value = if reveal {
+            item.value.clone()
+        } else {
+            display::mask_value(&item.value)
+        };
+        println!(
+            "  {:>2}  {}  {value}",
+            item.version,
+            display::format_timestamp(Some(&item.replaced_at))
+        );
+    }
+    println!("\n✦    Restore one with 'ik revert --key {key} --version <n>'");
+
+    Ok(())
+}
+
+fn handle_revert(vault: &mut Vault, key: String, version: usize) -> Result<()> {
+    vault.revert(&key, version)?;
+    println!("✓ Entry '{key}' reverted to version {version} (previous value kept in history)");
+
+    Ok(())
+}
+
+fn handle_label(vault: &mut Vault, key: String, text: Option<String>, clear: bool) -> Result<()> {
+    // Showing a label is the same as `ik get --field label`
+    if text.is_none() && !clear {
+        return handle_get_label(vault, key);
+    }
+
+    vault.set_label(&key, text)?;
+    if clear {
+        println!("✓ Label removed from '{key}'");
+    } else {
+        println!("✓ Label set on '{key}'");
+    }
+
+    Ok(())
+}
+
+fn handle_tag(
+    vault: &mut Vault,
+    key: String,
+    add: Option<String>,
+    remove: Option<String>,
+) -> Result<()> {
+    if let Some(tag) = add {
+        if vault.add_tag(&key, &tag)? {
+            println!("✓ Tagged '{key}' with '{}'", tag.trim());
+        } else {
+            println!("'{key}' is already tagged '{}'", tag.trim());
+        }
+    } else if let Some(tag) = remove {
+        if vault.remove_tag(&key, &tag)? {
+            println!("✓ Removed tag '{}' from '{key}'", tag.trim());
+        } else {
+            println!("✘ '{key}' has no tag '{}'", tag.trim());
+        }
+    } else {
+        let tags = vault.entry_metadata(&key)?.tags;
+        if tags.is_empty() {
+            println!("✘ Entry '{key}' has no tags");
+        } else {
+            println!("Tags: {}", tags.join(", "));
+        }
+    }
+
+    Ok(())
+}
+
+fn handle_note(vault: &mut Vault, key: String, set: Option<String>) -> Result<()> {
+    match set {
+        Some(note) => {
+            let cleared = note.trim().is_empty();
+            vault.set_note(&key, note)?;
+            if cleared {
+                println!("✓ Notes removed from '{key}'");
+            } else {
+                println!("✓ Notes set on '{key}'");
+            }
+        }
+        None => match vault.get_note(&key)?.map(Zeroizing::new) {
+            Some(note) => println!("{}", note.as_str()),
+            None => println!("✘ Entry '{key}' has no notes"),
+        },
+    }
+
+    Ok(())
+}
+
+/// Kind of password `ik generate` produces
+enum PasswordStyle {
+    Random {
+        lowercase: bool,
+        uppercase: bool,
+        numbers: bool,
+        symbols: bool,
+        exclude_ambiguous: bool,
+        custom_symbols: Option<String>,
+        enforce_classes: bool,
+    },
+    Pronounceable {
+        digits: bool,
+    },
+}
+
+#[allow(clippy::too_many_arguments)] // One flag per `ik generate` option
 fn handle_generate(
+    open: Option<&mut Vault>,
     length: usize,
-    use_lowercase: bool,
-    use_uppercase: bool,
-    use_numbers: bool,
-    use_symbols: bool,
+    style: PasswordStyle,
     copy: bool,
+    clipboard_timeout: u64,
     key: Option<String>,
+    rotate: bool,
+    primary: bool,
+    osc52: bool,
 ) -> Result<()> {
     // Generate password
-    let password = password_generator::generate(
-        length,
-        use_lowercase,
-        use_uppercase,
-        use_numbers,
-        use_symbols,
-    )?;
+    let password = match style {
+        PasswordStyle::Random {
+            lowercase,
+            uppercase,
+            numbers,
+            symbols,
+            exclude_ambiguous,
+            custom_symbols,
+            enforce_classes,
+        } => {
+            let password = password_generator::generate_with(
+                length,
+                &password_generator::GenerateOptions {
+                    lowercase,
+                    uppercase,
+                    numbers,
+                    symbols,
+                    exclude_ambiguous,
+                    custom_symbols: custom_symbols.as_deref(),
+                    enforce_classes,
+                },
+            )?;
+
+            let mut charset = match custom_symbols.as_deref() {
+                Some(custom) => password_generator::build_charset_with_symbols(
+                    lowercase,
+                    uppercase,
+                    numbers,
+                    symbols,
+                    &password_generator::validate_symbols(custom)?,
+                ),
+                None => password_generator::build_charset(lowercase, uppercase, numbers, symbols),
+            };
+            if exclude_ambiguous {
+                charset = password_generator::remove_ambiguous(&charset);
+            }
+            let bits = password_generator::entropy_bits(length, charset.len());
+            println!(
+                "✦   Entropy: {bits:.1} bits ({})",
+                password_generator::EntropyRating::from_bits(bits)
+            );
+            password
+        }
+        PasswordStyle::Pronounceable { digits } => {
+            let password = password_generator::generate_pronounceable(length, digits)?;
+
+            let full_charset = password_generator::build_charset(true, true, true, true).len();
+            let bits = password_generator::pronounceable_entropy_bits(length, digits);
+            println!(
+                "⚠   Pronounceable entropy: {bits:.1} bits ({}); a random password of this length has ~{:.0}",
+                password_generator::EntropyRating::from_bits(bits),
+                password_generator::entropy_bits(length, full_charset)
+            );
+            password
+        }
+    };
 
     // If key option is specified, save to vault
     if let Some(key_name) = key {
-        let master_password = prompt_password("Enter master password: ")?;
-        let mut vault = Vault::unlock(master_password)?;
-        vault.create_entry(key_name.clone(), password.clone())?;
-        println!("✓ Generated password saved as '{key_name}'");
+        let replaced_len = with_vault(open, "Enter master password: ", |vault| {
+            if rotate {
+                vault.rotate_entry(key_name.clone(), password.clone())
+            } else {
+                vault
+                    .create_entry(key_name.clone(), password.clone())
+                    .map(|()| None)
+            }
+        })?;
+
+        match replaced_len {
+            Some(old_len) => println!(
+                "✓ Rotated '{key_name}' (old value: {old_len} chars, new value: {} chars)",
+                password.chars().count()
+            ),
+            None => println!("✓ Generated password saved as '{key_name}'"),
+        }
     }
 
     // Handle display/clipboard
-    if copy {
+    if copy && use_osc52(osc52, primary) {
+        copy_via_osc52(&password, "Generated password")?;
+    } else if copy {
         clipboard::copy_to_clipboard(&password)?;
-        println!("✓ Generated password copied to clipboard! (auto-clearing in 30s)");
-        clipboard::auto_clear_clipboard(&password, std::time::Duration::from_secs(30))?;
+        if primary {
+            copy_to_primary_or_warn(&password);
+        }
+        println!(
+            "✓ Generated password copied to clipboard! (auto-clearing in {clipboard_timeout}s)"
+        );
+        clipboard::auto_clear_clipboard(
+            &password,
+            std::time::Duration::from_secs(clipboard_timeout),
+        )?;
     } else {
         println!("Generated password: {password}");
     }
@@ -337,77 +2004,218 @@ fn handle_generate(
     Ok(())
 }
 
-fn handle_export(
+fn handle_export_to_dir(dir: &Path, force: bool, tar: bool, plaintext: bool) -> Result<()> {
+    if !plaintext {
+        return Err(error::Error::InvalidInput(
+            "✘ --to-dir writes unencrypted secrets to disk; pass --plaintext to acknowledge"
+                .to_string(),
+        ));
+    }
+
+    let vault = unlock_with_retries("Enter master password: ")?;
+    let result = vault.export_to_dir(dir, force)?;
+
+    println!("⚠   WARNING: These files are NOT encrypted!");
+    println!(
+        "✓ Wrote {} entries and {} to {}",
+        result.written.len(),
+        export::DIR_MANIFEST_FILE,
+        dir.display()
+    );
+    for (key, path) in &result.written {
+        println!("  + {key} → {}", path.display());
+    }
+
+    if !result.skipped_locked.is_empty() {
+        println!(
+            "\n  Locked entries (listed in the manifest, values not written) ({}):",
+            result.skipped_locked.len()
+        );
+        for key in &result.skipped_locked {
+            println!("  - {key} [LOCKED]");
+        }
+    }
+
+    if tar {
+        let mut archive = dir.as_os_str().to_owned();
+        archive.push(".tar.gz");
+        let archive = std::path::PathBuf::from(archive);
+        export::archive_directory(dir, &archive, force)?;
+        println!("✓ Archived to {}", archive.display());
+        println!(
+            "   The archive is NOT encrypted either; delete {} once done.",
+            dir.display()
+        );
+    }
+
+    Ok(())
+}
+
+fn handle_export_plain(
     output: Option<std::path::PathBuf>,
     name: Option<String>,
     force: bool,
-    list: bool,
+    format: ExportFormat,
+    plaintext: bool,
 ) -> Result<()> {
-    // Handle --list flag
-    if list {
-        return list_exports();
+    if !plaintext {
+        return Err(error::Error::InvalidInput(format!(
+            "✘ --format {} writes unencrypted secrets; pass --plaintext (or --i-understand-plaintext) to acknowledge",
+            format
+                .to_possible_value()
+                .map_or_else(String::new, |v| v.get_name().to_string())
+        )));
     }
 
-    // Resolve output path based on flags
-    let output = match (output, name) {
+    let format = match format {
+        ExportFormat::Toml => export::PlainFormat::Toml,
+        ExportFormat::Yaml => export::PlainFormat::Yaml,
+        ExportFormat::Csv => return handle_export_csv(output, name, force),
+        ExportFormat::Ik | ExportFormat::Dir => {
+            unreachable!("encrypted and directory exports have their own handlers")
+        }
+    };
+
+    let output = resolve_export_path(output, name, format.extension())?;
+    let vault = unlock_with_retries("Enter master password: ")?;
+    let result = vault.export_to_plain_file(&output, format, force)?;
+
+    println!("⚠   WARNING: This file is NOT encrypted!");
+    println!(
+        "✓ Exported {} entries to {}",
+        result.written,
+        output.display()
+    );
+    for (key, written_as) in &result.renamed {
+        println!("  ~ {key} written as '{written_as}'");
+    }
+
+    if !result.skipped_locked.is_empty() {
+        println!(
+            "\n  Skipped locked entries ({}):",
+            result.skipped_locked.len()
+        );
+        for key in &result.skipped_locked {
+            println!("  - {key} [LOCKED]");
+        }
+    }
+
+    Ok(())
+}
+
+fn handle_export_csv(
+    output: Option<std::path::PathBuf>,
+    name: Option<String>,
+    force: bool,
+) -> Result<()> {
+    let output = resolve_export_path(output, name, "csv")?;
+    let vault = unlock_with_retries("Enter master password: ")?;
+    let written = vault.export_to_csv_file(&output, force)?;
+
+    println!("⚠   WARNING: This file is NOT encrypted and includes LOCKED entries!");
+    println!("⚠   Anyone who can read it can read every secret. Delete it once imported.");
+    println!("✓ Exported {written} entries to {}", output.display());
+
+    Ok(())
+}
+
+/// Resolve `--output`/`--name` to an export path with the given extension
+///
+/// With neither flag, a timestamped file in the default exports folder is used.
+fn resolve_export_path(
+    output: Option<std::path::PathBuf>,
+    name: Option<String>,
+    extension: &str,
+) -> Result<std::path::PathBuf> {
+    let mut path = match (output, name) {
         (None, None) => {
             // No flags: default location with auto-generated timestamp name
             let exports_dir = get_exports_directory()?;
             std::fs::create_dir_all(&exports_dir)?;
 
             let timestamp = chrono::Local::now().format("%Y-%m-%d_%H-%M-%S");
-            exports_dir.join(format!("vault_{timestamp}.ik"))
+            exports_dir.join(format!("vault_{timestamp}.{extension}"))
         }
         (None, Some(n)) => {
             // Only --name: use default exports folder
             let exports_dir = get_exports_directory()?;
             std::fs::create_dir_all(&exports_dir)?;
-
-            let mut path = exports_dir.join(&n);
-            // Auto-append .ik if missing
-            if path.extension().and_then(|s| s.to_str()) != Some("ik") {
-                path.set_extension("ik");
-            }
-            path
-        }
-        (Some(path), None) => {
-            // Only --output: use custom path
-            let mut output_path = path;
-            // Auto-append .ik if missing
-            if output_path.extension().and_then(|s| s.to_str()) != Some("ik") {
-                output_path.set_extension("ik");
-            }
-            output_path
+            exports_dir.join(&n)
         }
+        // Only --output: use custom path
+        (Some(path), None) => path,
         (Some(_), Some(_)) => {
             // Both flags: this should be prevented by clap's conflicts_with
             unreachable!("clap should prevent using both --output and --name");
         }
     };
 
-    // Prompt for master password
-    let master_password = prompt_password("Enter master password: ")?;
-    let vault = Vault::unlock(master_password)?;
+    // Auto-append the extension if missing
+    if path.extension().and_then(|s| s.to_str()) != Some(extension) {
+        path.set_extension(extension);
+    }
 
-    // Prompt for export password (with confirmation)
-    let export_password = prompt_password("Enter export password: ")?;
-    let export_password_confirm = prompt_password("Confirm export password: ")?;
+    Ok(path)
+}
 
-    if export_password != export_password_confirm {
-        return Err(error::Error::Io(
-            "✘ Export passwords do not match".to_string(),
-        ));
+fn handle_export(
+    output: Option<std::path::PathBuf>,
+    name: Option<String>,
+    force: bool,
+    stdout_base64: bool,
+    same_password: bool,
+    options: export::ExportOptions,
+    keys: Option<Vec<String>>,
+) -> Result<()> {
+    // Handle --stdout-base64 flag (stdout carries only the payload, messages go to stderr)
+    if stdout_base64 {
+        let vault = unlock_with_retries("Enter master password: ")?;
+        let export_password = prompt_export_password()?;
+
+        let encoded = vault.export_to_base64(export_password)?;
+
+        eprintln!("⚠   WARNING: Base64 is NOT encryption!");
+        eprintln!("   The payload is protected only by your export password.");
+        eprintln!("   Anyone who sees it can attempt to brute-force that password.");
+        println!("{encoded}");
+
+        return Ok(());
     }
 
+    // Resolve output path based on flags
+    let output = resolve_export_path(output, name, "ik")?;
+
+    // Prompt for master password (kept for --same-password, zeroized on drop)
+    let master_password = Zeroizing::new(get_master_password("Enter master password: ")?);
+    let vault = unlock_vault(master_password.to_string())?;
+
     // Export the vault
-    if force {
-        vault.export_to_file_force(&output, export_password)?;
+    if same_password {
+        println!("⚠   WARNING: This backup is only as strong as your master password.");
+        vault.export_to_file_with_verified_master(
+            &output,
+            master_password,
+            force,
+            options,
+            keys.as_deref(),
+        )?;
     } else {
-        vault.export_to_file(&output, export_password)?;
+        // Prompt for export password (with confirmation)
+        let export_password = prompt_export_password()?;
+        vault.export_to_file_with_options(
+            &output,
+            export_password,
+            force,
+            options,
+            keys.as_deref(),
+        )?;
     }
 
-    // Count entries by listing them (no filter)
-    let entry_count = vault.list_entries(None, None)?.len();
+    // Count the exported entries (repeated --key flags export once)
+    let entry_count = match &keys {
+        Some(keys) => keys.iter().collect::<std::collections::HashSet<_>>().len(),
+        None => vault.list_entries(None, None)?.len(),
+    };
 
     // Format path to hide username for default exports directory
     let display_path = format_export_path(&output)?;
@@ -422,6 +2230,54 @@ fn handle_export(
     Ok(())
 }
 
+/// `ik export --diff`: list what would be exported, without writing anything
+fn handle_export_diff(
+    output: Option<std::path::PathBuf>,
+    name: Option<String>,
+    force: bool,
+    options: export::ExportOptions,
+    keys: Option<Vec<String>>,
+) -> Result<()> {
+    let output = resolve_export_path(output, name, "ik")?;
+
+    let vault = unlock_with_retries("Enter master password: ")?;
+    let preview = vault.export_diff(options, keys.as_deref())?;
+
+    if json_output() {
+        let output = serde_json::to_string_pretty(&preview)
+            .map_err(|e| error::Error::Io(format!("Failed to serialize export preview: {e}")))?;
+        println!("{output}");
+        return Ok(());
+    }
+
+    println!("  Preview (nothing written):");
+    println!(
+        "\n  Would export {} {} ({} locked) to '{}'",
+        preview.total,
+        if preview.total == 1 {
+            "entry"
+        } else {
+            "entries"
+        },
+        preview.locked,
+        format_export_path(&output)?
+    );
+    for key in &preview.keys {
+        println!("    + {key}");
+    }
+
+    if output.exists() {
+        if force {
+            println!("\n⚠   The existing file would be overwritten");
+        } else {
+            println!("\n⚠   The file already exists; add --force to overwrite it");
+        }
+    }
+
+    println!("\n✦    Run without --diff to write the export");
+    Ok(())
+}
+
 /// Format export path to hide username in default exports directory
 fn format_export_path(path: &Path) -> Result<String> {
     let exports_dir = get_exports_directory()?;
@@ -465,7 +2321,7 @@ fn list_exports() -> Result<()> {
     }
 
     // Sort by modification time (newest first)
-    exports.sort_by(|a, b| b.2.cmp(&a.2));
+    exports.sort_by_key(|e| std::cmp::Reverse(e.2));
 
     println!("\n📦 Available Exports (in {}):\n", exports_dir.display());
 
@@ -544,62 +2400,35 @@ fn format_time_ago(duration: std::time::Duration) -> String {
 }
 
 fn handle_import(
-    input: Option<std::path::PathBuf>,
-    name: Option<String>,
-    _merge: bool,
+    source: ImportSource,
     replace: bool,
+    mirror: bool,
+    keys: Vec<String>,
     diff: bool,
+    json: bool,
+    confirm: &DestructiveConfirm,
 ) -> Result<()> {
-    // Resolve input path based on flags
-    let input = match (input, name) {
-        (None, None) => {
-            return Err(error::Error::Io(
-                "✘ Must specify either --input or --name".to_string(),
-            ));
-        }
-        (None, Some(n)) => {
-            // Only --name: search in default exports folder
-            let exports_dir = get_exports_directory()?;
-            let mut path = exports_dir.join(&n);
-
-            // Auto-append .ik if missing
-            if path.extension().and_then(|s| s.to_str()) != Some("ik") {
-                path.set_extension("ik");
-            }
-            path
-        }
-        (Some(path), None) => {
-            // Only --input: use custom path
-            path
-        }
-        (Some(_), Some(_)) => {
-            // Both flags: this should be prevented by clap's conflicts_with
-            unreachable!("clap should prevent using both --input and --name");
-        }
-    };
-
-    // Verify file exists
-    if !input.exists() {
-        return Err(error::Error::Io(format!(
-            "✘ Import file not found: {}",
-            input.display()
-        )));
+    if mirror && !matches!(source, ImportSource::File(_)) {
+        return Err(error::Error::InvalidInput(
+            "✘ --mirror only supports encrypted .ik files (--input or --name)".to_string(),
+        ));
     }
-
-    // Validate .ik extension
-    if input.extension().and_then(|s| s.to_str()) != Some("ik") {
-        return Err(error::Error::Io(format!(
-            "✘ Invalid file format: '{}'. Expected .ik file.",
-            input.display()
-        )));
+    if !keys.is_empty() && !matches!(source, ImportSource::File(_)) {
+        return Err(error::Error::InvalidInput(
+            "✘ --key only supports encrypted .ik files (--input or --name)".to_string(),
+        ));
     }
 
     // Prompt for master password
-    let master_password = prompt_password("Enter master password: ")?;
-    let mut vault = Vault::unlock(master_password)?;
+    let mut vault = unlock_with_retries("Enter master password: ")?;
 
-    // Prompt for import password
-    let import_password = prompt_password("Enter import password: ")?;
+    // Prompt for import password (plaintext input has none)
+    let import_password = match source {
+        ImportSource::PlainJson(_) | ImportSource::PlainCsv(_) | ImportSource::PlainEnv(_) => {
+            String::new()
+        }
+        _ => prompt_password("Enter import password: ")?,
+    };
 
     // Determine strategy (default to merge if none specified)
     let (merge_mode, replace_mode, diff_mode) = if diff {
@@ -611,21 +2440,61 @@ fn handle_import(
     };
 
     // Confirm replace mode (destructive operation)
-    if replace_mode {
-        println!("⚠   WARNING: Replace mode will OVERWRITE existing entries!");
-        let confirm = prompt_password("Type 'yes' to confirm: ")?;
-        if confirm.to_lowercase() != "yes" {
-            println!("Import cancelled.");
-            return Ok(());
-        }
+    if replace_mode
+        && !confirm_destructive(confirm, "Replace mode will OVERWRITE existing entries!")?
+    {
+        println!("Import cancelled.");
+        return Ok(());
+    }
+
+    if mirror
+        && !diff
+        && !confirm_destructive(
+            confirm,
+            "Mirror mode will OVERWRITE existing entries and DELETE entries missing from the backup!",
+        )?
+    {
+        println!("Import cancelled.");
+        return Ok(());
     }
 
     // Import the vault
-    let result =
-        vault.import_from_file(&input, import_password, merge_mode, replace_mode, diff_mode)?;
+    let result = match source {
+        ImportSource::File(input) if mirror => {
+            vault.import_from_file_mirror(&input, import_password, diff_mode)?
+        }
+        ImportSource::File(input) => vault.import_from_file_with_keys(
+            &input,
+            import_password,
+            merge_mode,
+            replace_mode,
+            diff_mode,
+            (!keys.is_empty()).then_some(keys.as_slice()),
+        )?,
+        ImportSource::Base64(payload) => vault.import_from_base64(
+            &payload,
+            import_password,
+            merge_mode,
+            replace_mode,
+            diff_mode,
+        )?,
+        ImportSource::PlainJson(input) => {
+            vault.import_from_plain_json(&input, merge_mode, replace_mode, diff_mode)?
+        }
+        ImportSource::PlainCsv(input) => {
+            vault.import_from_plain_csv(&input, merge_mode, replace_mode, diff_mode)?
+        }
+        ImportSource::PlainEnv(input) => {
+            vault.import_from_plain_env(&input, merge_mode, replace_mode, diff_mode)?
+        }
+    };
 
     // Display results
-    if diff_mode {
+    if json {
+        let output = serde_json::to_string_pretty(&result)
+            .map_err(|e| error::Error::Io(format!("Failed to serialize import result: {e}")))?;
+        println!("{output}");
+    } else if diff_mode {
         println!("  Preview (no changes made):");
         println!("  Total entries in export file: {}", result.total_in_export);
         println!(
@@ -643,7 +2512,7 @@ fn handle_import(
             }
         }
 
-        if replace_mode {
+        if replace_mode || mirror {
             println!(
                 "\n  Would update {} existing {}",
                 result.updated.len(),
@@ -675,6 +2544,21 @@ fn handle_import(
             }
         }
 
+        if mirror {
+            println!(
+                "\n  Would delete {} {} missing from the export",
+                result.deleted.len(),
+                if result.deleted.len() == 1 {
+                    "entry"
+                } else {
+                    "entries"
+                }
+            );
+            for key in &result.deleted {
+                println!("    ✘ {key}");
+            }
+        }
+
         println!("\n✦    Run without --diff to apply changes");
     } else {
         // Actual import completed
@@ -725,11 +2609,369 @@ fn handle_import(
                 println!("    - {key}");
             }
         }
+
+        if !result.deleted.is_empty() {
+            println!(
+                "\n  Deleted {} {} missing from the export:",
+                result.deleted.len(),
+                if result.deleted.len() == 1 {
+                    "entry"
+                } else {
+                    "entries"
+                }
+            );
+            for key in &result.deleted {
+                println!("    ✘ {key}");
+            }
+        }
+    }
+
+    if !json && !result.not_found.is_empty() {
+        println!(
+            "\n  ⚠  Not in the export file ({}):",
+            result.not_found.len()
+        );
+        for key in &result.not_found {
+            println!("    ? {key}");
+        }
+    }
+
+    Ok(())
+}
+
+/// Run `ik shell`: unlock once, then read and run commands until `exit` or Ctrl-D
+///
+/// Lines that carry a secret value are kept out of the in-memory history. After
+/// `lock_after` idle seconds a timer locks the session, even while the prompt is
+/// waiting for input, and the next command asks for the master password again.
+/// The vault is dropped (and its key zeroized) on exit.
+fn handle_shell(
+    config: &config::Config,
+    confirm: &DestructiveConfirm,
+    lock_after: u64,
+) -> Result<()> {
+    use rustyline::error::ReadlineError;
+
+    let vault = unlock_with_retries("Enter master password: ")?;
+    let mut editor =
+        rustyline::DefaultEditor::new().map_err(|e| error::Error::Io(e.to_string()))?;
+    let lock_after = std::time::Duration::from_secs(lock_after);
+
+    let session = std::sync::Arc::new(std::sync::Mutex::new(ShellSession {
+        vault: Some(vault),
+        last_command: std::time::Instant::now(),
+    }));
+    spawn_idle_lock(std::sync::Arc::clone(&session), lock_after);
+
+    println!(
+        "✓ Vault unlocked. Type a command (e.g. list, get -k KEY), 'help', or 'exit' to leave."
+    );
+
+    loop {
+        let line = match editor.readline("ik> ") {
+            Ok(line) => line,
+            Err(ReadlineError::Interrupted) => continue,
+            Err(ReadlineError::Eof) => break,
+            Err(e) => {
+                ShellSession::close(&session);
+                return Err(error::Error::Io(e.to_string()));
+            }
+        };
+
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        if line == "exit" || line == "quit" {
+            break;
+        }
+
+        let Some(words) = shlex::split(line) else {
+            eprintln!("✘ Unbalanced quotes in: {line}");
+            continue;
+        };
+        let command = match cli::ShellLine::try_parse_from(words) {
+            Ok(parsed) => parsed.command,
+            Err(e) => {
+                // Also prints `help` and `<command> --help`
+                let _ = e.print();
+                continue;
+            }
+        };
+
+        if !command.has_secret_argument() {
+            let _ = editor.add_history_entry(line);
+        }
+        if !command.runs_in_shell() {
+            eprintln!("✘ This command can't run inside 'ik shell'; run it as 'ik ...' instead");
+            continue;
+        }
+
+        // Held until the command finishes, so the timer can't lock mid-command
+        let mut guard = ShellSession::lock(&session);
+        let Some(vault) = guard.vault.as_mut() else {
+            break;
+        };
+        if vault.is_session_locked() {
+            println!("🔒 Session locked after {}s idle", lock_after.as_secs());
+            let unlocked = get_master_password("Enter master password: ")
+                .and_then(|password| vault.reunlock(password));
+            if let Err(e) = unlocked {
+                print_error(&e);
+                continue;
+            }
+        }
+
+        if let Err(e) = run_command(command, config, confirm, Some(vault)) {
+            print_error(&e);
+        }
+        guard.last_command = std::time::Instant::now();
+    }
+
+    ShellSession::close(&session);
+    println!("✓ Vault locked. Bye!");
+    Ok(())
+}
+
+/// The vault of an `ik shell`, shared with its idle-lock timer
+struct ShellSession {
+    /// None once the shell has exited
+    vault: Option<Vault>,
+    last_command: std::time::Instant,
+}
+
+impl ShellSession {
+    fn lock(session: &std::sync::Mutex<Self>) -> std::sync::MutexGuard<'_, Self> {
+        session
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// Drop the vault now (zeroizing its key) rather than when the timer thread lets go
+    fn close(session: &std::sync::Mutex<Self>) {
+        drop(Self::lock(session).vault.take());
+    }
+}
+
+/// Lock the shell's session once it has been idle for `lock_after`
+///
+/// Checks once a second; the thread ends when the shell closes the session.
+fn spawn_idle_lock(
+    session: std::sync::Arc<std::sync::Mutex<ShellSession>>,
+    lock_after: std::time::Duration,
+) {
+    std::thread::spawn(move || {
+        loop {
+            {
+                let mut guard = ShellSession::lock(&session);
+                let idle = guard.last_command.elapsed();
+                let Some(vault) = guard.vault.as_mut() else {
+                    return;
+                };
+                if idle >= lock_after && !vault.is_session_locked() {
+                    vault.lock_session();
+                }
+            }
+            std::thread::sleep(std::time::Duration::from_secs(1));
+        }
+    });
+}
+
+/// Confirm a destructive operation according to the active policy
+///
+/// Returns `Ok(true)` if the operation may proceed, `Ok(false)` if the user declined.
+fn confirm_destructive(confirm: &DestructiveConfirm, action_description: &str) -> Result<bool> {
+    match confirm.decide() {
+        ConfirmDecision::Proceed => Ok(true),
+        ConfirmDecision::Prompt => {
+            println!("⚠   WARNING: {action_description}");
+            let answer = prompt_password("Type 'yes' to confirm: ")?;
+            if answer.to_lowercase() == "yes" {
+                confirm.mark_confirmed();
+                Ok(true)
+            } else {
+                Ok(false)
+            }
+        }
+        ConfirmDecision::Refuse => Err(error::Error::InvalidInput(format!(
+            "{action_description} Pass --yes to confirm (policy: {:?})",
+            confirm.policy()
+        ))),
+    }
+}
+
+/// Where `ik import` reads the export from
+enum ImportSource {
+    File(std::path::PathBuf),
+    Base64(String),
+    PlainJson(std::path::PathBuf),
+    PlainCsv(std::path::PathBuf),
+    PlainEnv(std::path::PathBuf),
+}
+
+/// Work out the import source from the `ik import` flags
+fn resolve_import_source(
+    input: Option<std::path::PathBuf>,
+    name: Option<String>,
+    stdin_base64: bool,
+    format: ImportFormat,
+    plaintext: bool,
+) -> Result<ImportSource> {
+    match format {
+        ImportFormat::Ik => {
+            // Read base64 payload from stdin, or resolve the .ik file path
+            if stdin_base64 {
+                let mut payload = String::new();
+                std::io::Read::read_to_string(&mut std::io::stdin(), &mut payload)?;
+                Ok(ImportSource::Base64(payload))
+            } else {
+                Ok(ImportSource::File(resolve_import_path(input, name)?))
+            }
+        }
+        ImportFormat::JsonPlain | ImportFormat::Csv => {
+            let flag = format
+                .to_possible_value()
+                .map_or_else(String::new, |v| v.get_name().to_string());
+            if !plaintext {
+                return Err(plaintext_not_acknowledged(&format!("--format {flag}")));
+            }
+
+            let Some(input) = input.filter(|_| name.is_none() && !stdin_base64) else {
+                return Err(error::Error::InvalidInput(format!(
+                    "✘ --format {flag} requires --input <file>"
+                )));
+            };
+
+            warn_plaintext_input(&input)?;
+            if format == ImportFormat::Csv {
+                Ok(ImportSource::PlainCsv(input))
+            } else {
+                Ok(ImportSource::PlainJson(input))
+            }
+        }
+    }
+}
+
+/// Work out the import source for `ik import-env`
+fn resolve_env_import_source(input: std::path::PathBuf, plaintext: bool) -> Result<ImportSource> {
+    if !plaintext {
+        return Err(plaintext_not_acknowledged("ik import-env"));
+    }
+
+    warn_plaintext_input(&input)?;
+    Ok(ImportSource::PlainEnv(input))
+}
+
+fn plaintext_not_acknowledged(what: &str) -> error::Error {
+    error::Error::InvalidInput(format!(
+        "✘ {what} reads unencrypted secrets; pass --plaintext to acknowledge"
+    ))
+}
+
+/// Make sure a plaintext import file exists and warn loudly about it
+fn warn_plaintext_input(input: &Path) -> Result<()> {
+    if !input.exists() {
+        return Err(error::Error::Io(format!(
+            "✘ Import file not found: {}",
+            input.display()
+        )));
     }
 
+    eprintln!("⚠   WARNING: Importing secrets from an unencrypted file.");
+    eprintln!("   Delete {} once the import is done.", input.display());
     Ok(())
 }
 
+/// Resolve and validate the .ik file path for import from --input/--name
+fn resolve_import_path(
+    input: Option<std::path::PathBuf>,
+    name: Option<String>,
+) -> Result<std::path::PathBuf> {
+    let input = match (input, name) {
+        (None, None) => {
+            return Err(error::Error::Io(
+                "✘ Must specify either --input or --name".to_string(),
+            ));
+        }
+        (None, Some(n)) => {
+            // Only --name: search in default exports folder
+            let exports_dir = get_exports_directory()?;
+            let mut path = exports_dir.join(&n);
+
+            // Auto-append .ik if missing
+            if path.extension().and_then(|s| s.to_str()) != Some("ik") {
+                path.set_extension("ik");
+            }
+            path
+        }
+        (Some(path), None) => {
+            // Only --input: use custom path
+            path
+        }
+        (Some(_), Some(_)) => {
+            // Both flags: this should be prevented by clap's conflicts_with
+            unreachable!("clap should prevent using both --input and --name");
+        }
+    };
+
+    // Verify file exists
+    if !input.exists() {
+        return Err(error::Error::Io(format!(
+            "✘ Import file not found: {}",
+            input.display()
+        )));
+    }
+
+    // Validate .ik extension
+    if input.extension().and_then(|s| s.to_str()) != Some("ik") {
+        return Err(error::Error::Io(format!(
+            "✘ Invalid file format: '{}'. Expected .ik file.",
+            input.display()
+        )));
+    }
+
+    Ok(input)
+}
+
+/// Prompt for an export password with confirmation
+fn prompt_export_password() -> Result<String> {
+    let export_password = prompt_password("Enter export password: ")?;
+    let export_password_confirm = prompt_password("Confirm export password: ")?;
+
+    if export_password != export_password_confirm {
+        return Err(error::Error::Io(
+            "✘ Export passwords do not match".to_string(),
+        ));
+    }
+
+    Ok(export_password)
+}
+
+/// Environment variable that supplies the master password non-interactively
+const MASTER_PASSWORD_ENV: &str = "IRONKEY_MASTER_PASSWORD";
+
+/// Read the master password from `IRONKEY_MASTER_PASSWORD`, or prompt for it
+///
+/// The variable is ignored when empty. A warning goes to stderr whenever it is
+/// used, so stdout stays clean for `--json` output.
+fn get_master_password(prompt: &str) -> Result<String> {
+    match master_password_from_env() {
+        Some(password) => Ok(password),
+        None => prompt_password(prompt),
+    }
+}
+
+/// The master password from `IRONKEY_MASTER_PASSWORD`, if set and non-empty (warns when used)
+fn master_password_from_env() -> Option<String> {
+    match std::env::var(MASTER_PASSWORD_ENV) {
+        Ok(password) if !password.is_empty() => {
+            eprintln!("⚠   Using the master password from {MASTER_PASSWORD_ENV}");
+            Some(password)
+        }
+        _ => None,
+    }
+}
+
 fn prompt_password(prompt: &str) -> Result<String> {
     let password = rpassword::prompt_password(prompt)
         .map_err(|e| error::Error::Io(format!("✘ Failed to read password: {e}")))?;
@@ -1,12 +1,25 @@
 use crate::error::{Error, Result};
 use ring::rand::{SecureRandom, SystemRandom};
 
+mod strength;
+
+#[allow(unused_imports)] // Public API - may be used by external consumers
+pub use strength::WEAK_SCORE;
+pub use strength::{StrengthReport, estimate_strength};
+
 /// Character sets for password generation
 const LOWERCASE: &str = "abcdefghijklmnopqrstuvwxyz";
 const UPPERCASE: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
 const NUMBERS: &str = "0123456789";
 const SYMBOLS: &str = "!@#$%^&*()_+-=[]{}|;:,.<>?";
 
+/// Characters that are easy to confuse when read or transcribed (`--exclude-ambiguous`)
+pub const AMBIGUOUS: &str = "l1IO0oB8S5Z2";
+
+/// Letter sets for pronounceable passwords (hard-to-say consonants left out)
+const CONSONANTS: &str = "bcdfghjklmnprstvz";
+const VOWELS: &str = "aeiou";
+
 /// Build character set based on selected options
 ///
 /// Returns a string containing all allowed characters based on the flags.
@@ -15,6 +28,23 @@ pub fn build_charset(
     use_uppercase: bool,
     use_numbers: bool,
     use_symbols: bool,
+) -> String {
+    build_charset_with_symbols(
+        use_lowercase,
+        use_uppercase,
+        use_numbers,
+        use_symbols,
+        SYMBOLS,
+    )
+}
+
+/// Build character set like `build_charset`, with `symbols` in place of the built-in set
+pub fn build_charset_with_symbols(
+    use_lowercase: bool,
+    use_uppercase: bool,
+    use_numbers: bool,
+    use_symbols: bool,
+    symbols: &str,
 ) -> String {
     let mut charset = String::new();
 
@@ -28,14 +58,87 @@ pub fn build_charset(
         charset.push_str(NUMBERS);
     }
     if use_symbols {
-        charset.push_str(SYMBOLS);
+        charset.push_str(symbols);
+    }
+
+    charset
+}
+
+/// Check a custom symbol set (`ik generate --symbols`) and drop repeated characters
+///
+/// Repeats would make those symbols more likely, so each is kept once.
+///
+/// # Errors
+/// Returns `Error::InvalidInput` if the set is empty or contains anything other
+/// than printable ASCII
+pub fn validate_symbols(symbols: &str) -> Result<String> {
+    if symbols.is_empty() {
+        return Err(Error::InvalidInput(
+            "Symbol set cannot be empty".to_string(),
+        ));
+    }
+    if let Some(c) = symbols.chars().find(|c| !matches!(c, ' '..='~')) {
+        return Err(Error::InvalidInput(format!(
+            "Symbol set may only contain printable ASCII characters (found {c:?})"
+        )));
     }
 
+    let mut unique = String::with_capacity(symbols.len());
+    for c in symbols.chars() {
+        if !unique.contains(c) {
+            unique.push(c);
+        }
+    }
+    Ok(unique)
+}
+
+/// Remove `AMBIGUOUS` characters from a character set
+pub fn remove_ambiguous(charset: &str) -> String {
     charset
+        .chars()
+        .filter(|c| !AMBIGUOUS.contains(*c))
+        .collect()
+}
+
+/// Options for `generate_with`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GenerateOptions<'a> {
+    /// Include lowercase letters (a-z)
+    pub lowercase: bool,
+    /// Include uppercase letters (A-Z)
+    pub uppercase: bool,
+    /// Include numbers (0-9)
+    pub numbers: bool,
+    /// Include symbols (the built-in set, or `custom_symbols`)
+    pub symbols: bool,
+    /// Leave out easily confused characters (see `AMBIGUOUS`)
+    pub exclude_ambiguous: bool,
+    /// Symbols to use instead of the built-in set (see `validate_symbols`)
+    pub custom_symbols: Option<&'a str>,
+    /// Guarantee at least one character from each selected class
+    pub enforce_classes: bool,
+}
+
+impl Default for GenerateOptions<'_> {
+    /// All character types, every class guaranteed
+    fn default() -> Self {
+        Self {
+            lowercase: true,
+            uppercase: true,
+            numbers: true,
+            symbols: true,
+            exclude_ambiguous: false,
+            custom_symbols: None,
+            enforce_classes: true,
+        }
+    }
 }
 
 /// Generate a cryptographically secure random password
 ///
+/// Every selected character class is guaranteed to appear at least once
+/// (see `generate_with` to turn that off).
+///
 /// # Arguments
 /// * `length` - Length of the password to generate (must be > 0)
 /// * `use_lowercase` - Include lowercase letters (a-z)
@@ -43,12 +146,14 @@ pub fn build_charset(
 /// * `use_numbers` - Include numbers (0-9)
 /// * `use_symbols` - Include symbols (!@#$%^&*()_+-=[]{}|;:,.<>?)
 ///
+/// For ambiguous-character exclusion or a custom symbol set, use `generate_with`.
+///
 /// # Returns
 /// A randomly generated password string
 ///
 /// # Errors
 /// Returns an error if:
-/// - `length` is 0
+/// - `length` is 0, or shorter than the number of selected classes
 /// - No character types are selected
 /// - Random number generation fails
 ///
@@ -71,6 +176,29 @@ pub fn generate(
     use_numbers: bool,
     use_symbols: bool,
 ) -> Result<String> {
+    generate_with(
+        length,
+        &GenerateOptions {
+            lowercase: use_lowercase,
+            uppercase: use_uppercase,
+            numbers: use_numbers,
+            symbols: use_symbols,
+            ..GenerateOptions::default()
+        },
+    )
+}
+
+/// Generate a cryptographically secure random password from `GenerateOptions`
+///
+/// With `enforce_classes`, one character of each selected class is placed first,
+/// the rest are drawn from the combined set, and the result is shuffled
+/// (Fisher–Yates) so the guaranteed characters can land anywhere.
+///
+/// # Errors
+/// Same as `generate`, and also when every selected character is ambiguous or
+/// `custom_symbols` is empty or not printable ASCII; `length` only has to cover
+/// the selected classes when `enforce_classes` is set
+pub fn generate_with(length: usize, options: &GenerateOptions) -> Result<String> {
     // Validate length
     if length == 0 {
         return Err(Error::InvalidInput(
@@ -78,8 +206,36 @@ pub fn generate(
         ));
     }
 
+    let symbols = match options.custom_symbols {
+        Some(symbols) => validate_symbols(symbols)?,
+        None => SYMBOLS.to_string(),
+    };
+
+    // Selected classes, each reduced to its usable characters
+    let classes: Vec<(&str, Vec<char>)> = [
+        (options.lowercase, "lowercase", LOWERCASE),
+        (options.uppercase, "uppercase", UPPERCASE),
+        (options.numbers, "number", NUMBERS),
+        (options.symbols, "symbol", symbols.as_str()),
+    ]
+    .into_iter()
+    .filter(|(selected, _, _)| *selected)
+    .map(|(_, name, set)| {
+        let chars = if options.exclude_ambiguous {
+            remove_ambiguous(set)
+        } else {
+            set.to_string()
+        };
+        (name, chars.chars().collect())
+    })
+    .collect();
+
     // Build character set
-    let charset = build_charset(use_lowercase, use_uppercase, use_numbers, use_symbols);
+    let charset: Vec<char> = classes
+        .iter()
+        .flat_map(|(_, chars)| chars)
+        .copied()
+        .collect();
 
     // Validate character set
     if charset.is_empty() {
@@ -88,25 +244,183 @@ pub fn generate(
         ));
     }
 
-    // Generate random password
-    let charset_bytes: Vec<u8> = charset.bytes().collect();
-    let charset_len = charset_bytes.len();
+    let rng = SystemRandom::new();
+    let mut password = Vec::with_capacity(length);
+
+    if options.enforce_classes {
+        if length < classes.len() {
+            return Err(Error::InvalidInput(format!(
+                "Password length must be at least {} to include every selected character type",
+                classes.len()
+            )));
+        }
+
+        // One guaranteed character from each class
+        for (name, chars) in &classes {
+            if chars.is_empty() {
+                return Err(Error::InvalidInput(format!(
+                    "No {name} characters left once ambiguous ones are excluded"
+                )));
+            }
+            password.push(chars[random_index(&rng, chars.len())?]);
+        }
+    }
+
+    // Fill the rest from the combined set
+    while password.len() < length {
+        password.push(charset[random_index(&rng, charset.len())?]);
+    }
+
+    if options.enforce_classes {
+        shuffle(&rng, &mut password)?;
+    }
+
+    Ok(password.into_iter().collect())
+}
+
+/// Fisher–Yates shuffle driven by `SystemRandom`
+fn shuffle<T>(rng: &SystemRandom, items: &mut [T]) -> Result<()> {
+    for i in (1..items.len()).rev() {
+        let j = random_index(rng, i + 1)?;
+        items.swap(i, j);
+    }
+    Ok(())
+}
+
+/// Generate a pronounceable password of alternating consonants and vowels
+///
+/// Letters alternate consonant/vowel (e.g. `bakodu`). With `include_digits`, one digit
+/// per 8 characters (at least one) is inserted at random positions. All choices use
+/// unbiased sampling from `SystemRandom`.
+///
+/// Easier to remember and type, but weaker than `generate` at the same length;
+/// see `pronounceable_entropy_bits`.
+///
+/// # Errors
+/// Returns an error if `length` is 0 or random number generation fails
+pub fn generate_pronounceable(length: usize, include_digits: bool) -> Result<String> {
+    if length == 0 {
+        return Err(Error::InvalidInput(
+            "Password length must be greater than 0".to_string(),
+        ));
+    }
 
     let rng = SystemRandom::new();
-    let mut password = String::with_capacity(length);
+    let digit_count = pronounceable_digit_count(length, include_digits);
+    let consonants: Vec<char> = CONSONANTS.chars().collect();
+    let vowels: Vec<char> = VOWELS.chars().collect();
+    let digits: Vec<char> = NUMBERS.chars().collect();
+
+    let mut password: Vec<char> = Vec::with_capacity(length);
+    for position in 0..length - digit_count {
+        let set = if position % 2 == 0 {
+            &consonants
+        } else {
+            &vowels
+        };
+        password.push(set[random_index(&rng, set.len())?]);
+    }
+
+    for _ in 0..digit_count {
+        let digit = digits[random_index(&rng, digits.len())?];
+        let position = random_index(&rng, password.len() + 1)?;
+        password.insert(position, digit);
+    }
 
-    // Generate random bytes and map to characters from charset
-    let mut random_bytes = vec![0u8; length];
-    rng.fill(&mut random_bytes)
-        .map_err(|_| Error::Io("Failed to generate random bytes".to_string()))?;
+    Ok(password.into_iter().collect())
+}
+
+/// Estimated entropy (bits) of a `generate` password drawn from `charset_size` characters
+///
+/// `length * log2(charset_size)`; pass the size of the charset actually used
+/// (after custom symbols and `exclude_ambiguous`).
+pub fn entropy_bits(length: usize, charset_size: usize) -> f64 {
+    length as f64 * (charset_size as f64).log2()
+}
+
+/// How `ik generate` describes a password's entropy
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum EntropyRating {
+    /// Under 50 bits
+    Weak,
+    /// 50 to under 70 bits
+    Fair,
+    /// 70 to under 100 bits
+    Strong,
+    /// 100 bits or more
+    VeryStrong,
+}
+
+impl EntropyRating {
+    /// Rate an entropy estimate in bits
+    pub fn from_bits(bits: f64) -> Self {
+        if bits < 50.0 {
+            EntropyRating::Weak
+        } else if bits < 70.0 {
+            EntropyRating::Fair
+        } else if bits < 100.0 {
+            EntropyRating::Strong
+        } else {
+            EntropyRating::VeryStrong
+        }
+    }
+}
+
+impl std::fmt::Display for EntropyRating {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            EntropyRating::Weak => "weak",
+            EntropyRating::Fair => "fair",
+            EntropyRating::Strong => "strong",
+            EntropyRating::VeryStrong => "very strong",
+        })
+    }
+}
+
+/// Estimated entropy (bits) of a `generate_pronounceable` password
+///
+/// Counts the letter choices, the digit values, and where the digits were placed.
+pub fn pronounceable_entropy_bits(length: usize, include_digits: bool) -> f64 {
+    let digit_count = pronounceable_digit_count(length, include_digits);
+    let letters = length - digit_count;
 
-    for byte in random_bytes {
-        // Map random byte to charset index
-        let index = (byte as usize) % charset_len;
-        password.push(charset_bytes[index] as char);
+    let consonant_bits = letters.div_ceil(2) as f64 * (CONSONANTS.len() as f64).log2();
+    let vowel_bits = (letters / 2) as f64 * (VOWELS.len() as f64).log2();
+    let digit_bits = digit_count as f64 * (NUMBERS.len() as f64).log2();
+
+    // log2(C(length, digit_count)) ways to place the digits
+    let placement_bits: f64 = (0..digit_count)
+        .map(|i| ((length - i) as f64 / (i + 1) as f64).log2())
+        .sum();
+
+    consonant_bits + vowel_bits + digit_bits + placement_bits
+}
+
+/// Number of digits mixed into a pronounceable password of `length` characters
+fn pronounceable_digit_count(length: usize, include_digits: bool) -> usize {
+    if include_digits && length > 1 {
+        (length / 8).max(1)
+    } else {
+        0
     }
+}
+
+/// Uniform random index in `0..n` (rejection sampling, so no modulo bias)
+fn random_index(rng: &SystemRandom, n: usize) -> Result<usize> {
+    let n = n as u32;
+    // Largest multiple of n that fits in u32; values at or above it are redrawn
+    let zone = u32::MAX - (u32::MAX % n);
 
-    Ok(password)
+    loop {
+        let mut bytes = [0u8; 4];
+        rng.fill(&mut bytes)
+            .map_err(|_| Error::Io("Failed to generate random bytes".to_string()))?;
+
+        let value = u32::from_le_bytes(bytes);
+        if value < zone {
+            return Ok((value % n) as usize);
+        }
+    }
 }
 
 /// Generate a password with default settings (16 characters, all types)
@@ -0,0 +1,51 @@
+//! Password strength estimation (zxcvbn)
+//!
+//! Kept separate from the CLI so the scoring can be tested directly.
+
+/// Scores below this are reported as weak
+pub const WEAK_SCORE: u8 = 2;
+
+/// Result of `estimate_strength`
+#[derive(Debug, Clone, PartialEq)]
+pub struct StrengthReport {
+    /// zxcvbn score from 0 (too guessable) to 4 (very unguessable)
+    pub score: u8,
+    /// Estimated entropy in bits (log2 of the estimated guesses)
+    pub entropy_bits: f64,
+    /// Human-readable time to crack offline against a slow hash (1e4 guesses/second)
+    pub crack_time: String,
+    /// Why the password is weak, when zxcvbn can tell
+    pub warning: Option<String>,
+}
+
+impl StrengthReport {
+    /// Whether the score is below `WEAK_SCORE`
+    pub fn is_weak(&self) -> bool {
+        self.score < WEAK_SCORE
+    }
+}
+
+/// Estimate how hard a password would be to guess
+pub fn estimate_strength(password: &str) -> StrengthReport {
+    let estimate = zxcvbn::zxcvbn(password, &[]);
+
+    // Empty passwords report -inf guesses
+    let entropy_bits = if password.is_empty() {
+        0.0
+    } else {
+        estimate.guesses_log10() * std::f64::consts::LOG2_10
+    };
+
+    StrengthReport {
+        score: estimate.score().into(),
+        entropy_bits,
+        crack_time: estimate
+            .crack_times()
+            .offline_slow_hashing_1e4_per_second()
+            .to_string(),
+        warning: estimate
+            .feedback()
+            .and_then(|feedback| feedback.warning())
+            .map(|warning| warning.to_string()),
+    }
+}
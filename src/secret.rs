@@ -0,0 +1,67 @@
+//! Secret Strings
+//!
+//! Decrypted entry values handed out by the vault. The bytes are wiped when the
+//! value is dropped, so a secret that was printed or copied doesn't linger in
+//! freed heap memory once the command finishes.
+
+use std::fmt;
+use std::ops::Deref;
+use zeroize::Zeroize;
+
+/// A UTF-8 secret that zeroizes its buffer on drop
+///
+/// Derefs to `str` for printing and copying. There is deliberately no `Display`
+/// or `Clone`, and `Debug` is redacted, so the value isn't duplicated or logged
+/// by accident.
+pub struct SecretString {
+    bytes: Vec<u8>,
+}
+
+impl SecretString {
+    /// The secret as a string slice
+    pub fn as_str(&self) -> &str {
+        // Only ever built from a `String`, so the bytes are valid UTF-8
+        std::str::from_utf8(&self.bytes).expect("SecretString holds UTF-8")
+    }
+}
+
+impl From<String> for SecretString {
+    /// Take ownership of `value` without copying it
+    fn from(value: String) -> Self {
+        Self {
+            bytes: value.into_bytes(),
+        }
+    }
+}
+
+impl Deref for SecretString {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl PartialEq<str> for SecretString {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for SecretString {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl fmt::Debug for SecretString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SecretString(***)")
+    }
+}
+
+impl Drop for SecretString {
+    fn drop(&mut self) {
+        self.bytes.zeroize();
+    }
+}
@@ -0,0 +1,305 @@
+//! SQLite Storage Backend
+//!
+//! Stores each entry as its own row so create/update/delete only touch one row,
+//! instead of rewriting the whole vault like the JSON file backend.
+//!
+//! Schema:
+//! - `meta`: a single row with salt, hash, iterations and remaining database metadata (JSON)
+//! - `entries`: one row per entry (key, ciphertext, nonce, locked, metadata JSON)
+
+use crate::error::{Error, Result};
+use crate::storage::{Database, Entry, StorageBackend};
+use rusqlite::{Connection, OptionalExtension, params};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Entry fields that have dedicated columns (everything else goes into `metadata`)
+const ENTRY_COLUMNS: [&str; 3] = ["encrypted_value", "nonce", "is_locked"];
+
+/// Database fields that have dedicated columns in the meta row
+const META_COLUMNS: [&str; 4] = ["master_salt", "master_hash", "iterations", "entries"];
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS meta (
+        id          INTEGER PRIMARY KEY CHECK (id = 1),
+        master_salt TEXT NOT NULL,
+        master_hash TEXT NOT NULL,
+        iterations  INTEGER NOT NULL,
+        metadata    TEXT NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS entries (
+        key        TEXT PRIMARY KEY,
+        ciphertext TEXT NOT NULL,
+        nonce      TEXT NOT NULL,
+        locked     INTEGER NOT NULL,
+        metadata   TEXT NOT NULL
+    );
+";
+
+/// Upsert that leaves identical rows untouched
+const UPSERT_ENTRY: &str = "
+    INSERT INTO entries (key, ciphertext, nonce, locked, metadata)
+    VALUES (?1, ?2, ?3, ?4, ?5)
+    ON CONFLICT(key) DO UPDATE SET
+        ciphertext = excluded.ciphertext,
+        nonce = excluded.nonce,
+        locked = excluded.locked,
+        metadata = excluded.metadata
+    WHERE ciphertext IS NOT excluded.ciphertext
+        OR nonce IS NOT excluded.nonce
+        OR locked IS NOT excluded.locked
+        OR metadata IS NOT excluded.metadata
+";
+
+const UPSERT_META: &str = "
+    INSERT INTO meta (id, master_salt, master_hash, iterations, metadata)
+    VALUES (1, ?1, ?2, ?3, ?4)
+    ON CONFLICT(id) DO UPDATE SET
+        master_salt = excluded.master_salt,
+        master_hash = excluded.master_hash,
+        iterations = excluded.iterations,
+        metadata = excluded.metadata
+";
+
+/// SQLite-backed storage (one row per entry)
+pub struct SqliteBackend {
+    conn: Connection,
+    path: PathBuf,
+}
+
+impl SqliteBackend {
+    /// Open (or create) a SQLite vault database at the given path
+    pub fn open(path: &Path) -> Result<Self> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| Error::DatabaseLoadFailed(e.to_string()))?;
+        }
+
+        let conn = Connection::open(path).map_err(load_err)?;
+        conn.execute_batch(SCHEMA).map_err(load_err)?;
+        // Journal files SQLite creates next to the database copy its mode
+        crate::storage::set_private_permissions(path)
+            .map_err(|e| Error::DatabaseLoadFailed(e.to_string()))?;
+
+        Ok(Self {
+            conn,
+            path: path.to_path_buf(),
+        })
+    }
+
+    /// Open the SQLite database at the default location (see `get_sqlite_path`)
+    pub fn default_location() -> Result<Self> {
+        Self::open(&get_sqlite_path()?)
+    }
+
+    fn upsert_meta(conn: &Connection, database: &Database) -> Result<()> {
+        conn.execute(
+            UPSERT_META,
+            params![
+                database.master_salt,
+                database.master_hash,
+                database.iterations,
+                split_metadata(database, &META_COLUMNS)?,
+            ],
+        )
+        .map_err(save_err)?;
+
+        Ok(())
+    }
+
+    fn upsert_entry(conn: &Connection, key: &str, entry: &Entry) -> Result<()> {
+        conn.execute(
+            UPSERT_ENTRY,
+            params![
+                key,
+                entry.encrypted_value,
+                entry.nonce,
+                entry.is_locked,
+                split_metadata(entry, &ENTRY_COLUMNS)?,
+            ],
+        )
+        .map_err(save_err)?;
+
+        Ok(())
+    }
+}
+
+impl StorageBackend for SqliteBackend {
+    fn exists(&self) -> Result<bool> {
+        let count: i64 = self
+            .conn
+            .query_row("SELECT COUNT(*) FROM meta", [], |row| row.get(0))
+            .map_err(load_err)?;
+
+        Ok(count > 0)
+    }
+
+    fn load(&self) -> Result<Database> {
+        let meta = self
+            .conn
+            .query_row(
+                "SELECT master_salt, master_hash, iterations, metadata FROM meta WHERE id = 1",
+                [],
+                |row| {
+                    Ok((
+                        row.get::<_, String>(0)?,
+                        row.get::<_, String>(1)?,
+                        row.get::<_, u32>(2)?,
+                        row.get::<_, String>(3)?,
+                    ))
+                },
+            )
+            .optional()
+            .map_err(load_err)?
+            .ok_or(Error::DatabaseNotFound)?;
+
+        let (master_salt, master_hash, iterations, metadata) = meta;
+        let mut database: Database = join_metadata(
+            &metadata,
+            [
+                ("master_salt", Value::from(master_salt)),
+                ("master_hash", Value::from(master_hash)),
+                ("iterations", Value::from(iterations)),
+                ("entries", Value::Object(Map::new())),
+            ],
+        )?;
+
+        let mut stmt = self
+            .conn
+            .prepare("SELECT key, ciphertext, nonce, locked, metadata FROM entries")
+            .map_err(load_err)?;
+
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, bool>(3)?,
+                    row.get::<_, String>(4)?,
+                ))
+            })
+            .map_err(load_err)?;
+
+        let mut entries = HashMap::new();
+        for row in rows {
+            let (key, ciphertext, nonce, locked, metadata) = row.map_err(load_err)?;
+            let entry: Entry = join_metadata(
+                &metadata,
+                [
+                    ("encrypted_value", Value::from(ciphertext)),
+                    ("nonce", Value::from(nonce)),
+                    ("is_locked", Value::from(locked)),
+                ],
+            )?;
+            entries.insert(key, entry);
+        }
+        database.entries = entries;
+
+        Ok(database)
+    }
+
+    fn save(&self, database: &Database) -> Result<()> {
+        let tx = self.conn.unchecked_transaction().map_err(save_err)?;
+
+        Self::upsert_meta(&tx, database)?;
+
+        // Drop rows for entries that no longer exist
+        let stored_keys: Vec<String> = {
+            let mut stmt = tx.prepare("SELECT key FROM entries").map_err(save_err)?;
+            stmt.query_map([], |row| row.get(0))
+                .map_err(save_err)?
+                .collect::<std::result::Result<_, _>>()
+                .map_err(save_err)?
+        };
+        for key in stored_keys {
+            if !database.entries.contains_key(&key) {
+                tx.execute("DELETE FROM entries WHERE key = ?1", params![key])
+                    .map_err(save_err)?;
+            }
+        }
+
+        for (key, entry) in &database.entries {
+            Self::upsert_entry(&tx, key, entry)?;
+        }
+
+        tx.commit().map_err(save_err)
+    }
+
+    fn save_entry(&self, database: &Database, key: &str) -> Result<()> {
+        let Some(entry) = database.entries.get(key) else {
+            return self.delete_entry(database, key);
+        };
+
+        let tx = self.conn.unchecked_transaction().map_err(save_err)?;
+        Self::upsert_meta(&tx, database)?;
+        Self::upsert_entry(&tx, key, entry)?;
+        tx.commit().map_err(save_err)
+    }
+
+    fn delete_entry(&self, database: &Database, key: &str) -> Result<()> {
+        let tx = self.conn.unchecked_transaction().map_err(save_err)?;
+        Self::upsert_meta(&tx, database)?;
+        tx.execute("DELETE FROM entries WHERE key = ?1", params![key])
+            .map_err(save_err)?;
+        tx.commit().map_err(save_err)
+    }
+
+    fn remove(&self) -> Result<()> {
+        self.conn
+            .execute_batch("DELETE FROM entries; DELETE FROM meta;")
+            .map_err(save_err)
+    }
+
+    fn last_write_time(&self) -> Option<SystemTime> {
+        fs::metadata(&self.path).ok()?.modified().ok()
+    }
+}
+
+/// Get the SQLite database path of the selected vault (next to its JSON database)
+pub fn get_sqlite_path() -> Result<PathBuf> {
+    crate::storage::vault_database_path(crate::storage::selected_vault(), "db")
+}
+
+/// Serialize `value` and keep only the fields without a dedicated column, as JSON
+fn split_metadata<T: serde::Serialize>(value: &T, columns: &[&str]) -> Result<String> {
+    let Value::Object(mut fields) =
+        serde_json::to_value(value).map_err(|e| Error::DatabaseSaveFailed(e.to_string()))?
+    else {
+        return Err(Error::DatabaseSaveFailed(
+            "Expected a JSON object".to_string(),
+        ));
+    };
+
+    for column in columns {
+        fields.remove(*column);
+    }
+
+    serde_json::to_string(&fields).map_err(|e| Error::DatabaseSaveFailed(e.to_string()))
+}
+
+/// Rebuild a value from its metadata JSON plus the dedicated column values
+fn join_metadata<T: serde::de::DeserializeOwned, const N: usize>(
+    metadata: &str,
+    columns: [(&str, Value); N],
+) -> Result<T> {
+    let mut fields: Map<String, Value> =
+        serde_json::from_str(metadata).map_err(|e| Error::DatabaseLoadFailed(e.to_string()))?;
+
+    for (name, value) in columns {
+        fields.insert(name.to_string(), value);
+    }
+
+    serde_json::from_value(Value::Object(fields))
+        .map_err(|e| Error::DatabaseLoadFailed(e.to_string()))
+}
+
+fn load_err(e: rusqlite::Error) -> Error {
+    Error::DatabaseLoadFailed(e.to_string())
+}
+
+fn save_err(e: rusqlite::Error) -> Error {
+    Error::DatabaseSaveFailed(e.to_string())
+}
@@ -1,9 +1,17 @@
+use crate::crypto::{self, KdfAlgorithm, KdfParams};
 use crate::error::{Error, Result};
+use crate::machine::MachineBinding;
+use crate::sqlite_backend::SqliteBackend;
 use base64::{Engine as _, engine::general_purpose};
+use chrono::{DateTime, Utc};
+use clap::ValueEnum;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::fs;
 use std::path::PathBuf;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::SystemTime;
+use zeroize::Zeroizing;
 
 /// Entry stored in the database
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -11,25 +19,242 @@ pub struct Entry {
     pub encrypted_value: String, // Base64-encoded
     pub nonce: String,           // Base64-encoded
     pub is_locked: bool,
+    #[serde(default)]
+    pub access_count: u64, // Only updated when access tracking is enabled
+    #[serde(default)]
+    pub last_accessed: Option<String>, // RFC 3339 timestamp
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>, // Plaintext, readable even when locked
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unlock_after: Option<String>, // RFC 3339; a timed lock clears once this passes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lock_password: Option<LockPassword>, // Passphrase required to unlock
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub created_at: String, // RFC 3339; empty for entries created before tracking
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub updated_at: String, // RFC 3339 time of the last value change
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<EncryptedNote>, // Encrypted with the entry key, under its own nonce
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>, // Plaintext, sorted and unique
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub history: Vec<EncryptedHistoryItem>, // Previous values, newest first, at most MAX_HISTORY
+}
+
+/// How many previous values `Vault::update_entry` keeps per entry
+pub const MAX_HISTORY: usize = 5;
+
+/// A previous value of an entry, still encrypted with the entry key under its original nonce
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EncryptedHistoryItem {
+    pub ciphertext: String,  // Base64-encoded
+    pub nonce: String,       // Base64-encoded
+    pub replaced_at: String, // RFC 3339 time the value was overwritten
+}
+
+impl EncryptedHistoryItem {
+    /// Wrap encrypted value data replaced at `replaced_at`
+    pub fn new(encrypted: &crypto::EncryptedData, replaced_at: String) -> Self {
+        Self {
+            ciphertext: general_purpose::STANDARD.encode(&encrypted.ciphertext),
+            nonce: general_purpose::STANDARD.encode(&encrypted.nonce),
+            replaced_at,
+        }
+    }
+
+    /// Get the decoded ciphertext and nonce
+    pub fn encrypted_data(&self) -> Result<crypto::EncryptedData> {
+        let decode = |field: &str, what: &str| {
+            general_purpose::STANDARD
+                .decode(field)
+                .map_err(|e| Error::DecryptionFailed(format!("Invalid history {what}: {e}")))
+        };
+        Ok(crypto::EncryptedData {
+            ciphertext: decode(&self.ciphertext, "ciphertext")?,
+            nonce: decode(&self.nonce, "nonce")?,
+        })
+    }
+}
+
+/// Free-form notes attached to an entry, encrypted separately from the value
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EncryptedNote {
+    pub ciphertext: String, // Base64-encoded
+    pub nonce: String,      // Base64-encoded
+}
+
+impl EncryptedNote {
+    /// Wrap freshly encrypted note data
+    pub fn new(encrypted: &crypto::EncryptedData) -> Self {
+        Self {
+            ciphertext: general_purpose::STANDARD.encode(&encrypted.ciphertext),
+            nonce: general_purpose::STANDARD.encode(&encrypted.nonce),
+        }
+    }
+
+    /// Get the decoded ciphertext and nonce
+    pub fn encrypted_data(&self) -> Result<crypto::EncryptedData> {
+        let decode = |field: &str, what: &str| {
+            general_purpose::STANDARD
+                .decode(field)
+                .map_err(|e| Error::DecryptionFailed(format!("Invalid note {what}: {e}")))
+        };
+        Ok(crypto::EncryptedData {
+            ciphertext: decode(&self.ciphertext, "ciphertext")?,
+            nonce: decode(&self.nonce, "nonce")?,
+        })
+    }
+}
+
+/// Salted hash of an entry's lock passphrase (`ik lock --password`), made with the vault's KDF
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LockPassword {
+    pub salt: String, // Base64-encoded
+    pub hash: String, // Base64-encoded
 }
 
 /// Database file structure
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Database {
     pub master_salt: String, // Base64-encoded
     pub master_hash: String, // Base64-encoded
     pub iterations: u32,
+    #[serde(serialize_with = "serialize_sorted")]
     pub entries: HashMap<String, Entry>,
+    /// RFC 3339 time of the last change to entries (None for databases created before tracking)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_modified: Option<String>,
+    /// How entry encryption keys are derived from the master key
+    #[serde(default, skip_serializing_if = "KeyScheme::is_legacy")]
+    pub key_scheme: KeyScheme,
+    /// Machine the vault was bound to with `ik init --bind-machine` (tamper-evidence only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub machine: Option<MachineBinding>,
+    /// Password KDF for the master key (absent in PBKDF2 vaults); `iterations` is its time cost
+    #[serde(default, skip_serializing_if = "KdfAlgorithm::is_pbkdf2")]
+    pub kdf: KdfAlgorithm,
+    /// Argon2 memory cost in KiB
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kdf_memory_kib: Option<u32>,
+    /// Argon2 parallelism (lanes)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kdf_parallelism: Option<u32>,
+    /// HMAC-SHA256 over the serialized entries, Base64-encoded (None until the first save that adds it)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mac: Option<String>,
+}
+
+/// How entry values are keyed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum KeyScheme {
+    /// Every entry is encrypted directly with the master key (databases created before HKDF)
+    #[default]
+    Legacy,
+    /// Each entry has its own key, derived from the master key with HKDF (see `crypto::derive_entry_key`)
+    Hkdf,
+}
+
+impl KeyScheme {
+    fn is_legacy(&self) -> bool {
+        *self == KeyScheme::Legacy
+    }
+}
+
+/// Serialize entries in sorted key order so the database file is deterministic
+fn serialize_sorted<S>(
+    entries: &HashMap<String, Entry>,
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    let sorted: BTreeMap<&String, &Entry> = entries.iter().collect();
+    sorted.serialize(serializer)
 }
 
 impl Database {
     /// Create a new database with master key info
+    #[allow(dead_code)] // Public API - may be used by external consumers
     pub fn new(salt: Vec<u8>, hash: Vec<u8>, iterations: u32) -> Self {
+        Self::new_with_kdf(salt, hash, &KdfParams::pbkdf2(iterations))
+    }
+
+    /// Create a new database whose master key is derived with the given KDF
+    pub fn new_with_kdf(salt: Vec<u8>, hash: Vec<u8>, kdf: &KdfParams) -> Self {
+        let argon2 = kdf.algorithm == KdfAlgorithm::Argon2id;
         Self {
             master_salt: general_purpose::STANDARD.encode(&salt),
             master_hash: general_purpose::STANDARD.encode(&hash),
-            iterations,
+            iterations: kdf.iterations,
             entries: HashMap::new(),
+            last_modified: None,
+            key_scheme: KeyScheme::Legacy,
+            machine: None,
+            kdf: kdf.algorithm,
+            kdf_memory_kib: argon2.then_some(kdf.memory_kib),
+            kdf_parallelism: argon2.then_some(kdf.parallelism),
+            mac: None,
+        }
+    }
+
+    /// KDF settings the master key was derived with
+    pub fn kdf_params(&self) -> Result<KdfParams> {
+        match self.kdf {
+            KdfAlgorithm::Pbkdf2 => Ok(KdfParams::pbkdf2(self.iterations)),
+            KdfAlgorithm::Argon2id => match (self.kdf_memory_kib, self.kdf_parallelism) {
+                (Some(memory_kib), Some(parallelism)) => Ok(KdfParams::argon2id(
+                    memory_kib,
+                    self.iterations,
+                    parallelism,
+                )),
+                _ => Err(Error::DatabaseLoadFailed(
+                    "Argon2id vault is missing its memory/parallelism parameters".to_string(),
+                )),
+            },
+        }
+    }
+
+    /// Get the key used to encrypt the entry stored under `key`, according to `key_scheme`
+    pub fn entry_encryption_key(&self, master_key: &[u8], key: &str) -> Zeroizing<Vec<u8>> {
+        Zeroizing::new(match self.key_scheme {
+            KeyScheme::Legacy => master_key.to_vec(),
+            KeyScheme::Hkdf => crypto::derive_entry_key(master_key, key),
+        })
+    }
+
+    /// Bytes covered by `mac`: the entries, serialized in sorted key order
+    fn mac_input(&self) -> Result<Vec<u8>> {
+        let sorted: BTreeMap<&String, &Entry> = self.entries.iter().collect();
+        Ok(serde_json::to_vec(&sorted)?)
+    }
+
+    /// Recompute `mac` for the current entries; call before every save
+    pub fn seal(&mut self, master_key: &[u8]) -> Result<()> {
+        let mac_key = Zeroizing::new(crypto::derive_mac_key(master_key));
+        let mac = crypto::compute_mac(&mac_key, &self.mac_input()?);
+        self.mac = Some(general_purpose::STANDARD.encode(mac));
+        Ok(())
+    }
+
+    /// Check `mac` against the entries
+    ///
+    /// Databases saved before the MAC existed have none and pass; they get one
+    /// on their next save. Fails with `IntegrityCheckFailed` on a mismatch.
+    pub fn verify_mac(&self, master_key: &[u8]) -> Result<()> {
+        let Some(stored) = &self.mac else {
+            return Ok(());
+        };
+
+        let tag = general_purpose::STANDARD
+            .decode(stored)
+            .map_err(|_| Error::IntegrityCheckFailed)?;
+        let mac_key = Zeroizing::new(crypto::derive_mac_key(master_key));
+
+        if crypto::verify_mac(&mac_key, &self.mac_input()?, &tag) {
+            Ok(())
+        } else {
+            Err(Error::IntegrityCheckFailed)
         }
     }
 
@@ -46,6 +271,25 @@ impl Database {
             .decode(&self.master_hash)
             .map_err(|e| Error::DatabaseLoadFailed(format!("Invalid hash: {e}")))
     }
+
+    /// Unlock every entry whose timed lock has expired at `now`
+    ///
+    /// Returns the unlocked keys, sorted. Only the in-memory copy changes; the
+    /// cleared flag is written with the next save, and until then every load
+    /// clears it again.
+    pub fn clear_expired_locks(&mut self, now: DateTime<Utc>) -> Vec<String> {
+        let mut cleared: Vec<String> = self
+            .entries
+            .iter_mut()
+            .filter(|(_, entry)| entry.lock_expired(now))
+            .map(|(key, entry)| {
+                entry.clear_lock();
+                key.clone()
+            })
+            .collect();
+        cleared.sort();
+        cleared
+    }
 }
 
 impl Entry {
@@ -55,9 +299,76 @@ impl Entry {
             encrypted_value: general_purpose::STANDARD.encode(&encrypted_value),
             nonce: general_purpose::STANDARD.encode(&nonce),
             is_locked,
+            access_count: 0,
+            last_accessed: None,
+            label: None,
+            unlock_after: None,
+            lock_password: None,
+            created_at: String::new(),
+            updated_at: String::new(),
+            notes: None,
+            tags: Vec::new(),
+            history: Vec::new(),
         }
     }
 
+    /// A copy of this entry holding a new value, with the current value moved into history
+    ///
+    /// Usage counters, label, notes, tags and creation time are kept and the copy
+    /// is unlocked. History stays encrypted under the same entry key, so nothing
+    /// is re-encrypted; at most `MAX_HISTORY` previous values are kept.
+    ///
+    /// # Arguments
+    /// * `encrypted` - The new value, encrypted with this entry's key
+    /// * `now` - RFC 3339 time of the change (history timestamp and `updated_at`)
+    pub fn with_replaced_value(&self, encrypted: crypto::EncryptedData, now: &str) -> Entry {
+        let mut history = Vec::with_capacity(MAX_HISTORY);
+        history.push(EncryptedHistoryItem {
+            ciphertext: self.encrypted_value.clone(),
+            nonce: self.nonce.clone(),
+            replaced_at: now.to_string(),
+        });
+        history.extend(self.history.iter().take(MAX_HISTORY - 1).cloned());
+
+        let mut updated = Entry::new(encrypted.ciphertext, encrypted.nonce, false);
+        updated.access_count = self.access_count;
+        updated.last_accessed = self.last_accessed.clone();
+        updated.label = self.label.clone();
+        updated.notes = self.notes.clone();
+        updated.tags = self.tags.clone();
+        updated.history = history;
+        updated.created_at = self.created_at.clone();
+        updated.updated_at = now.to_string();
+        updated
+    }
+
+    /// Whether the entry is locked with a passphrase (`ik lock --password`)
+    ///
+    /// Bulk operations (exports, replace/mirror imports) leave these entries alone,
+    /// since the passphrase can't be checked there.
+    pub fn is_passphrase_locked(&self) -> bool {
+        self.lock_password.is_some()
+    }
+
+    /// Clear the lock and all lock settings
+    pub fn clear_lock(&mut self) {
+        self.is_locked = false;
+        self.unlock_after = None;
+        self.lock_password = None;
+    }
+
+    /// Whether this entry has a timed lock whose period has passed at `now`
+    ///
+    /// An unparseable timestamp never expires, so a damaged entry stays locked.
+    pub fn lock_expired(&self, now: DateTime<Utc>) -> bool {
+        self.is_locked
+            && self
+                .unlock_after
+                .as_deref()
+                .and_then(|t| DateTime::parse_from_rfc3339(t).ok())
+                .is_some_and(|t| t <= now)
+    }
+
     /// Get the decoded encrypted value
     pub fn get_encrypted_value(&self) -> Result<Vec<u8>> {
         general_purpose::STANDARD
@@ -73,51 +384,579 @@ impl Entry {
     }
 }
 
-/// Get the database file path
-pub fn get_database_path() -> Result<PathBuf> {
-    let config_dir = dirs::config_dir()
+/// Environment variable that overrides the database file location
+pub const DB_PATH_ENV: &str = "IRONKEY_DB_PATH";
+
+/// Name of the vault used when `--vault` isn't given
+pub const DEFAULT_VAULT: &str = "default";
+
+/// Longest accepted vault name
+pub const MAX_VAULT_NAME_LENGTH: usize = 64;
+
+/// Vault chosen with `--vault` for this process (see `select_vault`)
+static SELECTED_VAULT: OnceLock<String> = OnceLock::new();
+
+/// Choose the named vault that `get_database_path` and the default backends use
+///
+/// Can only be set once per process, before any backend is opened.
+pub fn select_vault(name: &str) -> Result<()> {
+    validate_vault_name(name)?;
+    SELECTED_VAULT
+        .set(name.to_string())
+        .map_err(|_| Error::InvalidInput("A vault has already been selected".to_string()))
+}
+
+/// The vault selected with `select_vault` (`DEFAULT_VAULT` if none was)
+pub fn selected_vault() -> &'static str {
+    SELECTED_VAULT.get().map_or(DEFAULT_VAULT, String::as_str)
+}
+
+/// Check that `name` can be used as a vault name (and so as a file name)
+///
+/// Letters, digits, `-`, `_` and `.` only, not starting with `.`, at most
+/// `MAX_VAULT_NAME_LENGTH` characters.
+pub fn validate_vault_name(name: &str) -> Result<()> {
+    let valid_chars = name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+
+    if name.is_empty()
+        || name.len() > MAX_VAULT_NAME_LENGTH
+        || name.starts_with('.')
+        || !valid_chars
+    {
+        return Err(Error::InvalidInput(format!(
+            "Invalid vault name '{name}': use up to {MAX_VAULT_NAME_LENGTH} letters, digits, '-', '_' or '.' (not starting with '.')"
+        )));
+    }
+
+    Ok(())
+}
+
+/// `IRONKEY_DB_PATH`, if set to a non-empty value
+fn db_path_override() -> Option<PathBuf> {
+    std::env::var_os(DB_PATH_ENV)
+        .filter(|p| !p.is_empty())
+        .map(PathBuf::from)
+}
+
+/// Directory holding the vault files: the platform config directory, or the
+/// directory of `IRONKEY_DB_PATH` when that is set
+fn ironkey_dir() -> Result<PathBuf> {
+    if let Some(path) = db_path_override() {
+        return Ok(path.parent().map(PathBuf::from).unwrap_or_default());
+    }
+
+    Ok(dirs::config_dir()
         .ok_or_else(|| Error::Io("Could not find config directory".to_string()))?
-        .join("ironkey");
+        .join("ironkey"))
+}
 
-    Ok(config_dir.join("ironkey.json"))
+/// Directory of named vaults (`vaults/` next to the default database)
+pub fn vaults_dir() -> Result<PathBuf> {
+    Ok(ironkey_dir()?.join("vaults"))
 }
 
-/// Check if the database exists
-pub fn exists() -> Result<bool> {
-    let path = get_database_path()?;
-    Ok(path.exists())
+/// Database file of the named vault, with the given extension (`json` or `db`)
+///
+/// Vaults live in `vaults/<name>.<extension>`. The default vault keeps using a
+/// database from before named vaults existed (`ironkey.<extension>`) until
+/// `vaults/default.<extension>` is created, and `IRONKEY_DB_PATH` replaces its
+/// JSON file entirely.
+pub fn vault_database_path(name: &str, extension: &str) -> Result<PathBuf> {
+    validate_vault_name(name)?;
+    let path = vaults_dir()?.join(format!("{name}.{extension}"));
+    if name != DEFAULT_VAULT {
+        return Ok(path);
+    }
+
+    if extension == "json"
+        && let Some(path) = db_path_override()
+    {
+        return Ok(path);
+    }
+
+    let legacy = ironkey_dir()?.join(format!("ironkey.{extension}"));
+    if !path.exists() && legacy.exists() {
+        Ok(legacy)
+    } else {
+        Ok(path)
+    }
 }
 
-/// Load the database from disk
-pub fn load() -> Result<Database> {
-    let path = get_database_path()?;
+/// Get the database file path of the selected vault (see `select_vault`)
+///
+/// `IRONKEY_DB_PATH` takes precedence for the default vault when set to a
+/// non-empty value; otherwise the database lives in the platform config directory.
+pub fn get_database_path() -> Result<PathBuf> {
+    vault_database_path(selected_vault(), "json")
+}
+
+/// Names of the vaults that have a database file, sorted
+pub fn list_vaults() -> Result<Vec<String>> {
+    let mut names = BTreeSet::new();
+
+    for extension in ["json", "db"] {
+        if vault_database_path(DEFAULT_VAULT, extension)?.exists() {
+            names.insert(DEFAULT_VAULT.to_string());
+        }
+    }
 
-    if !path.exists() {
-        return Err(Error::DatabaseNotFound);
+    let entries = match fs::read_dir(vaults_dir()?) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            return Ok(names.into_iter().collect());
+        }
+        Err(e) => return Err(Error::Io(format!("Failed to read vaults directory: {e}"))),
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let is_database = path
+            .extension()
+            .is_some_and(|extension| extension == "json" || extension == "db");
+        if let Some(name) = path.file_stem().and_then(|stem| stem.to_str())
+            && is_database
+            && validate_vault_name(name).is_ok()
+        {
+            names.insert(name.to_string());
+        }
     }
 
-    let content =
-        fs::read_to_string(&path).map_err(|e| Error::DatabaseLoadFailed(e.to_string()))?;
+    Ok(names.into_iter().collect())
+}
 
-    let database: Database =
-        serde_json::from_str(&content).map_err(|e| Error::DatabaseLoadFailed(e.to_string()))?;
+/// Delete every file of the named vault (JSON database and backups, SQLite database)
+///
+/// # Returns
+/// false if the vault had no files
+pub fn remove_vault(name: &str) -> Result<bool> {
+    let json = JsonFileBackend::new(vault_database_path(name, "json")?);
+    let sqlite = vault_database_path(name, "db")?;
+    let existed = json.path.exists() || sqlite.exists();
+
+    json.remove()?;
+    for suffix in ["", "-journal", "-wal", "-shm"] {
+        let mut path = sqlite.clone().into_os_string();
+        path.push(suffix);
+        let path = PathBuf::from(path);
+        if path.exists() {
+            fs::remove_file(&path).map_err(|e| Error::DatabaseSaveFailed(e.to_string()))?;
+        }
+    }
 
-    Ok(database)
+    Ok(existed)
 }
 
-/// Save the database to disk
-pub fn save(database: &Database) -> Result<()> {
-    let path = get_database_path()?;
+/// Persistence layer for the vault database
+///
+/// `Vault` talks to storage only through this trait, so the JSON file can be
+/// swapped for other backends (e.g. in-memory for tests).
+pub trait StorageBackend: Send {
+    /// Check if a database has been stored
+    fn exists(&self) -> Result<bool>;
+
+    /// Load the stored database
+    fn load(&self) -> Result<Database>;
+
+    /// Persist the whole database
+    fn save(&self, database: &Database) -> Result<()>;
+
+    /// Persist a single added or changed entry (plus database metadata)
+    ///
+    /// Backends that can't write partially fall back to saving everything.
+    fn save_entry(&self, database: &Database, key: &str) -> Result<()> {
+        let _ = key;
+        self.save(database)
+    }
+
+    /// Persist the removal of a single entry (plus database metadata)
+    fn delete_entry(&self, database: &Database, key: &str) -> Result<()> {
+        let _ = key;
+        self.save(database)
+    }
+
+    /// Remove the stored database (no-op if there is none)
+    fn remove(&self) -> Result<()>;
 
-    // Create parent directory if it doesn't exist
-    if let Some(parent) = path.parent() {
-        fs::create_dir_all(parent).map_err(|e| Error::DatabaseSaveFailed(e.to_string()))?;
+    /// When the stored database was last written, if the backend knows
+    fn last_write_time(&self) -> Option<SystemTime> {
+        None
     }
+}
 
-    let content = serde_json::to_string_pretty(database)
-        .map_err(|e| Error::DatabaseSaveFailed(e.to_string()))?;
+/// Which storage backend to use for the vault
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BackendKind {
+    /// Single JSON file (default)
+    #[default]
+    Json,
+    /// SQLite database with one row per entry
+    Sqlite,
+}
 
-    fs::write(&path, content).map_err(|e| Error::DatabaseSaveFailed(e.to_string()))?;
+/// Open the backend of the given kind at its default location
+///
+/// `safe_save` turns on read-back verification for the JSON file backend
+/// (SQLite already syncs its journal on every commit).
+pub fn open_backend(kind: BackendKind, safe_save: bool) -> Result<Box<dyn StorageBackend>> {
+    Ok(match kind {
+        BackendKind::Json => {
+            Box::new(JsonFileBackend::default_location()?.with_safe_save(safe_save))
+        }
+        BackendKind::Sqlite => Box::new(SqliteBackend::default_location()?),
+    })
+}
+
+/// Default backend: the whole database as one pretty-printed JSON file
+#[derive(Debug, Clone)]
+pub struct JsonFileBackend {
+    path: PathBuf,
+    safe_save: bool,
+}
+
+impl JsonFileBackend {
+    /// Use a JSON file at the given path
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            safe_save: false,
+        }
+    }
+
+    /// Also fsync the parent directory on every save and read the file back before reporting success
+    pub fn with_safe_save(mut self, safe_save: bool) -> Self {
+        self.safe_save = safe_save;
+        self
+    }
+
+    /// Use the JSON file at the default location (see `get_database_path`)
+    pub fn default_location() -> Result<Self> {
+        Ok(Self::new(get_database_path()?))
+    }
+
+    /// Path next to the database file with a suffix appended (e.g. `ironkey.json.bak`)
+    pub fn sibling_path(&self, suffix: &str) -> PathBuf {
+        let mut name = self.path.clone().into_os_string();
+        name.push(suffix);
+        PathBuf::from(name)
+    }
+
+    /// Path of rotating backup `index` (1 = newest, up to `BACKUP_COUNT`), e.g. `ironkey.json.bak.1`
+    pub fn backup_path(&self, index: usize) -> PathBuf {
+        self.sibling_path(&format!(".bak.{index}"))
+    }
+
+    /// Copy the current database to `.bak.1`, shifting older backups down
+    ///
+    /// The oldest backup (`BACKUP_COUNT`) is dropped. Does nothing before the
+    /// first save, when there is no database to back up.
+    fn rotate_backups(&self) -> Result<()> {
+        if !self.path.exists() {
+            return Ok(());
+        }
+
+        let save_err = |e: std::io::Error| Error::DatabaseSaveFailed(e.to_string());
+
+        for index in (1..BACKUP_COUNT).rev() {
+            let from = self.backup_path(index);
+            if from.exists() {
+                fs::rename(&from, self.backup_path(index + 1)).map_err(save_err)?;
+            }
+        }
+
+        let newest = self.backup_path(1);
+        fs::copy(&self.path, &newest).map_err(save_err)?;
+        set_private_permissions(&newest).map_err(save_err)?;
+
+        Ok(())
+    }
+
+    /// Replace the database with rotating backup `index` (see `backup_path`)
+    ///
+    /// The current database is rotated into `.bak.1` like any other save, so a
+    /// restore can itself be undone. Returns the number of entries restored.
+    pub fn restore_backup(&self, index: usize) -> Result<usize> {
+        if !(1..=BACKUP_COUNT).contains(&index) {
+            return Err(Error::InvalidInput(format!(
+                "Backup number must be between 1 and {BACKUP_COUNT}"
+            )));
+        }
+
+        let backup = self.backup_path(index);
+        if !backup.exists() {
+            return Err(Error::InvalidInput(format!(
+                "No backup #{index} at '{}'",
+                backup.display()
+            )));
+        }
+
+        let database = Self::read_database(&backup)?;
+        self.save(&database)?;
+        Ok(database.entries.len())
+    }
+
+    /// Read and parse a database file, checking its master key material decodes
+    fn read_database(path: &std::path::Path) -> Result<Database> {
+        let content =
+            fs::read_to_string(path).map_err(|e| Error::DatabaseLoadFailed(e.to_string()))?;
+
+        Self::parse_database(&content)
+    }
+
+    fn parse_database(content: &str) -> Result<Database> {
+        let database: Database =
+            serde_json::from_str(content).map_err(|e| Error::DatabaseLoadFailed(e.to_string()))?;
+
+        database.get_salt()?;
+        database.get_hash()?;
+
+        Ok(database)
+    }
+
+    /// Confirm that `path` holds exactly `expected` and that it parses as a database
+    ///
+    /// Used by safe saves after syncing. The read may be served from the OS page
+    /// cache, so this catches failed or short writes and filesystem errors rather
+    /// than every kind of media fault.
+    pub fn verify_written(path: &std::path::Path, expected: &[u8]) -> Result<()> {
+        let actual = fs::read(path)
+            .map_err(|e| Error::DatabaseSaveFailed(format!("Read-back failed: {e}")))?;
+
+        if actual != expected {
+            return Err(Error::DatabaseSaveFailed(format!(
+                "Read-back of '{}' does not match what was written",
+                path.display()
+            )));
+        }
+
+        let content = String::from_utf8_lossy(&actual);
+        Self::parse_database(&content).map_err(|e| {
+            Error::DatabaseSaveFailed(format!(
+                "Read-back of '{}' is not a valid database: {e}",
+                path.display()
+            ))
+        })?;
+
+        Ok(())
+    }
+
+    /// Replace the database file with `content` atomically
+    ///
+    /// The content is written and fsynced to `<name>.tmp`, then renamed over the
+    /// database, so a crash leaves either the old file or the new one (never a
+    /// truncated mix). A leftover `.tmp` from an interrupted save is simply
+    /// overwritten. On Unix the file is readable only by the owner (0600).
+    fn write_atomic(&self, content: &[u8], sync_dir: bool) -> Result<()> {
+        use std::io::Write;
+
+        let save_err = |e: std::io::Error| Error::DatabaseSaveFailed(e.to_string());
+        let staged = self.sibling_path(".tmp");
+
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+        let mut file = options.open(&staged).map_err(save_err)?;
+        // The mode above only applies to new files; a stale `.tmp` keeps its own
+        set_private_permissions(&staged).map_err(save_err)?;
+        file.write_all(content).map_err(save_err)?;
+        file.sync_all().map_err(save_err)?;
+        drop(file);
+
+        fs::rename(&staged, &self.path).map_err(save_err)?;
+
+        // Directory fsync makes the rename itself durable (not supported on Windows)
+        #[cfg(unix)]
+        if sync_dir && let Some(parent) = self.path.parent() {
+            fs::File::open(parent)
+                .and_then(|dir| dir.sync_all())
+                .map_err(save_err)?;
+        }
+        #[cfg(not(unix))]
+        let _ = sync_dir;
+
+        Ok(())
+    }
+
+    /// Find the newest valid copy of the database
+    ///
+    /// Candidates newest first: the staging file (`.tmp`, only left behind by an
+    /// interrupted save), then the rotating backups `.bak.1` to `.bak.N`.
+    pub fn find_recoverable(&self) -> Option<(PathBuf, Database)> {
+        std::iter::once(self.sibling_path(".tmp"))
+            .chain((1..=BACKUP_COUNT).map(|index| self.backup_path(index)))
+            .find_map(|path| {
+                let database = Self::read_database(&path).ok()?;
+                Some((path, database))
+            })
+    }
+
+    /// Replace the database with a recovered copy
+    ///
+    /// The unreadable database file (if any) is kept as `<name>.corrupt`.
+    /// Returns the number of entries restored.
+    pub fn recover_from(&self, candidate: &std::path::Path) -> Result<usize> {
+        let database = Self::read_database(candidate)?;
+
+        if self.path.exists() {
+            fs::rename(&self.path, self.sibling_path(".corrupt"))
+                .map_err(|e| Error::DatabaseSaveFailed(e.to_string()))?;
+        }
+
+        self.save(&database)?;
+        Ok(database.entries.len())
+    }
+}
+
+/// Restrict a file to its owner (0600)
+///
+/// TODO: Windows relies on the default ACL of the user's config directory.
+pub(crate) fn set_private_permissions(path: &std::path::Path) -> std::io::Result<()> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(path, fs::Permissions::from_mode(0o600))?;
+    }
+    #[cfg(not(unix))]
+    let _ = path;
 
     Ok(())
 }
+
+/// Number of rotating `.bak.N` copies kept by `JsonFileBackend::save`
+pub const BACKUP_COUNT: usize = 3;
+
+/// Restore the database at the default location from rotating backup `index`
+///
+/// See `JsonFileBackend::restore_backup`.
+#[allow(dead_code)] // Public API - may be used by external consumers
+pub fn restore_from_backup(index: usize) -> Result<usize> {
+    JsonFileBackend::default_location()?.restore_backup(index)
+}
+
+impl StorageBackend for JsonFileBackend {
+    fn exists(&self) -> Result<bool> {
+        Ok(self.path.exists())
+    }
+
+    fn load(&self) -> Result<Database> {
+        if !self.path.exists() {
+            return Err(Error::DatabaseNotFound);
+        }
+
+        Self::read_database(&self.path).map_err(|e| match (e, self.find_recoverable()) {
+            (Error::DatabaseLoadFailed(msg), Some((backup, _))) => {
+                Error::DatabaseLoadFailed(format!(
+                    "{msg} (a usable copy exists at {}; run 'ik recover-db')",
+                    backup.display()
+                ))
+            }
+            (e, _) => e,
+        })
+    }
+
+    fn save(&self, database: &Database) -> Result<()> {
+        // Create parent directory if it doesn't exist
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent).map_err(|e| Error::DatabaseSaveFailed(e.to_string()))?;
+        }
+
+        let content = serde_json::to_string_pretty(database)
+            .map_err(|e| Error::DatabaseSaveFailed(e.to_string()))?;
+
+        self.rotate_backups()?;
+        self.write_atomic(content.as_bytes(), self.safe_save)?;
+
+        if self.safe_save {
+            Self::verify_written(&self.path, content.as_bytes())?;
+        }
+
+        Ok(())
+    }
+
+    fn remove(&self) -> Result<()> {
+        // Rotating backups go too, so no copy of the vault is left behind
+        let backups = (1..=BACKUP_COUNT).map(|index| self.backup_path(index));
+        for path in std::iter::once(self.path.clone()).chain(backups) {
+            if path.exists() {
+                fs::remove_file(&path).map_err(|e| Error::DatabaseSaveFailed(e.to_string()))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn last_write_time(&self) -> Option<SystemTime> {
+        fs::metadata(&self.path).ok()?.modified().ok()
+    }
+}
+
+/// In-memory backend (nothing touches the filesystem)
+///
+/// Clones share the same storage, so a vault can be re-unlocked from a clone.
+#[derive(Debug, Clone, Default)]
+#[allow(dead_code)] // Public API - used by tests and external consumers
+pub struct MemoryBackend {
+    database: Arc<Mutex<Option<Database>>>,
+}
+
+#[allow(dead_code)] // Public API - used by tests and external consumers
+impl MemoryBackend {
+    /// Create an empty in-memory backend
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn stored(&self) -> std::sync::MutexGuard<'_, Option<Database>> {
+        // A poisoned lock only means another thread panicked mid-save; the data is still usable
+        self.database.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl StorageBackend for MemoryBackend {
+    fn exists(&self) -> Result<bool> {
+        Ok(self.stored().is_some())
+    }
+
+    fn load(&self) -> Result<Database> {
+        self.stored().clone().ok_or(Error::DatabaseNotFound)
+    }
+
+    fn save(&self, database: &Database) -> Result<()> {
+        *self.stored() = Some(database.clone());
+        Ok(())
+    }
+
+    fn remove(&self) -> Result<()> {
+        *self.stored() = None;
+        Ok(())
+    }
+}
+
+/// Check if the database exists
+#[allow(dead_code)] // Public API - may be used by external consumers
+pub fn exists() -> Result<bool> {
+    JsonFileBackend::default_location()?.exists()
+}
+
+/// Load the database from disk
+pub fn load() -> Result<Database> {
+    JsonFileBackend::default_location()?.load()
+}
+
+/// Save the database to disk
+pub fn save(database: &Database) -> Result<()> {
+    JsonFileBackend::default_location()?.save(database)
+}
+
+/// Rewrite the database file in canonical form (sorted keys, pretty-printed)
+///
+/// Doesn't require the master password since no entry is decrypted.
+/// Returns the number of entries in the database.
+pub fn normalize() -> Result<usize> {
+    let database = load()?;
+    save(&database)?;
+    Ok(database.entries.len())
+}
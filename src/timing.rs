@@ -0,0 +1,45 @@
+//! Operation Timing
+//!
+//! `--verbose` reports how long key derivation, database loads/saves and
+//! encryption batches take. Timings go to stderr so normal output (and
+//! `--json`) is unaffected; with the flag off nothing is measured or printed.
+
+use std::time::Instant;
+
+/// Whether slow operations report their elapsed time
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Verbosity {
+    /// Print nothing extra (the default)
+    #[default]
+    Quiet,
+    /// Print the elapsed time of each timed operation to stderr
+    Verbose,
+}
+
+impl Verbosity {
+    pub fn from_flag(verbose: bool) -> Self {
+        if verbose {
+            Verbosity::Verbose
+        } else {
+            Verbosity::Quiet
+        }
+    }
+
+    pub fn is_verbose(self) -> bool {
+        self == Verbosity::Verbose
+    }
+
+    /// Run `op`, printing `label` and its elapsed time to stderr when verbose
+    ///
+    /// The timing is reported whether or not `op` succeeds.
+    pub fn time<T>(self, label: &str, op: impl FnOnce() -> T) -> T {
+        if !self.is_verbose() {
+            return op();
+        }
+
+        let start = Instant::now();
+        let result = op();
+        eprintln!("⏱   {label}: {:.1?}", start.elapsed());
+        result
+    }
+}
@@ -0,0 +1,161 @@
+//! TOTP Codes
+//!
+//! Time-based one-time passwords (RFC 6238) for entries that store a 2FA seed,
+//! either as a bare base32 secret or as an `otpauth://totp/...` URI.
+
+use crate::error::{Error, Result};
+use ring::hmac;
+use zeroize::Zeroizing;
+
+/// Digits in a code when the URI doesn't say otherwise
+pub const DEFAULT_DIGITS: u32 = 6;
+
+/// Seconds each code is valid for when the URI doesn't say otherwise
+pub const DEFAULT_PERIOD: u64 = 30;
+
+/// HMAC algorithm used to derive codes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TotpAlgorithm {
+    Sha1,
+    Sha256,
+    Sha512,
+}
+
+impl TotpAlgorithm {
+    fn hmac_algorithm(self) -> hmac::Algorithm {
+        match self {
+            // SHA-1 is what RFC 6238 and nearly every authenticator app use
+            TotpAlgorithm::Sha1 => hmac::HMAC_SHA1_FOR_LEGACY_USE_ONLY,
+            TotpAlgorithm::Sha256 => hmac::HMAC_SHA256,
+            TotpAlgorithm::Sha512 => hmac::HMAC_SHA512,
+        }
+    }
+}
+
+/// A decoded TOTP secret and its parameters
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Totp {
+    /// Raw seed bytes (zeroized on drop)
+    pub secret: Zeroizing<Vec<u8>>,
+    pub digits: u32,
+    pub period: u64,
+    pub algorithm: TotpAlgorithm,
+}
+
+impl Totp {
+    /// Parse an entry value: a base32 secret or an `otpauth://totp/` URI
+    pub fn parse(value: &str) -> Result<Self> {
+        let value = value.trim();
+        if value.starts_with("otpauth://") {
+            return Self::parse_uri(value);
+        }
+
+        Ok(Self {
+            secret: decode_secret(value)?,
+            digits: DEFAULT_DIGITS,
+            period: DEFAULT_PERIOD,
+            algorithm: TotpAlgorithm::Sha1,
+        })
+    }
+
+    fn parse_uri(uri: &str) -> Result<Self> {
+        let rest = uri
+            .strip_prefix("otpauth://totp/")
+            .ok_or_else(|| invalid("only otpauth://totp/ URIs are supported"))?;
+        let query = rest.split_once('?').map_or("", |(_, query)| query);
+
+        let mut secret = None;
+        let mut totp = Self {
+            secret: Zeroizing::new(Vec::new()),
+            digits: DEFAULT_DIGITS,
+            period: DEFAULT_PERIOD,
+            algorithm: TotpAlgorithm::Sha1,
+        };
+
+        for pair in query.split('&') {
+            let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+            match name.to_ascii_lowercase().as_str() {
+                "secret" => secret = Some(decode_secret(value)?),
+                "digits" => {
+                    totp.digits = value
+                        .parse()
+                        .ok()
+                        .filter(|digits| (6..=10).contains(digits))
+                        .ok_or_else(|| invalid("digits must be between 6 and 10"))?;
+                }
+                "period" => {
+                    totp.period = value
+                        .parse()
+                        .ok()
+                        .filter(|period| *period > 0)
+                        .ok_or_else(|| invalid("period must be a positive number of seconds"))?;
+                }
+                "algorithm" => {
+                    totp.algorithm = match value.to_ascii_uppercase().as_str() {
+                        "SHA1" => TotpAlgorithm::Sha1,
+                        "SHA256" => TotpAlgorithm::Sha256,
+                        "SHA512" => TotpAlgorithm::Sha512,
+                        other => return Err(invalid(&format!("unknown algorithm '{other}'"))),
+                    };
+                }
+                // issuer, label parameters and the like don't affect the code
+                _ => {}
+            }
+        }
+
+        totp.secret = secret.ok_or_else(|| invalid("the URI has no secret parameter"))?;
+        Ok(totp)
+    }
+
+    /// Code valid at `time` (seconds since the Unix epoch)
+    pub fn code_at(&self, time: u64) -> String {
+        let counter = time / self.period;
+        let key = hmac::Key::new(self.algorithm.hmac_algorithm(), &self.secret);
+        let tag = hmac::sign(&key, &counter.to_be_bytes());
+        let hash = tag.as_ref();
+
+        // Dynamic truncation (RFC 4226 section 5.3)
+        let offset = usize::from(hash[hash.len() - 1] & 0x0f);
+        let binary = u32::from_be_bytes([
+            hash[offset] & 0x7f,
+            hash[offset + 1],
+            hash[offset + 2],
+            hash[offset + 3],
+        ]);
+
+        let code = u64::from(binary) % 10u64.pow(self.digits);
+        format!("{code:0width$}", width = self.digits as usize)
+    }
+
+    /// Seconds until the code valid at `time` expires
+    pub fn seconds_remaining(&self, time: u64) -> u64 {
+        self.period - time % self.period
+    }
+}
+
+/// Code for `secret` (base32 or `otpauth://` URI) at `time` (seconds since the Unix epoch)
+#[allow(dead_code)] // Public API - may be used by external consumers
+pub fn generate_totp(secret: &str, time: u64) -> Result<String> {
+    Ok(Totp::parse(secret)?.code_at(time))
+}
+
+/// Decode a base32 secret, ignoring spaces, dashes, case and padding
+fn decode_secret(secret: &str) -> Result<Zeroizing<Vec<u8>>> {
+    let cleaned: String = secret
+        .chars()
+        .filter(|c| !matches!(c, ' ' | '-' | '='))
+        .map(|c| c.to_ascii_uppercase())
+        .collect();
+    if cleaned.is_empty() {
+        return Err(invalid("the secret is empty"));
+    }
+
+    base32::decode(base32::Alphabet::Rfc4648 { padding: false }, &cleaned)
+        .filter(|bytes| !bytes.is_empty())
+        .map(Zeroizing::new)
+        .ok_or_else(|| invalid("the secret is not valid base32"))
+}
+
+fn invalid(reason: &str) -> Error {
+    Error::InvalidInput(format!("✘ Not a TOTP secret: {reason}"))
+}
@@ -1,106 +1,621 @@
-use crate::crypto::{self, EncryptedData};
+use crate::crypto::{self, EncryptedData, KdfAlgorithm, KdfParams};
 use crate::error::{Error, Result};
-use crate::storage::{self, Database, Entry};
-use zeroize::Zeroize;
+use crate::key_pattern::{KeyPattern, MatchMode};
+use crate::secret::SecretString;
+use crate::storage::{
+    Database, EncryptedHistoryItem, EncryptedNote, Entry, JsonFileBackend, KeyScheme, LockPassword,
+    StorageBackend,
+};
+use crate::timing::Verbosity;
+use base64::{Engine as _, engine::general_purpose};
+use chrono::{DateTime, Utc};
+use rayon::prelude::*;
+use std::cell::{OnceCell, RefCell};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+use zeroize::{Zeroize, Zeroizing};
+
+/// Longest entry key accepted by `Vault::validate_key`, in characters
+pub const MAX_KEY_LENGTH: usize = 256;
+
+/// Non-secret metadata about an entry (available without decrypting)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntrySummary {
+    pub key: String,
+    pub is_locked: bool,
+    pub access_count: u64,
+    pub last_accessed: Option<String>,
+    pub label: Option<String>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+    pub tag_count: usize,
+}
+
+/// Non-secret details about a single entry (see `Vault::entry_metadata`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntryMetadata {
+    pub key: String,
+    pub is_locked: bool,
+    pub label: Option<String>,
+    pub access_count: u64,
+    pub last_accessed: Option<String>,
+    /// RFC 3339 creation time (None for entries created before tracking)
+    pub created_at: Option<String>,
+    /// RFC 3339 time of the last value change (None for entries created before tracking)
+    pub updated_at: Option<String>,
+    pub tags: Vec<String>,
+}
+
+/// Scope and cost estimate of re-encrypting the whole vault (see `Vault::reencrypt_plan`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReencryptPlan {
+    /// Entries that would be re-encrypted
+    pub entry_count: usize,
+    /// How many of those are locked (re-encrypted all the same)
+    pub locked_count: usize,
+    /// KDF iterations the new key would be derived with
+    pub iterations: u32,
+    /// Measured time of one key derivation at `iterations`
+    pub kdf_time: Duration,
+    /// Estimated total time (key derivation plus per-entry re-encryption)
+    pub estimated_time: Duration,
+}
+
+/// Outcome of `Vault::verify_integrity` (keys only, never values)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IntegrityReport {
+    /// Entries whose value (notes and history) decode and decrypt, sorted
+    pub healthy: Vec<String>,
+    /// Damaged entries as (key, what is wrong), sorted by key
+    pub damaged: Vec<(String, String)>,
+    /// The entries don't match the database MAC (modified outside ironkey)
+    pub mac_mismatch: bool,
+}
+
+impl IntegrityReport {
+    /// Whether every entry could be decrypted and the database MAC matched
+    pub fn is_healthy(&self) -> bool {
+        self.damaged.is_empty() && !self.mac_mismatch
+    }
+}
+
+/// What a guarded create does when the key already exists
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OnExists {
+    /// Return `Error::EntryAlreadyExists` (default)
+    Fail,
+    /// Leave the existing entry untouched
+    Ignore,
+}
+
+/// What a guarded update does when the key is missing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OnMissing {
+    /// Return `Error::EntryNotFound` (default)
+    Fail,
+    /// Create the entry instead (upsert)
+    Create,
+}
+
+/// Result of a guarded create/update
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WriteOutcome {
+    Created,
+    Updated,
+    Unchanged,
+}
+
+/// A previous value of an entry (see `Vault::get_history`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HistoryVersion {
+    /// 1 for the most recently replaced value
+    pub version: usize,
+    /// RFC 3339 time the value was replaced
+    pub replaced_at: String,
+    pub value: String,
+}
+
+/// Result of `Vault::search_values` (keys only, never values)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ValueSearchResult {
+    /// Keys whose value contains the search text, sorted
+    pub matches: Vec<String>,
+    /// Locked entries that were not searched
+    pub skipped_locked: usize,
+}
+
+/// Master password attempts an interactive unlock gets before giving up
+pub const MAX_UNLOCK_ATTEMPTS: u32 = 3;
+
+/// How long to wait after `failed_attempts` wrong master passwords before the next try
+///
+/// Grows with each failure (1s, 2s, ...) to slow down guessing at the prompt.
+pub fn unlock_retry_delay(failed_attempts: u32) -> Duration {
+    Duration::from_secs(u64::from(failed_attempts))
+}
+
+/// Decide whether a vault last modified at `last_modified` is stale at `now`
+///
+/// A vault is stale once more than `max_age_days` full days have passed.
+pub fn is_stale(last_modified: DateTime<Utc>, now: DateTime<Utc>, max_age_days: u32) -> bool {
+    now.signed_duration_since(last_modified) > chrono::Duration::days(i64::from(max_age_days))
+}
+
+/// Re-encrypt an entry's notes from `old_key` to `new_key` under a fresh nonce
+fn reencrypt_note(notes: &EncryptedNote, old_key: &[u8], new_key: &[u8]) -> Result<EncryptedNote> {
+    let plaintext = Zeroizing::new(crypto::decrypt(&notes.encrypted_data()?, old_key)?);
+    Ok(EncryptedNote::new(&crypto::encrypt(&plaintext, new_key)?))
+}
+
+/// Re-encrypt an entry's previous values from `old_key` to `new_key` under fresh nonces
+fn reencrypt_history(
+    history: &[EncryptedHistoryItem],
+    old_key: &[u8],
+    new_key: &[u8],
+) -> Result<Vec<EncryptedHistoryItem>> {
+    history
+        .iter()
+        .map(|item| {
+            let plaintext = Zeroizing::new(crypto::decrypt(&item.encrypted_data()?, old_key)?);
+            Ok(EncryptedHistoryItem::new(
+                &crypto::encrypt(&plaintext, new_key)?,
+                item.replaced_at.clone(),
+            ))
+        })
+        .collect()
+}
+
+/// Configures where a vault lives and how its master key is derived
+///
+/// Lets library users (e.g. a GUI) pick the database location and KDF explicitly
+/// instead of relying on the CLI's default path:
+///
+/// ```no_run
+/// # use ironkey::vault::VaultBuilder;
+/// let vault = VaultBuilder::new()
+///     .path("/tmp/my-vault.json")
+///     .iterations(200_000)
+///     .init("master password".to_string())?;
+/// # Ok::<(), ironkey::error::Error>(())
+/// ```
+///
+/// Unset options fall back to the same defaults as `Vault::init`/`Vault::unlock`:
+/// the default database location, PBKDF2 with the default iteration count, and
+/// quiet timing.
+#[derive(Default)]
+pub struct VaultBuilder {
+    backend: Option<Box<dyn StorageBackend>>,
+    kdf: Option<KdfParams>,
+    iterations: Option<u32>,
+    verbosity: Verbosity,
+}
+
+impl VaultBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Store the vault as a JSON database at `path` (replaces any earlier `backend`)
+    #[allow(dead_code)] // Public API - may be used by external consumers
+    pub fn path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.backend = Some(Box::new(JsonFileBackend::new(path.into())));
+        self
+    }
+
+    /// Store the vault in the given backend (replaces any earlier `path`)
+    pub fn backend(mut self, backend: Box<dyn StorageBackend>) -> Self {
+        self.backend = Some(backend);
+        self
+    }
+
+    /// KDF for the master key of a new vault
+    ///
+    /// Ignored by `unlock`, which always uses the KDF the vault was created with.
+    pub fn kdf(mut self, kdf: KdfParams) -> Self {
+        self.kdf = Some(kdf);
+        self
+    }
+
+    /// Iteration count (or Argon2 time cost) for a new vault, overriding the `kdf` default
+    ///
+    /// Ignored by `unlock`, like `kdf`.
+    #[allow(dead_code)] // Public API - may be used by external consumers
+    pub fn iterations(mut self, iterations: u32) -> Self {
+        self.iterations = Some(iterations);
+        self
+    }
+
+    /// Report key derivation, storage and crypto timings to stderr
+    pub fn verbosity(mut self, verbosity: Verbosity) -> Self {
+        self.verbosity = verbosity;
+        self
+    }
+
+    /// Create a new vault protected by `master_password`
+    ///
+    /// Fails with `MasterKeyAlreadyExists` if the location already holds a vault.
+    pub fn init(mut self, master_password: String) -> Result<Vault> {
+        let kdf = self.kdf_params();
+        let mut vault =
+            Vault::create_unsaved(self.open_backend()?, master_password, kdf, self.verbosity)?;
+        vault.save_new()?;
+
+        Ok(vault)
+    }
+
+    /// Create a new vault and populate it from an encrypted .ik backup (replace mode)
+    ///
+    /// Nothing is written unless the import succeeds, see `Vault::init_from_backup`.
+    pub fn init_from_backup(
+        mut self,
+        master_password: String,
+        import_path: &std::path::Path,
+        import_password: String,
+    ) -> Result<(Vault, crate::import::ImportResult)> {
+        let kdf = self.kdf_params();
+        let verbosity = self.verbosity;
+        let mut vault =
+            Vault::create_unsaved(self.open_backend()?, master_password, kdf, verbosity)?;
+
+        let result = verbosity.time("import decryption", || {
+            crate::import::import_vault(
+                import_path,
+                import_password,
+                &mut vault.db,
+                &vault.master_key,
+                false,
+                true,
+                false,
+                None,
+            )
+        })?;
+        vault.save_new()?;
+
+        Ok((vault, result))
+    }
+
+    /// Unlock an existing vault with `master_password`
+    pub fn unlock(mut self, master_password: String) -> Result<Vault> {
+        Vault::open(self.open_backend()?, master_password, true, self.verbosity)
+    }
+
+    fn kdf_params(&self) -> KdfParams {
+        let mut kdf = self
+            .kdf
+            .unwrap_or_else(|| KdfParams::default_for(KdfAlgorithm::Pbkdf2));
+        if let Some(iterations) = self.iterations {
+            kdf.iterations = iterations;
+        }
+        kdf
+    }
+
+    fn open_backend(&mut self) -> Result<Box<dyn StorageBackend>> {
+        match self.backend.take() {
+            Some(backend) => Ok(backend),
+            None => Ok(Box::new(JsonFileBackend::default_location()?)),
+        }
+    }
+}
 
 /// The Vault manages all password entries and master key operations
 pub struct Vault {
     db: Database,
     master_key: Vec<u8>,
+    track_access: bool,
+    backend: Box<dyn StorageBackend>,
+    /// Lowercased keys for substring search, built on demand (see `prehash_search_index`)
+    search_index: OnceCell<Vec<(String, String)>>,
+    /// Values already decrypted by `get_entry` in this process (cleared on mutation and drop)
+    value_cache: RefCell<HashMap<String, Zeroizing<String>>>,
+    /// The stored MAC didn't match the entries (only possible via `unlock_unverified_with_backend`)
+    mac_mismatch: bool,
+    /// `master_key` was wiped by `lock_session`; operations fail until `reunlock`
+    locked_session: bool,
+    /// Whether key derivation, storage and crypto batches report their timing
+    verbosity: Verbosity,
 }
 
 impl Vault {
     /// Initialize a new vault with a master password
+    ///
+    /// Uses the default location and KDF; see `VaultBuilder` to choose them.
+    #[allow(dead_code)] // Public API - may be used by external consumers
     pub fn init(master_password: String) -> Result<Self> {
+        VaultBuilder::new().init(master_password)
+    }
+
+    /// First save of a newly created vault (always the last step of init)
+    ///
+    /// If the write fails, whatever was partially written is removed so a broken
+    /// database doesn't block the next `init` with `MasterKeyAlreadyExists`.
+    fn save_new(&mut self) -> Result<()> {
+        self.seal()?;
+        self.verbosity
+            .time("database save", || self.backend.save(&self.db))
+            .inspect_err(|_| {
+                let _ = self.backend.remove();
+            })
+    }
+
+    /// Build a new vault in memory without writing anything to the backend
+    fn create_unsaved(
+        backend: Box<dyn StorageBackend>,
+        master_password: String,
+        kdf: KdfParams,
+        verbosity: Verbosity,
+    ) -> Result<Self> {
         // Check if database already exists
-        if storage::exists()? {
+        if backend.exists()? {
             return Err(Error::MasterKeyAlreadyExists);
         }
 
         if master_password.trim().is_empty() {
             return Err(Error::EmptyPassword);
         }
+        kdf.validate()?;
 
-        // Generate salt and derive key
+        // Generate salt and derive key; the stored hash is that same key (see
+        // `open`), so one KDF run is enough
         let salt = crypto::generate_salt()?;
-        let iterations = crypto::default_iterations();
-        let master_key = crypto::derive_key(&master_password, &salt, iterations)?;
-
-        // Hash password for verification
-        let master_hash = crypto::hash_password(&master_password, &salt, iterations)?;
+        let master_key = verbosity.time("key derivation", || {
+            crypto::derive_key_with(&master_password, &salt, &kdf)
+        })?;
 
         // Create database
-        let db = Database::new(salt, master_hash, iterations);
+        let mut db = Database::new_with_kdf(salt, master_key.clone(), &kdf);
+        db.last_modified = Some(Utc::now().to_rfc3339());
+        db.key_scheme = KeyScheme::Hkdf;
 
-        // Save to disk
-        storage::save(&db)?;
+        Ok(Self {
+            db,
+            master_key,
+            track_access: false,
+            backend,
+            search_index: OnceCell::new(),
+            value_cache: RefCell::new(HashMap::new()),
+            mac_mismatch: false,
+            locked_session: false,
+            verbosity,
+        })
+    }
 
-        Ok(Self { db, master_key })
+    /// Initialize a new vault and populate it from an encrypted .ik backup
+    ///
+    /// Imports in replace mode. The vault is only written once the import has
+    /// succeeded, so a failed import (e.g. wrong import password) leaves no
+    /// half-created database behind and the command can simply be retried.
+    ///
+    /// # Arguments
+    /// * `master_password` - Master password for the new vault
+    /// * `import_path` - Path to the .ik file to import
+    /// * `import_password` - Password used to encrypt the export file
+    #[allow(dead_code)] // Public API - may be used by external consumers
+    pub fn init_from_backup(
+        master_password: String,
+        import_path: &std::path::Path,
+        import_password: String,
+    ) -> Result<(Self, crate::import::ImportResult)> {
+        VaultBuilder::new().init_from_backup(master_password, import_path, import_password)
     }
 
     /// Unlock an existing vault with master password
-    pub fn unlock(mut master_password: String) -> Result<Self> {
-        // Load database
-        let db = storage::load()?;
+    ///
+    /// Uses the default location; see `VaultBuilder` to choose another.
+    #[allow(dead_code)] // Public API - may be used by external consumers
+    pub fn unlock(master_password: String) -> Result<Self> {
+        VaultBuilder::new().unlock(master_password)
+    }
+
+    /// Open the vault stored in `backend` even if the MAC doesn't match
+    ///
+    /// For diagnosing a damaged vault (`ik doctor`); `verify_integrity` reports the mismatch.
+    pub fn unlock_unverified_with_backend(
+        backend: Box<dyn StorageBackend>,
+        master_password: String,
+    ) -> Result<Self> {
+        Self::open(backend, master_password, false, Verbosity::Quiet)
+    }
+
+    fn open(
+        backend: Box<dyn StorageBackend>,
+        mut master_password: String,
+        verify_mac: bool,
+        verbosity: Verbosity,
+    ) -> Result<Self> {
+        let mut db = verbosity.time("database load", || backend.load())?;
 
         // Get salt and hash
         let salt = db.get_salt()?;
         let stored_hash = db.get_hash()?;
 
-        // Verify password
-        let is_valid =
-            crypto::verify_password(&master_password, &salt, &stored_hash, db.iterations)?;
+        // Derive the key with the KDF the vault was created with; the stored
+        // hash is that same key, so one KDF run both verifies and unlocks
+        let kdf = db.kdf_params()?;
+        let derived = verbosity.time("key derivation", || {
+            crypto::derive_verified_key_with(&master_password, &salt, &stored_hash, &kdf)
+        });
 
-        if !is_valid {
-            master_password.zeroize();
+        // Zeroize password
+        master_password.zeroize();
+
+        let Some(mut master_key) = derived? else {
             return Err(Error::InvalidMasterPassword);
+        };
+
+        // Confirm the derived key actually decrypts the stored entries
+        if let Err(e) = verbosity.time("entry decryption check", || {
+            Self::check_key_against_entries(&db, &master_key)
+        }) {
+            master_key.zeroize();
+            return Err(e);
         }
 
-        // Derive encryption key
-        let master_key = crypto::derive_key(&master_password, &salt, db.iterations)?;
+        let mac_result = db.verify_mac(&master_key);
+        if verify_mac && let Err(e) = mac_result {
+            master_key.zeroize();
+            return Err(e);
+        }
+        let mac_mismatch = mac_result.is_err();
 
-        // Zeroize password
-        master_password.zeroize();
+        // Release timed locks that have run out
+        if !db.clear_expired_locks(Utc::now()).is_empty() && !mac_mismatch {
+            db.seal(&master_key)?;
+        }
+
+        Ok(Self {
+            db,
+            master_key,
+            track_access: false,
+            backend,
+            search_index: OnceCell::new(),
+            value_cache: RefCell::new(HashMap::new()),
+            mac_mismatch,
+            locked_session: false,
+            verbosity,
+        })
+    }
+
+    /// Test-decrypt entries to confirm the derived key matches the data
+    ///
+    /// The stored hash and the encryption key are derived the same way, so a hash
+    /// that verifies while no entry decrypts means the file was corrupted or tampered with.
+    /// Entries with undecodable fields are skipped; succeeding on any single entry is enough.
+    fn check_key_against_entries(db: &Database, master_key: &[u8]) -> Result<()> {
+        let mut attempted = false;
+
+        for (key, entry) in &db.entries {
+            let (Ok(ciphertext), Ok(nonce)) = (entry.get_encrypted_value(), entry.get_nonce())
+            else {
+                continue;
+            };
+
+            attempted = true;
+            let entry_key = db.entry_encryption_key(master_key, key);
+            if let Ok(mut plaintext) =
+                crypto::decrypt(&EncryptedData { ciphertext, nonce }, &entry_key)
+            {
+                plaintext.zeroize();
+                return Ok(());
+            }
+        }
 
-        Ok(Self { db, master_key })
+        if attempted {
+            Err(Error::KeyMismatch)
+        } else {
+            Ok(())
+        }
     }
 
     /// Verify that a master password is correct (for init command)
-    pub fn verify_master_password(mut master_password: String) -> Result<bool> {
-        let db = storage::load()?;
+    #[allow(dead_code)] // Public API - may be used by external consumers
+    pub fn verify_master_password(master_password: String) -> Result<bool> {
+        Self::verify_master_password_with_backend(
+            Box::new(JsonFileBackend::default_location()?),
+            master_password,
+        )
+    }
+
+    /// Verify a master password against the database in the given backend
+    pub fn verify_master_password_with_backend(
+        backend: Box<dyn StorageBackend>,
+        mut master_password: String,
+    ) -> Result<bool> {
+        let db = backend.load()?;
         let salt = db.get_salt()?;
         let stored_hash = db.get_hash()?;
 
-        let result = crypto::verify_password(&master_password, &salt, &stored_hash, db.iterations)?;
+        let result =
+            crypto::verify_password_with(&master_password, &salt, &stored_hash, &db.kdf_params()?)?;
         master_password.zeroize();
 
         Ok(result)
     }
 
+    /// Check that `key` is usable as an entry name
+    ///
+    /// Rejects empty (or all-whitespace) keys, keys containing control
+    /// characters such as newlines or tabs, and keys longer than
+    /// `MAX_KEY_LENGTH` characters, since those break `list` output and exports.
+    pub fn validate_key(key: &str) -> Result<()> {
+        if key.chars().any(char::is_control) {
+            return Err(Error::InvalidInput(format!(
+                "Entry key {key:?} contains control characters (e.g. newlines or tabs)"
+            )));
+        }
+        if key.trim().is_empty() {
+            return Err(Error::InvalidInput("Entry key cannot be empty".to_string()));
+        }
+        let length = key.chars().count();
+        if length > MAX_KEY_LENGTH {
+            return Err(Error::InvalidInput(format!(
+                "Entry key is too long: {length} characters (maximum is {MAX_KEY_LENGTH})"
+            )));
+        }
+
+        Ok(())
+    }
+
     /// Create a new entry
+    ///
+    /// The plaintext value is zeroized once it has been encrypted.
     pub fn create_entry(&mut self, key: String, value: String) -> Result<()> {
+        let value = Zeroizing::new(value);
+        self.ensure_session_unlocked()?;
+        Self::validate_key(&key)?;
+
         // Check if key already exists
         if self.db.entries.contains_key(&key) {
             return Err(Error::EntryAlreadyExists(key));
         }
 
         // Encrypt the value
-        let encrypted = crypto::encrypt(value.as_bytes(), &self.master_key)?;
+        let entry_key = self.db.entry_encryption_key(&self.master_key, &key);
+        let encrypted = crypto::encrypt(value.as_bytes(), &entry_key)?;
 
         // Create entry
-        let entry = Entry::new(encrypted.ciphertext, encrypted.nonce, false);
+        let mut entry = Entry::new(encrypted.ciphertext, encrypted.nonce, false);
+        let now = Utc::now().to_rfc3339();
+        entry.created_at = now.clone();
+        entry.updated_at = now;
 
         // Add to database
         self.db.entries.insert(key.clone(), entry);
 
         // Save to disk
-        storage::save(&self.db)?;
+        self.save_entry_modified(&key)?;
 
         Ok(())
     }
 
+    /// Create an entry, with explicit behavior when the key already exists
+    pub fn create_entry_guarded(
+        &mut self,
+        key: String,
+        value: String,
+        on_exists: OnExists,
+    ) -> Result<WriteOutcome> {
+        match self.create_entry(key, value) {
+            Ok(()) => Ok(WriteOutcome::Created),
+            Err(Error::EntryAlreadyExists(_)) if on_exists == OnExists::Ignore => {
+                Ok(WriteOutcome::Unchanged)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
     /// Get an entry's value
+    ///
+    /// The returned `String` isn't wiped when dropped; prefer `get_entry_secret`.
     pub fn get_entry(&self, key: &str) -> Result<String> {
+        self.get_entry_secret(key)
+            .map(|value| value.as_str().to_string())
+    }
+
+    /// Get an entry's value as a `SecretString`, which is zeroized on drop
+    pub fn get_entry_secret(&self, key: &str) -> Result<SecretString> {
+        self.ensure_session_unlocked()?;
+
         // Check if entry exists
         let entry = self
             .db
@@ -113,20 +628,109 @@ impl Vault {
             return Err(Error::EntryLocked(key.to_string()));
         }
 
-        // Decrypt the value
+        if let Some(value) = self.value_cache.borrow().get(key) {
+            return Ok(SecretString::from(value.to_string()));
+        }
+
+        let value = self.decrypt_value(key, entry)?;
+
+        self.value_cache
+            .borrow_mut()
+            .insert(key.to_string(), Zeroizing::new(value.clone()));
+
+        Ok(SecretString::from(value))
+    }
+
+    /// Get an entry's value even if it is locked (`ik get --unlock-once`)
+    ///
+    /// Skips only the lock check: `is_locked` stays set and nothing is written.
+    /// Callers must confirm the user's intent first (re-prompting for the
+    /// master password, or the lock passphrase if the entry has one). The value
+    /// isn't cached, so a later `get_entry` still refuses the entry.
+    pub fn get_entry_unlocked(&self, key: &str) -> Result<String> {
+        self.ensure_session_unlocked()?;
+
+        let entry = self
+            .db
+            .entries
+            .get(key)
+            .ok_or_else(|| Error::EntryNotFound(key.to_string()))?;
+
+        self.decrypt_value(key, entry)
+    }
+
+    fn decrypt_value(&self, key: &str, entry: &Entry) -> Result<String> {
         let encrypted = EncryptedData {
             ciphertext: entry.get_encrypted_value()?,
             nonce: entry.get_nonce()?,
         };
 
-        let decrypted = crypto::decrypt(&encrypted, &self.master_key)?;
-        let value = String::from_utf8(decrypted)?;
+        let entry_key = self.db.entry_encryption_key(&self.master_key, key);
+        let decrypted = crypto::decrypt(&encrypted, &entry_key)?;
+        Ok(String::from_utf8(decrypted)?)
+    }
+
+    /// Enable or disable access tracking for `access_entry`
+    ///
+    /// Disabled by default so that reading secrets never writes to disk.
+    pub fn set_access_tracking(&mut self, enabled: bool) {
+        self.track_access = enabled;
+    }
+
+    /// Get an entry's value, recording the access when tracking is enabled
+    ///
+    /// Increments `access_count`, sets `last_accessed` and saves the database.
+    /// With tracking disabled this behaves exactly like `get_entry`.
+    #[allow(dead_code)] // Public API - may be used by external consumers
+    pub fn access_entry(&mut self, key: &str) -> Result<String> {
+        self.access_entry_secret(key)
+            .map(|value| value.as_str().to_string())
+    }
+
+    /// Same as `access_entry`, returning a `SecretString` that is zeroized on drop
+    pub fn access_entry_secret(&mut self, key: &str) -> Result<SecretString> {
+        let value = self.get_entry_secret(key)?;
+
+        if self.track_access
+            && let Some(entry) = self.db.entries.get_mut(key)
+        {
+            entry.access_count += 1;
+            entry.last_accessed = Some(Utc::now().to_rfc3339());
+            self.seal()?;
+            self.verbosity
+                .time("database save", || self.backend.save_entry(&self.db, key))?;
+        }
 
         Ok(value)
     }
 
+    /// Get access statistics for all entries
+    ///
+    /// # Returns
+    /// Tuples of (key, access_count, last_accessed) sorted by most accessed first, then by key
+    pub fn access_stats(&self) -> Vec<(&String, u64, Option<&str>)> {
+        let mut stats: Vec<(&String, u64, Option<&str>)> = self
+            .db
+            .entries
+            .iter()
+            .map(|(key, entry)| (key, entry.access_count, entry.last_accessed.as_deref()))
+            .collect();
+
+        stats.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+
+        stats
+    }
+
     /// Update an existing entry's value
+    ///
+    /// The previous value is kept in the entry's history (up to `MAX_HISTORY`
+    /// versions, oldest dropped first). The plaintext value is zeroized once it
+    /// has been encrypted.
     pub fn update_entry(&mut self, key: String, new_value: String) -> Result<()> {
+        let new_value = Zeroizing::new(new_value);
+        self.ensure_session_unlocked()?;
+        Self::validate_key(&key)?;
+
         // Check if entry exists
         let entry = self
             .db
@@ -140,20 +744,202 @@ impl Vault {
         }
 
         // Encrypt the new value
-        let encrypted = crypto::encrypt(new_value.as_bytes(), &self.master_key)?;
+        let entry_key = self.db.entry_encryption_key(&self.master_key, &key);
+        let encrypted = crypto::encrypt(new_value.as_bytes(), &entry_key)?;
 
-        // Update entry with new encrypted value
-        let updated_entry = Entry::new(encrypted.ciphertext, encrypted.nonce, false);
+        // The old value moves to the front of the history (same entry key, so no re-encryption)
+        let updated_entry = entry.with_replaced_value(encrypted, &Utc::now().to_rfc3339());
 
         // Replace in database
-        self.db.entries.insert(key, updated_entry);
+        self.db.entries.insert(key.clone(), updated_entry);
 
         // Save to disk
-        storage::save(&self.db)?;
+        self.save_entry_modified(&key)?;
 
         Ok(())
     }
 
+    /// Update an entry, with explicit behavior when the key is missing
+    pub fn update_entry_guarded(
+        &mut self,
+        key: String,
+        new_value: String,
+        on_missing: OnMissing,
+    ) -> Result<WriteOutcome> {
+        if on_missing == OnMissing::Create && !self.db.entries.contains_key(&key) {
+            self.create_entry(key, new_value)?;
+            return Ok(WriteOutcome::Created);
+        }
+
+        self.update_entry(key, new_value)?;
+        Ok(WriteOutcome::Updated)
+    }
+
+    /// Replace an entry's value, or create it if it doesn't exist
+    ///
+    /// Returns the length in characters of the replaced value (`None` if the
+    /// entry was created); the old value itself is never returned. Locked
+    /// entries fail with `Error::EntryLocked`, like `update_entry`.
+    pub fn rotate_entry(&mut self, key: String, new_value: String) -> Result<Option<usize>> {
+        let old_len = match self.get_entry(&key) {
+            Ok(old) => Zeroizing::new(old).chars().count(),
+            Err(Error::EntryNotFound(_)) => {
+                self.create_entry(key, new_value)?;
+                return Ok(None);
+            }
+            Err(e) => return Err(e),
+        };
+
+        self.update_entry(key, new_value)?;
+        Ok(Some(old_len))
+    }
+
+    /// Decrypt an entry's previous values, most recent first
+    ///
+    /// Refuses locked entries the same way `get_entry` does.
+    pub fn get_history(&self, key: &str) -> Result<Vec<HistoryVersion>> {
+        self.ensure_session_unlocked()?;
+        let entry = self
+            .db
+            .entries
+            .get(key)
+            .ok_or_else(|| Error::EntryNotFound(key.to_string()))?;
+
+        if entry.is_locked {
+            return Err(Error::EntryLocked(key.to_string()));
+        }
+
+        let entry_key = self.db.entry_encryption_key(&self.master_key, key);
+        entry
+            .history
+            .iter()
+            .enumerate()
+            .map(|(index, item)| {
+                let decrypted = crypto::decrypt(&item.encrypted_data()?, &entry_key)?;
+                Ok(HistoryVersion {
+                    version: index + 1,
+                    replaced_at: item.replaced_at.clone(),
+                    value: String::from_utf8(decrypted)?,
+                })
+            })
+            .collect()
+    }
+
+    /// Restore a previous value (`version` as numbered by `get_history`)
+    ///
+    /// Works like `update_entry`, so the value being replaced is kept in the
+    /// history and the reverted change can itself be undone.
+    pub fn revert(&mut self, key: &str, version: usize) -> Result<()> {
+        let mut history = self.get_history(key)?;
+        if version == 0 || version > history.len() {
+            return Err(Error::InvalidInput(format!(
+                "Entry '{key}' has no version {version} ({} in history)",
+                history.len()
+            )));
+        }
+
+        let value = std::mem::take(&mut history[version - 1].value);
+        for item in &mut history {
+            item.value.zeroize();
+        }
+
+        self.update_entry(key.to_string(), value)
+    }
+
+    /// Rename a single entry, keeping its value, lock status and metadata
+    ///
+    /// Locked entries can be renamed and stay locked: the value is never
+    /// returned, though under `KeyScheme::Hkdf` it is decrypted internally to
+    /// re-encrypt it under the new key name. Only `new_key` is validated, so a
+    /// key that `validate_key` rejects can still be renamed to a valid one.
+    pub fn rename_entry(&mut self, old_key: &str, new_key: &str) -> Result<()> {
+        self.ensure_session_unlocked()?;
+        Self::validate_key(new_key)?;
+        let entry = self
+            .db
+            .entries
+            .get(old_key)
+            .ok_or_else(|| Error::EntryNotFound(old_key.to_string()))?;
+        if self.db.entries.contains_key(new_key) {
+            return Err(Error::EntryAlreadyExists(new_key.to_string()));
+        }
+
+        let moved = self.rekey_entry(entry, old_key, new_key)?;
+        self.db.entries.remove(old_key);
+        self.db.entries.insert(new_key.to_string(), moved);
+        self.save_modified()
+    }
+
+    /// Rename every key starting with `from_prefix` to start with `to_prefix` instead
+    ///
+    /// Every target is checked before anything changes, so a collision with an
+    /// existing key (or a locked entry) leaves the vault untouched. Metadata is
+    /// kept as-is; values are only re-encrypted when entry keys are derived from
+    /// the key name (`KeyScheme::Hkdf`). Saves once.
+    ///
+    /// # Returns
+    /// The (old key, new key) pairs, sorted by old key (empty if nothing matched)
+    pub fn rename_prefix(
+        &mut self,
+        from_prefix: &str,
+        to_prefix: &str,
+    ) -> Result<Vec<(String, String)>> {
+        self.ensure_session_unlocked()?;
+        if from_prefix.is_empty() {
+            return Err(Error::InvalidInput(
+                "Prefix to rename cannot be empty".to_string(),
+            ));
+        }
+        if from_prefix == to_prefix {
+            return Err(Error::InvalidInput(
+                "New prefix is the same as the old one".to_string(),
+            ));
+        }
+
+        let mut renames: Vec<(String, String)> = self
+            .db
+            .entries
+            .keys()
+            .filter_map(|key| {
+                key.strip_prefix(from_prefix)
+                    .map(|rest| (key.clone(), format!("{to_prefix}{rest}")))
+            })
+            .collect();
+        renames.sort();
+
+        // Validate everything up front (keys being renamed away don't count as collisions)
+        for (old, new) in &renames {
+            Self::validate_key(new)?;
+            if self.db.entries[old].is_locked {
+                return Err(Error::EntryLocked(old.clone()));
+            }
+            if self.db.entries.contains_key(new) && !new.starts_with(from_prefix) {
+                return Err(Error::EntryAlreadyExists(new.clone()));
+            }
+        }
+
+        // Build the renamed entries before touching the database
+        let mut moved = Vec::with_capacity(renames.len());
+        for (old, new) in &renames {
+            moved.push((
+                new.clone(),
+                self.rekey_entry(&self.db.entries[old], old, new)?,
+            ));
+        }
+
+        if moved.is_empty() {
+            return Ok(renames);
+        }
+
+        for (old, _) in &renames {
+            self.db.entries.remove(old);
+        }
+        self.db.entries.extend(moved);
+        self.save_modified()?;
+
+        Ok(renames)
+    }
+
     /// List entry keys with optional search and lock status filter
     ///
     /// # Arguments
@@ -167,28 +953,51 @@ impl Vault {
         search: Option<&str>,
         lock_filter: Option<bool>,
     ) -> Result<Vec<(&String, bool)>> {
-        let mut results: Vec<(&String, bool)> = self
-            .db
-            .entries
-            .iter()
-            .filter(|(key, entry)| {
-                // Apply search filter (case-insensitive)
-                let search_match = if let Some(search_term) = search {
-                    key.to_lowercase().contains(&search_term.to_lowercase())
-                } else {
-                    true // No search filter, match all
-                };
+        let pattern = search.map(KeyPattern::substring);
+        self.list_entries_matching(pattern.as_ref(), lock_filter, None)
+    }
+
+    /// List entries whose key matches a substring, glob or regex pattern
+    ///
+    /// Same filters and ordering as `list_entries`, which is this with a
+    /// case-insensitive substring pattern and no tag filter. `tag_filter` keeps
+    /// only entries carrying that tag (exact match).
+    pub fn list_entries_matching(
+        &self,
+        pattern: Option<&KeyPattern>,
+        lock_filter: Option<bool>,
+        tag_filter: Option<&str>,
+    ) -> Result<Vec<(&String, bool)>> {
+        let candidates: Vec<(&String, &Entry)> = match pattern {
+            // Substring searches use the cached lowercase index
+            Some(pattern) if pattern.mode() == MatchMode::Substring => {
+                let search_term = pattern.substring_term().unwrap_or_default();
+                self.search_index()
+                    .iter()
+                    .filter(|(lower, _)| lower.contains(search_term))
+                    .filter_map(|(_, key)| self.db.entries.get_key_value(key))
+                    .collect()
+            }
+            Some(pattern) => self
+                .db
+                .entries
+                .iter()
+                .filter(|(key, _)| pattern.is_match(key))
+                .collect(),
+            None => self.db.entries.iter().collect(), // No search filter, match all
+        };
 
+        let mut results: Vec<(&String, bool)> = candidates
+            .into_iter()
+            .filter(|(_, entry)| {
                 // Apply lock status filter
-                let lock_match = if let Some(required_lock_status) = lock_filter {
+                if let Some(required_lock_status) = lock_filter {
                     entry.is_locked == required_lock_status
                 } else {
                     true // No lock filter, match all
-                };
-
-                // Entry must match both filters
-                search_match && lock_match
+                }
             })
+            .filter(|(_, entry)| tag_filter.is_none_or(|tag| entry.tags.iter().any(|t| t == tag)))
             .map(|(key, entry)| (key, entry.is_locked))
             .collect();
 
@@ -198,12 +1007,546 @@ impl Vault {
         Ok(results)
     }
 
-    /// Delete an entry
-    pub fn delete_entry(&mut self, key: &str) -> Result<()> {
-        // Check if entry exists
-        let entry = self
-            .db
-            .entries
+    /// Build the lowercase key index used by searches ahead of time
+    ///
+    /// Searches build it lazily anyway; it is cached until the next change to the vault.
+    /// Only keys are indexed, never values.
+    #[allow(dead_code)] // Public API - may be used by external consumers
+    pub fn prehash_search_index(&self) {
+        self.search_index();
+    }
+
+    /// Find entries whose decrypted value contains `substr`
+    ///
+    /// Values are decrypted in memory only to compare them and are zeroized
+    /// afterwards; only keys are returned. Locked entries are skipped and counted.
+    ///
+    /// # Arguments
+    /// * `substr` - Text to look for (must not be empty)
+    /// * `ignore_case` - Compare case-insensitively (case-sensitive by default)
+    pub fn search_values(&self, substr: &str, ignore_case: bool) -> Result<ValueSearchResult> {
+        self.ensure_session_unlocked()?;
+
+        if substr.is_empty() {
+            return Err(Error::InvalidInput(
+                "✘ Search text can't be empty".to_string(),
+            ));
+        }
+        let needle = Zeroizing::new(if ignore_case {
+            substr.to_lowercase()
+        } else {
+            substr.to_string()
+        });
+
+        let mut result = ValueSearchResult::default();
+        for (key, entry) in &self.db.entries {
+            if entry.is_locked {
+                result.skipped_locked += 1;
+                continue;
+            }
+
+            let value = Zeroizing::new(self.decrypt_value(key, entry)?);
+            let found = if ignore_case {
+                Zeroizing::new(value.to_lowercase()).contains(needle.as_str())
+            } else {
+                value.contains(needle.as_str())
+            };
+            if found {
+                result.matches.push(key.clone());
+            }
+        }
+
+        result.matches.sort();
+        Ok(result)
+    }
+
+    fn search_index(&self) -> &[(String, String)] {
+        self.search_index.get_or_init(|| {
+            let mut index: Vec<(String, String)> = self
+                .db
+                .entries
+                .keys()
+                .map(|key| (key.to_lowercase(), key.clone()))
+                .collect();
+            index.sort_by(|a, b| a.1.cmp(&b.1));
+            index
+        })
+    }
+
+    /// List entry metadata with the same filters and ordering as `list_entries`
+    #[allow(dead_code)] // Public API - may be used by external consumers
+    pub fn entry_summaries(
+        &self,
+        search: Option<&str>,
+        lock_filter: Option<bool>,
+    ) -> Result<Vec<EntrySummary>> {
+        let pattern = search.map(KeyPattern::substring);
+        self.entry_summaries_matching(pattern.as_ref(), lock_filter, None)
+    }
+
+    /// List entry metadata with the same filters and ordering as `list_entries_matching`
+    pub fn entry_summaries_matching(
+        &self,
+        pattern: Option<&KeyPattern>,
+        lock_filter: Option<bool>,
+        tag_filter: Option<&str>,
+    ) -> Result<Vec<EntrySummary>> {
+        let summaries = self
+            .list_entries_matching(pattern, lock_filter, tag_filter)?
+            .into_iter()
+            .filter_map(|(key, _)| {
+                let timestamp = |ts: &str| (!ts.is_empty()).then(|| ts.to_string());
+                self.db.entries.get(key).map(|entry| EntrySummary {
+                    key: key.clone(),
+                    is_locked: entry.is_locked,
+                    access_count: entry.access_count,
+                    last_accessed: entry.last_accessed.clone(),
+                    label: entry.label.clone(),
+                    created_at: timestamp(&entry.created_at),
+                    updated_at: timestamp(&entry.updated_at),
+                    tag_count: entry.tags.len(),
+                })
+            })
+            .collect();
+
+        Ok(summaries)
+    }
+
+    /// Get an entry's metadata (readable even when the entry is locked)
+    pub fn entry_metadata(&self, key: &str) -> Result<EntryMetadata> {
+        let entry = self
+            .db
+            .entries
+            .get(key)
+            .ok_or_else(|| Error::EntryNotFound(key.to_string()))?;
+
+        let timestamp = |ts: &str| (!ts.is_empty()).then(|| ts.to_string());
+
+        Ok(EntryMetadata {
+            key: key.to_string(),
+            is_locked: entry.is_locked,
+            label: entry.label.clone(),
+            access_count: entry.access_count,
+            last_accessed: entry.last_accessed.clone(),
+            created_at: timestamp(&entry.created_at),
+            updated_at: timestamp(&entry.updated_at),
+            tags: entry.tags.clone(),
+        })
+    }
+
+    /// Get an entry's plaintext label (readable even when the entry is locked)
+    pub fn get_label(&self, key: &str) -> Result<Option<&str>> {
+        self.db
+            .entries
+            .get(key)
+            .map(|entry| entry.label.as_deref())
+            .ok_or_else(|| Error::EntryNotFound(key.to_string()))
+    }
+
+    /// Set or clear an entry's plaintext label
+    ///
+    /// Labels are non-secret annotations, so they can be changed on locked entries.
+    /// Empty labels are treated as cleared.
+    pub fn set_label(&mut self, key: &str, label: Option<String>) -> Result<()> {
+        self.ensure_session_unlocked()?;
+        let entry = self
+            .db
+            .entries
+            .get_mut(key)
+            .ok_or_else(|| Error::EntryNotFound(key.to_string()))?;
+
+        entry.label = label.filter(|l| !l.trim().is_empty());
+
+        // Save to disk
+        self.save_entry_modified(key)
+    }
+
+    /// Add a tag to an entry
+    ///
+    /// Tags are plaintext, so like labels they can be changed on locked entries.
+    /// Surrounding whitespace is trimmed; tags cannot be empty or contain spaces.
+    ///
+    /// # Returns
+    /// false if the entry already had the tag (nothing is saved)
+    pub fn add_tag(&mut self, key: &str, tag: &str) -> Result<bool> {
+        self.ensure_session_unlocked()?;
+        let tag = tag.trim();
+        if tag.is_empty() || tag.chars().any(char::is_whitespace) {
+            return Err(Error::InvalidInput(format!(
+                "Invalid tag '{tag}': tags must be a single non-empty word"
+            )));
+        }
+
+        let entry = self
+            .db
+            .entries
+            .get_mut(key)
+            .ok_or_else(|| Error::EntryNotFound(key.to_string()))?;
+
+        let Err(position) = entry.tags.binary_search_by(|t| t.as_str().cmp(tag)) else {
+            return Ok(false);
+        };
+        entry.tags.insert(position, tag.to_string());
+
+        self.save_entry_modified(key)?;
+        Ok(true)
+    }
+
+    /// Remove a tag from an entry
+    ///
+    /// # Returns
+    /// false if the entry didn't have the tag (nothing is saved)
+    pub fn remove_tag(&mut self, key: &str, tag: &str) -> Result<bool> {
+        self.ensure_session_unlocked()?;
+        let entry = self
+            .db
+            .entries
+            .get_mut(key)
+            .ok_or_else(|| Error::EntryNotFound(key.to_string()))?;
+
+        let tag = tag.trim();
+        let before = entry.tags.len();
+        entry.tags.retain(|t| t != tag);
+        if entry.tags.len() == before {
+            return Ok(false);
+        }
+
+        self.save_entry_modified(key)?;
+        Ok(true)
+    }
+
+    /// Decrypt an entry's notes
+    ///
+    /// Refuses locked entries the same way `get_entry` does.
+    ///
+    /// # Returns
+    /// None when the entry has no notes
+    pub fn get_note(&self, key: &str) -> Result<Option<String>> {
+        self.ensure_session_unlocked()?;
+        let entry = self
+            .db
+            .entries
+            .get(key)
+            .ok_or_else(|| Error::EntryNotFound(key.to_string()))?;
+
+        if entry.is_locked {
+            return Err(Error::EntryLocked(key.to_string()));
+        }
+
+        let Some(notes) = &entry.notes else {
+            return Ok(None);
+        };
+
+        let entry_key = self.db.entry_encryption_key(&self.master_key, key);
+        let plaintext = Zeroizing::new(crypto::decrypt(&notes.encrypted_data()?, &entry_key)?);
+        let note = std::str::from_utf8(&plaintext)
+            .map_err(|e| Error::DecryptionFailed(format!("Invalid note: {e}")))?;
+
+        Ok(Some(note.to_string()))
+    }
+
+    /// Encrypt and store an entry's notes, or clear them with an empty note
+    ///
+    /// Notes use the entry's key with a nonce of their own. Like value updates,
+    /// this is refused on locked entries.
+    pub fn set_note(&mut self, key: &str, note: String) -> Result<()> {
+        self.ensure_session_unlocked()?;
+        let note = Zeroizing::new(note);
+        let entry = self
+            .db
+            .entries
+            .get(key)
+            .ok_or_else(|| Error::EntryNotFound(key.to_string()))?;
+
+        if entry.is_locked {
+            return Err(Error::EntryLocked(key.to_string()));
+        }
+
+        let notes = if note.trim().is_empty() {
+            None
+        } else {
+            let entry_key = self.db.entry_encryption_key(&self.master_key, key);
+            Some(EncryptedNote::new(&crypto::encrypt(
+                note.as_bytes(),
+                &entry_key,
+            )?))
+        };
+
+        if let Some(entry) = self.db.entries.get_mut(key) {
+            entry.notes = notes;
+        }
+
+        // Save to disk
+        self.save_entry_modified(key)
+    }
+
+    /// Change the master password, re-encrypting every entry under the new key
+    ///
+    /// Verifies `old_password`, derives a fresh salt and key, then builds the
+    /// re-encrypted database entirely in memory and saves it once, so a failure
+    /// part way through leaves the stored vault untouched. Locked entries are
+    /// re-encrypted too, and legacy vaults move to per-entry HKDF keys since every
+    /// value is rewritten anyway. Passwords and plaintexts are zeroized.
+    #[allow(dead_code)] // Public API - may be used by external consumers
+    pub fn change_master_password(
+        &mut self,
+        old_password: String,
+        new_password: String,
+    ) -> Result<()> {
+        self.ensure_session_unlocked()?;
+        let old_password = Zeroizing::new(old_password);
+        let new_password = Zeroizing::new(new_password);
+
+        if new_password.trim().is_empty() {
+            return Err(Error::EmptyPassword);
+        }
+
+        // Verify the current password, the same way unlocking does
+        let salt = self.db.get_salt()?;
+        let stored_hash = self.db.get_hash()?;
+        let kdf = self.db.kdf_params()?;
+        let verified = self.verbosity.time("password verification", || {
+            crypto::derive_verified_key_with(&old_password, &salt, &stored_hash, &kdf)
+        })?;
+        // The derived key is only needed as proof; drop it wiped
+        if verified.map(Zeroizing::new).is_none() {
+            return Err(Error::InvalidMasterPassword);
+        }
+
+        self.change_master_password_verified(new_password)
+    }
+
+    /// Same as `change_master_password`, for a vault just unlocked with the current password
+    ///
+    /// Skips re-checking the current password (and its KDF run), so the only key
+    /// derivation is the one for `new_password`.
+    pub fn change_master_password_verified(
+        &mut self,
+        new_password: Zeroizing<String>,
+    ) -> Result<()> {
+        self.ensure_session_unlocked()?;
+        if new_password.trim().is_empty() {
+            return Err(Error::EmptyPassword);
+        }
+        let kdf = self.db.kdf_params()?;
+
+        // Fresh salt and key for the new password (same KDF settings); the stored
+        // hash is that same key, as at init
+        let new_salt = crypto::generate_salt()?;
+        let new_key = Zeroizing::new(self.verbosity.time("key derivation", || {
+            crypto::derive_key_with(&new_password, &new_salt, &kdf)
+        })?);
+
+        let mut new_db = Database::new_with_kdf(new_salt, new_key.to_vec(), &kdf);
+        new_db.key_scheme = KeyScheme::Hkdf;
+        new_db.machine = self.db.machine.clone();
+        new_db.last_modified = Some(Utc::now().to_rfc3339());
+
+        // Entries re-encrypt independently, so the batch runs in parallel
+        let label = format!("re-encryption of {} entries", self.db.entries.len());
+        let reencrypted: Vec<(String, Entry)> = self.verbosity.time(&label, || {
+            self.db
+                .entries
+                .par_iter()
+                .map(|(key, entry)| {
+                    let encrypted = EncryptedData {
+                        ciphertext: entry.get_encrypted_value()?,
+                        nonce: entry.get_nonce()?,
+                    };
+                    let old_entry_key = self.db.entry_encryption_key(&self.master_key, key);
+                    let plaintext = Zeroizing::new(crypto::decrypt(&encrypted, &old_entry_key)?);
+
+                    let new_entry_key = new_db.entry_encryption_key(&new_key, key);
+                    let reencrypted = crypto::encrypt(&plaintext, &new_entry_key)?;
+
+                    // Keep lock state, label and usage metadata
+                    let mut new_entry = entry.clone();
+                    new_entry.encrypted_value =
+                        general_purpose::STANDARD.encode(&reencrypted.ciphertext);
+                    new_entry.nonce = general_purpose::STANDARD.encode(reencrypted.nonce);
+                    if let Some(notes) = &entry.notes {
+                        new_entry.notes =
+                            Some(reencrypt_note(notes, &old_entry_key, &new_entry_key)?);
+                    }
+                    new_entry.history =
+                        reencrypt_history(&entry.history, &old_entry_key, &new_entry_key)?;
+                    Ok((key.clone(), new_entry))
+                })
+                .collect::<Result<_>>()
+        })?;
+        new_db.entries.extend(reencrypted);
+
+        // Single write: either the whole new vault is stored or nothing changes
+        new_db.seal(&new_key)?;
+        self.verbosity
+            .time("database save", || self.backend.save(&new_db))?;
+
+        self.db = new_db;
+        self.master_key.zeroize();
+        self.master_key = new_key.to_vec();
+        self.search_index.take();
+        self.clear_value_cache();
+
+        Ok(())
+    }
+
+    /// Check that every entry can still be decoded and decrypted
+    ///
+    /// Tries each entry's value, notes and history with the session key, locked entries
+    /// included, and reports whether the database MAC matched at unlock. Plaintexts
+    /// are zeroized immediately; only keys are reported.
+    pub fn verify_integrity(&self) -> Result<IntegrityReport> {
+        self.ensure_session_unlocked()?;
+        let mut report = IntegrityReport {
+            mac_mismatch: self.mac_mismatch,
+            ..IntegrityReport::default()
+        };
+
+        for (key, entry) in &self.db.entries {
+            match self.check_entry(key, entry) {
+                Ok(()) => report.healthy.push(key.clone()),
+                Err(problem) => report.damaged.push((key.clone(), problem.to_string())),
+            }
+        }
+
+        report.healthy.sort();
+        report.damaged.sort();
+        Ok(report)
+    }
+
+    /// Group unlocked entries that share the same value
+    ///
+    /// Values are decrypted in memory only to compare them and are zeroized
+    /// before returning. Locked entries and entries that fail to decrypt (see
+    /// `verify_integrity`) are not checked.
+    ///
+    /// # Returns
+    /// Groups of two or more keys with identical values, each sorted, ordered by first key
+    pub fn find_duplicates(&self) -> Result<Vec<Vec<String>>> {
+        self.ensure_session_unlocked()?;
+        let mut values: Vec<(Zeroizing<Vec<u8>>, &String)> = self
+            .db
+            .entries
+            .iter()
+            .filter(|(_, entry)| !entry.is_locked)
+            .filter_map(|(key, entry)| {
+                let encrypted = EncryptedData {
+                    ciphertext: entry.get_encrypted_value().ok()?,
+                    nonce: entry.get_nonce().ok()?,
+                };
+                let entry_key = self.db.entry_encryption_key(&self.master_key, key);
+                let plaintext = crypto::decrypt(&encrypted, &entry_key).ok()?;
+                Some((Zeroizing::new(plaintext), key))
+            })
+            .collect();
+        values.sort_by(|a, b| {
+            a.0.as_slice()
+                .cmp(b.0.as_slice())
+                .then_with(|| a.1.cmp(b.1))
+        });
+
+        let mut groups: Vec<Vec<String>> = values
+            .chunk_by(|a, b| a.0.as_slice() == b.0.as_slice())
+            .filter(|group| group.len() > 1)
+            .map(|group| group.iter().map(|(_, key)| key.to_string()).collect())
+            .collect();
+        groups.sort();
+        Ok(groups)
+    }
+
+    /// First problem found when decrypting `entry`, if any
+    fn check_entry(&self, key: &str, entry: &Entry) -> std::result::Result<(), &'static str> {
+        let ciphertext = entry
+            .get_encrypted_value()
+            .map_err(|_| "value is not valid base64")?;
+        let nonce = entry.get_nonce().map_err(|_| "nonce is not valid base64")?;
+
+        let entry_key = self.db.entry_encryption_key(&self.master_key, key);
+        let plaintext = Zeroizing::new(
+            crypto::decrypt(&EncryptedData { ciphertext, nonce }, &entry_key)
+                .map_err(|_| "value does not decrypt")?,
+        );
+        std::str::from_utf8(&plaintext).map_err(|_| "value is not valid UTF-8")?;
+
+        if let Some(notes) = &entry.notes {
+            let encrypted = notes
+                .encrypted_data()
+                .map_err(|_| "notes are not valid base64")?;
+            crypto::decrypt(&encrypted, &entry_key)
+                .map(Zeroizing::new)
+                .map_err(|_| "notes do not decrypt")?;
+        }
+
+        for item in &entry.history {
+            let encrypted = item
+                .encrypted_data()
+                .map_err(|_| "history is not valid base64")?;
+            crypto::decrypt(&encrypted, &entry_key)
+                .map(Zeroizing::new)
+                .map_err(|_| "history does not decrypt")?;
+        }
+
+        Ok(())
+    }
+
+    /// Report what re-encrypting every entry would involve, without changing anything
+    ///
+    /// Backs `--plan` for whole-vault operations (master password change, key rotation,
+    /// iteration upgrades). Per-entry cost is measured on one sample entry.
+    ///
+    /// # Arguments
+    /// * `new_iterations` - Iterations for the new key (None keeps the current count)
+    pub fn reencrypt_plan(&self, new_iterations: Option<u32>) -> Result<ReencryptPlan> {
+        self.ensure_session_unlocked()?;
+        let mut kdf = self.db.kdf_params()?;
+        if let Some(iterations) = new_iterations {
+            kdf.iterations = iterations;
+        }
+        let iterations = kdf.iterations;
+        let kdf_time = crypto::time_kdf_with(&kdf)?;
+
+        let entry_count = self.db.entries.len();
+        let locked_count = self.db.entries.values().filter(|e| e.is_locked).count();
+
+        // Time a decrypt + encrypt round trip on one entry and extrapolate
+        let per_entry = match self.db.entries.iter().next() {
+            Some((key, entry)) => {
+                let encrypted = EncryptedData {
+                    ciphertext: entry.get_encrypted_value()?,
+                    nonce: entry.get_nonce()?,
+                };
+
+                let started = Instant::now();
+                let entry_key = self.db.entry_encryption_key(&self.master_key, key);
+                let mut plaintext = crypto::decrypt(&encrypted, &entry_key)?;
+                crypto::encrypt(&plaintext, &entry_key)?;
+                let elapsed = started.elapsed();
+
+                plaintext.zeroize();
+                elapsed
+            }
+            None => Duration::ZERO,
+        };
+
+        let estimated_time = kdf_time + per_entry * u32::try_from(entry_count).unwrap_or(u32::MAX);
+
+        Ok(ReencryptPlan {
+            entry_count,
+            locked_count,
+            iterations,
+            kdf_time,
+            estimated_time,
+        })
+    }
+
+    /// Delete an entry
+    pub fn delete_entry(&mut self, key: &str) -> Result<()> {
+        self.ensure_session_unlocked()?;
+
+        // Check if entry exists
+        let entry = self
+            .db
+            .entries
             .get(key)
             .ok_or_else(|| Error::EntryNotFound(key.to_string()))?;
 
@@ -212,38 +1555,364 @@ impl Vault {
             return Err(Error::EntryLocked(key.to_string()));
         }
 
-        // Remove from database
-        self.db.entries.remove(key);
+        // Remove from database
+        self.db.entries.remove(key);
+
+        // Save to disk
+        self.search_index.take();
+        self.value_cache.borrow_mut().remove(key);
+        self.db.last_modified = Some(Utc::now().to_rfc3339());
+        self.seal()?;
+        self.backend.delete_entry(&self.db, key)?;
+
+        Ok(())
+    }
+
+    /// Delete every unlocked entry whose key contains `pattern`
+    ///
+    /// Matches like `list_entries` searches (case-insensitive substring). Locked
+    /// entries are never deleted; find them with `list_entries(Some(pattern), Some(true))`.
+    ///
+    /// # Returns
+    /// The deleted keys, sorted
+    pub fn delete_matching(&mut self, pattern: &str) -> Result<Vec<String>> {
+        self.ensure_session_unlocked()?;
+        if pattern.is_empty() {
+            return Err(Error::InvalidInput(
+                "Search pattern cannot be empty".to_string(),
+            ));
+        }
+
+        let keys: Vec<String> = self
+            .list_entries(Some(pattern), Some(false))?
+            .into_iter()
+            .map(|(key, _)| key.clone())
+            .collect();
+        if keys.is_empty() {
+            return Ok(keys);
+        }
+
+        for key in &keys {
+            self.db.entries.remove(key);
+        }
+        self.save_modified()?;
+
+        Ok(keys)
+    }
+
+    /// Toggle lock status of an entry
+    pub fn toggle_lock(&mut self, key: &str) -> Result<bool> {
+        self.ensure_session_unlocked()?;
+        // Check if entry exists
+        let entry = self
+            .db
+            .entries
+            .get_mut(key)
+            .ok_or_else(|| Error::EntryNotFound(key.to_string()))?;
+
+        // A passphrase lock can only be lifted with `unlock_with_password`
+        if entry.lock_password.is_some() {
+            return Err(Error::EntryLocked(key.to_string()));
+        }
+
+        // Toggle lock status (unlocking also cancels any timer)
+        if entry.is_locked {
+            entry.clear_lock();
+        } else {
+            entry.is_locked = true;
+        }
+        let new_status = entry.is_locked;
+
+        // Save to disk
+        self.save_entry_modified(key)?;
+
+        Ok(new_status)
+    }
+
+    /// Lock an entry until `duration` from now, after which it unlocks on the next load
+    ///
+    /// Re-locking an entry that already has a timed lock replaces its timer.
+    /// Returns the RFC 3339 time the lock expires.
+    pub fn lock_for(&mut self, key: &str, duration: chrono::Duration) -> Result<String> {
+        self.ensure_session_unlocked()?;
+        let entry = self
+            .db
+            .entries
+            .get_mut(key)
+            .ok_or_else(|| Error::EntryNotFound(key.to_string()))?;
+
+        if entry.lock_password.is_some() {
+            return Err(Error::EntryLocked(key.to_string()));
+        }
+
+        let unlock_after = (Utc::now() + duration).to_rfc3339();
+        entry.is_locked = true;
+        entry.unlock_after = Some(unlock_after.clone());
+
+        self.save_entry_modified(key)?;
+
+        Ok(unlock_after)
+    }
+
+    /// Lock an entry so that unlocking also requires `passphrase`
+    ///
+    /// The passphrase is stored as a salted hash made with the vault's KDF
+    /// (PBKDF2 or Argon2id, whichever the master key uses). The value itself stays
+    /// encrypted under the master key, so this guards the vault's own unlock
+    /// path rather than adding a second layer of encryption. To keep that path
+    /// the only way in, exports (.ik, CSV, plain, dir) leave passphrase-locked
+    /// entries out, and replace/mirror imports never overwrite or delete them.
+    pub fn lock_with_password(&mut self, key: &str, passphrase: &str) -> Result<()> {
+        self.ensure_session_unlocked()?;
+        if passphrase.is_empty() {
+            return Err(Error::EmptyPassword);
+        }
+
+        let kdf = self.db.kdf_params()?;
+        let entry = self
+            .db
+            .entries
+            .get_mut(key)
+            .ok_or_else(|| Error::EntryNotFound(key.to_string()))?;
+
+        if entry.is_locked {
+            return Err(Error::EntryLocked(key.to_string()));
+        }
+
+        let salt = crypto::generate_salt()?;
+        let hash = crypto::hash_password_with(passphrase, &salt, &kdf)?;
+        entry.is_locked = true;
+        entry.unlock_after = None;
+        entry.lock_password = Some(LockPassword {
+            salt: general_purpose::STANDARD.encode(&salt),
+            hash: general_purpose::STANDARD.encode(&hash),
+        });
+
+        self.save_entry_modified(key)?;
+
+        Ok(())
+    }
+
+    /// Lift a passphrase lock set by `lock_with_password`
+    pub fn unlock_with_password(&mut self, key: &str, passphrase: &str) -> Result<()> {
+        self.ensure_session_unlocked()?;
+        self.check_lock_password(key, passphrase)?;
+
+        if let Some(entry) = self.db.entries.get_mut(key) {
+            entry.clear_lock();
+        }
+        self.save_entry_modified(key)?;
+
+        Ok(())
+    }
+
+    /// Check the passphrase of a lock set by `lock_with_password`, leaving the lock in place
+    pub fn check_lock_password(&self, key: &str, passphrase: &str) -> Result<()> {
+        let entry = self
+            .db
+            .entries
+            .get(key)
+            .ok_or_else(|| Error::EntryNotFound(key.to_string()))?;
+
+        let lock = entry.lock_password.as_ref().ok_or_else(|| {
+            Error::InvalidInput(format!("Entry '{key}' is not locked with a passphrase"))
+        })?;
+        let salt = general_purpose::STANDARD.decode(&lock.salt)?;
+        let hash = general_purpose::STANDARD.decode(&lock.hash)?;
+
+        if !crypto::verify_password_with(passphrase, &salt, &hash, &self.db.kdf_params()?)? {
+            return Err(Error::InvalidInput(format!(
+                "Incorrect passphrase for '{key}'"
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Whether unlocking `key` requires its lock passphrase
+    pub fn has_lock_password(&self, key: &str) -> Result<bool> {
+        self.db
+            .entries
+            .get(key)
+            .map(|entry| entry.lock_password.is_some())
+            .ok_or_else(|| Error::EntryNotFound(key.to_string()))
+    }
+
+    /// Wipe the master key from memory until `reunlock` is called
+    ///
+    /// For idle timeouts in long-running sessions. Cached values are dropped too;
+    /// reading, creating, updating and deleting entries fails with
+    /// `InvalidMasterPassword` until the vault is re-unlocked.
+    #[allow(dead_code)] // Public API - may be used by external consumers
+    pub fn lock_session(&mut self) {
+        self.master_key.zeroize();
+        self.master_key.clear();
+        self.clear_value_cache();
+        self.locked_session = true;
+    }
+
+    /// Whether `lock_session` wiped the master key
+    #[allow(dead_code)] // Public API - may be used by external consumers
+    pub fn is_session_locked(&self) -> bool {
+        self.locked_session
+    }
+
+    /// Restore the master key after `lock_session`
+    ///
+    /// The password is checked against the database loaded at unlock. Calling
+    /// this on a vault that isn't locked just verifies the password.
+    #[allow(dead_code)] // Public API - may be used by external consumers
+    pub fn reunlock(&mut self, mut master_password: String) -> Result<()> {
+        let salt = self.db.get_salt()?;
+        let stored_hash = self.db.get_hash()?;
+        let kdf = self.db.kdf_params()?;
+
+        let result = self.verbosity.time("key derivation", || {
+            crypto::derive_verified_key_with(&master_password, &salt, &stored_hash, &kdf)
+        });
+        master_password.zeroize();
+
+        let master_key = result?.ok_or(Error::InvalidMasterPassword)?;
+        self.master_key.zeroize();
+        self.master_key = master_key;
+        self.locked_session = false;
+        Ok(())
+    }
+
+    fn ensure_session_unlocked(&self) -> Result<()> {
+        if self.locked_session {
+            Err(Error::InvalidMasterPassword)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Re-read the database from storage, keeping the vault unlocked
+    ///
+    /// Used by long-running views to pick up changes made by other processes.
+    /// Fails with `InvalidMasterPassword` if the master password changed since unlocking.
+    pub fn reload(&mut self) -> Result<()> {
+        self.ensure_session_unlocked()?;
+        let mut db = self
+            .verbosity
+            .time("database load", || self.backend.load())?;
+
+        if db.master_salt != self.db.master_salt
+            || !crypto::constant_time_eq(db.master_hash.as_bytes(), self.db.master_hash.as_bytes())
+        {
+            return Err(Error::InvalidMasterPassword);
+        }
+        Self::check_key_against_entries(&db, &self.master_key)?;
+        db.verify_mac(&self.master_key)?;
+
+        if !db.clear_expired_locks(Utc::now()).is_empty() {
+            db.seal(&self.master_key)?;
+        }
+
+        self.db = db;
+        self.search_index.take();
+        self.clear_value_cache();
+        Ok(())
+    }
+
+    /// Whether a decrypted value for `key` is cached (see `get_entry`)
+    #[allow(dead_code)] // Public API - may be used by external consumers
+    pub fn is_value_cached(&self, key: &str) -> bool {
+        self.value_cache.borrow().contains_key(key)
+    }
+
+    /// Drop every cached decrypted value (values are zeroized as they're dropped)
+    pub fn clear_value_cache(&self) {
+        self.value_cache.borrow_mut().clear();
+    }
+
+    /// Save the vault (useful after multiple operations)
+    #[allow(dead_code)] // Public API - may be used by external consumers
+    pub fn save(&self) -> Result<()> {
+        self.verbosity
+            .time("database save", || self.backend.save(&self.db))
+    }
 
-        // Save to disk
-        storage::save(&self.db)?;
+    /// Record the modification time and save (for changes to entries)
+    fn save_modified(&mut self) -> Result<()> {
+        self.search_index.take();
+        self.clear_value_cache();
+        self.db.last_modified = Some(Utc::now().to_rfc3339());
+        self.seal()?;
+        self.verbosity
+            .time("database save", || self.backend.save(&self.db))
+    }
 
-        Ok(())
+    /// Copy of `entry` encrypted for `new_key` instead of `old_key`
+    ///
+    /// Only `KeyScheme::Hkdf` ties the ciphertext to the key name; legacy
+    /// entries are copied unchanged.
+    fn rekey_entry(&self, entry: &Entry, old_key: &str, new_key: &str) -> Result<Entry> {
+        let mut entry = entry.clone();
+        if self.db.key_scheme == KeyScheme::Hkdf {
+            let encrypted = EncryptedData {
+                ciphertext: entry.get_encrypted_value()?,
+                nonce: entry.get_nonce()?,
+            };
+            let old_entry_key = self.db.entry_encryption_key(&self.master_key, old_key);
+            let plaintext = Zeroizing::new(crypto::decrypt(&encrypted, &old_entry_key)?);
+
+            let new_entry_key = self.db.entry_encryption_key(&self.master_key, new_key);
+            let encrypted = crypto::encrypt(&plaintext, &new_entry_key)?;
+            entry.encrypted_value = general_purpose::STANDARD.encode(&encrypted.ciphertext);
+            entry.nonce = general_purpose::STANDARD.encode(encrypted.nonce);
+            if let Some(notes) = &entry.notes {
+                entry.notes = Some(reencrypt_note(notes, &old_entry_key, &new_entry_key)?);
+            }
+            entry.history = reencrypt_history(&entry.history, &old_entry_key, &new_entry_key)?;
+        }
+        Ok(entry)
     }
 
-    /// Toggle lock status of an entry
-    pub fn toggle_lock(&mut self, key: &str) -> Result<bool> {
-        // Check if entry exists
-        let entry = self
-            .db
-            .entries
-            .get_mut(key)
-            .ok_or_else(|| Error::EntryNotFound(key.to_string()))?;
+    /// Recompute the database MAC before a save (refused while the session is locked)
+    fn seal(&mut self) -> Result<()> {
+        self.ensure_session_unlocked()?;
+        self.db.seal(&self.master_key)
+    }
 
-        // Toggle lock status
-        entry.is_locked = !entry.is_locked;
-        let new_status = entry.is_locked;
+    /// Record the modification time and save a single changed entry
+    fn save_entry_modified(&mut self, key: &str) -> Result<()> {
+        self.search_index.take();
+        self.value_cache.borrow_mut().remove(key);
+        self.db.last_modified = Some(Utc::now().to_rfc3339());
+        self.seal()?;
+        self.verbosity
+            .time("database save", || self.backend.save_entry(&self.db, key))
+    }
 
-        // Save to disk
-        storage::save(&self.db)?;
+    /// Machine this vault is bound to, if any (see `bind_machine`)
+    pub fn bound_machine(&self) -> Option<&crate::machine::MachineBinding> {
+        self.db.machine.as_ref()
+    }
 
-        Ok(new_status)
+    /// Record the machine this vault belongs to and save
+    ///
+    /// Tamper-evidence only: opening the vault elsewhere warns but still works.
+    pub fn bind_machine(&mut self, binding: crate::machine::MachineBinding) -> Result<()> {
+        self.ensure_session_unlocked()?;
+        self.db.machine = Some(binding);
+        self.seal()?;
+        self.verbosity
+            .time("database save", || self.backend.save(&self.db))
     }
 
-    /// Save the vault (useful after multiple operations)
-    #[allow(dead_code)] // Public API - may be used by external consumers
-    pub fn save(&self) -> Result<()> {
-        storage::save(&self.db)
+    /// When the vault's entries were last changed
+    ///
+    /// Falls back to the backend's last write time (file mtime for JSON) for
+    /// databases written before modification times were recorded.
+    pub fn last_modified(&self) -> Option<DateTime<Utc>> {
+        if let Some(ts) = &self.db.last_modified {
+            return DateTime::parse_from_rfc3339(ts)
+                .ok()
+                .map(|ts| ts.with_timezone(&Utc));
+        }
+
+        self.backend.last_write_time().map(DateTime::<Utc>::from)
     }
 
     /// Export vault to encrypted file
@@ -254,17 +1923,21 @@ impl Vault {
     ///
     /// # Errors
     /// Returns error if file already exists (use export_to_file_force to overwrite)
+    #[allow(dead_code)] // Public API - may be used by external consumers
     pub fn export_to_file(
         &self,
         output_path: &std::path::Path,
         export_password: String,
     ) -> Result<()> {
+        self.ensure_session_unlocked()?;
         crate::export::export_vault(
             &self.db,
             &self.master_key,
             output_path,
             export_password,
             false,
+            crate::export::ExportOptions::default(),
+            None,
         )
     }
 
@@ -273,20 +1946,251 @@ impl Vault {
     /// # Arguments
     /// * `output_path` - Path where export file will be written
     /// * `export_password` - Password to encrypt the export file
+    #[allow(dead_code)] // Public API - may be used by external consumers
     pub fn export_to_file_force(
         &self,
         output_path: &std::path::Path,
         export_password: String,
     ) -> Result<()> {
+        self.ensure_session_unlocked()?;
         crate::export::export_vault(
             &self.db,
             &self.master_key,
             output_path,
             export_password,
             true,
+            crate::export::ExportOptions::default(),
+            None,
+        )
+    }
+
+    /// Export vault to encrypted file, storing identical values only once
+    ///
+    /// Entries reference a shared value table, which shrinks exports where many
+    /// entries share a value. Import expands the references transparently.
+    ///
+    /// # Arguments
+    /// * `output_path` - Path where export file will be written
+    /// * `export_password` - Password to encrypt the export file
+    /// * `force` - Whether to overwrite an existing file
+    #[allow(dead_code)] // Public API - may be used by external consumers
+    pub fn export_to_file_dedup(
+        &self,
+        output_path: &std::path::Path,
+        export_password: String,
+        force: bool,
+    ) -> Result<()> {
+        let options = crate::export::ExportOptions {
+            dedup: true,
+            ..Default::default()
+        };
+        self.export_to_file_with_options(output_path, export_password, force, options, None)
+    }
+
+    /// Export vault to encrypted file with explicit export options
+    ///
+    /// # Arguments
+    /// * `output_path` - Path where export file will be written
+    /// * `export_password` - Password to encrypt the export file
+    /// * `force` - Whether to overwrite an existing file
+    /// * `options` - Dedup/minimal export options
+    /// * `keys` - Only export these entries (None exports every entry)
+    pub fn export_to_file_with_options(
+        &self,
+        output_path: &std::path::Path,
+        export_password: String,
+        force: bool,
+        options: crate::export::ExportOptions,
+        keys: Option<&[String]>,
+    ) -> Result<()> {
+        self.ensure_session_unlocked()?;
+        self.verbosity.time("export encryption", || {
+            crate::export::export_vault(
+                &self.db,
+                &self.master_key,
+                output_path,
+                export_password,
+                force,
+                options,
+                keys,
+            )
+        })
+    }
+
+    /// Export the vault as encrypted .ik JSON to any writer (e.g. a `Vec<u8>` or socket)
+    ///
+    /// Produces exactly what `export_to_file_with_options` writes to disk, without
+    /// touching the filesystem.
+    ///
+    /// # Arguments
+    /// * `writer` - Destination of the .ik JSON
+    /// * `export_password` - Password to encrypt the export with
+    /// * `options` - Dedup/minimal/compress export options
+    /// * `keys` - Only export these entries (None exports every entry)
+    #[allow(dead_code)] // Public API - may be used by external consumers
+    pub fn export_to_writer<W: std::io::Write>(
+        &self,
+        writer: W,
+        export_password: String,
+        options: crate::export::ExportOptions,
+        keys: Option<&[String]>,
+    ) -> Result<()> {
+        self.ensure_session_unlocked()?;
+        self.verbosity.time("export encryption", || {
+            crate::export::export_vault_to_writer(
+                &self.db,
+                &self.master_key,
+                writer,
+                export_password,
+                options,
+                keys,
+            )
+        })
+    }
+
+    /// Export vault to encrypted file using the master password as the export password
+    ///
+    /// The password is checked against the vault first. The export still gets its own
+    /// salt and nonce, so the file is independent of the vault database.
+    ///
+    /// # Arguments
+    /// * `output_path` - Path where export file will be written
+    /// * `master_password` - The vault's master password
+    /// * `force` - Whether to overwrite an existing file
+    /// * `options` - Dedup/minimal export options
+    /// * `keys` - Only export these entries (None exports every entry)
+    #[allow(dead_code)] // Public API - may be used by external consumers
+    pub fn export_to_file_with_master(
+        &self,
+        output_path: &std::path::Path,
+        mut master_password: String,
+        force: bool,
+        options: crate::export::ExportOptions,
+        keys: Option<&[String]>,
+    ) -> Result<()> {
+        let salt = self.db.get_salt()?;
+        let stored_hash = self.db.get_hash()?;
+
+        if !crypto::verify_password_with(
+            &master_password,
+            &salt,
+            &stored_hash,
+            &self.db.kdf_params()?,
+        )? {
+            master_password.zeroize();
+            return Err(Error::InvalidMasterPassword);
+        }
+
+        self.export_to_file_with_options(output_path, master_password, force, options, keys)
+    }
+
+    /// Same as `export_to_file_with_master`, for the password this vault was just unlocked with
+    ///
+    /// Skips the second password check (and its KDF run), so only pass a password
+    /// that has already been verified by unlocking.
+    pub fn export_to_file_with_verified_master(
+        &self,
+        output_path: &std::path::Path,
+        mut master_password: Zeroizing<String>,
+        force: bool,
+        options: crate::export::ExportOptions,
+        keys: Option<&[String]>,
+    ) -> Result<()> {
+        let password = std::mem::take(&mut *master_password);
+        self.export_to_file_with_options(output_path, password, force, options, keys)
+    }
+
+    /// Describe what an export would contain (keys and counts only, nothing decrypted)
+    #[allow(dead_code)] // Public API - may be used by external consumers
+    pub fn export_preview(
+        &self,
+        options: crate::export::ExportOptions,
+    ) -> crate::export::ExportPreview {
+        crate::export::ExportPreview::new(&self.db, options)
+    }
+
+    /// Dry run of an export: decrypt the entries it would contain, then report their keys
+    ///
+    /// Nothing is serialized or written. Unlike `export_preview` this decrypts every
+    /// selected entry, so it also fails if one of them is damaged.
+    ///
+    /// # Arguments
+    /// * `keys` - Only include these entries (None includes every entry)
+    pub fn export_diff(
+        &self,
+        options: crate::export::ExportOptions,
+        keys: Option<&[String]>,
+    ) -> Result<crate::export::ExportPreview> {
+        self.ensure_session_unlocked()?;
+        let entries = crate::export::collect_export_entries(&self.db, &self.master_key, keys)?;
+        Ok(crate::export::ExportPreview::from_entries(
+            &entries, options,
+        ))
+    }
+
+    /// Export vault as a single base64 line for text-only transport
+    ///
+    /// # Arguments
+    /// * `export_password` - Password to encrypt the wrapped export
+    pub fn export_to_base64(&self, export_password: String) -> Result<String> {
+        self.ensure_session_unlocked()?;
+        crate::export::export_vault_to_base64(&self.db, &self.master_key, export_password)
+    }
+
+    /// Write each unlocked entry's value to its own file in `dir`, plus a manifest (UNENCRYPTED)
+    ///
+    /// See `export::export_vault_to_dir` for naming, manifest and permission details.
+    ///
+    /// # Arguments
+    /// * `dir` - Target directory (created if missing)
+    /// * `force` - Whether to overwrite existing files
+    pub fn export_to_dir(
+        &self,
+        dir: &std::path::Path,
+        force: bool,
+    ) -> Result<crate::export::DirExportResult> {
+        self.ensure_session_unlocked()?;
+        crate::export::export_vault_to_dir(&self.db, &self.master_key, dir, force)
+    }
+
+    /// Write every unlocked entry to a plaintext TOML or YAML file (UNENCRYPTED)
+    ///
+    /// See `export::export_vault_plain` for key sanitizing and escaping.
+    ///
+    /// # Arguments
+    /// * `output_path` - File to write (created with 0600 permissions on Unix)
+    /// * `format` - TOML or YAML
+    /// * `force` - Whether to overwrite an existing file
+    pub fn export_to_plain_file(
+        &self,
+        output_path: &std::path::Path,
+        format: crate::export::PlainFormat,
+        force: bool,
+    ) -> Result<crate::export::PlainExportResult> {
+        self.ensure_session_unlocked()?;
+        crate::export::export_vault_plain_to_file(
+            &self.db,
+            &self.master_key,
+            output_path,
+            format,
+            force,
         )
     }
 
+    /// Export every entry, locked ones included, to a plaintext `key,value,locked` CSV file
+    ///
+    /// Passphrase-locked entries are left out.
+    ///
+    /// # Security
+    /// The file contains the values UNENCRYPTED.
+    ///
+    /// # Returns
+    /// The number of entries written
+    pub fn export_to_csv_file(&self, output_path: &std::path::Path, force: bool) -> Result<usize> {
+        self.ensure_session_unlocked()?;
+        crate::export::export_plaintext_csv(&self.db, &self.master_key, output_path, force)
+    }
+
     /// Import vault entries from encrypted .ik file
     ///
     /// # Arguments
@@ -299,6 +2203,7 @@ impl Vault {
     /// # Returns
     /// * `Ok(ImportResult)` - Information about what was imported
     /// * `Err(Error)` - If import fails
+    #[allow(dead_code)] // Public API - may be used by external consumers
     pub fn import_from_file(
         &mut self,
         import_path: &std::path::Path,
@@ -307,8 +2212,136 @@ impl Vault {
         replace: bool,
         diff: bool,
     ) -> Result<crate::import::ImportResult> {
-        let result = crate::import::import_vault(
-            import_path,
+        self.import_from_file_with_keys(import_path, import_password, merge, replace, diff, None)
+    }
+
+    /// Import only some entries from an encrypted .ik file
+    ///
+    /// Takes the same strategy flags as `import_from_file`. Requested keys that
+    /// the export doesn't contain are listed in `ImportResult::not_found`.
+    ///
+    /// # Arguments
+    /// * `keys` - Only import these entries (None imports every entry)
+    pub fn import_from_file_with_keys(
+        &mut self,
+        import_path: &std::path::Path,
+        import_password: String,
+        merge: bool,
+        replace: bool,
+        diff: bool,
+        keys: Option<&[String]>,
+    ) -> Result<crate::import::ImportResult> {
+        self.ensure_session_unlocked()?;
+        let result = self.verbosity.time("import decryption", || {
+            crate::import::import_vault(
+                import_path,
+                import_password,
+                &mut self.db,
+                &self.master_key,
+                merge,
+                replace,
+                diff,
+                keys,
+            )
+        })?;
+
+        // Save the updated database (unless in diff mode)
+        if !diff {
+            self.save_modified()?;
+        }
+
+        Ok(result)
+    }
+
+    /// Import encrypted .ik JSON from any reader (e.g. a byte slice or socket)
+    ///
+    /// Same strategies as `import_from_file_with_keys`, without touching the filesystem.
+    ///
+    /// # Arguments
+    /// * `keys` - Only import these entries (None imports every entry)
+    #[allow(dead_code)] // Public API - may be used by external consumers
+    pub fn import_from_reader<R: std::io::Read>(
+        &mut self,
+        reader: R,
+        import_password: String,
+        merge: bool,
+        replace: bool,
+        diff: bool,
+        keys: Option<&[String]>,
+    ) -> Result<crate::import::ImportResult> {
+        self.ensure_session_unlocked()?;
+        let result = self.verbosity.time("import decryption", || {
+            crate::import::import_vault_from_reader_with_keys(
+                reader,
+                import_password,
+                &mut self.db,
+                &self.master_key,
+                merge,
+                replace,
+                diff,
+                keys,
+            )
+        })?;
+
+        // Save the updated database (unless in diff mode)
+        if !diff {
+            self.save_modified()?;
+        }
+
+        Ok(result)
+    }
+
+    /// Import from an encrypted .ik file so the vault becomes an exact mirror of it
+    ///
+    /// Entries from the file are added or overwritten (like replace mode), then every
+    /// unlocked entry missing from the file is deleted and listed in `ImportResult::deleted`.
+    ///
+    /// # Arguments
+    /// * `import_path` - Path to the .ik file to import
+    /// * `import_password` - Password used to encrypt the export file
+    /// * `diff` - If true, dry-run mode (report what would change without applying it)
+    pub fn import_from_file_mirror(
+        &mut self,
+        import_path: &std::path::Path,
+        import_password: String,
+        diff: bool,
+    ) -> Result<crate::import::ImportResult> {
+        self.ensure_session_unlocked()?;
+        let mut result = self.verbosity.time("import decryption", || {
+            crate::import::import_vault(
+                import_path,
+                import_password,
+                &mut self.db,
+                &self.master_key,
+                false,
+                true,
+                diff,
+                None,
+            )
+        })?;
+        crate::import::remove_missing(&mut self.db, &mut result, diff);
+
+        if !diff {
+            self.save_modified()?;
+        }
+
+        Ok(result)
+    }
+
+    /// Import vault entries from a base64-wrapped export (see `export_to_base64`)
+    ///
+    /// Takes the same strategy flags as `import_from_file`.
+    pub fn import_from_base64(
+        &mut self,
+        encoded: &str,
+        import_password: String,
+        merge: bool,
+        replace: bool,
+        diff: bool,
+    ) -> Result<crate::import::ImportResult> {
+        self.ensure_session_unlocked()?;
+        let result = crate::import::import_vault_from_base64(
+            encoded,
             import_password,
             &mut self.db,
             &self.master_key,
@@ -319,7 +2352,97 @@ impl Vault {
 
         // Save the updated database (unless in diff mode)
         if !diff {
-            self.save()?;
+            self.save_modified()?;
+        }
+
+        Ok(result)
+    }
+
+    /// Import entries from a plaintext CSV file with `key`, `value` and optional `locked` columns
+    ///
+    /// Takes the same strategy flags as `import_from_file`. The file is not encrypted,
+    /// so callers must make sure the user acknowledged that.
+    pub fn import_from_plain_csv(
+        &mut self,
+        import_path: &std::path::Path,
+        merge: bool,
+        replace: bool,
+        diff: bool,
+    ) -> Result<crate::import::ImportResult> {
+        self.ensure_session_unlocked()?;
+        let result = crate::import::import_plaintext_csv(
+            import_path,
+            &mut self.db,
+            &self.master_key,
+            merge,
+            replace,
+            diff,
+        )?;
+
+        // Save the updated database (unless in diff mode)
+        if !diff {
+            self.save_modified()?;
+        }
+
+        Ok(result)
+    }
+
+    /// Import entries from a plaintext dotenv-style file of `KEY=VALUE` lines
+    ///
+    /// Takes the same strategy flags as `import_from_file`. The file is not encrypted,
+    /// so callers must make sure the user acknowledged that.
+    pub fn import_from_plain_env(
+        &mut self,
+        import_path: &std::path::Path,
+        merge: bool,
+        replace: bool,
+        diff: bool,
+    ) -> Result<crate::import::ImportResult> {
+        self.ensure_session_unlocked()?;
+        let result = crate::import::import_plaintext_env(
+            import_path,
+            &mut self.db,
+            &self.master_key,
+            merge,
+            replace,
+            diff,
+        )?;
+
+        // Save the updated database (unless in diff mode)
+        if !diff {
+            self.save_modified()?;
+        }
+
+        Ok(result)
+    }
+
+    /// Import entries from a plaintext JSON file (`[{"key", "value", "locked"}]`)
+    ///
+    /// Takes the same strategy flags as `import_from_file`. The file is not encrypted,
+    /// so callers must make sure the user acknowledged that.
+    pub fn import_from_plain_json(
+        &mut self,
+        import_path: &std::path::Path,
+        merge: bool,
+        replace: bool,
+        diff: bool,
+    ) -> Result<crate::import::ImportResult> {
+        self.ensure_session_unlocked()?;
+        let file = std::fs::File::open(import_path)
+            .map_err(|e| Error::Io(format!("Failed to read import file: {e}")))?;
+
+        let result = crate::import::import_plain_json_from_reader(
+            file,
+            &mut self.db,
+            &self.master_key,
+            merge,
+            replace,
+            diff,
+        )?;
+
+        // Save the updated database (unless in diff mode)
+        if !diff {
+            self.save_modified()?;
         }
 
         Ok(result)
@@ -328,7 +2451,8 @@ impl Vault {
 
 impl Drop for Vault {
     fn drop(&mut self) {
-        // Zeroize master key when vault is dropped
+        // Zeroize master key and cached values when vault is dropped
         self.master_key.zeroize();
+        self.clear_value_cache();
     }
 }
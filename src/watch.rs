@@ -0,0 +1,78 @@
+//! Database Change Watching
+//!
+//! Polls the storage backend's last write time so long-running views
+//! (e.g. `ik list --watch`) can re-render when the database changes.
+//! Polling the path (rather than holding a file handle) also picks up
+//! databases that are replaced atomically via rename.
+
+use crate::error::Result;
+use crate::storage::StorageBackend;
+use std::ops::ControlFlow;
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+
+/// How often the backend is polled by default
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Why a watch loop ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WatchExit {
+    /// The callback asked to stop
+    Stopped,
+    /// `lock_after` elapsed, so the vault should be locked
+    TimedOut,
+}
+
+/// Call `on_change` once immediately and then after every change to the database
+///
+/// A change is only reported once the write time has been the same for two
+/// polls in a row, so a database caught mid-write isn't re-read.
+///
+/// # Arguments
+/// * `backend` - Backend to poll (see `StorageBackend::last_write_time`)
+/// * `interval` - Delay between polls
+/// * `lock_after` - Total time to watch before giving up with `WatchExit::TimedOut`
+/// * `on_change` - Render callback; return `ControlFlow::Break` to stop watching
+pub fn watch_changes<F>(
+    backend: &dyn StorageBackend,
+    interval: Duration,
+    lock_after: Duration,
+    mut on_change: F,
+) -> Result<WatchExit>
+where
+    F: FnMut() -> Result<ControlFlow<()>>,
+{
+    let started = Instant::now();
+    let mut rendered = backend.last_write_time();
+    let mut pending: Option<Option<SystemTime>> = None;
+
+    if on_change()?.is_break() {
+        return Ok(WatchExit::Stopped);
+    }
+
+    loop {
+        let remaining = lock_after.saturating_sub(started.elapsed());
+        if remaining.is_zero() {
+            return Ok(WatchExit::TimedOut);
+        }
+        thread::sleep(interval.min(remaining));
+
+        let current = backend.last_write_time();
+        if current == rendered {
+            pending = None;
+            continue;
+        }
+
+        // Wait for the write time to settle before re-reading
+        if pending != Some(current) {
+            pending = Some(current);
+            continue;
+        }
+
+        rendered = current;
+        pending = None;
+        if on_change()?.is_break() {
+            return Ok(WatchExit::Stopped);
+        }
+    }
+}
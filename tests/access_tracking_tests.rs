@@ -0,0 +1,119 @@
+//! Access Tracking Tests
+//!
+//! Tests for per-entry access counters and last-accessed timestamps.
+//! NOTE: These tests must run serially because they share the same database file.
+
+use ironkey::config::Config;
+use ironkey::storage;
+use ironkey::vault::Vault;
+use std::fs;
+
+mod common;
+
+/// Cleanup function
+fn cleanup() {
+    common::use_isolated_database();
+    if let Ok(db_path) = storage::get_database_path() {
+        let _ = fs::remove_file(db_path);
+    }
+}
+
+fn stats_for<'a>(vault: &'a Vault, key: &str) -> (u64, Option<&'a str>) {
+    vault
+        .access_stats()
+        .into_iter()
+        .find(|(k, _, _)| k.as_str() == key)
+        .map(|(_, count, last)| (count, last))
+        .unwrap()
+}
+
+#[test]
+fn test_access_count_increments_when_tracking_enabled() {
+    cleanup();
+    let mut vault = Vault::init("master".to_string()).unwrap();
+    vault
+        .create_entry("github".to_string(), "token".to_string())
+        .unwrap();
+    vault.set_access_tracking(true);
+
+    assert_eq!(vault.access_entry("github").unwrap(), "token");
+    assert_eq!(vault.access_entry("github").unwrap(), "token");
+
+    let (count, last) = stats_for(&vault, "github");
+    assert_eq!(count, 2);
+    assert!(last.is_some());
+    drop(vault);
+
+    // Counters are persisted
+    let vault = Vault::unlock("master".to_string()).unwrap();
+    assert_eq!(stats_for(&vault, "github").0, 2);
+
+    cleanup();
+}
+
+#[test]
+fn test_access_not_recorded_by_default() {
+    cleanup();
+    let mut vault = Vault::init("master".to_string()).unwrap();
+    vault
+        .create_entry("github".to_string(), "token".to_string())
+        .unwrap();
+
+    vault.access_entry("github").unwrap();
+    vault.get_entry("github").unwrap();
+
+    let (count, last) = stats_for(&vault, "github");
+    assert_eq!(count, 0);
+    assert!(last.is_none());
+
+    cleanup();
+}
+
+#[test]
+fn test_update_preserves_access_count() {
+    cleanup();
+    let mut vault = Vault::init("master".to_string()).unwrap();
+    vault
+        .create_entry("github".to_string(), "token".to_string())
+        .unwrap();
+    vault.set_access_tracking(true);
+    vault.access_entry("github").unwrap();
+
+    vault
+        .update_entry("github".to_string(), "new_token".to_string())
+        .unwrap();
+
+    assert_eq!(stats_for(&vault, "github").0, 1);
+
+    cleanup();
+}
+
+#[test]
+fn test_access_stats_sorted_by_count() {
+    cleanup();
+    let mut vault = Vault::init("master".to_string()).unwrap();
+    vault
+        .create_entry("a".to_string(), "1".to_string())
+        .unwrap();
+    vault
+        .create_entry("b".to_string(), "2".to_string())
+        .unwrap();
+    vault.set_access_tracking(true);
+    vault.access_entry("b").unwrap();
+
+    let stats = vault.access_stats();
+    assert_eq!(stats[0].0, "b");
+    assert_eq!(stats[1].0, "a");
+
+    cleanup();
+}
+
+#[test]
+fn test_config_track_access_flag() {
+    assert!(!Config::from_toml_str("").unwrap().track_access);
+    assert!(
+        Config::from_toml_str("track_access = true")
+            .unwrap()
+            .track_access
+    );
+}
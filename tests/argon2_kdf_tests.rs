@@ -0,0 +1,235 @@
+//! Argon2id KDF Tests
+//!
+//! Tests for `KdfAlgorithm::Argon2id` master key derivation (`ik init --kdf argon2`).
+
+use clap::ValueEnum;
+use ironkey::crypto::{self, KdfAlgorithm, KdfParams};
+use ironkey::error::Error;
+use ironkey::storage::{Database, MemoryBackend, StorageBackend};
+use ironkey::vault::VaultBuilder;
+
+/// Cheap Argon2id settings so tests don't allocate 64 MiB per derivation
+fn small_argon2() -> KdfParams {
+    KdfParams::argon2id(1024, 1, 1)
+}
+
+#[test]
+fn test_argon2_derivation_is_deterministic() {
+    let salt = crypto::generate_salt().unwrap();
+    let params = small_argon2();
+
+    let a = crypto::derive_key_with("password", &salt, &params).unwrap();
+    let b = crypto::derive_key_with("password", &salt, &params).unwrap();
+    assert_eq!(a, b);
+    assert_eq!(a.len(), 32);
+
+    // Different password, parameters or algorithm give a different key
+    assert_ne!(a, crypto::derive_key_with("other", &salt, &params).unwrap());
+    assert_ne!(
+        a,
+        crypto::derive_key_with("password", &salt, &KdfParams::argon2id(2048, 1, 1)).unwrap()
+    );
+    assert_ne!(
+        a,
+        crypto::derive_key_with("password", &salt, &KdfParams::pbkdf2(1000)).unwrap()
+    );
+}
+
+#[test]
+fn test_pbkdf2_params_match_legacy_functions() {
+    let salt = crypto::generate_salt().unwrap();
+    assert_eq!(
+        crypto::derive_key("password", &salt, 1000).unwrap(),
+        crypto::derive_key_with("password", &salt, &KdfParams::pbkdf2(1000)).unwrap()
+    );
+}
+
+#[test]
+fn test_argon2_verify_password() {
+    let salt = crypto::generate_salt().unwrap();
+    let params = small_argon2();
+    let hash = crypto::hash_password_with("password", &salt, &params).unwrap();
+
+    assert!(crypto::verify_password_with("password", &salt, &hash, &params).unwrap());
+    assert!(!crypto::verify_password_with("wrong", &salt, &hash, &params).unwrap());
+}
+
+#[test]
+fn test_invalid_argon2_params_rejected() {
+    let salt = crypto::generate_salt().unwrap();
+    // Argon2 needs at least 8 KiB of memory per lane
+    let result = crypto::derive_key_with("password", &salt, &KdfParams::argon2id(1, 1, 1));
+    assert!(matches!(result, Err(Error::KeyDerivationFailed(_))));
+}
+
+#[test]
+fn test_argon2_defaults() {
+    let params = KdfParams::default_for(KdfAlgorithm::Argon2id);
+    assert_eq!(params.algorithm, KdfAlgorithm::Argon2id);
+    assert_eq!(params.memory_kib, 64 * 1024);
+    assert_eq!(params.iterations, 3);
+
+    assert_eq!(
+        KdfParams::default_for(KdfAlgorithm::Pbkdf2),
+        KdfParams::pbkdf2(crypto::default_iterations())
+    );
+}
+
+#[test]
+fn test_cli_accepts_argon2_spellings() {
+    assert_eq!(
+        KdfAlgorithm::from_str("argon2", false).unwrap(),
+        KdfAlgorithm::Argon2id
+    );
+    assert_eq!(
+        KdfAlgorithm::from_str("argon2id", false).unwrap(),
+        KdfAlgorithm::Argon2id
+    );
+    assert_eq!(
+        KdfAlgorithm::from_str("pbkdf2", false).unwrap(),
+        KdfAlgorithm::Pbkdf2
+    );
+}
+
+#[test]
+fn test_old_database_defaults_to_pbkdf2() {
+    let json = r#"{
+        "master_salt": "AQID",
+        "master_hash": "BAUG",
+        "iterations": 100000,
+        "entries": {}
+    }"#;
+    let db: Database = serde_json::from_str(json).unwrap();
+
+    assert_eq!(db.kdf, KdfAlgorithm::Pbkdf2);
+    assert_eq!(db.kdf_params().unwrap(), KdfParams::pbkdf2(100_000));
+
+    // PBKDF2 vaults serialize exactly as before
+    let serialized = serde_json::to_string(&db).unwrap();
+    assert!(!serialized.contains("kdf"));
+}
+
+#[test]
+fn test_argon2_database_round_trips_params() {
+    let db = Database::new_with_kdf(vec![1, 2, 3], vec![4, 5, 6], &small_argon2());
+    let serialized = serde_json::to_string(&db).unwrap();
+    assert!(serialized.contains(r#""kdf":"argon2id""#));
+
+    let parsed: Database = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(parsed.kdf_params().unwrap(), small_argon2());
+}
+
+#[test]
+fn test_argon2_database_missing_params_is_an_error() {
+    let mut db = Database::new_with_kdf(vec![1, 2, 3], vec![4, 5, 6], &small_argon2());
+    db.kdf_memory_kib = None;
+
+    assert!(matches!(db.kdf_params(), Err(Error::DatabaseLoadFailed(_))));
+}
+
+#[test]
+fn test_argon2_vault_unlocks_with_stored_algorithm() {
+    let backend = MemoryBackend::new();
+    let mut vault = VaultBuilder::new()
+        .backend(Box::new(backend.clone()))
+        .kdf(small_argon2())
+        .init("master".to_string())
+        .unwrap();
+    vault
+        .create_entry("github".to_string(), "ghp_token".to_string())
+        .unwrap();
+    drop(vault);
+
+    let stored = backend.load().unwrap();
+    assert_eq!(stored.kdf, KdfAlgorithm::Argon2id);
+    assert_eq!(stored.kdf_memory_kib, Some(1024));
+    assert_eq!(stored.kdf_parallelism, Some(1));
+
+    let vault = VaultBuilder::new()
+        .backend(Box::new(backend.clone()))
+        .unlock("master".to_string())
+        .unwrap();
+    assert_eq!(vault.get_entry("github").unwrap(), "ghp_token");
+
+    assert!(matches!(
+        VaultBuilder::new()
+            .backend(Box::new(backend))
+            .unlock("wrong".to_string()),
+        Err(Error::InvalidMasterPassword)
+    ));
+}
+
+#[test]
+fn test_change_master_keeps_argon2() {
+    let backend = MemoryBackend::new();
+    let mut vault = VaultBuilder::new()
+        .backend(Box::new(backend.clone()))
+        .kdf(small_argon2())
+        .init("master".to_string())
+        .unwrap();
+    vault
+        .create_entry("github".to_string(), "ghp_token".to_string())
+        .unwrap();
+
+    vault
+        .change_master_password("master".to_string(), "new_master".to_string())
+        .unwrap();
+    drop(vault);
+
+    assert_eq!(
+        backend.load().unwrap().kdf_params().unwrap(),
+        small_argon2()
+    );
+    let vault = VaultBuilder::new()
+        .backend(Box::new(backend))
+        .unlock("new_master".to_string())
+        .unwrap();
+    assert_eq!(vault.get_entry("github").unwrap(), "ghp_token");
+}
+
+#[test]
+fn test_lock_passphrase_uses_vault_kdf() {
+    let backend = MemoryBackend::new();
+    let mut vault = VaultBuilder::new()
+        .backend(Box::new(backend))
+        .kdf(small_argon2())
+        .init("master".to_string())
+        .unwrap();
+    vault
+        .create_entry("github".to_string(), "ghp_token".to_string())
+        .unwrap();
+
+    vault.lock_with_password("github", "open sesame").unwrap();
+    assert!(vault.unlock_with_password("github", "wrong").is_err());
+    vault.unlock_with_password("github", "open sesame").unwrap();
+    assert_eq!(vault.get_entry("github").unwrap(), "ghp_token");
+}
+
+#[test]
+fn test_argon2_params_survive_sqlite_backend() {
+    use ironkey::sqlite_backend::SqliteBackend;
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let path = temp_dir.path().join("ironkey.db");
+
+    let mut vault = VaultBuilder::new()
+        .backend(Box::new(SqliteBackend::open(&path).unwrap()))
+        .kdf(small_argon2())
+        .init("master".to_string())
+        .unwrap();
+    vault
+        .create_entry("github".to_string(), "ghp_token".to_string())
+        .unwrap();
+    drop(vault);
+
+    let backend = SqliteBackend::open(&path).unwrap();
+    assert_eq!(
+        backend.load().unwrap().kdf_params().unwrap(),
+        small_argon2()
+    );
+    let vault = VaultBuilder::new()
+        .backend(Box::new(backend))
+        .unlock("master".to_string())
+        .unwrap();
+    assert_eq!(vault.get_entry("github").unwrap(), "ghp_token");
+}
@@ -0,0 +1,102 @@
+//! Atomic Save Tests
+//!
+//! Tests that `JsonFileBackend::save` stages to `<name>.tmp` and renames it into place.
+
+use ironkey::storage::{JsonFileBackend, StorageBackend};
+use ironkey::vault::VaultBuilder;
+use std::fs;
+use tempfile::TempDir;
+
+fn assert_complete_database(path: &std::path::Path) {
+    let content = fs::read_to_string(path).unwrap();
+    serde_json::from_str::<serde_json::Value>(&content)
+        .unwrap_or_else(|e| panic!("{} is not complete JSON: {e}", path.display()));
+}
+
+#[test]
+fn test_save_leaves_no_staging_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("ironkey.json");
+    let backend = JsonFileBackend::new(path.clone());
+
+    let mut vault = VaultBuilder::new()
+        .backend(Box::new(backend.clone()))
+        .init("master".to_string())
+        .unwrap();
+    vault
+        .create_entry("github".to_string(), "ghp_token".to_string())
+        .unwrap();
+
+    assert_complete_database(&path);
+    assert!(!backend.sibling_path(".tmp").exists());
+}
+
+#[test]
+fn test_partial_staging_file_is_overwritten() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("ironkey.json");
+    let backend = JsonFileBackend::new(path.clone());
+    let mut vault = VaultBuilder::new()
+        .backend(Box::new(backend.clone()))
+        .init("master".to_string())
+        .unwrap();
+
+    // Simulate a save that was killed halfway through writing the staging file
+    let full = fs::read_to_string(&path).unwrap();
+    fs::write(backend.sibling_path(".tmp"), &full[..full.len() / 2]).unwrap();
+
+    // The real database is untouched by the interrupted save
+    assert_complete_database(&path);
+    drop(vault);
+    vault = VaultBuilder::new()
+        .backend(Box::new(backend.clone()))
+        .unlock("master".to_string())
+        .unwrap();
+
+    // The next save replaces the stale staging file and lands a complete database
+    vault
+        .create_entry("github".to_string(), "ghp_token".to_string())
+        .unwrap();
+    assert_complete_database(&path);
+    assert!(!backend.sibling_path(".tmp").exists());
+
+    drop(vault);
+    let vault = VaultBuilder::new()
+        .backend(Box::new(backend))
+        .unlock("master".to_string())
+        .unwrap();
+    assert_eq!(vault.get_entry("github").unwrap(), "ghp_token");
+}
+
+#[test]
+fn test_database_is_always_complete_across_saves() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("ironkey.json");
+    let backend = JsonFileBackend::new(path.clone());
+    let mut vault = VaultBuilder::new()
+        .backend(Box::new(backend.clone()))
+        .init("master".to_string())
+        .unwrap();
+
+    for i in 0..20 {
+        vault
+            .create_entry(format!("key_{i}"), "x".repeat(i * 50))
+            .unwrap();
+        assert_complete_database(&path);
+    }
+    assert_eq!(backend.load().unwrap().entries.len(), 20);
+}
+
+#[test]
+fn test_save_creates_missing_parent_directory() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("a").join("b").join("ironkey.json");
+
+    drop(
+        VaultBuilder::new()
+            .backend(Box::new(JsonFileBackend::new(path.clone())))
+            .init("master".to_string())
+            .unwrap(),
+    );
+    assert_complete_database(&path);
+}
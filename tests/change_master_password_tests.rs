@@ -0,0 +1,198 @@
+//! Master Password Change Tests
+//!
+//! Tests for `Vault::change_master_password` (`ik change-master`).
+
+use base64::{Engine as _, engine::general_purpose};
+use ironkey::crypto;
+use ironkey::error::Error;
+use ironkey::storage::{Entry, KeyScheme, MemoryBackend, StorageBackend};
+use ironkey::vault::{Vault, VaultBuilder};
+use zeroize::Zeroizing;
+
+mod common;
+
+fn unlock(backend: &MemoryBackend, password: &str) -> ironkey::error::Result<Vault> {
+    VaultBuilder::new()
+        .backend(Box::new(backend.clone()))
+        .unlock(password.to_string())
+}
+
+#[test]
+fn test_change_master_password_reencrypts_everything() {
+    let backend = MemoryBackend::new();
+    let mut vault =
+        common::vault_with_entries(&backend, &[("github", "ghp_token"), ("prod", "secret")]);
+    vault
+        .set_label("github", Some("work account".to_string()))
+        .unwrap();
+    vault.toggle_lock("prod").unwrap();
+    let before = backend.load().unwrap();
+
+    vault
+        .change_master_password("master".to_string(), "new_master".to_string())
+        .unwrap();
+
+    // The open vault keeps working with the new key
+    assert_eq!(vault.get_entry("github").unwrap(), "ghp_token");
+    drop(vault);
+
+    let after = backend.load().unwrap();
+    assert_ne!(after.master_salt, before.master_salt);
+    assert_ne!(after.master_hash, before.master_hash);
+    assert_ne!(
+        after.entries["github"].encrypted_value,
+        before.entries["github"].encrypted_value
+    );
+
+    assert!(matches!(
+        unlock(&backend, "master"),
+        Err(Error::InvalidMasterPassword)
+    ));
+
+    let mut vault = unlock(&backend, "new_master").unwrap();
+    assert_eq!(vault.get_entry("github").unwrap(), "ghp_token");
+    assert_eq!(vault.get_label("github").unwrap(), Some("work account"));
+
+    // Locked entries are re-encrypted and stay locked
+    assert!(matches!(
+        vault.get_entry("prod"),
+        Err(Error::EntryLocked(_))
+    ));
+    vault.toggle_lock("prod").unwrap();
+    assert_eq!(vault.get_entry("prod").unwrap(), "secret");
+}
+
+#[test]
+fn test_wrong_old_password_changes_nothing() {
+    let backend = MemoryBackend::new();
+    let mut vault = common::vault_with_entries(&backend, &[("github", "ghp_token")]);
+    let before = serde_json::to_string(&backend.load().unwrap()).unwrap();
+
+    assert!(matches!(
+        vault.change_master_password("wrong".to_string(), "new_master".to_string()),
+        Err(Error::InvalidMasterPassword)
+    ));
+
+    assert_eq!(
+        serde_json::to_string(&backend.load().unwrap()).unwrap(),
+        before
+    );
+    assert_eq!(vault.get_entry("github").unwrap(), "ghp_token");
+}
+
+#[test]
+fn test_empty_new_password_rejected() {
+    let backend = MemoryBackend::new();
+    let mut vault = common::vault_with_entries(&backend, &[("github", "ghp_token")]);
+
+    assert!(matches!(
+        vault.change_master_password("master".to_string(), "   ".to_string()),
+        Err(Error::EmptyPassword)
+    ));
+    drop(vault);
+
+    assert!(unlock(&backend, "master").is_ok());
+}
+
+#[test]
+fn test_failure_partway_leaves_vault_untouched() {
+    let backend = MemoryBackend::new();
+    drop(common::vault_with_entries(
+        &backend,
+        &[("a_good", "one"), ("b_bad", "two"), ("c_good", "three")],
+    ));
+
+    // Damage one entry's ciphertext in a vault saved before the MAC existed;
+    // unlocking still works because other entries decrypt
+    let mut db = backend.load().unwrap();
+    db.mac = None;
+    let mut ciphertext = db.entries["b_bad"].get_encrypted_value().unwrap();
+    ciphertext[0] ^= 0xff;
+    db.entries.get_mut("b_bad").unwrap().encrypted_value =
+        general_purpose::STANDARD.encode(&ciphertext);
+    backend.save(&db).unwrap();
+    let before = serde_json::to_string(&backend.load().unwrap()).unwrap();
+
+    let mut vault = unlock(&backend, "master").unwrap();
+    assert!(
+        vault
+            .change_master_password("master".to_string(), "new_master".to_string())
+            .is_err()
+    );
+
+    // Nothing was written, and the old password still opens the vault
+    assert_eq!(
+        serde_json::to_string(&backend.load().unwrap()).unwrap(),
+        before
+    );
+    assert_eq!(vault.get_entry("a_good").unwrap(), "one");
+    drop(vault);
+    assert!(unlock(&backend, "master").is_ok());
+    assert!(unlock(&backend, "new_master").is_err());
+}
+
+#[test]
+fn test_legacy_vault_upgrades_to_hkdf() {
+    let backend = MemoryBackend::new();
+    drop(common::vault_with_entries(&backend, &[]));
+
+    // Rewrite as a pre-HKDF vault with one entry under the raw master key
+    let mut db = backend.load().unwrap();
+    let master = crypto::derive_key("master", &db.get_salt().unwrap(), db.iterations).unwrap();
+    db.key_scheme = KeyScheme::Legacy;
+    db.mac = None;
+    let encrypted = crypto::encrypt(b"legacy_secret", &master).unwrap();
+    db.entries.insert(
+        "old".to_string(),
+        Entry::new(encrypted.ciphertext, encrypted.nonce, false),
+    );
+    backend.save(&db).unwrap();
+
+    let mut vault = unlock(&backend, "master").unwrap();
+    vault
+        .change_master_password("master".to_string(), "new_master".to_string())
+        .unwrap();
+    drop(vault);
+
+    assert_eq!(backend.load().unwrap().key_scheme, KeyScheme::Hkdf);
+    assert_eq!(
+        unlock(&backend, "new_master")
+            .unwrap()
+            .get_entry("old")
+            .unwrap(),
+        "legacy_secret"
+    );
+}
+
+#[test]
+fn test_verified_change_stores_the_new_key_as_hash() {
+    let backend = MemoryBackend::new();
+    let mut vault = common::vault_with_entries(&backend, &[("github", "ghp_token")]);
+
+    vault
+        .change_master_password_verified(Zeroizing::new("new_master".to_string()))
+        .unwrap();
+    drop(vault);
+
+    // Like init, the stored hash is the derived key itself
+    let db = backend.load().unwrap();
+    let key = crypto::derive_key_with(
+        "new_master",
+        &db.get_salt().unwrap(),
+        &db.kdf_params().unwrap(),
+    )
+    .unwrap();
+    assert_eq!(db.get_hash().unwrap(), key.to_vec());
+
+    assert!(matches!(
+        unlock(&backend, "master"),
+        Err(Error::InvalidMasterPassword)
+    ));
+    assert_eq!(
+        unlock(&backend, "new_master")
+            .unwrap()
+            .get_entry("github")
+            .unwrap(),
+        "ghp_token"
+    );
+}
@@ -135,3 +135,32 @@ fn test_multiple_auto_clear_operations() {
         "Clipboard should be cleared after second timeout"
     );
 }
+
+#[test]
+fn test_then_run_clears_after_command_exits() {
+    let test_value = "then_run_test_value";
+    let status =
+        clipboard::copy_and_run(test_value, &["true".to_string()]).expect("Failed to copy and run");
+    assert!(status.success());
+
+    // Cleared as soon as the command returned, no timer involved
+    let clipboard_after = clipboard::get_from_clipboard().unwrap_or_default();
+    assert_ne!(clipboard_after, test_value);
+}
+
+#[test]
+fn test_then_run_clears_on_failing_command() {
+    let test_value = "then_run_failure_value";
+    let status = clipboard::copy_and_run(test_value, &["false".to_string()])
+        .expect("Failed to copy and run");
+    assert!(!status.success());
+
+    let clipboard_after = clipboard::get_from_clipboard().unwrap_or_default();
+    assert_ne!(clipboard_after, test_value);
+}
+
+#[test]
+fn test_then_run_rejects_empty_command() {
+    assert!(clipboard::copy_and_run("value", &[]).is_err());
+    assert!(clipboard::copy_and_run("value", &[" ".to_string()]).is_err());
+}
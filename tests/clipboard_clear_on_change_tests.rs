@@ -0,0 +1,51 @@
+//! Clear-On-Change Tests
+//!
+//! Tests for the wait logic behind `ik get --copy --clear-on-change`.
+//! These feed a simulated clipboard instead of the system one, so they run
+//! without a display.
+
+use ironkey::clipboard::{ClearTrigger, wait_for_clear};
+use std::time::{Duration, Instant};
+
+const POLL: Duration = Duration::from_millis(10);
+
+#[test]
+fn test_changed_clipboard_triggers_early_clear() {
+    let mut reads = 0;
+    let started = Instant::now();
+
+    let trigger = wait_for_clear("secret", Duration::from_secs(10), POLL, || {
+        reads += 1;
+        // The user copies something else on the third poll
+        Some(
+            if reads < 3 {
+                "secret"
+            } else {
+                "something else"
+            }
+            .to_string(),
+        )
+    });
+
+    assert_eq!(trigger, ClearTrigger::Changed);
+    assert_eq!(reads, 3);
+    assert!(started.elapsed() < Duration::from_secs(5));
+}
+
+#[test]
+fn test_unchanged_clipboard_waits_for_timeout() {
+    let started = Instant::now();
+
+    let trigger = wait_for_clear("secret", Duration::from_millis(100), POLL, || {
+        Some("secret".to_string())
+    });
+
+    assert_eq!(trigger, ClearTrigger::Timeout);
+    assert!(started.elapsed() >= Duration::from_millis(100));
+}
+
+#[test]
+fn test_unreadable_clipboard_counts_as_unchanged() {
+    let trigger = wait_for_clear("secret", Duration::from_millis(50), POLL, || None);
+    assert_eq!(trigger, ClearTrigger::Timeout);
+}
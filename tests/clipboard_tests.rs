@@ -1,6 +1,6 @@
 // Clipboard functionality tests
 
-use ironkey::clipboard::{copy_to_clipboard, get_from_clipboard};
+use ironkey::clipboard::{clear_clipboard, copy_to_clipboard, get_from_clipboard};
 
 #[test]
 fn test_clipboard_copy_and_retrieve() {
@@ -82,3 +82,14 @@ fn test_clipboard_overwrites_previous() {
     let second_retrieved = get_from_clipboard().unwrap();
     assert_eq!(second_retrieved, second_text);
 }
+
+#[test]
+fn test_clear_clipboard() {
+    copy_to_clipboard("secret_to_wipe").unwrap();
+
+    clear_clipboard().unwrap();
+    assert_eq!(get_from_clipboard().unwrap_or_default(), "");
+
+    // Clearing an already-empty clipboard is fine
+    clear_clipboard().unwrap();
+}
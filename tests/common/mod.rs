@@ -0,0 +1,69 @@
+//! Shared helpers for integration tests.
+//!
+//! Each test binary compiles this module separately and uses only some of it.
+#![allow(dead_code)]
+
+use ironkey::storage::MemoryBackend;
+use ironkey::vault::{Vault, VaultBuilder};
+use std::sync::OnceLock;
+use tempfile::TempDir;
+
+/// Point `IRONKEY_DB_PATH` at a database private to this test binary
+///
+/// Each test binary gets its own temporary directory, so binaries no longer
+/// clash with each other or with the real vault in the config directory.
+/// Tests within one binary still share the file and must run serially.
+pub fn use_isolated_database() {
+    static DIR: OnceLock<TempDir> = OnceLock::new();
+    DIR.get_or_init(|| {
+        let dir = TempDir::new().expect("create test database directory");
+        // SAFETY: set once per process, before any test reads the variable
+        unsafe {
+            std::env::set_var(
+                ironkey::storage::DB_PATH_ENV,
+                dir.path().join("ironkey.json"),
+            );
+        }
+        dir
+    });
+}
+
+/// Create a vault on `backend` (master password "master") holding the given entries
+pub fn vault_with_entries(backend: &MemoryBackend, entries: &[(&str, &str)]) -> Vault {
+    let mut vault = VaultBuilder::new()
+        .backend(Box::new(backend.clone()))
+        .init("master".to_string())
+        .unwrap();
+    for (key, value) in entries {
+        vault
+            .create_entry(key.to_string(), value.to_string())
+            .unwrap();
+    }
+    vault
+}
+
+/// Like `vault_with_entries`, with each key's value set to "<key>_value"
+pub fn vault_with_keys(backend: &MemoryBackend, keys: &[&str]) -> Vault {
+    let entries: Vec<(&str, String)> = keys
+        .iter()
+        .map(|key| (*key, format!("{key}_value")))
+        .collect();
+    let entries: Vec<(&str, &str)> = entries
+        .iter()
+        .map(|(key, value)| (*key, value.as_str()))
+        .collect();
+    vault_with_entries(backend, &entries)
+}
+
+/// Create a vault on `backend` holding a single entry
+pub fn vault_with_entry(backend: &MemoryBackend, key: &str, value: &str) -> Vault {
+    vault_with_entries(backend, &[(key, value)])
+}
+
+/// Unlock the vault stored in `backend` again
+pub fn reopen(backend: &MemoryBackend, password: &str) -> Vault {
+    VaultBuilder::new()
+        .backend(Box::new(backend.clone()))
+        .unlock(password.to_string())
+        .unwrap()
+}
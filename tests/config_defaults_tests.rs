@@ -0,0 +1,69 @@
+//! Config Defaults Tests
+//!
+//! Tests for the clipboard timeout, password length and KDF settings in
+//! `config.toml`, and their precedence: CLI flag, then config, then built-in default.
+
+use ironkey::config::{Config, DEFAULT_CLIPBOARD_TIMEOUT, DEFAULT_PASSWORD_LENGTH};
+use ironkey::crypto::KdfAlgorithm;
+
+const CONFIGURED: &str = r#"
+clipboard_timeout = 10
+default_password_length = 24
+kdf = "argon2"
+"#;
+
+#[test]
+fn test_parses_new_keys() {
+    let config = Config::from_toml_str(CONFIGURED).unwrap();
+
+    assert_eq!(config.clipboard_timeout, Some(10));
+    assert_eq!(config.default_password_length, Some(24));
+    assert_eq!(config.kdf, Some(KdfAlgorithm::Argon2id));
+}
+
+#[test]
+fn test_built_in_defaults_without_config_or_flag() {
+    let config = Config::from_toml_str("").unwrap();
+
+    assert_eq!(config.effective_clipboard_timeout(None), 30);
+    assert_eq!(config.effective_password_length(None), 16);
+    assert_eq!(config.effective_kdf(None), KdfAlgorithm::Pbkdf2);
+    assert_eq!(DEFAULT_CLIPBOARD_TIMEOUT, 30);
+    assert_eq!(DEFAULT_PASSWORD_LENGTH, 16);
+}
+
+#[test]
+fn test_config_overrides_built_in_defaults() {
+    let config = Config::from_toml_str(CONFIGURED).unwrap();
+
+    assert_eq!(config.effective_clipboard_timeout(None), 10);
+    assert_eq!(config.effective_password_length(None), 24);
+    assert_eq!(config.effective_kdf(None), KdfAlgorithm::Argon2id);
+}
+
+#[test]
+fn test_flags_override_config() {
+    let config = Config::from_toml_str(CONFIGURED).unwrap();
+
+    assert_eq!(config.effective_clipboard_timeout(Some(60)), 60);
+    assert_eq!(config.effective_password_length(Some(12)), 12);
+    assert_eq!(
+        config.effective_kdf(Some(KdfAlgorithm::Pbkdf2)),
+        KdfAlgorithm::Pbkdf2
+    );
+}
+
+#[test]
+fn test_flags_override_built_in_defaults() {
+    let config = Config::default();
+
+    assert_eq!(config.effective_clipboard_timeout(Some(0)), 0);
+    assert_eq!(config.effective_password_length(Some(40)), 40);
+}
+
+#[test]
+fn test_invalid_values_are_rejected() {
+    assert!(Config::from_toml_str("kdf = \"scrypt\"").is_err());
+    assert!(Config::from_toml_str("clipboard_timeout = -5").is_err());
+    assert!(Config::from_toml_str("default_password_length = \"long\"").is_err());
+}
@@ -0,0 +1,78 @@
+//! Confirmation Policy Tests
+//!
+//! Tests for the decision logic behind `--confirm-destructive`.
+
+use ironkey::config::Config;
+use ironkey::confirm::{ConfirmDecision, ConfirmPolicy, DestructiveConfirm, decide};
+
+#[test]
+fn test_always_policy_prompts_every_time() {
+    assert_eq!(
+        decide(ConfirmPolicy::Always, false, false),
+        ConfirmDecision::Prompt
+    );
+    assert_eq!(
+        decide(ConfirmPolicy::Always, false, true),
+        ConfirmDecision::Prompt
+    );
+}
+
+#[test]
+fn test_once_policy_prompts_only_first_time() {
+    assert_eq!(
+        decide(ConfirmPolicy::Once, false, false),
+        ConfirmDecision::Prompt
+    );
+    assert_eq!(
+        decide(ConfirmPolicy::Once, false, true),
+        ConfirmDecision::Proceed
+    );
+}
+
+#[test]
+fn test_never_policy_refuses_without_yes() {
+    assert_eq!(
+        decide(ConfirmPolicy::Never, false, false),
+        ConfirmDecision::Refuse
+    );
+    assert_eq!(
+        decide(ConfirmPolicy::Never, false, true),
+        ConfirmDecision::Refuse
+    );
+}
+
+#[test]
+fn test_yes_flag_proceeds_for_every_policy() {
+    for policy in [
+        ConfirmPolicy::Always,
+        ConfirmPolicy::Once,
+        ConfirmPolicy::Never,
+    ] {
+        assert_eq!(decide(policy, true, false), ConfirmDecision::Proceed);
+    }
+}
+
+#[test]
+fn test_tracker_remembers_confirmation_for_once() {
+    let confirm = DestructiveConfirm::new(ConfirmPolicy::Once, false);
+    assert_eq!(confirm.decide(), ConfirmDecision::Prompt);
+
+    confirm.mark_confirmed();
+    assert_eq!(confirm.decide(), ConfirmDecision::Proceed);
+}
+
+#[test]
+fn test_default_policy_is_always() {
+    assert_eq!(ConfirmPolicy::default(), ConfirmPolicy::Always);
+}
+
+#[test]
+fn test_config_sets_default_policy() {
+    let config = Config::from_toml_str("confirm_destructive = \"never\"").unwrap();
+    assert_eq!(config.confirm_destructive, Some(ConfirmPolicy::Never));
+
+    let empty = Config::from_toml_str("").unwrap();
+    assert!(empty.confirm_destructive.is_none());
+
+    assert!(Config::from_toml_str("confirm_destructive = \"sometimes\"").is_err());
+}
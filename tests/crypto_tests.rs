@@ -1,8 +1,11 @@
 // Crypto module tests
 
 use ironkey::crypto::{
-    decrypt, derive_key, encrypt, generate_salt, hash_password, verify_password,
+    EncryptedData, KdfParams, MAX_PASSWORD_LENGTH, constant_time_eq, decrypt, decrypt_into,
+    derive_key, derive_key_into, derive_verified_key_with, encrypt, encrypt_into, generate_salt,
+    hash_password, verify_derived, verify_password,
 };
+use ironkey::error::Error;
 
 const TEST_ITERATIONS: u32 = 100_000;
 
@@ -41,3 +44,144 @@ fn test_different_nonces() {
     assert_ne!(encrypted1.nonce, encrypted2.nonce);
     assert_ne!(encrypted1.ciphertext, encrypted2.ciphertext);
 }
+
+#[test]
+fn test_into_variants_match_allocating_versions() {
+    let password = "test_password";
+    let salt = generate_salt().unwrap();
+
+    // derive_key_into writes exactly what derive_key returns
+    let key = derive_key(password, &salt, TEST_ITERATIONS).unwrap();
+    let mut key_buf = [0xAAu8; 32];
+    derive_key_into(password, &salt, TEST_ITERATIONS, &mut key_buf).unwrap();
+    assert_eq!(key, key_buf);
+
+    // Reuse a dirty buffer with larger capacity for encryption
+    let plaintext = b"bulk rotation value";
+    let mut scratch = vec![0xFFu8; 128];
+    let nonce = encrypt_into(plaintext, &key, &mut scratch).unwrap();
+    let encrypted = EncryptedData {
+        ciphertext: scratch.clone(),
+        nonce: nonce.to_vec(),
+    };
+    assert_eq!(decrypt(&encrypted, &key).unwrap(), plaintext);
+
+    // decrypt_into yields the same plaintext as decrypt for allocating encrypt output
+    let allocated = encrypt(plaintext, &key).unwrap();
+    decrypt_into(&allocated, &key, &mut scratch).unwrap();
+    assert_eq!(scratch, decrypt(&allocated, &key).unwrap());
+    assert_eq!(allocated.ciphertext.len(), encrypted.ciphertext.len());
+}
+
+#[test]
+fn test_into_variants_reject_bad_buffers() {
+    let salt = generate_salt().unwrap();
+    let mut short = [0u8; 16];
+    assert!(derive_key_into("pw", &salt, TEST_ITERATIONS, &mut short).is_err());
+
+    // Failed decryption leaves the output buffer empty
+    let key = vec![1u8; 32];
+    let wrong_key = vec![2u8; 32];
+    let encrypted = encrypt(b"secret", &key).unwrap();
+    let mut out = b"stale data".to_vec();
+    assert!(decrypt_into(&encrypted, &wrong_key, &mut out).is_err());
+    assert!(out.is_empty());
+}
+
+#[test]
+fn test_verify_derived_matching_and_mismatching() {
+    let salt = generate_salt().unwrap();
+    let expected = derive_key("password", &salt, TEST_ITERATIONS).unwrap();
+    let same = derive_key("password", &salt, TEST_ITERATIONS).unwrap();
+    let other = derive_key("other", &salt, TEST_ITERATIONS).unwrap();
+
+    assert!(verify_derived(&expected, &same));
+    assert!(!verify_derived(&expected, &other));
+
+    // A single differing byte is enough to fail
+    let mut flipped = expected.clone();
+    flipped[31] ^= 1;
+    assert!(!verify_derived(&expected, &flipped));
+}
+
+#[test]
+fn test_verify_derived_unequal_lengths() {
+    let expected = vec![7u8; 32];
+
+    assert!(!verify_derived(&expected, &expected[..16]));
+    assert!(!verify_derived(&expected[..16], &expected));
+    assert!(!verify_derived(&expected, &[]));
+    assert!(verify_derived(&[], &[]));
+
+    // A truncated stored hash never verifies, even for the right password
+    let salt = generate_salt().unwrap();
+    let hash = hash_password("password", &salt, TEST_ITERATIONS).unwrap();
+    assert!(!verify_password("password", &salt, &hash[..16], TEST_ITERATIONS).unwrap());
+}
+
+#[test]
+fn test_password_length_limit() {
+    let salt = generate_salt().unwrap();
+
+    let at_limit = "a".repeat(MAX_PASSWORD_LENGTH);
+    let hash = hash_password(&at_limit, &salt, TEST_ITERATIONS).unwrap();
+    assert!(verify_password(&at_limit, &salt, &hash, TEST_ITERATIONS).unwrap());
+
+    let over_limit = "a".repeat(MAX_PASSWORD_LENGTH + 1);
+    assert!(matches!(
+        derive_key(&over_limit, &salt, TEST_ITERATIONS),
+        Err(Error::InvalidInput(_))
+    ));
+    assert!(matches!(
+        hash_password(&over_limit, &salt, TEST_ITERATIONS),
+        Err(Error::InvalidInput(_))
+    ));
+    assert!(matches!(
+        verify_password(&over_limit, &salt, &hash, TEST_ITERATIONS),
+        Err(Error::InvalidInput(_))
+    ));
+
+    // The limit is in bytes, not characters
+    let multibyte = "é".repeat(MAX_PASSWORD_LENGTH / 2 + 1);
+    assert!(derive_key(&multibyte, &salt, TEST_ITERATIONS).is_err());
+}
+
+#[test]
+fn test_derive_verified_key_matches_separate_verify_and_derive() {
+    let salt = generate_salt().unwrap();
+    let params = KdfParams::pbkdf2(TEST_ITERATIONS);
+    let hash = hash_password("password", &salt, TEST_ITERATIONS).unwrap();
+
+    let key = derive_verified_key_with("password", &salt, &hash, &params)
+        .unwrap()
+        .expect("correct password should yield the key");
+    assert_eq!(key, derive_key("password", &salt, TEST_ITERATIONS).unwrap());
+
+    assert_eq!(
+        derive_verified_key_with("wrong_password", &salt, &hash, &params).unwrap(),
+        None
+    );
+    // Like verify_password, an empty password is just a wrong password
+    assert_eq!(
+        derive_verified_key_with("", &salt, &hash, &params).unwrap(),
+        None
+    );
+    assert_eq!(
+        derive_verified_key_with("password", &salt, &hash[..16], &params).unwrap(),
+        None
+    );
+}
+
+#[test]
+fn test_constant_time_eq() {
+    assert!(constant_time_eq(b"derived key bytes", b"derived key bytes"));
+    assert!(constant_time_eq(&[], &[]));
+
+    assert!(!constant_time_eq(
+        b"derived key bytes",
+        b"derived key byteS"
+    ));
+    assert!(!constant_time_eq(b"derived key bytes", b"derived key"));
+    assert!(!constant_time_eq(b"derived", b"derived key bytes"));
+    assert!(!constant_time_eq(b"derived key bytes", &[]));
+}
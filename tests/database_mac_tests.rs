@@ -0,0 +1,136 @@
+//! Database MAC Tests
+//!
+//! Tests for the HMAC over the entries (`Database.mac`) checked on unlock.
+
+use ironkey::crypto;
+use ironkey::error::Error;
+use ironkey::storage::{MemoryBackend, StorageBackend};
+use ironkey::vault::{Vault, VaultBuilder};
+
+mod common;
+
+fn unlock(backend: &MemoryBackend) -> ironkey::error::Result<Vault> {
+    VaultBuilder::new()
+        .backend(Box::new(backend.clone()))
+        .unlock("master".to_string())
+}
+
+#[test]
+fn test_saves_store_a_mac() {
+    let backend = MemoryBackend::new();
+    let mut vault = common::vault_with_keys(&backend, &[]);
+    let empty_mac = backend.load().unwrap().mac.unwrap();
+
+    vault
+        .create_entry("github".to_string(), "ghp_token".to_string())
+        .unwrap();
+    let mac = backend.load().unwrap().mac.unwrap();
+    assert_ne!(mac, empty_mac);
+
+    drop(vault);
+    assert!(unlock(&backend).is_ok());
+}
+
+#[test]
+fn test_modified_entries_fail_unlock() {
+    let backend = MemoryBackend::new();
+    drop(common::vault_with_keys(&backend, &["github", "aws"]));
+
+    // Lock an entry by hand: no ciphertext changes, so every entry still decrypts
+    let mut db = backend.load().unwrap();
+    db.entries.get_mut("github").unwrap().is_locked = true;
+    backend.save(&db).unwrap();
+
+    assert!(matches!(unlock(&backend), Err(Error::IntegrityCheckFailed)));
+}
+
+#[test]
+fn test_removed_entry_fails_unlock() {
+    let backend = MemoryBackend::new();
+    drop(common::vault_with_keys(&backend, &["github", "aws"]));
+
+    let mut db = backend.load().unwrap();
+    db.entries.remove("aws");
+    backend.save(&db).unwrap();
+
+    assert!(matches!(unlock(&backend), Err(Error::IntegrityCheckFailed)));
+}
+
+#[test]
+fn test_vault_without_mac_unlocks_and_gains_one_on_save() {
+    let backend = MemoryBackend::new();
+    drop(common::vault_with_keys(&backend, &["github"]));
+
+    // Vaults saved before the MAC existed have no `mac` field
+    let mut db = backend.load().unwrap();
+    db.mac = None;
+    backend.save(&db).unwrap();
+    assert!(!serde_json::to_string(&db).unwrap().contains("\"mac\""));
+
+    let mut vault = unlock(&backend).unwrap();
+    assert_eq!(vault.get_entry("github").unwrap(), "github_value");
+    vault
+        .create_entry("aws".to_string(), "aws_value".to_string())
+        .unwrap();
+    drop(vault);
+
+    assert!(backend.load().unwrap().mac.is_some());
+    assert!(unlock(&backend).is_ok());
+}
+
+#[test]
+fn test_unverified_unlock_reports_mismatch() {
+    let backend = MemoryBackend::new();
+    drop(common::vault_with_keys(&backend, &["github"]));
+    assert!(
+        !unlock(&backend)
+            .unwrap()
+            .verify_integrity()
+            .unwrap()
+            .mac_mismatch
+    );
+
+    let mut db = backend.load().unwrap();
+    db.entries.get_mut("github").unwrap().label = Some("edited".to_string());
+    backend.save(&db).unwrap();
+
+    let vault =
+        Vault::unlock_unverified_with_backend(Box::new(backend.clone()), "master".to_string())
+            .unwrap();
+    let report = vault.verify_integrity().unwrap();
+    assert!(report.mac_mismatch);
+    assert!(!report.is_healthy());
+    assert_eq!(report.healthy, ["github"]);
+}
+
+#[test]
+fn test_mac_follows_master_password_change() {
+    let backend = MemoryBackend::new();
+    let mut vault = common::vault_with_keys(&backend, &["github"]);
+    let before = backend.load().unwrap().mac;
+
+    vault
+        .change_master_password("master".to_string(), "new_master".to_string())
+        .unwrap();
+    drop(vault);
+
+    assert_ne!(backend.load().unwrap().mac, before);
+    assert!(
+        VaultBuilder::new()
+            .backend(Box::new(backend.clone()))
+            .unlock("new_master".to_string())
+            .is_ok()
+    );
+}
+
+#[test]
+fn test_mac_key_differs_from_entry_keys() {
+    let master = [7u8; 32];
+    let mac_key = crypto::derive_mac_key(&master);
+    assert_ne!(mac_key, master);
+    assert_ne!(mac_key, crypto::derive_entry_key(&master, ""));
+
+    let tag = crypto::compute_mac(&mac_key, b"entries");
+    assert!(crypto::verify_mac(&mac_key, b"entries", &tag));
+    assert!(!crypto::verify_mac(&mac_key, b"entries!", &tag));
+}
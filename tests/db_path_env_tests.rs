@@ -0,0 +1,51 @@
+//! Database Path Override Tests
+//!
+//! Tests that `IRONKEY_DB_PATH` relocates the JSON database for every
+//! operation. The variable is process-wide, so this binary sets it once to a
+//! temporary directory and its tests must run serially.
+
+use ironkey::storage::{self, DB_PATH_ENV, JsonFileBackend, StorageBackend};
+use ironkey::vault::Vault;
+use std::fs;
+
+mod common;
+
+#[test]
+fn test_database_path_follows_env_var() {
+    common::use_isolated_database();
+    let expected = std::env::var_os(DB_PATH_ENV).unwrap();
+
+    assert_eq!(storage::get_database_path().unwrap(), expected);
+    assert_eq!(
+        JsonFileBackend::default_location()
+            .unwrap()
+            .sibling_path(""),
+        expected
+    );
+}
+
+#[test]
+fn test_vault_reads_and_writes_overridden_path() {
+    common::use_isolated_database();
+    let db_path = storage::get_database_path().unwrap();
+    let _ = fs::remove_file(&db_path);
+    assert!(!storage::exists().unwrap());
+
+    let mut vault = Vault::init("master".to_string()).unwrap();
+    vault
+        .create_entry("api".to_string(), "secret".to_string())
+        .unwrap();
+    assert!(db_path.exists());
+    assert!(storage::exists().unwrap());
+
+    let vault = Vault::unlock("master".to_string()).unwrap();
+    assert_eq!(vault.get_entry("api").unwrap(), "secret");
+    assert!(
+        JsonFileBackend::default_location()
+            .unwrap()
+            .exists()
+            .unwrap()
+    );
+
+    fs::remove_file(&db_path).unwrap();
+}
@@ -0,0 +1,64 @@
+//! Bulk Delete Tests
+//!
+//! Tests for `Vault::delete_matching` (`ik delete --search <pattern> --yes`).
+
+use ironkey::error::Error;
+use ironkey::storage::{MemoryBackend, StorageBackend};
+
+mod common;
+
+fn stored_keys(backend: &MemoryBackend) -> Vec<String> {
+    let mut keys: Vec<String> = backend.load().unwrap().entries.into_keys().collect();
+    keys.sort();
+    keys
+}
+
+#[test]
+fn test_deletes_matching_entries_case_insensitively() {
+    let backend = MemoryBackend::new();
+    let mut vault = common::vault_with_keys(&backend, &["test_a", "TEST_b", "github", "my_test"]);
+
+    let deleted = vault.delete_matching("test").unwrap();
+
+    assert_eq!(deleted, ["TEST_b", "my_test", "test_a"]);
+    assert_eq!(stored_keys(&backend), ["github"]);
+    assert!(matches!(
+        vault.get_entry("test_a"),
+        Err(Error::EntryNotFound(_))
+    ));
+}
+
+#[test]
+fn test_locked_entries_are_kept() {
+    let backend = MemoryBackend::new();
+    let mut vault = common::vault_with_keys(&backend, &["test_a", "test_locked"]);
+    vault.toggle_lock("test_locked").unwrap();
+
+    let deleted = vault.delete_matching("test").unwrap();
+
+    assert_eq!(deleted, ["test_a"]);
+    assert_eq!(stored_keys(&backend), ["test_locked"]);
+    let locked = vault.list_entries(Some("test"), Some(true)).unwrap();
+    assert_eq!(locked, [(&"test_locked".to_string(), true)]);
+}
+
+#[test]
+fn test_no_match_deletes_nothing() {
+    let backend = MemoryBackend::new();
+    let mut vault = common::vault_with_keys(&backend, &["github"]);
+
+    assert!(vault.delete_matching("aws").unwrap().is_empty());
+    assert_eq!(stored_keys(&backend), ["github"]);
+}
+
+#[test]
+fn test_empty_pattern_is_rejected() {
+    let backend = MemoryBackend::new();
+    let mut vault = common::vault_with_keys(&backend, &["github"]);
+
+    assert!(matches!(
+        vault.delete_matching(""),
+        Err(Error::InvalidInput(_))
+    ));
+    assert_eq!(stored_keys(&backend), ["github"]);
+}
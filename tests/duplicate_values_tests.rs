@@ -0,0 +1,61 @@
+//! Duplicate Value Tests
+//!
+//! Tests for `Vault::find_duplicates` (`ik audit --duplicates`).
+
+use ironkey::storage::MemoryBackend;
+
+mod common;
+
+#[test]
+fn test_groups_keys_with_identical_values() {
+    let vault = common::vault_with_entries(
+        &MemoryBackend::new(),
+        &[
+            ("github", "hunter2"),
+            ("email", "shared-secret"),
+            ("aws", "hunter2"),
+            ("bank", "shared-secret"),
+            ("gitlab", "hunter2"),
+            ("unique", "only-once"),
+        ],
+    );
+
+    assert_eq!(
+        vault.find_duplicates().unwrap(),
+        vec![
+            vec![
+                "aws".to_string(),
+                "github".to_string(),
+                "gitlab".to_string()
+            ],
+            vec!["bank".to_string(), "email".to_string()],
+        ]
+    );
+}
+
+#[test]
+fn test_no_duplicates() {
+    let vault = common::vault_with_entries(
+        &MemoryBackend::new(),
+        &[("a", "one"), ("b", "two"), ("c", "One")],
+    );
+    assert!(vault.find_duplicates().unwrap().is_empty());
+}
+
+#[test]
+fn test_locked_entries_are_not_checked() {
+    let mut vault = common::vault_with_entries(
+        &MemoryBackend::new(),
+        &[("a", "same"), ("b", "same"), ("c", "same")],
+    );
+    vault.toggle_lock("b").unwrap();
+    vault.toggle_lock("c").unwrap();
+
+    assert!(vault.find_duplicates().unwrap().is_empty());
+
+    vault.toggle_lock("c").unwrap();
+    assert_eq!(
+        vault.find_duplicates().unwrap(),
+        vec![vec!["a".to_string(), "c".to_string()]]
+    );
+}
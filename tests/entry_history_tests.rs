@@ -0,0 +1,205 @@
+//! Entry History Tests
+//!
+//! Tests for previous-value history (`ik history` / `ik revert`).
+
+use ironkey::error::Error;
+use ironkey::storage::{Entry, MAX_HISTORY, MemoryBackend, StorageBackend};
+use ironkey::vault::{Vault, VaultBuilder};
+
+mod common;
+
+fn history_values(vault: &Vault, key: &str) -> Vec<String> {
+    vault
+        .get_history(key)
+        .unwrap()
+        .into_iter()
+        .map(|item| item.value)
+        .collect()
+}
+
+#[test]
+fn test_update_keeps_previous_values_newest_first() {
+    let backend = MemoryBackend::new();
+    let mut vault = common::vault_with_entry(&backend, "aws", "v1");
+    assert!(vault.get_history("aws").unwrap().is_empty());
+
+    vault
+        .update_entry("aws".to_string(), "v2".to_string())
+        .unwrap();
+    vault
+        .update_entry("aws".to_string(), "v3".to_string())
+        .unwrap();
+
+    let history = common::reopen(&backend, "master")
+        .get_history("aws")
+        .unwrap();
+    let versions: Vec<(usize, &str)> = history
+        .iter()
+        .map(|item| (item.version, item.value.as_str()))
+        .collect();
+    assert_eq!(versions, vec![(1, "v2"), (2, "v1")]);
+    assert!(
+        history
+            .iter()
+            .all(|item| chrono::DateTime::parse_from_rfc3339(&item.replaced_at).is_ok())
+    );
+
+    let entry = &backend.load().unwrap().entries["aws"];
+    assert!(
+        entry
+            .history
+            .iter()
+            .all(|item| !item.ciphertext.contains("v1"))
+    );
+}
+
+#[test]
+fn test_history_is_capped() {
+    let backend = MemoryBackend::new();
+    let mut vault = common::vault_with_entry(&backend, "aws", "v1");
+    for n in 2..=MAX_HISTORY + 3 {
+        vault
+            .update_entry("aws".to_string(), format!("v{n}"))
+            .unwrap();
+    }
+
+    let values = history_values(&vault, "aws");
+    assert_eq!(values.len(), MAX_HISTORY);
+    assert_eq!(values[0], format!("v{}", MAX_HISTORY + 2));
+    assert_eq!(values[MAX_HISTORY - 1], "v3");
+}
+
+#[test]
+fn test_revert_restores_value_and_keeps_current_in_history() {
+    let backend = MemoryBackend::new();
+    let mut vault = common::vault_with_entry(&backend, "aws", "v1");
+    vault
+        .update_entry("aws".to_string(), "v2".to_string())
+        .unwrap();
+
+    vault.revert("aws", 1).unwrap();
+
+    let vault = common::reopen(&backend, "master");
+    assert_eq!(vault.get_entry("aws").unwrap(), "v1");
+    assert_eq!(history_values(&vault, "aws"), vec!["v2", "v1"]);
+}
+
+#[test]
+fn test_revert_rejects_unknown_version() {
+    let backend = MemoryBackend::new();
+    let mut vault = common::vault_with_entry(&backend, "aws", "v1");
+    vault
+        .update_entry("aws".to_string(), "v2".to_string())
+        .unwrap();
+
+    for version in [0, 2] {
+        assert!(matches!(
+            vault.revert("aws", version),
+            Err(Error::InvalidInput(_))
+        ));
+    }
+    assert_eq!(vault.get_entry("aws").unwrap(), "v2");
+}
+
+#[test]
+fn test_locked_entry_refuses_history_and_revert() {
+    let backend = MemoryBackend::new();
+    let mut vault = common::vault_with_entry(&backend, "aws", "v1");
+    vault
+        .update_entry("aws".to_string(), "v2".to_string())
+        .unwrap();
+    vault.toggle_lock("aws").unwrap();
+
+    assert!(matches!(
+        vault.get_history("aws"),
+        Err(Error::EntryLocked(_))
+    ));
+    assert!(matches!(vault.revert("aws", 1), Err(Error::EntryLocked(_))));
+    assert!(matches!(
+        vault.get_history("nope"),
+        Err(Error::EntryNotFound(_))
+    ));
+}
+
+#[test]
+fn test_history_survives_rename_and_password_change() {
+    let backend = MemoryBackend::new();
+    let mut vault = common::vault_with_entry(&backend, "aws", "v1");
+    vault
+        .update_entry("aws".to_string(), "v2".to_string())
+        .unwrap();
+
+    vault.rename_entry("aws", "aws_prod").unwrap();
+    vault
+        .change_master_password("master".to_string(), "new master".to_string())
+        .unwrap();
+    drop(vault);
+
+    let vault = common::reopen(&backend, "new master");
+    assert_eq!(history_values(&vault, "aws_prod"), vec!["v1"]);
+    assert!(vault.verify_integrity().unwrap().is_healthy());
+}
+
+#[test]
+fn test_entry_without_history_field_deserializes() {
+    let json = r#"{"encrypted_value":"AAAA","nonce":"AAAA","is_locked":false}"#;
+    let entry: Entry = serde_json::from_str(json).unwrap();
+    assert!(entry.history.is_empty());
+    assert!(!serde_json::to_string(&entry).unwrap().contains("history"));
+}
+
+#[test]
+fn test_replace_import_keeps_history_and_notes() {
+    let backend = MemoryBackend::new();
+    let mut vault = common::vault_with_entry(&backend, "aws", "v1");
+    vault
+        .update_entry("aws".to_string(), "v2".to_string())
+        .unwrap();
+    vault
+        .set_note("aws", "rotate quarterly".to_string())
+        .unwrap();
+    let created_at = vault.entry_metadata("aws").unwrap().created_at;
+
+    let mut source = VaultBuilder::new()
+        .backend(Box::new(MemoryBackend::new()))
+        .init("other".to_string())
+        .unwrap();
+    source
+        .create_entry("aws".to_string(), "v3".to_string())
+        .unwrap();
+    let mut buffer = Vec::new();
+    source
+        .export_to_writer(
+            &mut buffer,
+            "export".to_string(),
+            ironkey::export::ExportOptions::default(),
+            None,
+        )
+        .unwrap();
+
+    let result = vault
+        .import_from_reader(
+            buffer.as_slice(),
+            "export".to_string(),
+            false,
+            true,
+            false,
+            None,
+        )
+        .unwrap();
+    assert_eq!(result.updated, vec!["aws"]);
+
+    let vault = common::reopen(&backend, "master");
+    assert_eq!(vault.get_entry("aws").unwrap(), "v3");
+    assert_eq!(history_values(&vault, "aws"), vec!["v2", "v1"]);
+    assert_eq!(
+        vault.get_note("aws").unwrap().as_deref(),
+        Some("rotate quarterly")
+    );
+    assert_eq!(vault.entry_metadata("aws").unwrap().created_at, created_at);
+
+    // The import is reversible like any other update
+    let mut vault = vault;
+    vault.revert("aws", 1).unwrap();
+    assert_eq!(vault.get_entry("aws").unwrap(), "v2");
+}
@@ -0,0 +1,128 @@
+//! Per-Entry Key Derivation Tests
+//!
+//! Tests for HKDF-derived entry keys and the legacy single-key scheme.
+
+use ironkey::crypto::{self, EncryptedData};
+use ironkey::storage::{Entry, KeyScheme, MemoryBackend, StorageBackend};
+use ironkey::vault::VaultBuilder;
+
+fn stored_ciphertext(backend: &MemoryBackend, key: &str) -> EncryptedData {
+    let entry = backend.load().unwrap().entries[key].clone();
+    EncryptedData {
+        ciphertext: entry.get_encrypted_value().unwrap(),
+        nonce: entry.get_nonce().unwrap(),
+    }
+}
+
+fn master_key(backend: &MemoryBackend, password: &str) -> Vec<u8> {
+    let db = backend.load().unwrap();
+    crypto::derive_key(password, &db.get_salt().unwrap(), db.iterations).unwrap()
+}
+
+#[test]
+fn test_derive_entry_key_is_deterministic_and_distinct() {
+    let master = vec![7u8; 32];
+
+    let github = crypto::derive_entry_key(&master, "github");
+    assert_eq!(github.len(), 32);
+    assert_eq!(github, crypto::derive_entry_key(&master, "github"));
+
+    assert_ne!(github, crypto::derive_entry_key(&master, "aws"));
+    assert_ne!(github, master);
+    assert_ne!(github, crypto::derive_entry_key(&[8u8; 32], "github"));
+}
+
+#[test]
+fn test_new_vault_uses_hkdf_and_roundtrips() {
+    let backend = MemoryBackend::new();
+    let mut vault = VaultBuilder::new()
+        .backend(Box::new(backend.clone()))
+        .init("master".to_string())
+        .unwrap();
+    assert_eq!(backend.load().unwrap().key_scheme, KeyScheme::Hkdf);
+
+    vault
+        .create_entry("github".to_string(), "ghp_token".to_string())
+        .unwrap();
+    vault
+        .update_entry("github".to_string(), "ghp_new".to_string())
+        .unwrap();
+    drop(vault);
+
+    let vault = VaultBuilder::new()
+        .backend(Box::new(backend.clone()))
+        .unlock("master".to_string())
+        .unwrap();
+    assert_eq!(vault.get_entry("github").unwrap(), "ghp_new");
+
+    // Stored under the derived key, not the master key
+    let master = master_key(&backend, "master");
+    let encrypted = stored_ciphertext(&backend, "github");
+    assert!(crypto::decrypt(&encrypted, &master).is_err());
+    let entry_key = crypto::derive_entry_key(&master, "github");
+    assert_eq!(crypto::decrypt(&encrypted, &entry_key).unwrap(), b"ghp_new");
+}
+
+#[test]
+fn test_same_value_under_two_keys_is_unrelated() {
+    let backend = MemoryBackend::new();
+    let mut vault = VaultBuilder::new()
+        .backend(Box::new(backend.clone()))
+        .init("master".to_string())
+        .unwrap();
+    vault
+        .create_entry("first".to_string(), "shared".to_string())
+        .unwrap();
+    vault
+        .create_entry("second".to_string(), "shared".to_string())
+        .unwrap();
+
+    let first = stored_ciphertext(&backend, "first");
+    let second = stored_ciphertext(&backend, "second");
+    assert_ne!(first.ciphertext, second.ciphertext);
+
+    // Neither entry's key opens the other entry
+    let master = master_key(&backend, "master");
+    let first_key = crypto::derive_entry_key(&master, "first");
+    let second_key = crypto::derive_entry_key(&master, "second");
+    assert!(crypto::decrypt(&second, &first_key).is_err());
+    assert!(crypto::decrypt(&first, &second_key).is_err());
+}
+
+#[test]
+fn test_legacy_database_still_decrypts() {
+    let backend = MemoryBackend::new();
+    drop(
+        VaultBuilder::new()
+            .backend(Box::new(backend.clone()))
+            .init("master".to_string())
+            .unwrap(),
+    );
+
+    // Rewrite the database as a pre-HKDF vault: one entry under the raw master key
+    let master = master_key(&backend, "master");
+    let mut db = backend.load().unwrap();
+    db.key_scheme = KeyScheme::Legacy;
+    db.mac = None;
+    let encrypted = crypto::encrypt(b"old_secret", &master).unwrap();
+    db.entries.insert(
+        "old".to_string(),
+        Entry::new(encrypted.ciphertext, encrypted.nonce, false),
+    );
+    backend.save(&db).unwrap();
+    assert!(!serde_json::to_string(&db).unwrap().contains("key_scheme"));
+
+    let mut vault = VaultBuilder::new()
+        .backend(Box::new(backend.clone()))
+        .unlock("master".to_string())
+        .unwrap();
+    assert_eq!(vault.get_entry("old").unwrap(), "old_secret");
+
+    // New entries in a legacy vault keep using the legacy scheme
+    vault
+        .create_entry("new".to_string(), "new_secret".to_string())
+        .unwrap();
+    assert_eq!(backend.load().unwrap().key_scheme, KeyScheme::Legacy);
+    let stored = stored_ciphertext(&backend, "new");
+    assert_eq!(crypto::decrypt(&stored, &master).unwrap(), b"new_secret");
+}
@@ -0,0 +1,102 @@
+//! Entry Label Tests
+//!
+//! Tests for plaintext labels, which stay readable when an entry is locked.
+//! NOTE: These tests must run serially because they share the same database file.
+
+use ironkey::error::Error;
+use ironkey::storage;
+use ironkey::vault::Vault;
+use std::fs;
+use tempfile::TempDir;
+
+mod common;
+
+fn cleanup() {
+    common::use_isolated_database();
+    if let Ok(db_path) = storage::get_database_path() {
+        let _ = fs::remove_file(db_path);
+    }
+}
+
+#[test]
+fn test_locked_entry_label_is_readable() {
+    cleanup();
+    let mut vault = Vault::init("master".to_string()).unwrap();
+    vault
+        .create_entry("prod_key".to_string(), "secret".to_string())
+        .unwrap();
+    vault
+        .set_label("prod_key", Some("old prod key, do not delete".to_string()))
+        .unwrap();
+    vault.toggle_lock("prod_key").unwrap();
+
+    assert_eq!(
+        vault.get_label("prod_key").unwrap(),
+        Some("old prod key, do not delete")
+    );
+    assert!(matches!(
+        vault.get_entry("prod_key"),
+        Err(Error::EntryLocked(_))
+    ));
+
+    // Labels can still be changed while locked, and appear in summaries
+    vault
+        .set_label("prod_key", Some("retired".to_string()))
+        .unwrap();
+    let summaries = vault.entry_summaries(None, None).unwrap();
+    assert_eq!(summaries[0].label.as_deref(), Some("retired"));
+
+    cleanup();
+}
+
+#[test]
+fn test_label_survives_update_and_can_be_cleared() {
+    cleanup();
+    let mut vault = Vault::init("master".to_string()).unwrap();
+    vault
+        .create_entry("api".to_string(), "v1".to_string())
+        .unwrap();
+    vault.set_label("api", Some("staging".to_string())).unwrap();
+
+    vault
+        .update_entry("api".to_string(), "v2".to_string())
+        .unwrap();
+    assert_eq!(vault.get_label("api").unwrap(), Some("staging"));
+
+    vault.set_label("api", None).unwrap();
+    assert_eq!(vault.get_label("api").unwrap(), None);
+    assert!(matches!(
+        vault.get_label("missing"),
+        Err(Error::EntryNotFound(_))
+    ));
+
+    cleanup();
+}
+
+#[test]
+fn test_label_preserved_through_export_import() {
+    let temp_dir = TempDir::new().unwrap();
+    let export_path = temp_dir.path().join("labels.ik");
+
+    cleanup();
+    let mut vault = Vault::init("source".to_string()).unwrap();
+    vault
+        .create_entry("db".to_string(), "pw".to_string())
+        .unwrap();
+    vault
+        .set_label("db", Some("replica only".to_string()))
+        .unwrap();
+    vault
+        .export_to_file(&export_path, "export_pw".to_string())
+        .unwrap();
+    drop(vault);
+
+    cleanup();
+    let mut vault = Vault::init("dest".to_string()).unwrap();
+    vault
+        .import_from_file(&export_path, "export_pw".to_string(), true, false, false)
+        .unwrap();
+    assert_eq!(vault.get_label("db").unwrap(), Some("replica only"));
+
+    cleanup();
+}
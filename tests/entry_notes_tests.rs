@@ -0,0 +1,110 @@
+//! Entry Notes Tests
+//!
+//! Tests for encrypted per-entry notes (`ik note --key <k> --set/--get`).
+
+use ironkey::error::Error;
+use ironkey::storage::{Entry, MemoryBackend, StorageBackend};
+
+mod common;
+
+#[test]
+fn test_note_roundtrip_is_encrypted() {
+    let backend = MemoryBackend::new();
+    let mut vault = common::vault_with_entry(&backend, "aws", "AKIA123");
+    assert_eq!(vault.get_note("aws").unwrap(), None);
+
+    vault
+        .set_note("aws", "rotate quarterly".to_string())
+        .unwrap();
+    assert_eq!(
+        common::reopen(&backend, "master")
+            .get_note("aws")
+            .unwrap()
+            .as_deref(),
+        Some("rotate quarterly")
+    );
+
+    let entry = &backend.load().unwrap().entries["aws"];
+    let notes = entry.notes.as_ref().unwrap();
+    assert!(!notes.ciphertext.contains("rotate"));
+    assert_ne!(notes.nonce, entry.nonce);
+}
+
+#[test]
+fn test_empty_note_clears() {
+    let backend = MemoryBackend::new();
+    let mut vault = common::vault_with_entry(&backend, "aws", "AKIA123");
+    vault.set_note("aws", "temporary".to_string()).unwrap();
+    vault.set_note("aws", "  ".to_string()).unwrap();
+
+    assert_eq!(vault.get_note("aws").unwrap(), None);
+    assert!(backend.load().unwrap().entries["aws"].notes.is_none());
+}
+
+#[test]
+fn test_locked_entry_refuses_notes() {
+    let backend = MemoryBackend::new();
+    let mut vault = common::vault_with_entry(&backend, "aws", "AKIA123");
+    vault.set_note("aws", "recovery url".to_string()).unwrap();
+    vault.toggle_lock("aws").unwrap();
+
+    assert!(matches!(vault.get_note("aws"), Err(Error::EntryLocked(_))));
+    assert!(matches!(
+        vault.set_note("aws", "changed".to_string()),
+        Err(Error::EntryLocked(_))
+    ));
+
+    vault.toggle_lock("aws").unwrap();
+    assert_eq!(
+        vault.get_note("aws").unwrap().as_deref(),
+        Some("recovery url")
+    );
+}
+
+#[test]
+fn test_missing_entry_note() {
+    let backend = MemoryBackend::new();
+    let mut vault = common::vault_with_entry(&backend, "aws", "AKIA123");
+
+    assert!(matches!(
+        vault.get_note("nope"),
+        Err(Error::EntryNotFound(_))
+    ));
+    assert!(matches!(
+        vault.set_note("nope", "x".to_string()),
+        Err(Error::EntryNotFound(_))
+    ));
+}
+
+#[test]
+fn test_note_survives_update_rename_and_password_change() {
+    let backend = MemoryBackend::new();
+    let mut vault = common::vault_with_entry(&backend, "aws", "AKIA123");
+    vault
+        .set_note("aws", "rotate quarterly".to_string())
+        .unwrap();
+
+    vault
+        .update_entry("aws".to_string(), "AKIA456".to_string())
+        .unwrap();
+    vault.rename_entry("aws", "aws_prod").unwrap();
+    vault
+        .change_master_password("master".to_string(), "new master".to_string())
+        .unwrap();
+    drop(vault);
+
+    let vault = common::reopen(&backend, "new master");
+    assert_eq!(vault.get_entry("aws_prod").unwrap(), "AKIA456");
+    assert_eq!(
+        vault.get_note("aws_prod").unwrap().as_deref(),
+        Some("rotate quarterly")
+    );
+}
+
+#[test]
+fn test_entry_without_notes_field_deserializes() {
+    let json = r#"{"encrypted_value":"AAAA","nonce":"AAAA","is_locked":false}"#;
+    let entry: Entry = serde_json::from_str(json).unwrap();
+    assert!(entry.notes.is_none());
+    assert!(!serde_json::to_string(&entry).unwrap().contains("notes"));
+}
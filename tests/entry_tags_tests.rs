@@ -0,0 +1,132 @@
+//! Entry Tag Tests
+//!
+//! Tests for plaintext entry tags (`ik tag`, `ik list --tag`) and their
+//! export/import round trip.
+
+use ironkey::error::Error;
+use ironkey::export::ExportFile;
+use ironkey::storage::{Entry, MemoryBackend, StorageBackend};
+use ironkey::vault::Vault;
+use std::fs;
+use tempfile::TempDir;
+
+mod common;
+
+fn listed(vault: &Vault, tag: Option<&str>) -> Vec<String> {
+    vault
+        .list_entries_matching(None, None, tag)
+        .unwrap()
+        .into_iter()
+        .map(|(key, _)| key.clone())
+        .collect()
+}
+
+#[test]
+fn test_add_and_remove_tags() {
+    let backend = MemoryBackend::new();
+    let mut vault = common::vault_with_keys(&backend, &["github"]);
+
+    assert!(vault.add_tag("github", "work").unwrap());
+    assert!(vault.add_tag("github", " personal ").unwrap());
+    assert!(!vault.add_tag("github", "work").unwrap());
+    assert_eq!(
+        backend.load().unwrap().entries["github"].tags,
+        ["personal", "work"]
+    );
+
+    assert!(vault.remove_tag("github", "personal").unwrap());
+    assert!(!vault.remove_tag("github", "personal").unwrap());
+    assert_eq!(vault.entry_metadata("github").unwrap().tags, ["work"]);
+}
+
+#[test]
+fn test_invalid_tags_and_missing_entries() {
+    let backend = MemoryBackend::new();
+    let mut vault = common::vault_with_keys(&backend, &["github"]);
+
+    for tag in ["", "   ", "two words"] {
+        assert!(matches!(
+            vault.add_tag("github", tag),
+            Err(Error::InvalidInput(_))
+        ));
+    }
+    assert!(matches!(
+        vault.add_tag("nope", "work"),
+        Err(Error::EntryNotFound(_))
+    ));
+    assert!(matches!(
+        vault.remove_tag("nope", "work"),
+        Err(Error::EntryNotFound(_))
+    ));
+}
+
+#[test]
+fn test_list_filters_by_tag() {
+    let backend = MemoryBackend::new();
+    let mut vault = common::vault_with_keys(&backend, &["aws", "github", "netflix"]);
+    vault.add_tag("aws", "work").unwrap();
+    vault.add_tag("github", "work").unwrap();
+    vault.add_tag("netflix", "home").unwrap();
+    vault.toggle_lock("github").unwrap();
+
+    assert_eq!(listed(&vault, Some("work")), ["aws", "github"]);
+    assert_eq!(listed(&vault, Some("home")), ["netflix"]);
+    assert!(listed(&vault, Some("Work")).is_empty());
+    assert_eq!(listed(&vault, None).len(), 3);
+
+    let unlocked_work = vault
+        .list_entries_matching(None, Some(false), Some("work"))
+        .unwrap();
+    assert_eq!(unlocked_work.len(), 1);
+    assert_eq!(unlocked_work[0].0, "aws");
+}
+
+#[test]
+fn test_tags_kept_on_locked_entry_and_update() {
+    let backend = MemoryBackend::new();
+    let mut vault = common::vault_with_keys(&backend, &["aws"]);
+    vault.toggle_lock("aws").unwrap();
+    vault.add_tag("aws", "work").unwrap();
+    vault.toggle_lock("aws").unwrap();
+
+    vault
+        .update_entry("aws".to_string(), "rotated".to_string())
+        .unwrap();
+    assert_eq!(vault.entry_metadata("aws").unwrap().tags, ["work"]);
+}
+
+#[test]
+fn test_tags_survive_export_import() {
+    let temp_dir = TempDir::new().unwrap();
+    let export_path = temp_dir.path().join("tagged.ik");
+
+    let mut vault = common::vault_with_keys(&MemoryBackend::new(), &["aws", "github"]);
+    vault.add_tag("aws", "work").unwrap();
+    vault.add_tag("aws", "cloud").unwrap();
+    vault.add_tag("github", "work").unwrap();
+    vault
+        .export_to_file(&export_path, "export_pw".to_string())
+        .unwrap();
+
+    let export: ExportFile =
+        serde_json::from_str(&fs::read_to_string(&export_path).unwrap()).unwrap();
+    assert_eq!(export.metadata.tags.unwrap(), ["cloud", "work"]);
+
+    let mut target = common::vault_with_keys(&MemoryBackend::new(), &[]);
+    target
+        .import_from_file(&export_path, "export_pw".to_string(), true, false, false)
+        .unwrap();
+    assert_eq!(
+        target.entry_metadata("aws").unwrap().tags,
+        ["cloud", "work"]
+    );
+    assert_eq!(target.entry_metadata("github").unwrap().tags, ["work"]);
+}
+
+#[test]
+fn test_entry_without_tags_field_deserializes() {
+    let json = r#"{"encrypted_value":"AAAA","nonce":"AAAA","is_locked":false}"#;
+    let entry: Entry = serde_json::from_str(json).unwrap();
+    assert!(entry.tags.is_empty());
+    assert!(!serde_json::to_string(&entry).unwrap().contains("tags"));
+}
@@ -0,0 +1,200 @@
+//! Entry Timestamp Tests
+//!
+//! Tests for per-entry `created_at`/`updated_at` and `Vault::entry_metadata` (`ik info`).
+
+use chrono::DateTime;
+use ironkey::error::Error;
+use ironkey::storage::{Entry, MemoryBackend, StorageBackend};
+use ironkey::vault::{Vault, VaultBuilder};
+
+fn new_vault(backend: &MemoryBackend) -> Vault {
+    VaultBuilder::new()
+        .backend(Box::new(backend.clone()))
+        .init("master".to_string())
+        .unwrap()
+}
+
+#[test]
+fn test_create_sets_both_timestamps() {
+    let backend = MemoryBackend::new();
+    let mut vault = new_vault(&backend);
+    vault
+        .create_entry("github".to_string(), "ghp_token".to_string())
+        .unwrap();
+
+    let info = vault.entry_metadata("github").unwrap();
+    let created = info.created_at.unwrap();
+    assert!(DateTime::parse_from_rfc3339(&created).is_ok());
+    assert_eq!(info.updated_at.as_deref(), Some(created.as_str()));
+
+    // Persisted with the entry
+    let stored = &backend.load().unwrap().entries["github"];
+    assert_eq!(stored.created_at, created);
+    assert_eq!(stored.updated_at, created);
+}
+
+#[test]
+fn test_update_keeps_created_and_refreshes_updated() {
+    let backend = MemoryBackend::new();
+    let mut vault = new_vault(&backend);
+    vault
+        .create_entry("github".to_string(), "ghp_token".to_string())
+        .unwrap();
+
+    // Backdate both so the refresh is observable (hand edits drop the MAC, as in an old vault)
+    let mut db = backend.load().unwrap();
+    db.mac = None;
+    let old = "2020-01-01T00:00:00+00:00".to_string();
+    let entry = db.entries.get_mut("github").unwrap();
+    entry.created_at = old.clone();
+    entry.updated_at = old.clone();
+    backend.save(&db).unwrap();
+    drop(vault);
+
+    let mut vault = VaultBuilder::new()
+        .backend(Box::new(backend))
+        .unlock("master".to_string())
+        .unwrap();
+    vault
+        .update_entry("github".to_string(), "ghp_new".to_string())
+        .unwrap();
+
+    let info = vault.entry_metadata("github").unwrap();
+    assert_eq!(info.created_at.as_deref(), Some(old.as_str()));
+    let updated = DateTime::parse_from_rfc3339(info.updated_at.as_deref().unwrap()).unwrap();
+    assert!(updated > DateTime::parse_from_rfc3339(&old).unwrap());
+}
+
+#[test]
+fn test_old_entries_have_no_timestamps() {
+    let json = r#"{
+        "encrypted_value": "AQID",
+        "nonce": "BAUG",
+        "is_locked": false
+    }"#;
+    let entry: Entry = serde_json::from_str(json).unwrap();
+    assert!(entry.created_at.is_empty());
+    assert!(entry.updated_at.is_empty());
+
+    // Untracked entries serialize exactly as before
+    let serialized = serde_json::to_string(&entry).unwrap();
+    assert!(!serialized.contains("created_at"));
+    assert!(!serialized.contains("updated_at"));
+}
+
+#[test]
+fn test_metadata_for_untracked_entry_is_none() {
+    let backend = MemoryBackend::new();
+    let mut vault = new_vault(&backend);
+    vault
+        .create_entry("github".to_string(), "ghp_token".to_string())
+        .unwrap();
+
+    let mut db = backend.load().unwrap();
+    let entry = db.entries.get_mut("github").unwrap();
+    entry.created_at.clear();
+    entry.updated_at.clear();
+    db.mac = None;
+    backend.save(&db).unwrap();
+    drop(vault);
+
+    let vault = VaultBuilder::new()
+        .backend(Box::new(backend))
+        .unlock("master".to_string())
+        .unwrap();
+    let info = vault.entry_metadata("github").unwrap();
+    assert_eq!(info.created_at, None);
+    assert_eq!(info.updated_at, None);
+}
+
+#[test]
+fn test_metadata_readable_when_locked() {
+    let backend = MemoryBackend::new();
+    let mut vault = new_vault(&backend);
+    vault
+        .create_entry("github".to_string(), "ghp_token".to_string())
+        .unwrap();
+    vault.toggle_lock("github").unwrap();
+
+    let info = vault.entry_metadata("github").unwrap();
+    assert!(info.is_locked);
+    assert!(info.created_at.is_some());
+}
+
+#[test]
+fn test_metadata_missing_entry() {
+    let backend = MemoryBackend::new();
+    let vault = new_vault(&backend);
+
+    assert!(matches!(
+        vault.entry_metadata("missing"),
+        Err(Error::EntryNotFound(_))
+    ));
+}
+
+/// Export `entries` from a fresh vault as .ik bytes (export password "export")
+fn export_of(entries: &[(&str, &str)]) -> Vec<u8> {
+    let mut source = new_vault(&MemoryBackend::new());
+    for (key, value) in entries {
+        source
+            .create_entry(key.to_string(), value.to_string())
+            .unwrap();
+    }
+    let mut buffer = Vec::new();
+    source
+        .export_to_writer(
+            &mut buffer,
+            "export".to_string(),
+            ironkey::export::ExportOptions::default(),
+            None,
+        )
+        .unwrap();
+    buffer
+}
+
+#[test]
+fn test_import_timestamps() {
+    let backend = MemoryBackend::new();
+    let mut vault = new_vault(&backend);
+    vault
+        .create_entry("github".to_string(), "ghp_token".to_string())
+        .unwrap();
+
+    // Backdate the existing entry (hand edits drop the MAC, as in an old vault)
+    let mut db = backend.load().unwrap();
+    db.mac = None;
+    let old = "2020-01-01T00:00:00+00:00".to_string();
+    let entry = db.entries.get_mut("github").unwrap();
+    entry.created_at = old.clone();
+    entry.updated_at = old.clone();
+    backend.save(&db).unwrap();
+    drop(vault);
+
+    let mut vault = VaultBuilder::new()
+        .backend(Box::new(backend.clone()))
+        .unlock("master".to_string())
+        .unwrap();
+    let export = export_of(&[("github", "ghp_new"), ("aws", "aws_secret")]);
+    vault
+        .import_from_reader(
+            export.as_slice(),
+            "export".to_string(),
+            false,
+            true,
+            false,
+            None,
+        )
+        .unwrap();
+
+    // Added: both set to the import time
+    let added = vault.entry_metadata("aws").unwrap();
+    let created = added.created_at.unwrap();
+    assert!(DateTime::parse_from_rfc3339(&created).is_ok());
+    assert_eq!(added.updated_at.as_deref(), Some(created.as_str()));
+
+    // Replaced: creation time kept, update time refreshed
+    let replaced = vault.entry_metadata("github").unwrap();
+    assert_eq!(replaced.created_at.as_deref(), Some(old.as_str()));
+    let updated = DateTime::parse_from_rfc3339(replaced.updated_at.as_deref().unwrap()).unwrap();
+    assert!(updated > DateTime::parse_from_rfc3339(&old).unwrap());
+}
@@ -0,0 +1,144 @@
+//! Error Classification Tests
+//!
+//! Tests for `Error::is_retryable()`, `Error::is_user_error()`, `Error::exit_code()`
+//! and `Error::kind_str()`.
+
+use ironkey::crypto;
+use ironkey::error::Error;
+use ironkey::storage::MemoryBackend;
+use ironkey::vault::VaultBuilder;
+
+#[test]
+fn test_transient_errors_are_retryable() {
+    assert!(Error::Io("Failed to access clipboard: busy".to_string()).is_retryable());
+    assert!(Error::DatabaseSaveFailed("disk full".to_string()).is_retryable());
+}
+
+#[test]
+fn test_permanent_errors_are_not_retryable() {
+    let permanent = [
+        Error::EntryNotFound("github".to_string()),
+        Error::EntryAlreadyExists("github".to_string()),
+        Error::EntryLocked("github".to_string()),
+        Error::InvalidMasterPassword,
+        Error::EmptyPassword,
+        Error::KeyMismatch,
+        Error::DecryptionFailed("bad tag".to_string()),
+        Error::DatabaseNotFound,
+        Error::DatabaseLoadFailed("expected value".to_string()),
+        Error::InvalidInput("bad key".to_string()),
+    ];
+    for err in permanent {
+        assert!(!err.is_retryable(), "{err:?} should not be retryable");
+    }
+}
+
+#[test]
+fn test_user_errors() {
+    let user = [
+        Error::EntryNotFound("github".to_string()),
+        Error::EntryAlreadyExists("github".to_string()),
+        Error::EntryLocked("github".to_string()),
+        Error::InvalidMasterPassword,
+        Error::MasterKeyAlreadyExists,
+        Error::EmptyPassword,
+        Error::DatabaseNotFound,
+        Error::InvalidInput("bad key".to_string()),
+    ];
+    for err in user {
+        assert!(err.is_user_error(), "{err:?} should be a user error");
+    }
+}
+
+#[test]
+fn test_system_errors() {
+    let system = [
+        Error::Io("permission denied".to_string()),
+        Error::KeyMismatch,
+        Error::EncryptionFailed("rng".to_string()),
+        Error::DecryptionFailed("bad tag".to_string()),
+        Error::KeyDerivationFailed("oom".to_string()),
+        Error::DatabaseLoadFailed("expected value".to_string()),
+        Error::DatabaseSaveFailed("disk full".to_string()),
+    ];
+    for err in system {
+        assert!(!err.is_user_error(), "{err:?} should not be a user error");
+    }
+}
+
+#[test]
+fn test_errors_from_real_operations() {
+    let mut vault = VaultBuilder::new()
+        .backend(Box::new(MemoryBackend::new()))
+        .init("master".to_string())
+        .unwrap();
+    vault
+        .create_entry("github".to_string(), "ghp_token".to_string())
+        .unwrap();
+
+    let missing = vault.get_entry("nope").unwrap_err();
+    assert!(missing.is_user_error());
+    assert!(!missing.is_retryable());
+
+    let duplicate = vault
+        .create_entry("github".to_string(), "other".to_string())
+        .unwrap_err();
+    assert!(duplicate.is_user_error());
+
+    let too_long = "a".repeat(crypto::MAX_PASSWORD_LENGTH + 1);
+    let err = crypto::verify_password(&too_long, &[0u8; 32], &[0u8; 32], 1).unwrap_err();
+    assert!(err.is_user_error());
+    assert!(!err.is_retryable());
+
+    let io: Error = std::io::Error::other("interrupted").into();
+    assert!(io.is_retryable());
+    assert!(!io.is_user_error());
+}
+
+#[test]
+fn test_exit_codes_per_category() {
+    assert_eq!(Error::InvalidMasterPassword.exit_code(), 2);
+    assert_eq!(Error::EntryNotFound("github".to_string()).exit_code(), 3);
+    assert_eq!(Error::EntryLocked("github".to_string()).exit_code(), 4);
+    assert_eq!(Error::Io("disk full".to_string()).exit_code(), 5);
+}
+
+#[test]
+fn test_unmapped_errors_exit_with_one() {
+    let unmapped = [
+        Error::EntryAlreadyExists("github".to_string()),
+        Error::EmptyPassword,
+        Error::KeyMismatch,
+        Error::DatabaseNotFound,
+        Error::DatabaseSaveFailed("disk full".to_string()),
+        Error::InvalidInput("bad key".to_string()),
+    ];
+    for err in unmapped {
+        assert_eq!(err.exit_code(), 1, "{err:?} should exit with 1");
+    }
+}
+
+#[test]
+fn test_kind_str_names_the_variant() {
+    assert_eq!(
+        Error::EntryNotFound("github".to_string()).kind_str(),
+        "EntryNotFound"
+    );
+    assert_eq!(
+        Error::InvalidMasterPassword.kind_str(),
+        "InvalidMasterPassword"
+    );
+    assert_eq!(Error::Io("disk full".to_string()).kind_str(), "Io");
+    assert_eq!(
+        Error::InvalidInput("bad key".to_string()).kind_str(),
+        "InvalidInput"
+    );
+}
+
+#[test]
+fn test_kind_str_ignores_message() {
+    assert_eq!(
+        Error::DecryptionFailed("bad tag".to_string()).kind_str(),
+        Error::DecryptionFailed("truncated".to_string()).kind_str()
+    );
+}
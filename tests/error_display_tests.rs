@@ -0,0 +1,106 @@
+//! Error Display Tests
+//!
+//! Tests that every `Error` variant has a Display message that mentions its
+//! details. `detail` matches on every variant without a wildcard, so adding a
+//! variant without extending this test fails to compile.
+
+use ironkey::error::Error;
+use std::collections::HashSet;
+
+/// The detail a variant carries, which its message should mention
+fn detail(error: &Error) -> Option<&str> {
+    match error {
+        Error::EntryNotFound(detail)
+        | Error::EntryAlreadyExists(detail)
+        | Error::EntryLocked(detail)
+        | Error::EncryptionFailed(detail)
+        | Error::DecryptionFailed(detail)
+        | Error::KeyDerivationFailed(detail)
+        | Error::DatabaseLoadFailed(detail)
+        | Error::DatabaseSaveFailed(detail)
+        | Error::Io(detail)
+        | Error::InvalidInput(detail) => Some(detail),
+
+        Error::InvalidMasterPassword
+        | Error::MasterKeyNotInitialized
+        | Error::MasterKeyAlreadyExists
+        | Error::EmptyPassword
+        | Error::KeyMismatch
+        | Error::DatabaseNotFound
+        | Error::IntegrityCheckFailed => None,
+    }
+}
+
+/// One value of every variant
+fn every_variant() -> Vec<Error> {
+    vec![
+        Error::EntryNotFound("github".to_string()),
+        Error::EntryAlreadyExists("github".to_string()),
+        Error::EntryLocked("github".to_string()),
+        Error::InvalidMasterPassword,
+        Error::MasterKeyNotInitialized,
+        Error::MasterKeyAlreadyExists,
+        Error::EmptyPassword,
+        Error::KeyMismatch,
+        Error::EncryptionFailed("seal failed".to_string()),
+        Error::DecryptionFailed("bad tag".to_string()),
+        Error::KeyDerivationFailed("out of memory".to_string()),
+        Error::DatabaseNotFound,
+        Error::DatabaseLoadFailed("expected value".to_string()),
+        Error::DatabaseSaveFailed("disk full".to_string()),
+        Error::IntegrityCheckFailed,
+        Error::Io("permission denied".to_string()),
+        Error::InvalidInput("length must be at least 4".to_string()),
+    ]
+}
+
+#[test]
+fn test_every_variant_has_a_message() {
+    for error in every_variant() {
+        let message = error.to_string();
+        assert!(!message.trim().is_empty(), "{error:?} has an empty message");
+
+        if let Some(detail) = detail(&error) {
+            assert!(
+                message.contains(detail),
+                "{error:?} message '{message}' doesn't mention '{detail}'"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_every_variant_is_listed_once() {
+    let kinds: HashSet<&str> = every_variant().iter().map(Error::kind_str).collect();
+    assert_eq!(kinds.len(), every_variant().len());
+}
+
+#[test]
+fn test_invalid_input_message() {
+    let error = Error::InvalidInput("length must be at least 4".to_string());
+    assert!(
+        error
+            .to_string()
+            .ends_with("Invalid input: length must be at least 4")
+    );
+}
+
+#[test]
+fn test_generator_errors_display() {
+    let error = ironkey::password_generator::generate_with(
+        0,
+        &ironkey::password_generator::GenerateOptions {
+            lowercase: true,
+            uppercase: true,
+            numbers: true,
+            symbols: true,
+            exclude_ambiguous: false,
+            custom_symbols: None,
+            enforce_classes: true,
+        },
+    )
+    .unwrap_err();
+
+    assert_eq!(error.kind_str(), "InvalidInput");
+    assert!(error.to_string().contains("Invalid input"));
+}
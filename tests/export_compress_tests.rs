@@ -0,0 +1,132 @@
+//! Compressed Export Tests
+//!
+//! Tests for `ik export --compress`, which gzips the entries before encrypting them.
+
+use ironkey::export::{ExportFile, ExportOptions};
+use ironkey::storage::MemoryBackend;
+use ironkey::vault::{Vault, VaultBuilder};
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+fn new_vault(master: &str) -> Vault {
+    VaultBuilder::new()
+        .backend(Box::new(MemoryBackend::new()))
+        .init(master.to_string())
+        .unwrap()
+}
+
+/// A vault of long, note-like values (compressible like real notes)
+fn vault_with_notes() -> Vault {
+    let mut vault = new_vault("source");
+    for i in 0..50 {
+        let value = format!("note {i}: ").repeat(40);
+        vault.create_entry(format!("note_{i:02}"), value).unwrap();
+    }
+    vault.toggle_lock("note_07").unwrap();
+    vault
+}
+
+fn export(vault: &Vault, path: &Path, options: ExportOptions) {
+    vault
+        .export_to_file_with_options(path, "export_pw".to_string(), false, options, None)
+        .unwrap();
+}
+
+fn read_export(path: &Path) -> ExportFile {
+    serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap()
+}
+
+#[test]
+fn test_compressed_export_round_trips() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("compressed.ik");
+    let mut source = vault_with_notes();
+    let options = ExportOptions {
+        compress: true,
+        ..Default::default()
+    };
+
+    export(&source, &path, options);
+    assert!(read_export(&path).encryption.compressed);
+
+    let mut dest = new_vault("dest");
+    let result = dest
+        .import_from_file(&path, "export_pw".to_string(), true, false, false)
+        .unwrap();
+    assert_eq!(result.added.len(), 50);
+
+    source.toggle_lock("note_07").unwrap();
+    dest.toggle_lock("note_07").unwrap();
+    for i in 0..50 {
+        let key = format!("note_{i:02}");
+        assert_eq!(
+            dest.get_entry(&key).unwrap(),
+            source.get_entry(&key).unwrap()
+        );
+    }
+}
+
+#[test]
+fn test_compressed_dedup_export_round_trips() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("compressed_dedup.ik");
+    let mut source = new_vault("source");
+    for key in ["a", "b", "c"] {
+        source
+            .create_entry(key.to_string(), "shared".to_string())
+            .unwrap();
+    }
+    let options = ExportOptions {
+        compress: true,
+        dedup: true,
+        ..Default::default()
+    };
+
+    export(&source, &path, options);
+
+    let mut dest = new_vault("dest");
+    dest.import_from_file(&path, "export_pw".to_string(), true, false, false)
+        .unwrap();
+    for key in ["a", "b", "c"] {
+        assert_eq!(dest.get_entry(key).unwrap(), "shared");
+    }
+}
+
+#[test]
+fn test_compression_shrinks_large_exports() {
+    let temp_dir = TempDir::new().unwrap();
+    let plain_path = temp_dir.path().join("plain.ik");
+    let compressed_path = temp_dir.path().join("compressed.ik");
+    let vault = vault_with_notes();
+
+    export(&vault, &plain_path, ExportOptions::default());
+    export(
+        &vault,
+        &compressed_path,
+        ExportOptions {
+            compress: true,
+            ..Default::default()
+        },
+    );
+
+    let plain = fs::metadata(&plain_path).unwrap().len();
+    let compressed = fs::metadata(&compressed_path).unwrap().len();
+    assert!(compressed * 4 < plain, "{compressed} vs {plain} bytes");
+}
+
+#[test]
+fn test_uncompressed_export_has_no_flag() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("plain.ik");
+    let mut vault = new_vault("source");
+    vault
+        .create_entry("github".to_string(), "token".to_string())
+        .unwrap();
+
+    export(&vault, &path, ExportOptions::default());
+
+    // Files stay readable by ironkey versions that predate --compress
+    assert!(!fs::read_to_string(&path).unwrap().contains("compressed"));
+    assert!(!read_export(&path).encryption.compressed);
+}
@@ -0,0 +1,145 @@
+//! Plaintext CSV Export Tests
+//!
+//! Tests for `ik export --format csv --i-understand-plaintext`.
+
+use ironkey::export::csv_field;
+use ironkey::storage::MemoryBackend;
+use std::fs;
+use tempfile::TempDir;
+
+mod common;
+
+const TRICKY_VALUES: &[(&str, &str)] = &[
+    ("comma", "one, two, three"),
+    ("quotes", r#"say "hi" and "bye""#),
+    ("multiline", "line one\nline two\r\nline three"),
+    ("plain", "ghp_token"),
+    ("empty_quotes", r#""""#),
+    ("unicode", "pässwörd ✓"),
+];
+
+/// Minimal RFC 4180 reader, independent of the exporter
+fn parse_csv(text: &str) -> Vec<Vec<String>> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut chars = text.chars().peekable();
+    let mut quoted = false;
+
+    while let Some(c) = chars.next() {
+        match (quoted, c) {
+            (true, '"') if chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            (true, '"') => quoted = false,
+            (true, c) => field.push(c),
+            (false, '"') => quoted = true,
+            (false, ',') => row.push(std::mem::take(&mut field)),
+            (false, '\r') if chars.peek() == Some(&'\n') => {}
+            (false, '\n') => {
+                row.push(std::mem::take(&mut field));
+                rows.push(std::mem::take(&mut row));
+            }
+            (false, c) => field.push(c),
+        }
+    }
+    rows
+}
+
+#[test]
+fn test_csv_field_quoting() {
+    assert_eq!(csv_field("plain"), "plain");
+    assert_eq!(csv_field("a,b"), r#""a,b""#);
+    assert_eq!(csv_field(r#"say "hi""#), r#""say ""hi""""#);
+    assert_eq!(csv_field("two\nlines"), "\"two\nlines\"");
+    assert_eq!(csv_field(""), "");
+}
+
+#[test]
+fn test_csv_export_round_trips_tricky_values() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("vault.csv");
+    let vault = common::vault_with_entries(&MemoryBackend::new(), TRICKY_VALUES);
+
+    let written = vault.export_to_csv_file(&path, false).unwrap();
+    assert_eq!(written, TRICKY_VALUES.len());
+
+    let rows = parse_csv(&fs::read_to_string(&path).unwrap());
+    assert_eq!(rows[0], vec!["key", "value", "locked"]);
+    assert_eq!(rows.len(), TRICKY_VALUES.len() + 1);
+
+    for (key, value) in TRICKY_VALUES {
+        let row = rows.iter().find(|row| row[0] == *key).unwrap();
+        assert_eq!(
+            row,
+            &vec![key.to_string(), value.to_string(), "false".to_string()]
+        );
+    }
+}
+
+#[test]
+fn test_csv_export_includes_locked_entries() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("vault.csv");
+    let mut vault = common::vault_with_entries(
+        &MemoryBackend::new(),
+        &[("github", "ghp_token"), ("prod", "secret")],
+    );
+    vault.toggle_lock("prod").unwrap();
+
+    vault.export_to_csv_file(&path, false).unwrap();
+
+    let rows = parse_csv(&fs::read_to_string(&path).unwrap());
+    assert_eq!(
+        rows,
+        vec![
+            vec!["key", "value", "locked"],
+            vec!["github", "ghp_token", "false"],
+            vec!["prod", "secret", "true"],
+        ]
+    );
+}
+
+#[test]
+fn test_csv_export_uses_crlf_line_endings() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("vault.csv");
+    common::vault_with_entries(&MemoryBackend::new(), &[("github", "ghp_token")])
+        .export_to_csv_file(&path, false)
+        .unwrap();
+
+    assert_eq!(
+        fs::read_to_string(&path).unwrap(),
+        "key,value,locked\r\ngithub,ghp_token,false\r\n"
+    );
+}
+
+#[test]
+fn test_csv_export_refuses_to_overwrite_without_force() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("vault.csv");
+    fs::write(&path, "keep me").unwrap();
+    let vault = common::vault_with_entries(&MemoryBackend::new(), &[("github", "ghp_token")]);
+
+    assert!(vault.export_to_csv_file(&path, false).is_err());
+    assert_eq!(fs::read_to_string(&path).unwrap(), "keep me");
+
+    vault.export_to_csv_file(&path, true).unwrap();
+    assert!(fs::read_to_string(&path).unwrap().contains("ghp_token"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_csv_export_is_private() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("vault.csv");
+    common::vault_with_entries(&MemoryBackend::new(), &[("github", "ghp_token")])
+        .export_to_csv_file(&path, false)
+        .unwrap();
+
+    let mode = fs::metadata(&path).unwrap().permissions().mode();
+    assert_eq!(mode & 0o777, 0o600);
+}
@@ -0,0 +1,108 @@
+//! Deduplicated Export Tests
+//!
+//! Tests for `ik export --dedup`, which stores identical values once.
+//! NOTE: These tests must run serially because they share the same database file.
+
+use ironkey::export::{
+    DedupEntry, DedupPayload, EXPORT_FORMAT_VERSION_DEDUP, ExportEntry, ExportFile,
+};
+use ironkey::storage;
+use ironkey::vault::Vault;
+use std::fs;
+use tempfile::TempDir;
+
+mod common;
+
+fn cleanup() {
+    common::use_isolated_database();
+    if let Ok(db_path) = storage::get_database_path() {
+        let _ = fs::remove_file(db_path);
+    }
+}
+
+fn export_entry(key: &str, value: &str) -> ExportEntry {
+    ExportEntry {
+        key: key.to_string(),
+        value: value.to_string(),
+        locked: false,
+        label: None,
+        tags: Vec::new(),
+    }
+}
+
+#[test]
+fn test_shared_value_stored_once() {
+    let payload = DedupPayload::from_entries(vec![
+        export_entry("svc_a", "shared_secret"),
+        export_entry("svc_b", "shared_secret"),
+        export_entry("svc_c", "shared_secret"),
+    ]);
+
+    assert_eq!(payload.values, vec!["shared_secret".to_string()]);
+    assert_eq!(payload.entries.len(), 3);
+    assert!(payload.entries.iter().all(|e| e.value_ref == 0));
+
+    let entries = payload.into_entries().unwrap();
+    assert_eq!(entries.len(), 3);
+    assert!(entries.iter().all(|e| e.value == "shared_secret"));
+}
+
+#[test]
+fn test_invalid_value_reference_is_rejected() {
+    let payload = DedupPayload {
+        values: vec!["only".to_string()],
+        entries: vec![DedupEntry {
+            key: "broken".to_string(),
+            value_ref: 3,
+            locked: false,
+            label: None,
+            tags: Vec::new(),
+        }],
+    };
+
+    assert!(payload.into_entries().is_err());
+}
+
+#[test]
+fn test_dedup_export_import_roundtrip() {
+    let temp_dir = TempDir::new().unwrap();
+    let export_path = temp_dir.path().join("dedup.ik");
+
+    cleanup();
+    let mut vault = Vault::init("source".to_string()).unwrap();
+    for key in ["svc_a", "svc_b", "svc_c"] {
+        vault
+            .create_entry(key.to_string(), "shared_secret".to_string())
+            .unwrap();
+    }
+    vault
+        .create_entry("unique".to_string(), "other".to_string())
+        .unwrap();
+    vault.toggle_lock("svc_b").unwrap();
+    vault
+        .export_to_file_dedup(&export_path, "export_pw".to_string(), false)
+        .unwrap();
+    drop(vault);
+
+    let export_file: ExportFile =
+        serde_json::from_str(&fs::read_to_string(&export_path).unwrap()).unwrap();
+    assert_eq!(export_file.format_version, EXPORT_FORMAT_VERSION_DEDUP);
+    assert_eq!(export_file.entry_count, 4);
+
+    cleanup();
+    let mut vault = Vault::init("dest".to_string()).unwrap();
+    let result = vault
+        .import_from_file(&export_path, "export_pw".to_string(), true, false, false)
+        .unwrap();
+    assert_eq!(result.added.len(), 4);
+
+    assert_eq!(vault.get_entry("svc_a").unwrap(), "shared_secret");
+    assert_eq!(vault.get_entry("svc_c").unwrap(), "shared_secret");
+    assert_eq!(vault.get_entry("unique").unwrap(), "other");
+    assert_eq!(
+        vault.list_entries(None, Some(true)).unwrap(),
+        vec![(&"svc_b".to_string(), true)]
+    );
+
+    cleanup();
+}
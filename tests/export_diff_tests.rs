@@ -0,0 +1,88 @@
+//! Export Diff Tests
+//!
+//! Tests for `ik export --diff` (`Vault::export_diff`) and the shared
+//! `export::collect_export_entries` step.
+
+use ironkey::error::Error;
+use ironkey::export::{EXPORT_FORMAT_VERSION, ExportOptions, collect_export_entries};
+use ironkey::storage::{MemoryBackend, StorageBackend};
+
+mod common;
+
+#[test]
+fn test_diff_lists_every_entry_sorted() {
+    let backend = MemoryBackend::new();
+    let mut vault = common::vault_with_keys(&backend, &["github", "aws", "db"]);
+    vault.toggle_lock("db").unwrap();
+
+    let preview = vault.export_diff(ExportOptions::default(), None).unwrap();
+
+    assert_eq!(preview.keys, ["aws", "db", "github"]);
+    assert_eq!(preview.total, 3);
+    assert_eq!(preview.locked, 1);
+    assert_eq!(preview.format_version, EXPORT_FORMAT_VERSION);
+}
+
+#[test]
+fn test_diff_respects_selected_keys() {
+    let backend = MemoryBackend::new();
+    let vault = common::vault_with_keys(&backend, &["github", "aws", "db"]);
+    let keys = vec![
+        "github".to_string(),
+        "aws".to_string(),
+        "github".to_string(),
+    ];
+
+    let preview = vault
+        .export_diff(ExportOptions::default(), Some(&keys))
+        .unwrap();
+
+    assert_eq!(preview.keys, ["aws", "github"]);
+    assert_eq!(preview.total, 2);
+}
+
+#[test]
+fn test_diff_rejects_unknown_key() {
+    let backend = MemoryBackend::new();
+    let vault = common::vault_with_keys(&backend, &["github"]);
+    let keys = vec!["missing".to_string()];
+
+    let result = vault.export_diff(ExportOptions::default(), Some(&keys));
+
+    assert!(matches!(result, Err(Error::EntryNotFound(key)) if key == "missing"));
+}
+
+#[test]
+fn test_diff_matches_preview_without_selection() {
+    let backend = MemoryBackend::new();
+    let mut vault = common::vault_with_keys(&backend, &["github", "aws"]);
+    vault.toggle_lock("aws").unwrap();
+    let options = ExportOptions {
+        dedup: true,
+        ..Default::default()
+    };
+
+    assert_eq!(
+        vault.export_diff(options, None).unwrap(),
+        vault.export_preview(options)
+    );
+}
+
+#[test]
+fn test_collect_decrypts_values() {
+    let backend = MemoryBackend::new();
+    let _vault = common::vault_with_keys(&backend, &["github", "aws"]);
+    let db = backend.load().unwrap();
+    let master_key = master_key_for(&db);
+
+    let mut entries = collect_export_entries(&db, &master_key, None).unwrap();
+    entries.sort_by(|a, b| a.key.cmp(&b.key));
+
+    let values: Vec<&str> = entries.iter().map(|entry| entry.value.as_str()).collect();
+    assert_eq!(values, ["aws_value", "github_value"]);
+}
+
+fn master_key_for(db: &ironkey::storage::Database) -> Vec<u8> {
+    let salt = db.get_salt().unwrap();
+    ironkey::crypto::derive_key_with("master", &salt, &db.kdf_params().unwrap()).unwrap()
+}
@@ -8,8 +8,11 @@ use ironkey::vault::Vault;
 use std::fs;
 use tempfile::TempDir;
 
+mod common;
+
 /// Cleanup function
 fn cleanup() {
+    common::use_isolated_database();
     if let Ok(db_path) = storage::get_database_path() {
         let _ = fs::remove_file(db_path);
     }
@@ -0,0 +1,79 @@
+//! Minimal Export Tests
+//!
+//! Tests for `ik export --minimal`, which omits version and timestamp metadata.
+//! NOTE: These tests must run serially because they share the same database file.
+
+use ironkey::export::ExportOptions;
+use ironkey::storage;
+use ironkey::vault::Vault;
+use std::fs;
+use tempfile::TempDir;
+
+mod common;
+
+fn cleanup() {
+    common::use_isolated_database();
+    if let Ok(db_path) = storage::get_database_path() {
+        let _ = fs::remove_file(db_path);
+    }
+}
+
+#[test]
+fn test_minimal_export_has_no_metadata_and_imports() {
+    let temp_dir = TempDir::new().unwrap();
+    let export_path = temp_dir.path().join("minimal.ik");
+
+    cleanup();
+    let mut vault = Vault::init("source".to_string()).unwrap();
+    vault
+        .create_entry("github".to_string(), "ghp_token".to_string())
+        .unwrap();
+    let options = ExportOptions {
+        minimal: true,
+        ..Default::default()
+    };
+    vault
+        .export_to_file_with_options(&export_path, "export_pw".to_string(), false, options, None)
+        .unwrap();
+    drop(vault);
+
+    let content = fs::read_to_string(&export_path).unwrap();
+    for field in [
+        "exported_from",
+        "exported_at",
+        "vault_name",
+        "tags",
+        "metadata",
+    ] {
+        assert!(!content.contains(field), "minimal export contains {field}");
+    }
+    assert!(!content.contains(env!("CARGO_PKG_VERSION")));
+
+    cleanup();
+    let mut vault = Vault::init("dest".to_string()).unwrap();
+    let result = vault
+        .import_from_file(&export_path, "export_pw".to_string(), true, false, false)
+        .unwrap();
+    assert_eq!(result.added, vec!["github".to_string()]);
+    assert_eq!(vault.get_entry("github").unwrap(), "ghp_token");
+
+    cleanup();
+}
+
+#[test]
+fn test_default_export_keeps_metadata() {
+    let temp_dir = TempDir::new().unwrap();
+    let export_path = temp_dir.path().join("full.ik");
+
+    cleanup();
+    let vault = Vault::init("source".to_string()).unwrap();
+    vault
+        .export_to_file(&export_path, "export_pw".to_string())
+        .unwrap();
+
+    let content = fs::read_to_string(&export_path).unwrap();
+    assert!(content.contains("exported_from"));
+    assert!(content.contains("exported_at"));
+
+    cleanup();
+}
@@ -0,0 +1,172 @@
+//! Plaintext TOML/YAML Export Tests
+//!
+//! Tests for `ik export --format toml|yaml --plaintext`.
+
+use ironkey::error::Error;
+use ironkey::export::{PlainFormat, sanitize_identifier};
+use ironkey::storage::MemoryBackend;
+use std::collections::BTreeMap;
+use std::fs;
+use tempfile::TempDir;
+
+mod common;
+
+const TRICKY_VALUES: &[(&str, &str)] = &[
+    ("quotes", r#"say "hi" and 'bye'"#),
+    ("multiline", "line one\nline two\n"),
+    ("yaml_like", "key: value # not a comment"),
+    ("brackets", "[not, a, list] {nor: a map}"),
+    ("backslash", r"C:\path\to\thing"),
+    ("leading_space", "  padded  "),
+    ("boolish", "true"),
+    ("numberish", "0123"),
+    ("unicode", "pässwörd ✓"),
+];
+
+fn expected(entries: &[(&str, &str)]) -> BTreeMap<String, String> {
+    entries
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect()
+}
+
+#[test]
+fn test_toml_round_trip_special_characters() {
+    let vault = common::vault_with_entries(&MemoryBackend::new(), TRICKY_VALUES);
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("secrets.toml");
+
+    let result = vault
+        .export_to_plain_file(&path, PlainFormat::Toml, false)
+        .unwrap();
+    assert_eq!(result.written, TRICKY_VALUES.len());
+    assert!(result.renamed.is_empty());
+
+    let parsed: BTreeMap<String, String> =
+        toml::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+    assert_eq!(parsed, expected(TRICKY_VALUES));
+}
+
+#[test]
+fn test_yaml_round_trip_special_characters() {
+    let vault = common::vault_with_entries(&MemoryBackend::new(), TRICKY_VALUES);
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("secrets.yaml");
+
+    let result = vault
+        .export_to_plain_file(&path, PlainFormat::Yaml, false)
+        .unwrap();
+    assert_eq!(result.written, TRICKY_VALUES.len());
+
+    let parsed: BTreeMap<String, String> =
+        serde_yaml::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+    assert_eq!(parsed, expected(TRICKY_VALUES));
+}
+
+#[test]
+fn test_locked_entries_skipped() {
+    let mut vault = common::vault_with_entries(
+        &MemoryBackend::new(),
+        &[("github", "ghp_token"), ("prod", "top_secret")],
+    );
+    vault.toggle_lock("prod").unwrap();
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("secrets.toml");
+
+    let result = vault
+        .export_to_plain_file(&path, PlainFormat::Toml, false)
+        .unwrap();
+    assert_eq!(result.written, 1);
+    assert_eq!(result.skipped_locked, vec!["prod".to_string()]);
+
+    let contents = fs::read_to_string(&path).unwrap();
+    assert!(!contents.contains("top_secret"));
+    assert!(!contents.contains("prod"));
+}
+
+#[test]
+fn test_keys_sanitized_per_format() {
+    assert_eq!(
+        sanitize_identifier("db/password", PlainFormat::Toml),
+        "db_password"
+    );
+    assert_eq!(sanitize_identifier("api-key", PlainFormat::Toml), "api-key");
+    assert_eq!(sanitize_identifier("api-key", PlainFormat::Yaml), "api_key");
+    assert_eq!(sanitize_identifier("api.key", PlainFormat::Yaml), "api_key");
+    assert_eq!(sanitize_identifier("2fa", PlainFormat::Yaml), "_2fa");
+    assert_eq!(sanitize_identifier("", PlainFormat::Toml), "_");
+}
+
+#[test]
+fn test_renamed_keys_reported() {
+    let vault = common::vault_with_entries(
+        &MemoryBackend::new(),
+        &[("db/password", "hunter2"), ("plain", "x")],
+    );
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("secrets.yaml");
+
+    let result = vault
+        .export_to_plain_file(&path, PlainFormat::Yaml, false)
+        .unwrap();
+    assert_eq!(
+        result.renamed,
+        vec![("db/password".to_string(), "db_password".to_string())]
+    );
+
+    let parsed: BTreeMap<String, String> =
+        serde_yaml::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+    assert_eq!(parsed["db_password"], "hunter2");
+}
+
+#[test]
+fn test_sanitized_collision_rejected() {
+    let vault = common::vault_with_entries(
+        &MemoryBackend::new(),
+        &[("db/password", "a"), ("db.password", "b")],
+    );
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("secrets.toml");
+
+    let err = vault
+        .export_to_plain_file(&path, PlainFormat::Toml, false)
+        .unwrap_err();
+    assert!(matches!(err, Error::InvalidInput(_)));
+    assert!(!path.exists());
+}
+
+#[test]
+fn test_existing_file_requires_force() {
+    let vault = common::vault_with_entries(&MemoryBackend::new(), &[("github", "ghp_token")]);
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("secrets.toml");
+    fs::write(&path, "old").unwrap();
+
+    assert!(
+        vault
+            .export_to_plain_file(&path, PlainFormat::Toml, false)
+            .is_err()
+    );
+    assert_eq!(fs::read_to_string(&path).unwrap(), "old");
+
+    vault
+        .export_to_plain_file(&path, PlainFormat::Toml, true)
+        .unwrap();
+    assert!(fs::read_to_string(&path).unwrap().contains("ghp_token"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_plain_export_is_owner_only() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let vault = common::vault_with_entries(&MemoryBackend::new(), &[("github", "ghp_token")]);
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("secrets.yaml");
+
+    vault
+        .export_to_plain_file(&path, PlainFormat::Yaml, false)
+        .unwrap();
+    let mode = fs::metadata(&path).unwrap().permissions().mode();
+    assert_eq!(mode & 0o777, 0o600);
+}
@@ -0,0 +1,112 @@
+//! Same-Password Export Tests
+//!
+//! Tests for `ik export --same-password`, which encrypts the backup with the master password.
+//! NOTE: These tests must run serially because they share the same database file.
+
+use ironkey::error::Error;
+use ironkey::export::{ExportFile, ExportOptions};
+use ironkey::storage;
+use ironkey::vault::Vault;
+use std::fs;
+use tempfile::TempDir;
+use zeroize::Zeroizing;
+
+mod common;
+
+fn cleanup() {
+    common::use_isolated_database();
+    if let Ok(db_path) = storage::get_database_path() {
+        let _ = fs::remove_file(db_path);
+    }
+}
+
+#[test]
+fn test_master_password_export_imports_with_master_password() {
+    let temp_dir = TempDir::new().unwrap();
+    let export_path = temp_dir.path().join("same.ik");
+
+    cleanup();
+    let mut vault = Vault::init("master_pw".to_string()).unwrap();
+    vault
+        .create_entry("github".to_string(), "ghp_token".to_string())
+        .unwrap();
+    vault
+        .export_to_file_with_master(
+            &export_path,
+            "master_pw".to_string(),
+            false,
+            ExportOptions::default(),
+            None,
+        )
+        .unwrap();
+
+    // The export carries its own salt, independent of the vault
+    let export_file: ExportFile =
+        serde_json::from_str(&fs::read_to_string(&export_path).unwrap()).unwrap();
+    assert_ne!(
+        export_file.encryption.salt,
+        storage::load().unwrap().master_salt
+    );
+    drop(vault);
+
+    cleanup();
+    let mut vault = Vault::init("other".to_string()).unwrap();
+    let result = vault
+        .import_from_file(&export_path, "master_pw".to_string(), true, false, false)
+        .unwrap();
+    assert_eq!(result.added, vec!["github".to_string()]);
+    assert_eq!(vault.get_entry("github").unwrap(), "ghp_token");
+
+    cleanup();
+}
+
+#[test]
+fn test_master_password_export_rejects_wrong_password() {
+    let temp_dir = TempDir::new().unwrap();
+    let export_path = temp_dir.path().join("same.ik");
+
+    cleanup();
+    let vault = Vault::init("master_pw".to_string()).unwrap();
+    let result = vault.export_to_file_with_master(
+        &export_path,
+        "not_master".to_string(),
+        false,
+        ExportOptions::default(),
+        None,
+    );
+    assert!(matches!(result, Err(Error::InvalidMasterPassword)));
+    assert!(!export_path.exists());
+
+    cleanup();
+}
+
+#[test]
+fn test_verified_master_export_imports_with_master_password() {
+    let temp_dir = TempDir::new().unwrap();
+    let export_path = temp_dir.path().join("same.ik");
+
+    cleanup();
+    let mut vault = Vault::init("master_pw".to_string()).unwrap();
+    vault
+        .create_entry("github".to_string(), "ghp_token".to_string())
+        .unwrap();
+    vault
+        .export_to_file_with_verified_master(
+            &export_path,
+            Zeroizing::new("master_pw".to_string()),
+            false,
+            ExportOptions::default(),
+            None,
+        )
+        .unwrap();
+    drop(vault);
+
+    cleanup();
+    let mut vault = Vault::init("other".to_string()).unwrap();
+    vault
+        .import_from_file(&export_path, "master_pw".to_string(), true, false, false)
+        .unwrap();
+    assert_eq!(vault.get_entry("github").unwrap(), "ghp_token");
+
+    cleanup();
+}
@@ -0,0 +1,90 @@
+//! Selective Export Tests
+//!
+//! Tests for exporting only some entries (`ik export --key a --key b`).
+
+use ironkey::error::Error;
+use ironkey::export::{ExportFile, ExportOptions};
+use ironkey::storage::MemoryBackend;
+use ironkey::vault::Vault;
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+mod common;
+
+fn export_keys(vault: &Vault, path: &Path, keys: &[&str]) -> ironkey::error::Result<()> {
+    let keys: Vec<String> = keys.iter().map(|k| k.to_string()).collect();
+    vault.export_to_file_with_options(
+        path,
+        "export_pw".to_string(),
+        false,
+        ExportOptions::default(),
+        Some(&keys),
+    )
+}
+
+fn read_export(path: &Path) -> ExportFile {
+    serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap()
+}
+
+#[test]
+fn test_export_only_requested_keys() {
+    let temp_dir = TempDir::new().unwrap();
+    let export_path = temp_dir.path().join("subset.ik");
+    let mut vault = common::vault_with_keys(&MemoryBackend::new(), &["aws", "github", "netflix"]);
+    vault.toggle_lock("github").unwrap();
+
+    export_keys(&vault, &export_path, &["github", "aws"]).unwrap();
+    assert_eq!(read_export(&export_path).entry_count, 2);
+
+    let mut target = common::vault_with_keys(&MemoryBackend::new(), &[]);
+    let result = target
+        .import_from_file(&export_path, "export_pw".to_string(), true, false, false)
+        .unwrap();
+    assert_eq!(result.added.len(), 2);
+    assert!(target.get_label("netflix").is_err());
+    assert_eq!(target.list_entries(None, Some(true)).unwrap().len(), 1);
+
+    target.toggle_lock("github").unwrap();
+    assert_eq!(target.get_entry("aws").unwrap(), "aws_value");
+    assert_eq!(target.get_entry("github").unwrap(), "github_value");
+}
+
+#[test]
+fn test_repeated_key_is_exported_once() {
+    let temp_dir = TempDir::new().unwrap();
+    let export_path = temp_dir.path().join("repeated.ik");
+    let vault = common::vault_with_keys(&MemoryBackend::new(), &["aws", "github"]);
+
+    export_keys(&vault, &export_path, &["aws", "aws"]).unwrap();
+    assert_eq!(read_export(&export_path).entry_count, 1);
+}
+
+#[test]
+fn test_missing_key_fails_without_writing() {
+    let temp_dir = TempDir::new().unwrap();
+    let export_path = temp_dir.path().join("missing.ik");
+    let vault = common::vault_with_keys(&MemoryBackend::new(), &["aws"]);
+
+    let result = export_keys(&vault, &export_path, &["aws", "nope"]);
+    assert!(matches!(result, Err(Error::EntryNotFound(key)) if key == "nope"));
+    assert!(!export_path.exists());
+}
+
+#[test]
+fn test_no_key_filter_exports_everything() {
+    let temp_dir = TempDir::new().unwrap();
+    let export_path = temp_dir.path().join("all.ik");
+    let vault = common::vault_with_keys(&MemoryBackend::new(), &["aws", "github", "netflix"]);
+
+    vault
+        .export_to_file_with_options(
+            &export_path,
+            "export_pw".to_string(),
+            false,
+            ExportOptions::default(),
+            None,
+        )
+        .unwrap();
+    assert_eq!(read_export(&export_path).entry_count, 3);
+}
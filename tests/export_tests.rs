@@ -10,8 +10,11 @@ use ironkey::vault::Vault;
 use std::fs;
 use std::path::PathBuf;
 
+mod common;
+
 /// Helper function to create a test vault with sample entries
 fn setup_test_vault() -> Result<Vault> {
+    common::use_isolated_database();
     // Clean up any existing test database
     let db_path = storage::get_database_path()?;
     let _ = fs::remove_file(&db_path);
@@ -33,16 +36,17 @@ fn setup_test_vault() -> Result<Vault> {
 
 /// Cleanup function to remove test database and export files
 fn cleanup_test_files() {
+    common::use_isolated_database();
     if let Ok(db_path) = storage::get_database_path() {
         let _ = fs::remove_file(db_path);
     }
     // Clean up any .ik files in current directory
     if let Ok(entries) = fs::read_dir(".") {
         for entry in entries.flatten() {
-            if let Some(ext) = entry.path().extension() {
-                if ext == "ik" {
-                    let _ = fs::remove_file(entry.path());
-                }
+            if let Some(ext) = entry.path().extension()
+                && ext == "ik"
+            {
+                let _ = fs::remove_file(entry.path());
             }
         }
     }
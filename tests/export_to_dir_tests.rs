@@ -0,0 +1,193 @@
+//! Directory Export Tests
+//!
+//! Tests for `ik export --to-dir` (one plaintext file per unlocked entry, plus a manifest).
+
+use ironkey::error::Error;
+use ironkey::export::{DIR_MANIFEST_FILE, DirManifest, archive_directory, sanitize_filename};
+use ironkey::storage::MemoryBackend;
+use std::fs;
+use tempfile::TempDir;
+
+mod common;
+
+#[test]
+fn test_one_file_per_unlocked_entry() {
+    let mut vault = common::vault_with_entries(
+        &MemoryBackend::new(),
+        &[
+            ("github", "ghp_token"),
+            ("db/password", "hunter2"),
+            ("prod", "top_secret"),
+        ],
+    );
+    vault.toggle_lock("prod").unwrap();
+
+    let temp_dir = TempDir::new().unwrap();
+    let dir = temp_dir.path().join("secrets");
+    let result = vault.export_to_dir(&dir, false).unwrap();
+
+    assert_eq!(result.written.len(), 2);
+    assert_eq!(result.skipped_locked, vec!["prod".to_string()]);
+
+    let mut files: Vec<String> = fs::read_dir(&dir)
+        .unwrap()
+        .map(|e| e.unwrap().file_name().into_string().unwrap())
+        .collect();
+    files.sort();
+    assert_eq!(files, vec!["db_password", "github", DIR_MANIFEST_FILE]);
+
+    assert_eq!(fs::read_to_string(dir.join("github")).unwrap(), "ghp_token");
+    assert_eq!(
+        fs::read_to_string(dir.join("db_password")).unwrap(),
+        "hunter2"
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn test_files_are_owner_only() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let vault = common::vault_with_entries(&MemoryBackend::new(), &[("github", "ghp_token")]);
+    let temp_dir = TempDir::new().unwrap();
+    let dir = temp_dir.path().join("secrets");
+    vault.export_to_dir(&dir, false).unwrap();
+
+    let mode = fs::metadata(dir.join("github"))
+        .unwrap()
+        .permissions()
+        .mode();
+    assert_eq!(mode & 0o777, 0o600);
+    let dir_mode = fs::metadata(&dir).unwrap().permissions().mode();
+    assert_eq!(dir_mode & 0o777, 0o700);
+
+    // Overwriting an existing, more permissive file tightens it
+    fs::set_permissions(dir.join("github"), fs::Permissions::from_mode(0o644)).unwrap();
+    vault.export_to_dir(&dir, true).unwrap();
+    let mode = fs::metadata(dir.join("github"))
+        .unwrap()
+        .permissions()
+        .mode();
+    assert_eq!(mode & 0o777, 0o600);
+}
+
+#[test]
+fn test_colliding_names_write_nothing() {
+    let vault = common::vault_with_entries(&MemoryBackend::new(), &[("a/b", "1"), ("a:b", "2")]);
+    let temp_dir = TempDir::new().unwrap();
+    let dir = temp_dir.path().join("secrets");
+
+    let result = vault.export_to_dir(&dir, false);
+    assert!(matches!(result, Err(Error::InvalidInput(_))));
+    assert!(!dir.exists());
+}
+
+#[test]
+fn test_existing_file_requires_force() {
+    let vault = common::vault_with_entries(&MemoryBackend::new(), &[("github", "ghp_token")]);
+    let temp_dir = TempDir::new().unwrap();
+    fs::write(temp_dir.path().join("github"), "old").unwrap();
+
+    assert!(vault.export_to_dir(temp_dir.path(), false).is_err());
+    assert_eq!(
+        fs::read_to_string(temp_dir.path().join("github")).unwrap(),
+        "old"
+    );
+
+    vault.export_to_dir(temp_dir.path(), true).unwrap();
+    assert_eq!(
+        fs::read_to_string(temp_dir.path().join("github")).unwrap(),
+        "ghp_token"
+    );
+}
+
+#[test]
+fn test_sanitize_filename() {
+    assert_eq!(sanitize_filename("github_token"), "github_token");
+    assert_eq!(sanitize_filename("aws.prod-key"), "aws.prod-key");
+    assert_eq!(sanitize_filename("../etc/passwd"), "_.._etc_passwd");
+    assert_eq!(sanitize_filename("my key"), "my_key");
+    assert_eq!(sanitize_filename(".env"), "_.env");
+    assert_eq!(sanitize_filename(".."), "_..");
+    assert_eq!(sanitize_filename(""), "_");
+}
+
+#[test]
+fn test_manifest_lists_every_entry() {
+    let mut vault = common::vault_with_entries(
+        &MemoryBackend::new(),
+        &[
+            ("github", "ghp_token"),
+            ("db/password", "hunter2"),
+            ("prod", "top_secret"),
+        ],
+    );
+    vault.toggle_lock("prod").unwrap();
+
+    let temp_dir = TempDir::new().unwrap();
+    let dir = temp_dir.path().join("out");
+    vault.export_to_dir(&dir, false).unwrap();
+
+    let manifest: DirManifest =
+        serde_json::from_str(&fs::read_to_string(dir.join(DIR_MANIFEST_FILE)).unwrap()).unwrap();
+    let entries: Vec<(&str, Option<&str>, bool)> = manifest
+        .entries
+        .iter()
+        .map(|e| (e.key.as_str(), e.file.as_deref(), e.locked))
+        .collect();
+    assert_eq!(
+        entries,
+        vec![
+            ("db/password", Some("db_password"), false),
+            ("github", Some("github"), false),
+            ("prod", None, true),
+        ]
+    );
+    assert!(
+        !fs::read_to_string(dir.join(DIR_MANIFEST_FILE))
+            .unwrap()
+            .contains("top_secret")
+    );
+}
+
+#[test]
+fn test_manifest_name_is_reserved() {
+    let vault = common::vault_with_entries(&MemoryBackend::new(), &[("manifest.json", "x")]);
+    let temp_dir = TempDir::new().unwrap();
+    let dir = temp_dir.path().join("out");
+    assert!(matches!(
+        vault.export_to_dir(&dir, false),
+        Err(Error::InvalidInput(_))
+    ));
+    assert!(!dir.exists());
+
+    let vault = common::vault_with_entries(&MemoryBackend::new(), &[("github", "ghp_token")]);
+    fs::create_dir(&dir).unwrap();
+    fs::write(dir.join(DIR_MANIFEST_FILE), "{}").unwrap();
+    assert!(vault.export_to_dir(&dir, false).is_err());
+    assert!(!dir.join("github").exists());
+    vault.export_to_dir(&dir, true).unwrap();
+}
+
+#[test]
+fn test_archive_directory_round_trip() {
+    let vault = common::vault_with_entries(&MemoryBackend::new(), &[("github", "ghp_token")]);
+    let temp_dir = TempDir::new().unwrap();
+    let dir = temp_dir.path().join("out");
+    vault.export_to_dir(&dir, false).unwrap();
+
+    let archive = temp_dir.path().join("out.tar.gz");
+    archive_directory(&dir, &archive, false).unwrap();
+    assert!(archive_directory(&dir, &archive, false).is_err());
+
+    let mut tarball = tar::Archive::new(flate2::read::GzDecoder::new(
+        fs::File::open(&archive).unwrap(),
+    ));
+    let unpacked = temp_dir.path().join("unpacked");
+    tarball.unpack(&unpacked).unwrap();
+    assert_eq!(
+        fs::read_to_string(unpacked.join("out").join("github")).unwrap(),
+        "ghp_token"
+    );
+    assert!(unpacked.join("out").join(DIR_MANIFEST_FILE).exists());
+}
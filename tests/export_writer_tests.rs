@@ -0,0 +1,169 @@
+//! In-Memory Export/Import Tests
+//!
+//! Tests that `Vault::export_to_writer` and `Vault::import_from_reader` round-trip
+//! through a byte buffer and produce the same .ik JSON as the file-based export.
+//! These use `MemoryBackend` and never touch the filesystem.
+
+use ironkey::export::{EXPORT_FORMAT_VERSION, ExportFile, ExportOptions};
+use ironkey::storage::MemoryBackend;
+use ironkey::vault::{Vault, VaultBuilder};
+
+fn new_vault() -> Vault {
+    VaultBuilder::new()
+        .backend(Box::new(MemoryBackend::new()))
+        .init("master".to_string())
+        .unwrap()
+}
+
+fn populated_vault() -> Vault {
+    let mut vault = new_vault();
+    vault
+        .create_entry("github".to_string(), "gh-token".to_string())
+        .unwrap();
+    vault
+        .create_entry("aws".to_string(), "aws-secret".to_string())
+        .unwrap();
+    vault
+        .create_entry("db".to_string(), "db-password".to_string())
+        .unwrap();
+    vault
+}
+
+#[test]
+fn test_round_trip_through_byte_buffer() {
+    let source = populated_vault();
+    let mut buffer = Vec::new();
+    source
+        .export_to_writer(
+            &mut buffer,
+            "export".to_string(),
+            ExportOptions::default(),
+            None,
+        )
+        .unwrap();
+    assert!(!buffer.is_empty());
+
+    let mut target = new_vault();
+    let result = target
+        .import_from_reader(
+            buffer.as_slice(),
+            "export".to_string(),
+            true,
+            false,
+            false,
+            None,
+        )
+        .unwrap();
+
+    assert_eq!(result.added, vec!["aws", "db", "github"]);
+    assert_eq!(target.get_entry_unlocked("github").unwrap(), "gh-token");
+    assert_eq!(target.get_entry_unlocked("db").unwrap(), "db-password");
+}
+
+#[test]
+fn test_writer_output_is_standard_ik_json() {
+    let vault = populated_vault();
+    let mut buffer = Vec::new();
+    vault
+        .export_to_writer(
+            &mut buffer,
+            "export".to_string(),
+            ExportOptions::default(),
+            None,
+        )
+        .unwrap();
+
+    let export: ExportFile = serde_json::from_slice(&buffer).unwrap();
+    assert_eq!(export.format_version, EXPORT_FORMAT_VERSION);
+    assert_eq!(export.entry_count, 3);
+    assert!(!export.encryption.salt.is_empty());
+}
+
+#[test]
+fn test_selected_keys_only() {
+    let source = populated_vault();
+    let keys = vec!["aws".to_string(), "github".to_string()];
+    let mut buffer = Vec::new();
+    source
+        .export_to_writer(
+            &mut buffer,
+            "export".to_string(),
+            ExportOptions::default(),
+            Some(&keys),
+        )
+        .unwrap();
+
+    let mut target = new_vault();
+    let only = vec!["github".to_string(), "missing".to_string()];
+    let result = target
+        .import_from_reader(
+            buffer.as_slice(),
+            "export".to_string(),
+            true,
+            false,
+            false,
+            Some(&only),
+        )
+        .unwrap();
+
+    assert_eq!(result.added, vec!["github"]);
+    assert_eq!(result.not_found, vec!["missing"]);
+    assert!(target.get_entry_unlocked("aws").is_err());
+}
+
+#[test]
+fn test_diff_mode_leaves_vault_unchanged() {
+    let source = populated_vault();
+    let mut buffer = Vec::new();
+    source
+        .export_to_writer(
+            &mut buffer,
+            "export".to_string(),
+            ExportOptions::default(),
+            None,
+        )
+        .unwrap();
+
+    let mut target = new_vault();
+    let result = target
+        .import_from_reader(
+            buffer.as_slice(),
+            "export".to_string(),
+            true,
+            false,
+            true,
+            None,
+        )
+        .unwrap();
+
+    assert_eq!(result.added.len(), 3);
+    assert!(target.list_entries(None, None).unwrap().is_empty());
+}
+
+#[test]
+fn test_wrong_password_is_rejected() {
+    let source = populated_vault();
+    let mut buffer = Vec::new();
+    source
+        .export_to_writer(
+            &mut buffer,
+            "export".to_string(),
+            ExportOptions::default(),
+            None,
+        )
+        .unwrap();
+
+    let mut target = new_vault();
+    assert!(
+        target
+            .import_from_reader(
+                buffer.as_slice(),
+                "wrong".to_string(),
+                true,
+                false,
+                false,
+                None,
+            )
+            .is_err()
+    );
+}
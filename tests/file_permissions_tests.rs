@@ -0,0 +1,96 @@
+//! File Permission Tests
+//!
+//! Tests that the database and encrypted exports are readable only by their owner (0600).
+//! Unix only.
+
+#![cfg(unix)]
+
+use ironkey::sqlite_backend::SqliteBackend;
+use ironkey::storage::{JsonFileBackend, MemoryBackend};
+use ironkey::vault::VaultBuilder;
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+use tempfile::TempDir;
+
+fn mode(path: &Path) -> u32 {
+    fs::metadata(path).unwrap().permissions().mode() & 0o777
+}
+
+#[test]
+fn test_new_database_is_private() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("ironkey.json");
+
+    drop(
+        VaultBuilder::new()
+            .backend(Box::new(JsonFileBackend::new(path.clone())))
+            .init("master".to_string())
+            .unwrap(),
+    );
+
+    assert_eq!(mode(&path), 0o600);
+}
+
+#[test]
+fn test_overwritten_database_is_made_private() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("ironkey.json");
+    let backend = JsonFileBackend::new(path.clone());
+    let mut vault = VaultBuilder::new()
+        .backend(Box::new(backend.clone()))
+        .init("master".to_string())
+        .unwrap();
+
+    // A world-readable database (and stale staging file) from before this change
+    fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+    let staged = backend.sibling_path(".tmp");
+    fs::write(&staged, "partial").unwrap();
+    fs::set_permissions(&staged, fs::Permissions::from_mode(0o644)).unwrap();
+
+    vault
+        .create_entry("github".to_string(), "ghp_token".to_string())
+        .unwrap();
+
+    assert_eq!(mode(&path), 0o600);
+}
+
+#[test]
+fn test_sqlite_database_is_private() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("ironkey.db");
+
+    drop(
+        VaultBuilder::new()
+            .backend(Box::new(SqliteBackend::open(&path).unwrap()))
+            .init("master".to_string())
+            .unwrap(),
+    );
+    assert_eq!(mode(&path), 0o600);
+
+    fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+    drop(SqliteBackend::open(&path).unwrap());
+    assert_eq!(mode(&path), 0o600);
+}
+
+#[test]
+fn test_encrypted_export_is_private() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("backup.ik");
+    let vault = VaultBuilder::new()
+        .backend(Box::new(MemoryBackend::new()))
+        .init("master".to_string())
+        .unwrap();
+
+    vault
+        .export_to_file(&path, "export_pw".to_string())
+        .unwrap();
+    assert_eq!(mode(&path), 0o600);
+
+    // Overwriting with --force tightens an existing file too
+    fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+    vault
+        .export_to_file_force(&path, "export_pw".to_string())
+        .unwrap();
+    assert_eq!(mode(&path), 0o600);
+}
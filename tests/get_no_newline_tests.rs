@@ -0,0 +1,49 @@
+//! Get --no-newline Tests
+//!
+//! Tests for parsing `ik get --no-newline`, which prints the bare value for piping.
+
+use clap::Parser;
+use ironkey::cli::{CliArgs, Commands};
+
+fn parse(args: &[&str]) -> Result<Commands, clap::Error> {
+    CliArgs::try_parse_from(std::iter::once("ik").chain(args.iter().copied()))
+        .map(|cli| cli.command.expect("subcommand"))
+}
+
+#[test]
+fn test_no_newline_defaults_to_off() {
+    let command = parse(&["get", "-k", "token"]).unwrap();
+    assert!(matches!(
+        command,
+        Commands::Get {
+            no_newline: false,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn test_no_newline_long_and_short() {
+    for flag in ["--no-newline", "-n"] {
+        let command = parse(&["get", "-k", "token", flag]).unwrap();
+        assert!(matches!(
+            command,
+            Commands::Get {
+                no_newline: true,
+                ..
+            }
+        ));
+    }
+}
+
+#[test]
+fn test_no_newline_conflicts_with_other_outputs() {
+    for other in [&["--copy"][..], &["--qr"], &["--field", "label"]] {
+        let mut args = vec!["get", "-k", "token", "--no-newline"];
+        args.extend_from_slice(other);
+        let err = parse(&args)
+            .err()
+            .expect("conflicting flags should be rejected");
+        assert_eq!(err.kind(), clap::error::ErrorKind::ArgumentConflict);
+    }
+}
@@ -0,0 +1,78 @@
+//! Unlock-Once Read Tests
+//!
+//! Tests for `Vault::get_entry_unlocked` (`ik get --unlock-once`).
+
+use ironkey::error::Error;
+use ironkey::storage::{MemoryBackend, StorageBackend};
+use ironkey::vault::{Vault, VaultBuilder};
+
+fn locked_vault(backend: &MemoryBackend) -> Vault {
+    let mut vault = VaultBuilder::new()
+        .backend(Box::new(backend.clone()))
+        .init("master".to_string())
+        .unwrap();
+    vault
+        .create_entry("github".to_string(), "ghp_token".to_string())
+        .unwrap();
+    vault.toggle_lock("github").unwrap();
+    vault
+}
+
+#[test]
+fn test_reads_locked_entry_without_unlocking_it() {
+    let backend = MemoryBackend::new();
+    let vault = locked_vault(&backend);
+    let before = serde_json::to_string(&backend.load().unwrap()).unwrap();
+
+    assert_eq!(vault.get_entry_unlocked("github").unwrap(), "ghp_token");
+
+    // Still locked, in memory and on disk, and nothing was written
+    assert!(matches!(
+        vault.get_entry("github"),
+        Err(Error::EntryLocked(_))
+    ));
+    assert_eq!(
+        serde_json::to_string(&backend.load().unwrap()).unwrap(),
+        before
+    );
+}
+
+#[test]
+fn test_reads_unlocked_entry_too() {
+    let backend = MemoryBackend::new();
+    let mut vault = locked_vault(&backend);
+    vault.toggle_lock("github").unwrap();
+
+    assert_eq!(vault.get_entry_unlocked("github").unwrap(), "ghp_token");
+}
+
+#[test]
+fn test_missing_entry() {
+    let backend = MemoryBackend::new();
+    let vault = locked_vault(&backend);
+
+    assert!(matches!(
+        vault.get_entry_unlocked("missing"),
+        Err(Error::EntryNotFound(_))
+    ));
+}
+
+#[test]
+fn test_check_lock_password_keeps_lock() {
+    let backend = MemoryBackend::new();
+    let mut vault = locked_vault(&backend);
+    vault.toggle_lock("github").unwrap();
+    vault.lock_with_password("github", "open sesame").unwrap();
+
+    assert!(matches!(
+        vault.check_lock_password("github", "wrong"),
+        Err(Error::InvalidInput(_))
+    ));
+    vault.check_lock_password("github", "open sesame").unwrap();
+
+    assert!(vault.has_lock_password("github").unwrap());
+    assert!(matches!(
+        vault.get_entry("github"),
+        Err(Error::EntryLocked(_))
+    ));
+}
@@ -0,0 +1,228 @@
+//! Plaintext CSV Import Tests
+//!
+//! Tests for `ik import --format csv` (`key,value[,locked]` tables from other managers).
+
+use ironkey::error::Error;
+use ironkey::import::parse_csv_entries;
+use ironkey::storage::{MemoryBackend, StorageBackend};
+use ironkey::vault::VaultBuilder;
+use std::fs;
+use std::path::PathBuf;
+use tempfile::TempDir;
+
+mod common;
+
+fn write_csv(dir: &TempDir, content: &str) -> PathBuf {
+    let path = dir.path().join("data.csv");
+    fs::write(&path, content).unwrap();
+    path
+}
+
+fn invalid_input_message<T: std::fmt::Debug>(result: ironkey::error::Result<T>) -> String {
+    match result {
+        Err(Error::InvalidInput(message)) => message,
+        other => panic!("expected InvalidInput, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_import_csv_merge_mode() {
+    let backend = MemoryBackend::new();
+    let mut vault = common::vault_with_entries(&backend, &[("existing", "keep_me")]);
+    let temp_dir = TempDir::new().unwrap();
+    let path = write_csv(
+        &temp_dir,
+        "key,value,locked\napi_key,sk-123,false\nexisting,overwritten,false\nprod,secret,true\n",
+    );
+
+    let result = vault
+        .import_from_plain_csv(&path, true, false, false)
+        .unwrap();
+    assert_eq!(result.total_in_export, 3);
+    assert_eq!(result.added, vec!["api_key", "prod"]);
+    assert_eq!(result.skipped, vec!["existing"]);
+    drop(vault);
+
+    let mut vault = VaultBuilder::new()
+        .backend(Box::new(backend))
+        .unlock("master".to_string())
+        .unwrap();
+    assert_eq!(vault.get_entry("api_key").unwrap(), "sk-123");
+    assert_eq!(vault.get_entry("existing").unwrap(), "keep_me");
+    assert!(matches!(
+        vault.get_entry("prod"),
+        Err(Error::EntryLocked(_))
+    ));
+    vault.toggle_lock("prod").unwrap();
+    assert_eq!(vault.get_entry("prod").unwrap(), "secret");
+}
+
+#[test]
+fn test_import_csv_replace_and_diff_modes() {
+    let backend = MemoryBackend::new();
+    let mut vault = common::vault_with_entries(&backend, &[("existing", "old")]);
+    let temp_dir = TempDir::new().unwrap();
+    let path = write_csv(&temp_dir, "key,value\nexisting,new\nadded,value\n");
+
+    let before = serde_json::to_string(&backend.load().unwrap()).unwrap();
+    let preview = vault
+        .import_from_plain_csv(&path, false, false, true)
+        .unwrap();
+    assert_eq!(preview.added, vec!["added"]);
+    assert_eq!(
+        serde_json::to_string(&backend.load().unwrap()).unwrap(),
+        before
+    );
+
+    let result = vault
+        .import_from_plain_csv(&path, false, true, false)
+        .unwrap();
+    assert_eq!(result.updated, vec!["existing"]);
+    assert_eq!(result.added, vec!["added"]);
+    assert_eq!(vault.get_entry("existing").unwrap(), "new");
+}
+
+#[test]
+fn test_parse_csv_quoted_fields() {
+    let entries = parse_csv_entries(
+        "key,value\r\n\"comma\",\"a, b\"\r\nquotes,\"say \"\"hi\"\"\"\r\nmultiline,\"one\ntwo\"\r\n",
+    )
+    .unwrap();
+
+    let values: Vec<(&str, &str)> = entries
+        .iter()
+        .map(|e| (e.key.as_str(), e.value.as_str()))
+        .collect();
+    assert_eq!(
+        values,
+        vec![
+            ("comma", "a, b"),
+            ("quotes", r#"say "hi""#),
+            ("multiline", "one\ntwo"),
+        ]
+    );
+}
+
+#[test]
+fn test_parse_csv_header_variants() {
+    // Column order, case, extra columns, a BOM and a missing trailing newline are all fine
+    let entries =
+        parse_csv_entries("\u{feff}URL,Value,Key,Locked\nhttps://x,secret,site,yes").unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].key, "site");
+    assert_eq!(entries[0].value, "secret");
+    assert!(entries[0].locked);
+
+    assert!(invalid_input_message(parse_csv_entries("name,password\na,b\n")).contains("header"));
+    assert!(invalid_input_message(parse_csv_entries("")).contains("empty"));
+}
+
+#[test]
+fn test_parse_csv_skips_blank_lines() {
+    let entries = parse_csv_entries("key,value\n\na,1\n   \r\nb,2\n\n").unwrap();
+    let keys: Vec<&str> = entries.iter().map(|e| e.key.as_str()).collect();
+    assert_eq!(keys, vec!["a", "b"]);
+}
+
+#[test]
+fn test_parse_csv_reports_every_bad_row_with_line_numbers() {
+    let message = invalid_input_message(parse_csv_entries(
+        "key,value,locked\n\
+         good,1,false\n\
+         too,many,fields,here\n\
+         ,no_key,false\n\
+         good,again,false\n\
+         flag,x,maybe\n",
+    ));
+
+    assert!(
+        message.contains("line 3: expected 3 fields, found 4"),
+        "{message}"
+    );
+    assert!(message.contains("line 4: key is empty"), "{message}");
+    assert!(
+        message.contains("line 5: duplicate key 'good' (first on line 2)"),
+        "{message}"
+    );
+    assert!(
+        message.contains("line 6: invalid locked value 'maybe'"),
+        "{message}"
+    );
+}
+
+#[test]
+fn test_parse_csv_line_numbers_follow_multiline_fields() {
+    let message = invalid_input_message(parse_csv_entries(
+        "key,value\nnote,\"spans\ntwo lines\"\nbad\n",
+    ));
+    assert!(
+        message.contains("line 4: expected 2 fields, found 1"),
+        "{message}"
+    );
+}
+
+#[test]
+fn test_parse_csv_quoting_errors() {
+    let message = invalid_input_message(parse_csv_entries("key,value\na,\"unterminated\n"));
+    assert!(
+        message.contains("line 2: unterminated quoted field"),
+        "{message}"
+    );
+
+    let message = invalid_input_message(parse_csv_entries("key,value\na,b\"c\n"));
+    assert!(
+        message.contains("line 2: quote inside an unquoted field"),
+        "{message}"
+    );
+
+    let message = invalid_input_message(parse_csv_entries("key,value\na,\"b\"c\n"));
+    assert!(
+        message.contains("line 2: unexpected text after closing quote"),
+        "{message}"
+    );
+}
+
+#[test]
+fn test_malformed_csv_imports_nothing() {
+    let backend = MemoryBackend::new();
+    let mut vault = common::vault_with_entries(&backend, &[]);
+    let temp_dir = TempDir::new().unwrap();
+    let path = write_csv(&temp_dir, "key,value\ngood,1\nbad\n");
+
+    assert!(
+        vault
+            .import_from_plain_csv(&path, true, false, false)
+            .is_err()
+    );
+    assert!(backend.load().unwrap().entries.is_empty());
+}
+
+#[test]
+fn test_csv_export_round_trip() {
+    let source = MemoryBackend::new();
+    let mut vault = common::vault_with_entries(
+        &source,
+        &[
+            ("comma", "a, b"),
+            ("quotes", r#"say "hi""#),
+            ("multiline", "one\r\ntwo"),
+        ],
+    );
+    vault.toggle_lock("quotes").unwrap();
+
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("vault.csv");
+    vault.export_to_csv_file(&path, false).unwrap();
+
+    let target = MemoryBackend::new();
+    let mut copy = common::vault_with_entries(&target, &[]);
+    let result = copy
+        .import_from_plain_csv(&path, true, false, false)
+        .unwrap();
+    assert_eq!(result.added.len(), 3);
+
+    assert_eq!(copy.get_entry("comma").unwrap(), "a, b");
+    assert_eq!(copy.get_entry("multiline").unwrap(), "one\r\ntwo");
+    copy.toggle_lock("quotes").unwrap();
+    assert_eq!(copy.get_entry("quotes").unwrap(), r#"say "hi""#);
+}
@@ -0,0 +1,130 @@
+//! Dotenv Import Tests
+//!
+//! Tests for `ik import-env` (plaintext `KEY=VALUE` files).
+
+use ironkey::error::Error;
+use ironkey::import::parse_env_entries;
+use ironkey::storage::{MemoryBackend, StorageBackend};
+use ironkey::vault::VaultBuilder;
+use std::fs;
+use std::path::PathBuf;
+use tempfile::TempDir;
+
+mod common;
+
+fn write_env(dir: &TempDir, content: &str) -> PathBuf {
+    let path = dir.path().join("secrets.env");
+    fs::write(&path, content).unwrap();
+    path
+}
+
+fn invalid_input_message<T: std::fmt::Debug>(result: ironkey::error::Result<T>) -> String {
+    match result {
+        Err(Error::InvalidInput(message)) => message,
+        other => panic!("expected InvalidInput, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_parse_env_skips_comments_and_blanks() {
+    let entries = parse_env_entries(
+        "# database\n\nDB_URL=postgres://u:p@host/db?sslmode=require\n  \nexport API_KEY = sk-123 \n",
+    )
+    .unwrap();
+
+    let pairs: Vec<(&str, &str)> = entries
+        .iter()
+        .map(|entry| (entry.key.as_str(), entry.value.as_str()))
+        .collect();
+    assert_eq!(
+        pairs,
+        vec![
+            ("DB_URL", "postgres://u:p@host/db?sslmode=require"),
+            ("API_KEY", "sk-123"),
+        ]
+    );
+    assert!(entries.iter().all(|entry| !entry.locked));
+}
+
+#[test]
+fn test_parse_env_splits_on_first_equals_and_strips_quotes() {
+    let entries =
+        parse_env_entries("TOKEN=abc==\nQUOTED=\"a b=c\"\nSINGLE='x'\nEMPTY=\nHALF=\"open\n")
+            .unwrap();
+
+    let values: Vec<&str> = entries.iter().map(|entry| entry.value.as_str()).collect();
+    assert_eq!(values, vec!["abc==", "a b=c", "x", "", "\"open"]);
+}
+
+#[test]
+fn test_parse_env_reports_every_bad_line() {
+    let message = invalid_input_message(parse_env_entries(
+        "GOOD=1\nno_equals_here\n=orphan\nGOOD=2\n",
+    ));
+
+    assert!(message.contains("line 2: expected KEY=VALUE"), "{message}");
+    assert!(message.contains("line 3: key is empty"), "{message}");
+    assert!(
+        message.contains("line 4: duplicate key 'GOOD' (first on line 1)"),
+        "{message}"
+    );
+}
+
+#[test]
+fn test_import_env_merge_mode() {
+    let backend = MemoryBackend::new();
+    let mut vault = common::vault_with_entries(&backend, &[("existing", "keep_me")]);
+    let temp_dir = TempDir::new().unwrap();
+    let path = write_env(&temp_dir, "api_key=sk-123\nexisting=overwritten\n");
+
+    let result = vault
+        .import_from_plain_env(&path, true, false, false)
+        .unwrap();
+    assert_eq!(result.total_in_export, 2);
+    assert_eq!(result.added, vec!["api_key"]);
+    assert_eq!(result.skipped, vec!["existing"]);
+    drop(vault);
+
+    let vault = VaultBuilder::new()
+        .backend(Box::new(backend))
+        .unlock("master".to_string())
+        .unwrap();
+    assert_eq!(vault.get_entry("api_key").unwrap(), "sk-123");
+    assert_eq!(vault.get_entry("existing").unwrap(), "keep_me");
+}
+
+#[test]
+fn test_import_env_replace_and_diff_modes() {
+    let backend = MemoryBackend::new();
+    let mut vault = common::vault_with_entries(&backend, &[("existing", "old")]);
+    let temp_dir = TempDir::new().unwrap();
+    let path = write_env(&temp_dir, "existing=new\nadded=value\n");
+
+    let before = serde_json::to_string(&backend.load().unwrap()).unwrap();
+    let preview = vault
+        .import_from_plain_env(&path, false, false, true)
+        .unwrap();
+    assert_eq!(preview.added, vec!["added"]);
+    assert_eq!(
+        serde_json::to_string(&backend.load().unwrap()).unwrap(),
+        before
+    );
+
+    let result = vault
+        .import_from_plain_env(&path, false, true, false)
+        .unwrap();
+    assert_eq!(result.added, vec!["added"]);
+    assert_eq!(result.updated, vec!["existing"]);
+    assert_eq!(vault.get_entry("existing").unwrap(), "new");
+}
+
+#[test]
+fn test_import_env_with_errors_changes_nothing() {
+    let backend = MemoryBackend::new();
+    let mut vault = common::vault_with_entries(&backend, &[]);
+    let temp_dir = TempDir::new().unwrap();
+    let path = write_env(&temp_dir, "ok=1\nbroken\n");
+
+    invalid_input_message(vault.import_from_plain_env(&path, true, false, false));
+    assert!(vault.list_entries(None, None).unwrap().is_empty());
+}
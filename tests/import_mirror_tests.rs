@@ -0,0 +1,84 @@
+//! Mirror Import Tests
+//!
+//! Tests for importing in mirror mode (exact snapshot restore).
+
+use ironkey::storage::MemoryBackend;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+mod common;
+
+/// Export `entries` from a throwaway vault and return the file path
+fn backup_of(dir: &Path, entries: &[(&str, &str)]) -> PathBuf {
+    let path = dir.join("backup.ik");
+    common::vault_with_entries(&MemoryBackend::new(), entries)
+        .export_to_file(&path, "export_pw".to_string())
+        .unwrap();
+    path
+}
+
+#[test]
+fn test_mirror_removes_entries_absent_from_export() {
+    let temp_dir = TempDir::new().unwrap();
+    let backup = backup_of(
+        temp_dir.path(),
+        &[("github", "ghp_backup"), ("aws", "aws_key")],
+    );
+
+    let mut vault = common::vault_with_entries(
+        &MemoryBackend::new(),
+        &[("github", "ghp_local"), ("stray", "remove_me")],
+    );
+    let result = vault
+        .import_from_file_mirror(&backup, "export_pw".to_string(), false)
+        .unwrap();
+
+    assert_eq!(result.added, vec!["aws".to_string()]);
+    assert_eq!(result.updated, vec!["github".to_string()]);
+    assert_eq!(result.deleted, vec!["stray".to_string()]);
+
+    let mut keys: Vec<&String> = vault
+        .list_entries(None, None)
+        .unwrap()
+        .into_iter()
+        .map(|(key, _)| key)
+        .collect();
+    keys.sort();
+    assert_eq!(keys, vec!["aws", "github"]);
+    assert_eq!(vault.get_entry("github").unwrap(), "ghp_backup");
+}
+
+#[test]
+fn test_mirror_diff_changes_nothing() {
+    let temp_dir = TempDir::new().unwrap();
+    let backup = backup_of(temp_dir.path(), &[("github", "ghp_backup")]);
+
+    let mut vault = common::vault_with_entries(
+        &MemoryBackend::new(),
+        &[("github", "ghp_local"), ("stray", "keep_me")],
+    );
+    let result = vault
+        .import_from_file_mirror(&backup, "export_pw".to_string(), true)
+        .unwrap();
+
+    assert_eq!(result.deleted, vec!["stray".to_string()]);
+    assert_eq!(vault.get_entry("stray").unwrap(), "keep_me");
+    assert_eq!(vault.get_entry("github").unwrap(), "ghp_local");
+}
+
+#[test]
+fn test_mirror_keeps_locked_entries() {
+    let temp_dir = TempDir::new().unwrap();
+    let backup = backup_of(temp_dir.path(), &[("github", "ghp_backup")]);
+
+    let mut vault = common::vault_with_entries(&MemoryBackend::new(), &[("protected", "secret")]);
+    vault.toggle_lock("protected").unwrap();
+
+    let result = vault
+        .import_from_file_mirror(&backup, "export_pw".to_string(), false)
+        .unwrap();
+
+    assert!(result.deleted.is_empty());
+    assert_eq!(result.skipped, vec!["protected".to_string()]);
+    assert_eq!(vault.list_entries(None, None).unwrap().len(), 2);
+}
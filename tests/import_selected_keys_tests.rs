@@ -0,0 +1,114 @@
+//! Selective Import Tests
+//!
+//! Tests for importing only some entries from an .ik file (`ik import --key a --key b`).
+
+use ironkey::storage::MemoryBackend;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+mod common;
+
+/// Export aws/github/netflix to `dir` and return the file path
+fn create_export(dir: &Path) -> PathBuf {
+    let export_path = dir.join("backup.ik");
+    common::vault_with_entries(
+        &MemoryBackend::new(),
+        &[
+            ("aws", "aws_backup"),
+            ("github", "github_backup"),
+            ("netflix", "netflix_backup"),
+        ],
+    )
+    .export_to_file(&export_path, "export_pw".to_string())
+    .unwrap();
+    export_path
+}
+
+fn keys(names: &[&str]) -> Vec<String> {
+    names.iter().map(|k| k.to_string()).collect()
+}
+
+#[test]
+fn test_import_only_requested_keys() {
+    let temp_dir = TempDir::new().unwrap();
+    let export_path = create_export(temp_dir.path());
+
+    let mut vault = common::vault_with_entries(&MemoryBackend::new(), &[]);
+    let result = vault
+        .import_from_file_with_keys(
+            &export_path,
+            "export_pw".to_string(),
+            true,
+            false,
+            false,
+            Some(&keys(&["github"])),
+        )
+        .unwrap();
+
+    assert_eq!(result.added, ["github"]);
+    assert!(result.not_found.is_empty());
+    assert_eq!(result.total_in_export, 3);
+    assert_eq!(vault.get_entry("github").unwrap(), "github_backup");
+    assert_eq!(vault.list_entries(None, None).unwrap().len(), 1);
+}
+
+#[test]
+fn test_missing_keys_are_reported() {
+    let temp_dir = TempDir::new().unwrap();
+    let export_path = create_export(temp_dir.path());
+
+    let mut vault = common::vault_with_entries(&MemoryBackend::new(), &[]);
+    let result = vault
+        .import_from_file_with_keys(
+            &export_path,
+            "export_pw".to_string(),
+            true,
+            false,
+            false,
+            Some(&keys(&["nope", "aws", "gone", "nope"])),
+        )
+        .unwrap();
+
+    assert_eq!(result.added, ["aws"]);
+    assert_eq!(result.not_found, ["gone", "nope"]);
+}
+
+#[test]
+fn test_selected_keys_with_replace_and_diff() {
+    let temp_dir = TempDir::new().unwrap();
+    let export_path = create_export(temp_dir.path());
+
+    let mut vault = common::vault_with_entries(
+        &MemoryBackend::new(),
+        &[("aws", "aws_current"), ("github", "github_current")],
+    );
+
+    // The preview only covers the requested subset and changes nothing
+    let preview = vault
+        .import_from_file_with_keys(
+            &export_path,
+            "export_pw".to_string(),
+            false,
+            false,
+            true,
+            Some(&keys(&["aws", "netflix"])),
+        )
+        .unwrap();
+    assert_eq!(preview.added, ["netflix"]);
+    assert!(preview.skipped.is_empty() && preview.updated.is_empty());
+    assert_eq!(vault.list_entries(None, None).unwrap().len(), 2);
+
+    let result = vault
+        .import_from_file_with_keys(
+            &export_path,
+            "export_pw".to_string(),
+            false,
+            true,
+            false,
+            Some(&keys(&["aws"])),
+        )
+        .unwrap();
+    assert_eq!(result.updated, ["aws"]);
+    assert_eq!(vault.get_entry("aws").unwrap(), "aws_backup");
+    assert_eq!(vault.get_entry("github").unwrap(), "github_current");
+}
@@ -11,6 +11,8 @@ use std::fs;
 use std::path::PathBuf;
 use tempfile::TempDir;
 
+mod common;
+
 /// Helper: Create a test vault with specific entries
 fn create_test_vault_with_entries(
     master_password: &str,
@@ -36,6 +38,7 @@ fn create_test_vault_with_entries(
 
 /// Cleanup function
 fn cleanup_test_files(export_path: Option<&PathBuf>) {
+    common::use_isolated_database();
     if let Ok(db_path) = storage::get_database_path() {
         let _ = fs::remove_file(db_path);
     }
@@ -391,6 +394,36 @@ fn test_import_validates_format_version() {
     cleanup_test_files(Some(&export_path));
 }
 
+#[test]
+fn test_import_accepts_newer_patch_version() {
+    let temp_dir = TempDir::new().unwrap();
+    let export_path = temp_dir.path().join("export.ik");
+
+    {
+        let vault =
+            create_test_vault_with_entries("master123", vec![("github", "ghp_token", false)])
+                .unwrap();
+        vault
+            .export_to_file(&export_path, "export123".to_string())
+            .unwrap();
+    }
+
+    // Same major version, newer patch: still readable
+    let mut json: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&export_path).unwrap()).unwrap();
+    json["format_version"] = serde_json::Value::from("1.0.5");
+    fs::write(&export_path, json.to_string()).unwrap();
+
+    let mut vault = create_test_vault_with_entries("master456", vec![]).unwrap();
+    let result = vault
+        .import_from_file(&export_path, "export123".to_string(), true, false, false)
+        .unwrap();
+    assert_eq!(result.added, vec!["github"]);
+    assert_eq!(vault.get_entry("github").unwrap(), "ghp_token");
+
+    cleanup_test_files(Some(&export_path));
+}
+
 #[test]
 fn test_import_malformed_json() {
     let temp_dir = TempDir::new().unwrap();
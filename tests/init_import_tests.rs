@@ -0,0 +1,78 @@
+//! Init-With-Import Tests
+//!
+//! Tests for `ik init --import`, bootstrapping a new vault from a backup.
+//! NOTE: These tests must run serially because they share the same database file.
+
+use ironkey::storage;
+use ironkey::vault::Vault;
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+mod common;
+
+fn cleanup() {
+    common::use_isolated_database();
+    if let Ok(db_path) = storage::get_database_path() {
+        let _ = fs::remove_file(db_path);
+    }
+}
+
+/// Create a backup with two entries (one locked) and remove the source vault
+fn create_backup(export_path: &Path) {
+    cleanup();
+    let mut vault = Vault::init("old_master".to_string()).unwrap();
+    vault
+        .create_entry("github".to_string(), "ghp_token".to_string())
+        .unwrap();
+    vault
+        .create_entry("aws".to_string(), "aws_secret".to_string())
+        .unwrap();
+    vault.toggle_lock("aws").unwrap();
+    vault
+        .export_to_file(export_path, "backup_pw".to_string())
+        .unwrap();
+    drop(vault);
+    cleanup();
+}
+
+#[test]
+fn test_init_with_import_contains_backup_entries() {
+    let temp_dir = TempDir::new().unwrap();
+    let export_path = temp_dir.path().join("backup.ik");
+    create_backup(&export_path);
+
+    let (vault, result) = Vault::init_from_backup(
+        "new_master".to_string(),
+        &export_path,
+        "backup_pw".to_string(),
+    )
+    .unwrap();
+    assert_eq!(result.added.len(), 2);
+    assert_eq!(vault.get_entry("github").unwrap(), "ghp_token");
+    drop(vault);
+
+    // New master password unlocks the bootstrapped vault
+    let vault = Vault::unlock("new_master".to_string()).unwrap();
+    assert_eq!(vault.list_entries(None, None).unwrap().len(), 2);
+    assert_eq!(
+        vault.list_entries(None, Some(true)).unwrap(),
+        vec![(&"aws".to_string(), true)]
+    );
+
+    cleanup();
+}
+
+#[test]
+fn test_init_with_import_wrong_password_leaves_no_vault() {
+    let temp_dir = TempDir::new().unwrap();
+    let export_path = temp_dir.path().join("backup.ik");
+    create_backup(&export_path);
+
+    let result =
+        Vault::init_from_backup("new_master".to_string(), &export_path, "wrong".to_string());
+    assert!(result.is_err());
+    assert!(!storage::exists().unwrap());
+
+    cleanup();
+}
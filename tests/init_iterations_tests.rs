@@ -0,0 +1,90 @@
+//! Init Iterations Tests
+//!
+//! Tests for `ik init --iterations <N>` (custom PBKDF2 cost for new vaults).
+
+use ironkey::crypto::{KdfAlgorithm, KdfParams, MIN_PBKDF2_ITERATIONS};
+use ironkey::error::Error;
+use ironkey::storage::{MemoryBackend, StorageBackend};
+use ironkey::vault::VaultBuilder;
+
+#[test]
+fn test_custom_iterations_are_stored_and_used() {
+    let backend = MemoryBackend::new();
+    let mut vault = VaultBuilder::new()
+        .backend(Box::new(backend.clone()))
+        .kdf(KdfParams::pbkdf2(250_000))
+        .init("master".to_string())
+        .unwrap();
+    vault
+        .create_entry("github".to_string(), "ghp_token".to_string())
+        .unwrap();
+    drop(vault);
+
+    let db = backend.load().unwrap();
+    assert_eq!(db.iterations, 250_000);
+    assert_eq!(db.kdf, KdfAlgorithm::Pbkdf2);
+
+    let vault = VaultBuilder::new()
+        .backend(Box::new(backend))
+        .unlock("master".to_string())
+        .unwrap();
+    assert_eq!(vault.get_entry("github").unwrap(), "ghp_token");
+}
+
+#[test]
+fn test_too_few_iterations_rejected() {
+    let backend = MemoryBackend::new();
+    let result = VaultBuilder::new()
+        .backend(Box::new(backend.clone()))
+        .kdf(KdfParams::pbkdf2(MIN_PBKDF2_ITERATIONS - 1))
+        .init("master".to_string());
+
+    assert!(matches!(result, Err(Error::InvalidInput(_))));
+    assert!(!backend.exists().unwrap());
+}
+
+#[test]
+fn test_minimum_is_the_default() {
+    assert_eq!(MIN_PBKDF2_ITERATIONS, 100_000);
+    assert_eq!(
+        KdfParams::default_for(KdfAlgorithm::Pbkdf2),
+        KdfParams::pbkdf2(MIN_PBKDF2_ITERATIONS)
+    );
+    assert!(KdfParams::pbkdf2(MIN_PBKDF2_ITERATIONS).validate().is_ok());
+}
+
+#[test]
+fn test_minimum_only_applies_to_pbkdf2() {
+    // Argon2's iteration count is a time cost measured in passes, not PBKDF2 rounds
+    assert!(KdfParams::argon2id(1024, 3, 1).validate().is_ok());
+    assert!(KdfParams::pbkdf2(10_000).validate().is_err());
+}
+
+#[test]
+fn test_existing_low_iteration_vaults_still_unlock() {
+    // Vaults created before the minimum existed keep whatever count they were stored with
+    let backend = MemoryBackend::new();
+    drop(
+        VaultBuilder::new()
+            .backend(Box::new(backend.clone()))
+            .init("master".to_string())
+            .unwrap(),
+    );
+
+    let mut db = backend.load().unwrap();
+    let salt = db.get_salt().unwrap();
+    db.iterations = 1_000;
+    db.mac = None;
+    db.master_hash = base64::Engine::encode(
+        &base64::engine::general_purpose::STANDARD,
+        ironkey::crypto::hash_password("master", &salt, 1_000).unwrap(),
+    );
+    backend.save(&db).unwrap();
+
+    assert!(
+        VaultBuilder::new()
+            .backend(Box::new(backend))
+            .unlock("master".to_string())
+            .is_ok()
+    );
+}
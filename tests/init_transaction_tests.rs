@@ -0,0 +1,86 @@
+//! Transactional Init Tests
+//!
+//! Tests that a failed `init` never leaves a half-created vault behind.
+
+use ironkey::error::{Error, Result};
+use ironkey::storage::{Database, JsonFileBackend, StorageBackend};
+use ironkey::vault::VaultBuilder;
+use std::fs;
+use std::path::PathBuf;
+use tempfile::TempDir;
+
+/// JSON backend whose writes fail after leaving a truncated file behind
+struct PartialWriteBackend {
+    path: PathBuf,
+}
+
+impl StorageBackend for PartialWriteBackend {
+    fn exists(&self) -> Result<bool> {
+        Ok(self.path.exists())
+    }
+
+    fn load(&self) -> Result<Database> {
+        JsonFileBackend::new(self.path.clone()).load()
+    }
+
+    fn save(&self, _database: &Database) -> Result<()> {
+        fs::write(&self.path, "{\"master_salt\": \"tru").unwrap();
+        Err(Error::DatabaseSaveFailed("disk full".to_string()))
+    }
+
+    fn remove(&self) -> Result<()> {
+        JsonFileBackend::new(self.path.clone()).remove()
+    }
+}
+
+#[test]
+fn test_failed_write_leaves_no_partial_vault() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("ironkey.json");
+
+    let result = VaultBuilder::new()
+        .backend(Box::new(PartialWriteBackend { path: path.clone() }))
+        .init("master".to_string());
+    assert!(matches!(result, Err(Error::DatabaseSaveFailed(_))));
+    assert!(!path.exists());
+
+    // Nothing blocks a retry
+    VaultBuilder::new()
+        .backend(Box::new(JsonFileBackend::new(path.clone())))
+        .init("master".to_string())
+        .unwrap();
+    assert!(path.exists());
+}
+
+#[test]
+fn test_failed_backup_import_writes_nothing() {
+    let temp_dir = TempDir::new().unwrap();
+    let backup = temp_dir.path().join("backup.ik");
+    let mut source = VaultBuilder::new()
+        .backend(Box::new(JsonFileBackend::new(
+            temp_dir.path().join("source.json"),
+        )))
+        .init("master".to_string())
+        .unwrap();
+    source
+        .create_entry("github".to_string(), "ghp_token".to_string())
+        .unwrap();
+    source
+        .export_to_file(&backup, "export_pw".to_string())
+        .unwrap();
+
+    let path = temp_dir.path().join("ironkey.json");
+    let result = VaultBuilder::new()
+        .backend(Box::new(JsonFileBackend::new(path.clone())))
+        .init_from_backup("master".to_string(), &backup, "wrong_pw".to_string());
+    assert!(result.is_err());
+    assert!(!path.exists());
+
+    // Retrying with the right password succeeds
+    let (vault, imported) = VaultBuilder::new()
+        .backend(Box::new(JsonFileBackend::new(path.clone())))
+        .init_from_backup("master".to_string(), &backup, "export_pw".to_string())
+        .unwrap();
+    assert_eq!(imported.added, vec!["github".to_string()]);
+    assert_eq!(vault.get_entry("github").unwrap(), "ghp_token");
+}
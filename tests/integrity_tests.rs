@@ -0,0 +1,84 @@
+//! Integrity Check Tests
+//!
+//! Tests for `Vault::verify_integrity` (`ik doctor`).
+
+use base64::{Engine as _, engine::general_purpose};
+use ironkey::storage::{MemoryBackend, StorageBackend};
+use ironkey::vault::Vault;
+
+mod common;
+
+/// Open the way `ik doctor` does, so a MAC mismatch doesn't stop the check
+fn reopen_unverified(backend: &MemoryBackend) -> Vault {
+    Vault::unlock_unverified_with_backend(Box::new(backend.clone()), "master".to_string()).unwrap()
+}
+
+#[test]
+fn test_healthy_vault() {
+    let backend = MemoryBackend::new();
+    let mut vault = common::vault_with_keys(&backend, &["github", "aws"]);
+    vault.toggle_lock("github").unwrap();
+    vault
+        .set_note("aws", "rotate quarterly".to_string())
+        .unwrap();
+
+    let report = vault.verify_integrity().unwrap();
+    assert!(report.is_healthy());
+    assert_eq!(report.healthy, ["aws", "github"]);
+}
+
+#[test]
+fn test_damaged_entries_are_reported() {
+    let backend = MemoryBackend::new();
+    let mut vault =
+        common::vault_with_keys(&backend, &["bad_base64", "bad_cipher", "bad_nonce", "good"]);
+    vault.toggle_lock("bad_cipher").unwrap();
+    drop(vault);
+
+    let mut db = backend.load().unwrap();
+    db.entries.get_mut("bad_base64").unwrap().encrypted_value = "not base64!".to_string();
+    let entry = db.entries.get_mut("bad_cipher").unwrap();
+    let mut ciphertext = entry.get_encrypted_value().unwrap();
+    ciphertext[0] ^= 0xFF;
+    entry.encrypted_value = general_purpose::STANDARD.encode(&ciphertext);
+    db.entries.get_mut("bad_nonce").unwrap().nonce = "%%%".to_string();
+    backend.save(&db).unwrap();
+
+    let report = reopen_unverified(&backend).verify_integrity().unwrap();
+    assert!(!report.is_healthy());
+    assert!(report.mac_mismatch);
+    assert_eq!(report.healthy, ["good"]);
+    let damaged: Vec<(&str, &str)> = report
+        .damaged
+        .iter()
+        .map(|(key, problem)| (key.as_str(), problem.as_str()))
+        .collect();
+    assert_eq!(
+        damaged,
+        [
+            ("bad_base64", "value is not valid base64"),
+            ("bad_cipher", "value does not decrypt"),
+            ("bad_nonce", "nonce is not valid base64"),
+        ]
+    );
+}
+
+#[test]
+fn test_damaged_notes_are_reported() {
+    let backend = MemoryBackend::new();
+    let mut vault = common::vault_with_keys(&backend, &["aws", "good"]);
+    vault.set_note("aws", "recovery url".to_string()).unwrap();
+    drop(vault);
+
+    let mut db = backend.load().unwrap();
+    let notes = db.entries.get_mut("aws").unwrap().notes.as_mut().unwrap();
+    notes.nonce = general_purpose::STANDARD.encode([0u8; 12]);
+    backend.save(&db).unwrap();
+
+    let report = reopen_unverified(&backend).verify_integrity().unwrap();
+    assert_eq!(report.healthy, ["good"]);
+    assert_eq!(
+        report.damaged,
+        [("aws".to_string(), "notes do not decrypt".to_string())]
+    );
+}
@@ -0,0 +1,124 @@
+//! JSON Output Tests
+//!
+//! Tests for the serialized shape of `ImportResult`, `ExportPreview` and the
+//! `ik list`/`ik get` records and errors (`--json` output).
+
+use ironkey::display;
+use ironkey::export::{EXPORT_FORMAT_VERSION_DEDUP, ExportOptions};
+use ironkey::import::ImportResult;
+use ironkey::storage::MemoryBackend;
+use ironkey::vault::VaultBuilder;
+use serde_json::{Value, json};
+
+#[test]
+fn test_import_result_json_shape() {
+    let result = ImportResult {
+        added: vec!["aws".to_string()],
+        updated: vec!["github".to_string()],
+        skipped: vec![],
+        deleted: vec![],
+        not_found: vec![],
+        total_in_export: 2,
+    };
+
+    let value: Value = serde_json::to_value(&result).unwrap();
+    assert_eq!(
+        value,
+        json!({
+            "added": ["aws"],
+            "updated": ["github"],
+            "skipped": [],
+            "total_in_export": 2
+        })
+    );
+}
+
+#[test]
+fn test_import_result_json_includes_mirror_deletions() {
+    let result = ImportResult {
+        added: vec![],
+        updated: vec![],
+        skipped: vec![],
+        deleted: vec!["stray".to_string()],
+        not_found: vec![],
+        total_in_export: 0,
+    };
+
+    let value: Value = serde_json::to_value(&result).unwrap();
+    assert_eq!(value["deleted"], json!(["stray"]));
+}
+
+#[test]
+fn test_export_preview_json_shape() {
+    let mut vault = VaultBuilder::new()
+        .backend(Box::new(MemoryBackend::new()))
+        .init("master".to_string())
+        .unwrap();
+    for key in ["github", "aws"] {
+        vault
+            .create_entry(key.to_string(), "value".to_string())
+            .unwrap();
+    }
+    vault.toggle_lock("aws").unwrap();
+
+    let preview = vault.export_preview(ExportOptions {
+        dedup: true,
+        ..Default::default()
+    });
+
+    let value: Value = serde_json::to_value(&preview).unwrap();
+    assert_eq!(
+        value,
+        json!({
+            "format_version": EXPORT_FORMAT_VERSION_DEDUP,
+            "total": 2,
+            "locked": 1,
+            "keys": ["aws", "github"]
+        })
+    );
+}
+
+#[test]
+fn test_list_json_shape() {
+    let mut vault = VaultBuilder::new()
+        .backend(Box::new(MemoryBackend::new()))
+        .init("master".to_string())
+        .unwrap();
+    vault
+        .create_entry("github".to_string(), "ghp_token".to_string())
+        .unwrap();
+    vault
+        .create_entry("aws".to_string(), "AKIA".to_string())
+        .unwrap();
+    vault.toggle_lock("github").unwrap();
+
+    let summaries = vault.entry_summaries(None, None).unwrap();
+    let value: Value = serde_json::from_str(&display::list_json(&summaries)).unwrap();
+    assert_eq!(
+        value,
+        json!([
+            {"key": "aws", "locked": false},
+            {"key": "github", "locked": true}
+        ])
+    );
+    assert_eq!(display::list_json(&[]), "[]");
+}
+
+#[test]
+fn test_value_json_escapes_value() {
+    let output = display::value_json("api", "a\"b\\c\n");
+    let value: Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(value, json!({"key": "api", "value": "a\"b\\c\n"}));
+    assert!(!output.contains('\n'));
+}
+
+#[test]
+fn test_error_json_shape() {
+    let error = ironkey::error::Error::EntryNotFound("x".to_string());
+    let value: Value = serde_json::from_str(&display::error_json(&error)).unwrap();
+
+    assert_eq!(
+        value,
+        json!({ "error": "EntryNotFound", "message": "Entry 'x' not found" })
+    );
+}
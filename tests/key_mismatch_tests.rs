@@ -0,0 +1,93 @@
+//! Key Mismatch Detection Tests
+//!
+//! Tests that unlock test-decrypts entries after verifying the master hash.
+//! NOTE: These tests must run serially because they share the same database file.
+
+use ironkey::crypto::{derive_key, encrypt, generate_salt, hash_password};
+use ironkey::error::Error;
+use ironkey::storage::{self, Database, Entry};
+use ironkey::vault::Vault;
+use std::fs;
+
+mod common;
+
+const TEST_ITERATIONS: u32 = 100_000;
+
+fn cleanup() {
+    common::use_isolated_database();
+    if let Ok(db_path) = storage::get_database_path() {
+        let _ = fs::remove_file(db_path);
+    }
+}
+
+/// Build a database whose hash matches `password` but whose entries use `entry_key`
+fn database_with_entry_key(password: &str, entry_key: &[u8], count: usize) -> Database {
+    let salt = generate_salt().unwrap();
+    let hash = hash_password(password, &salt, TEST_ITERATIONS).unwrap();
+    let mut db = Database::new(salt, hash, TEST_ITERATIONS);
+
+    for i in 0..count {
+        let encrypted = encrypt(format!("value_{i}").as_bytes(), entry_key).unwrap();
+        db.entries.insert(
+            format!("key_{i}"),
+            Entry::new(encrypted.ciphertext, encrypted.nonce, false),
+        );
+    }
+
+    db
+}
+
+#[test]
+fn test_valid_hash_with_foreign_entries_is_key_mismatch() {
+    cleanup();
+    let other_key = derive_key("someone_else", &generate_salt().unwrap(), TEST_ITERATIONS).unwrap();
+    let db = database_with_entry_key("master", &other_key, 3);
+    storage::save(&db).unwrap();
+
+    let result = Vault::unlock("master".to_string());
+    assert!(matches!(result, Err(Error::KeyMismatch)));
+
+    // Wrong password is still reported as such
+    let result = Vault::unlock("wrong".to_string());
+    assert!(matches!(result, Err(Error::InvalidMasterPassword)));
+
+    cleanup();
+}
+
+#[test]
+fn test_single_corrupt_entry_does_not_block_unlock() {
+    cleanup();
+    let mut vault = Vault::init("master".to_string()).unwrap();
+    vault
+        .create_entry("good".to_string(), "value".to_string())
+        .unwrap();
+    vault
+        .create_entry("bad".to_string(), "value".to_string())
+        .unwrap();
+    drop(vault);
+
+    // Corrupt one entry's ciphertext (still valid base64) in a vault saved before the MAC existed
+    let mut db = storage::load().unwrap();
+    db.mac = None;
+    let bad = db.entries.get_mut("bad").unwrap();
+    let mut ciphertext = bad.get_encrypted_value().unwrap();
+    ciphertext[0] ^= 0xFF;
+    *bad = Entry::new(ciphertext, bad.get_nonce().unwrap(), false);
+    storage::save(&db).unwrap();
+
+    let vault = Vault::unlock("master".to_string()).unwrap();
+    assert_eq!(vault.get_entry("good").unwrap(), "value");
+    assert!(vault.get_entry("bad").is_err());
+
+    cleanup();
+}
+
+#[test]
+fn test_empty_vault_unlocks() {
+    cleanup();
+    drop(Vault::init("master".to_string()).unwrap());
+
+    assert!(Vault::unlock("master".to_string()).is_ok());
+
+    cleanup();
+}
@@ -0,0 +1,108 @@
+//! Key Pattern Tests
+//!
+//! Tests for `ik list --glob` / `--regex` (`KeyPattern` and
+//! `Vault::list_entries_matching`). These use `MemoryBackend`, so they are safe
+//! to run in parallel.
+
+use ironkey::error::Error;
+use ironkey::key_pattern::{KeyPattern, MatchMode};
+use ironkey::storage::MemoryBackend;
+use ironkey::vault::Vault;
+
+mod common;
+
+fn matching(vault: &Vault, pattern: &KeyPattern) -> Vec<String> {
+    vault
+        .list_entries_matching(Some(pattern), None, None)
+        .unwrap()
+        .into_iter()
+        .map(|(key, _)| key.clone())
+        .collect()
+}
+
+const KEYS: [&str; 5] = [
+    "aws/dev/prod",
+    "aws/eu/prod",
+    "aws/eu/west/prod",
+    "aws/eu/staging",
+    "gcp/eu/prod",
+];
+
+#[test]
+fn test_glob_star_stays_within_one_segment() {
+    let vault = common::vault_with_keys(&MemoryBackend::new(), &KEYS);
+    let pattern = KeyPattern::new("aws/*/prod", MatchMode::Glob).unwrap();
+
+    assert_eq!(matching(&vault, &pattern), ["aws/dev/prod", "aws/eu/prod"]);
+}
+
+#[test]
+fn test_glob_double_star_crosses_segments() {
+    let vault = common::vault_with_keys(&MemoryBackend::new(), &KEYS);
+    let pattern = KeyPattern::new("aws/**/prod", MatchMode::Glob).unwrap();
+
+    assert_eq!(
+        matching(&vault, &pattern),
+        ["aws/dev/prod", "aws/eu/prod", "aws/eu/west/prod"]
+    );
+}
+
+#[test]
+fn test_glob_matches_whole_key() {
+    let pattern = KeyPattern::new("aws", MatchMode::Glob).unwrap();
+
+    assert!(pattern.is_match("aws"));
+    assert!(!pattern.is_match("aws/eu/prod"));
+}
+
+#[test]
+fn test_regex_matches_anywhere_unless_anchored() {
+    let vault = common::vault_with_keys(&MemoryBackend::new(), &KEYS);
+
+    let unanchored = KeyPattern::new("eu/(prod|staging)", MatchMode::Regex).unwrap();
+    assert_eq!(
+        matching(&vault, &unanchored),
+        ["aws/eu/prod", "aws/eu/staging", "gcp/eu/prod"]
+    );
+
+    let anchored = KeyPattern::new("^aws/[a-z]+/prod$", MatchMode::Regex).unwrap();
+    assert_eq!(matching(&vault, &anchored), ["aws/dev/prod", "aws/eu/prod"]);
+}
+
+#[test]
+fn test_substring_mode_matches_list_entries_search() {
+    let vault = common::vault_with_keys(&MemoryBackend::new(), &KEYS);
+    let pattern = KeyPattern::new("EU/P", MatchMode::Substring).unwrap();
+
+    let expected: Vec<String> = vault
+        .list_entries(Some("EU/P"), None)
+        .unwrap()
+        .into_iter()
+        .map(|(key, _)| key.clone())
+        .collect();
+    assert_eq!(matching(&vault, &pattern), expected);
+    assert_eq!(expected, ["aws/eu/prod", "gcp/eu/prod"]);
+}
+
+#[test]
+fn test_pattern_combines_with_lock_filter() {
+    let mut vault = common::vault_with_keys(&MemoryBackend::new(), &KEYS);
+    vault.toggle_lock("aws/eu/prod").unwrap();
+    let pattern = KeyPattern::new("aws/*/prod", MatchMode::Glob).unwrap();
+
+    let locked = vault
+        .list_entries_matching(Some(&pattern), Some(true), None)
+        .unwrap();
+
+    assert_eq!(locked.len(), 1);
+    assert_eq!(locked[0].0, "aws/eu/prod");
+}
+
+#[test]
+fn test_invalid_patterns_are_rejected() {
+    let glob = KeyPattern::new("aws/[prod", MatchMode::Glob);
+    assert!(matches!(glob, Err(Error::InvalidInput(msg)) if msg.contains("glob")));
+
+    let regex = KeyPattern::new("aws/(prod", MatchMode::Regex);
+    assert!(matches!(regex, Err(Error::InvalidInput(msg)) if msg.contains("regex")));
+}
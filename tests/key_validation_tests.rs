@@ -0,0 +1,96 @@
+//! Key Validation Tests
+//!
+//! Tests for `Vault::validate_key` and the create, update and rename paths that use it.
+
+use ironkey::error::Error;
+use ironkey::storage::MemoryBackend;
+use ironkey::vault::{MAX_KEY_LENGTH, Vault, VaultBuilder};
+
+fn new_vault() -> Vault {
+    VaultBuilder::new()
+        .backend(Box::new(MemoryBackend::new()))
+        .init("master".to_string())
+        .unwrap()
+}
+
+fn assert_invalid(result: ironkey::error::Result<()>, expected: &str) {
+    match result {
+        Err(Error::InvalidInput(msg)) => assert!(
+            msg.contains(expected),
+            "message {msg:?} should mention {expected:?}"
+        ),
+        other => panic!("expected InvalidInput, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_normal_keys_pass() {
+    for key in [
+        "github",
+        "aws/prod/access_key",
+        "my key with spaces",
+        "ключ",
+        "emoji-🔑",
+        &"k".repeat(MAX_KEY_LENGTH),
+    ] {
+        assert!(Vault::validate_key(key).is_ok(), "{key:?} should be valid");
+    }
+}
+
+#[test]
+fn test_rejects_empty_keys() {
+    assert_invalid(Vault::validate_key(""), "empty");
+    assert_invalid(Vault::validate_key("   "), "empty");
+}
+
+#[test]
+fn test_rejects_control_characters() {
+    for key in [
+        "\n",
+        "line\nbreak",
+        "tab\there",
+        "cr\r",
+        "nul\0",
+        "esc\x1b[31m",
+    ] {
+        assert_invalid(Vault::validate_key(key), "control characters");
+    }
+}
+
+#[test]
+fn test_rejects_long_keys() {
+    // The limit counts characters, not bytes
+    assert!(Vault::validate_key(&"é".repeat(MAX_KEY_LENGTH)).is_ok());
+    assert_invalid(
+        Vault::validate_key(&"k".repeat(MAX_KEY_LENGTH + 1)),
+        "too long",
+    );
+}
+
+#[test]
+fn test_create_update_and_rename_validate() {
+    let mut vault = new_vault();
+
+    assert_invalid(
+        vault.create_entry("bad\nkey".to_string(), "value".to_string()),
+        "control characters",
+    );
+    assert_invalid(
+        vault.update_entry(String::new(), "value".to_string()),
+        "empty",
+    );
+
+    vault
+        .create_entry("good".to_string(), "value".to_string())
+        .unwrap();
+    assert_invalid(vault.rename_entry("good", "bad\tkey"), "control characters");
+    assert_eq!(vault.get_entry("good").unwrap(), "value");
+    assert!(matches!(
+        vault.rename_prefix("go", "\n"),
+        Err(Error::InvalidInput(_))
+    ));
+    assert_eq!(vault.get_entry("good").unwrap(), "value");
+
+    vault.rename_entry("good", "better").unwrap();
+    assert_eq!(vault.get_entry("better").unwrap(), "value");
+}
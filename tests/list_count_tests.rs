@@ -0,0 +1,39 @@
+//! List Summary Tests
+//!
+//! Tests for the trailing `ik list` summary line.
+
+use ironkey::display::list_summary;
+use ironkey::vault::EntrySummary;
+
+fn entry(key: &str, is_locked: bool) -> EntrySummary {
+    EntrySummary {
+        key: key.to_string(),
+        is_locked,
+        access_count: 0,
+        last_accessed: None,
+        label: None,
+        created_at: None,
+        updated_at: None,
+        tag_count: 0,
+    }
+}
+
+#[test]
+fn test_summary_counts_locked_entries() {
+    let entries = [
+        entry("aws", false),
+        entry("github", true),
+        entry("prod", false),
+    ];
+    assert_eq!(list_summary(&entries), "3 entries (1 locked)");
+}
+
+#[test]
+fn test_summary_singular() {
+    assert_eq!(list_summary(&[entry("aws", false)]), "1 entry (0 locked)");
+}
+
+#[test]
+fn test_summary_empty() {
+    assert_eq!(list_summary(&[]), "0 entries (0 locked)");
+}
@@ -0,0 +1,138 @@
+//! List Long-Format Tests
+//!
+//! Tests for the `ik list --long` table formatting and masked previews.
+
+use ironkey::display::{
+    Column, format_long_header, format_long_row, key_column_width, long_columns, mask_value,
+};
+use ironkey::vault::EntrySummary;
+
+fn sample_entry() -> EntrySummary {
+    EntrySummary {
+        key: "github_token".to_string(),
+        is_locked: true,
+        access_count: 7,
+        last_accessed: None,
+        label: None,
+        created_at: None,
+        updated_at: None,
+        tag_count: 3,
+    }
+}
+
+#[test]
+fn test_row_formatting_produces_expected_columns() {
+    let entry = sample_entry();
+    let columns = vec![
+        Column::Key,
+        Column::Locked,
+        Column::Accessed,
+        Column::LastAccessed,
+        Column::Created,
+        Column::Updated,
+        Column::Tags,
+        Column::Preview,
+    ];
+    let key_width = key_column_width(std::slice::from_ref(&entry));
+
+    let row = format_long_row(&entry, None, &columns, key_width);
+    let cells: Vec<&str> = row.split_whitespace().collect();
+
+    assert_eq!(
+        cells,
+        vec!["github_token", "yes", "7", "-", "-", "-", "3", "-"]
+    );
+}
+
+#[test]
+fn test_timestamp_columns_are_formatted() {
+    let mut entry = sample_entry();
+    entry.created_at = Some("2024-01-15T10:30:00+00:00".to_string());
+    entry.updated_at = Some("not a timestamp".to_string());
+    let columns = vec![Column::Key, Column::Created, Column::Updated];
+    let key_width = key_column_width(std::slice::from_ref(&entry));
+
+    let row = format_long_row(&entry, None, &columns, key_width);
+    let header = format_long_header(&columns, key_width);
+
+    let created = ironkey::display::format_timestamp(entry.created_at.as_deref());
+    assert_ne!(created, "-");
+    assert_eq!(header.find("CREATED"), row.find(&created));
+    assert!(row.trim_end().ends_with('-'));
+}
+
+#[test]
+fn test_header_and_row_are_aligned() {
+    let entry = sample_entry();
+    let columns = vec![Column::Key, Column::Locked, Column::Accessed];
+    let key_width = key_column_width(std::slice::from_ref(&entry));
+
+    let header = format_long_header(&columns, key_width);
+    let row = format_long_row(&entry, None, &columns, key_width);
+
+    assert_eq!(header.find("LOCKED"), row.find("yes"));
+    assert_eq!(header.find("READS"), row.find('7'));
+}
+
+#[test]
+fn test_preview_is_shown_when_provided() {
+    let mut entry = sample_entry();
+    entry.is_locked = false;
+    let columns = vec![Column::Key, Column::Preview];
+    let preview = mask_value("ghp_secret_value");
+
+    let row = format_long_row(&entry, Some(&preview), &columns, 12);
+    assert!(row.ends_with("gh••••••"));
+}
+
+#[test]
+fn test_mask_value_hides_secret() {
+    assert_eq!(mask_value("ghp_secret_value"), "gh••••••");
+    assert_eq!(mask_value("abcd"), "••••••");
+    assert_eq!(mask_value(""), "••••••");
+
+    // Mask length doesn't leak value length
+    assert_eq!(
+        mask_value("abcdefgh").chars().count(),
+        mask_value("abcdefghijklmnop").chars().count()
+    );
+}
+
+#[test]
+fn test_narrow_terminal_drops_optional_columns() {
+    let wide = long_columns(200, 20, true);
+    assert_eq!(wide.len(), 8);
+
+    let narrow = long_columns(35, 20, true);
+    assert_eq!(narrow, vec![Column::Key, Column::Locked]);
+
+    let medium = long_columns(60, 20, true);
+    assert!(!medium.contains(&Column::Preview));
+    assert!(!medium.contains(&Column::Tags));
+    assert!(medium.contains(&Column::Key));
+
+    // Timestamps go before access metadata
+    let wider = long_columns(80, 20, true);
+    assert!(!wider.contains(&Column::Created));
+    assert!(wider.contains(&Column::Accessed));
+}
+
+#[test]
+fn test_long_keys_are_truncated() {
+    let entry = EntrySummary {
+        key: "k".repeat(100),
+        is_locked: false,
+        access_count: 0,
+        last_accessed: None,
+        label: None,
+        created_at: None,
+        updated_at: None,
+        tag_count: 0,
+    };
+    let key_width = key_column_width(std::slice::from_ref(&entry));
+    assert_eq!(key_width, 40);
+
+    let row = format_long_row(&entry, None, &[Column::Key], key_width);
+    assert_eq!(row.trim().chars().count(), 40);
+    assert!(row.trim_end().ends_with('…'));
+}
@@ -0,0 +1,149 @@
+//! List Watch Tests
+//!
+//! Tests for the change-detection loop behind `ik list --watch` and `Vault::reload`.
+
+use ironkey::error::Error;
+use ironkey::storage::{JsonFileBackend, StorageBackend};
+use ironkey::vault::VaultBuilder;
+use ironkey::watch::{WatchExit, watch_changes};
+use std::fs;
+use std::ops::ControlFlow;
+use std::thread;
+use std::time::Duration;
+use tempfile::TempDir;
+
+const POLL: Duration = Duration::from_millis(20);
+
+fn temp_backend(dir: &TempDir) -> JsonFileBackend {
+    JsonFileBackend::new(dir.path().join("ironkey.json"))
+}
+
+#[test]
+fn test_write_triggers_rerender() {
+    let dir = TempDir::new().unwrap();
+    let backend = temp_backend(&dir);
+    let mut writer = VaultBuilder::new()
+        .backend(Box::new(backend.clone()))
+        .init("master".to_string())
+        .unwrap();
+
+    let handle = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(200));
+        writer
+            .create_entry("github".to_string(), "ghp_token".to_string())
+            .unwrap();
+    });
+
+    let mut renders = 0;
+    let exit = watch_changes(&backend, POLL, Duration::from_secs(10), || {
+        renders += 1;
+        Ok(if renders == 2 {
+            ControlFlow::Break(())
+        } else {
+            ControlFlow::Continue(())
+        })
+    })
+    .unwrap();
+    handle.join().unwrap();
+
+    assert_eq!(exit, WatchExit::Stopped);
+    assert_eq!(renders, 2);
+}
+
+#[test]
+fn test_atomic_rename_is_detected() {
+    let dir = TempDir::new().unwrap();
+    let backend = temp_backend(&dir);
+    drop(
+        VaultBuilder::new()
+            .backend(Box::new(backend.clone()))
+            .init("master".to_string())
+            .unwrap(),
+    );
+
+    let path = dir.path().join("ironkey.json");
+    let staged = dir.path().join("ironkey.json.tmp");
+    let handle = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(200));
+        fs::copy(&path, &staged).unwrap();
+        fs::rename(&staged, &path).unwrap();
+    });
+
+    let mut renders = 0;
+    let exit = watch_changes(&backend, POLL, Duration::from_secs(10), || {
+        renders += 1;
+        Ok(if renders == 2 {
+            ControlFlow::Break(())
+        } else {
+            ControlFlow::Continue(())
+        })
+    })
+    .unwrap();
+    handle.join().unwrap();
+
+    assert_eq!(exit, WatchExit::Stopped);
+}
+
+#[test]
+fn test_no_change_times_out_after_initial_render() {
+    let dir = TempDir::new().unwrap();
+    let backend = temp_backend(&dir);
+    drop(
+        VaultBuilder::new()
+            .backend(Box::new(backend.clone()))
+            .init("master".to_string())
+            .unwrap(),
+    );
+
+    let mut renders = 0;
+    let exit = watch_changes(&backend, POLL, Duration::from_millis(200), || {
+        renders += 1;
+        Ok(ControlFlow::Continue(()))
+    })
+    .unwrap();
+
+    assert_eq!(exit, WatchExit::TimedOut);
+    assert_eq!(renders, 1);
+}
+
+#[test]
+fn test_reload_picks_up_changes_from_another_vault() {
+    let dir = TempDir::new().unwrap();
+    let backend = temp_backend(&dir);
+    let mut writer = VaultBuilder::new()
+        .backend(Box::new(backend.clone()))
+        .init("master".to_string())
+        .unwrap();
+    let mut reader = VaultBuilder::new()
+        .backend(Box::new(backend.clone()))
+        .unlock("master".to_string())
+        .unwrap();
+
+    writer
+        .create_entry("github".to_string(), "ghp_token".to_string())
+        .unwrap();
+    assert!(reader.get_entry("github").is_err());
+
+    reader.reload().unwrap();
+    assert_eq!(reader.get_entry("github").unwrap(), "ghp_token");
+}
+
+#[test]
+fn test_reload_rejects_replaced_master_password() {
+    let dir = TempDir::new().unwrap();
+    let backend = temp_backend(&dir);
+    let mut reader = VaultBuilder::new()
+        .backend(Box::new(backend.clone()))
+        .init("master".to_string())
+        .unwrap();
+
+    backend.remove().unwrap();
+    drop(
+        VaultBuilder::new()
+            .backend(Box::new(backend.clone()))
+            .init("other".to_string())
+            .unwrap(),
+    );
+
+    assert!(matches!(reader.reload(), Err(Error::InvalidMasterPassword)));
+}
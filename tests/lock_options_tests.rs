@@ -0,0 +1,305 @@
+//! Timed and Passphrase Lock Tests
+//!
+//! Tests for `ik lock --timer` and `ik lock --password`.
+
+use chrono::{Duration, Utc};
+use ironkey::cli::parse_lock_duration;
+use ironkey::error::Error;
+use ironkey::export::ExportOptions;
+use ironkey::storage::{JsonFileBackend, MemoryBackend, StorageBackend};
+use ironkey::vault::{Vault, VaultBuilder};
+use std::path::Path;
+use tempfile::TempDir;
+
+fn create_vault(path: &Path) -> Vault {
+    let mut vault = VaultBuilder::new()
+        .backend(Box::new(JsonFileBackend::new(path.to_path_buf())))
+        .init("master".to_string())
+        .unwrap();
+    vault
+        .create_entry("github".to_string(), "ghp_token".to_string())
+        .unwrap();
+    vault
+}
+
+fn reopen(path: &Path) -> Vault {
+    VaultBuilder::new()
+        .backend(Box::new(JsonFileBackend::new(path.to_path_buf())))
+        .unlock("master".to_string())
+        .unwrap()
+}
+
+#[test]
+fn test_timed_lock_clears_after_period_on_next_load() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("ironkey.json");
+    let mut vault = create_vault(&path);
+
+    vault.lock_for("github", Duration::seconds(1)).unwrap();
+    drop(vault);
+
+    // Still inside the period: stays locked
+    let vault = reopen(&path);
+    assert!(matches!(
+        vault.get_entry("github"),
+        Err(Error::EntryLocked(_))
+    ));
+    drop(vault);
+
+    std::thread::sleep(std::time::Duration::from_millis(1100));
+
+    let vault = reopen(&path);
+    assert_eq!(vault.get_entry("github").unwrap(), "ghp_token");
+}
+
+#[test]
+fn test_clear_expired_locks_only_touches_expired_timers() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("ironkey.json");
+    let mut vault = create_vault(&path);
+    vault
+        .create_entry("prod".to_string(), "top_secret".to_string())
+        .unwrap();
+    vault
+        .create_entry("staging".to_string(), "secret".to_string())
+        .unwrap();
+
+    vault.lock_for("github", Duration::hours(1)).unwrap();
+    vault.lock_for("staging", Duration::hours(3)).unwrap();
+    vault.toggle_lock("prod").unwrap();
+    drop(vault);
+
+    let mut db = JsonFileBackend::new(path).load().unwrap();
+    let cleared = db.clear_expired_locks(Utc::now() + Duration::hours(2));
+
+    assert_eq!(cleared, vec!["github".to_string()]);
+    assert!(!db.entries["github"].is_locked);
+    assert!(db.entries["github"].unlock_after.is_none());
+    assert!(db.entries["staging"].is_locked);
+    // Plain locks never expire
+    assert!(db.entries["prod"].is_locked);
+}
+
+#[test]
+fn test_unparseable_timer_stays_locked() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("ironkey.json");
+    let mut vault = create_vault(&path);
+    vault.lock_for("github", Duration::hours(1)).unwrap();
+    drop(vault);
+
+    let backend = JsonFileBackend::new(path.clone());
+    let mut db = backend.load().unwrap();
+    db.entries.get_mut("github").unwrap().unlock_after = Some("not a time".to_string());
+    db.mac = None;
+    backend.save(&db).unwrap();
+
+    let vault = reopen(&path);
+    assert!(matches!(
+        vault.get_entry("github"),
+        Err(Error::EntryLocked(_))
+    ));
+}
+
+#[test]
+fn test_toggle_unlock_cancels_timer() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("ironkey.json");
+    let mut vault = create_vault(&path);
+
+    vault.lock_for("github", Duration::hours(1)).unwrap();
+    assert!(!vault.toggle_lock("github").unwrap());
+    drop(vault);
+
+    let db = JsonFileBackend::new(path).load().unwrap();
+    assert!(!db.entries["github"].is_locked);
+    assert!(db.entries["github"].unlock_after.is_none());
+}
+
+#[test]
+fn test_passphrase_lock_round_trip() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("ironkey.json");
+    let mut vault = create_vault(&path);
+
+    vault.lock_with_password("github", "open sesame").unwrap();
+    assert!(vault.has_lock_password("github").unwrap());
+    drop(vault);
+
+    let mut vault = reopen(&path);
+    assert!(matches!(
+        vault.get_entry("github"),
+        Err(Error::EntryLocked(_))
+    ));
+    // The master password alone can't lift it
+    assert!(matches!(
+        vault.toggle_lock("github"),
+        Err(Error::EntryLocked(_))
+    ));
+    assert!(matches!(
+        vault.unlock_with_password("github", "wrong"),
+        Err(Error::InvalidInput(_))
+    ));
+
+    vault.unlock_with_password("github", "open sesame").unwrap();
+    assert!(!vault.has_lock_password("github").unwrap());
+    assert_eq!(vault.get_entry("github").unwrap(), "ghp_token");
+}
+
+#[test]
+fn test_passphrase_lock_rejects_locked_or_empty() {
+    let mut vault = VaultBuilder::new()
+        .backend(Box::new(MemoryBackend::new()))
+        .init("master".to_string())
+        .unwrap();
+    vault
+        .create_entry("github".to_string(), "ghp_token".to_string())
+        .unwrap();
+
+    assert!(matches!(
+        vault.lock_with_password("github", ""),
+        Err(Error::EmptyPassword)
+    ));
+
+    vault.toggle_lock("github").unwrap();
+    assert!(matches!(
+        vault.lock_with_password("github", "open sesame"),
+        Err(Error::EntryLocked(_))
+    ));
+}
+
+#[test]
+fn test_timer_cannot_override_passphrase_lock() {
+    let mut vault = VaultBuilder::new()
+        .backend(Box::new(MemoryBackend::new()))
+        .init("master".to_string())
+        .unwrap();
+    vault
+        .create_entry("github".to_string(), "ghp_token".to_string())
+        .unwrap();
+    vault.lock_with_password("github", "open sesame").unwrap();
+
+    assert!(matches!(
+        vault.lock_for("github", Duration::seconds(1)),
+        Err(Error::EntryLocked(_))
+    ));
+}
+
+#[test]
+fn test_parse_lock_duration() {
+    assert_eq!(parse_lock_duration("90s").unwrap(), Duration::seconds(90));
+    assert_eq!(parse_lock_duration("30m").unwrap(), Duration::minutes(30));
+    assert_eq!(parse_lock_duration("1h").unwrap(), Duration::hours(1));
+    assert_eq!(parse_lock_duration("2d").unwrap(), Duration::days(2));
+
+    assert!(parse_lock_duration("").is_err());
+    assert!(parse_lock_duration("h").is_err());
+    assert!(parse_lock_duration("0m").is_err());
+    assert!(parse_lock_duration("-5m").is_err());
+    assert!(parse_lock_duration("5w").is_err());
+    assert!(parse_lock_duration("5").is_err());
+}
+
+/// A vault holding "github" (passphrase-locked) and "aws" (plain)
+fn vault_with_passphrase_lock(path: &Path) -> Vault {
+    let mut vault = create_vault(path);
+    vault
+        .create_entry("aws".to_string(), "aws_secret".to_string())
+        .unwrap();
+    vault.lock_with_password("github", "open sesame").unwrap();
+    vault
+}
+
+#[test]
+fn test_exports_leave_out_passphrase_locked_entries() {
+    let temp_dir = TempDir::new().unwrap();
+    let vault = vault_with_passphrase_lock(&temp_dir.path().join("ironkey.json"));
+
+    let preview = vault.export_preview(ExportOptions::default());
+    assert_eq!(preview.keys, vec!["aws"]);
+
+    let mut buffer = Vec::new();
+    vault
+        .export_to_writer(
+            &mut buffer,
+            "export".to_string(),
+            ExportOptions::default(),
+            None,
+        )
+        .unwrap();
+    let mut target = VaultBuilder::new()
+        .backend(Box::new(MemoryBackend::new()))
+        .init("master".to_string())
+        .unwrap();
+    let result = target
+        .import_from_reader(
+            buffer.as_slice(),
+            "export".to_string(),
+            true,
+            false,
+            false,
+            None,
+        )
+        .unwrap();
+    assert_eq!(result.added, vec!["aws"]);
+
+    let csv_path = temp_dir.path().join("entries.csv");
+    assert_eq!(vault.export_to_csv_file(&csv_path, false).unwrap(), 1);
+    let csv = std::fs::read_to_string(&csv_path).unwrap();
+    assert!(!csv.contains("ghp_token"));
+}
+
+#[test]
+fn test_export_by_key_refuses_passphrase_locked_entry() {
+    let temp_dir = TempDir::new().unwrap();
+    let vault = vault_with_passphrase_lock(&temp_dir.path().join("ironkey.json"));
+
+    let keys = vec!["github".to_string()];
+    let result = vault.export_to_writer(
+        Vec::new(),
+        "export".to_string(),
+        ExportOptions::default(),
+        Some(&keys),
+    );
+    assert!(matches!(result, Err(Error::EntryLocked(key)) if key == "github"));
+}
+
+#[test]
+fn test_replace_import_keeps_passphrase_lock() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut vault = vault_with_passphrase_lock(&temp_dir.path().join("ironkey.json"));
+
+    let mut source = VaultBuilder::new()
+        .backend(Box::new(MemoryBackend::new()))
+        .init("master".to_string())
+        .unwrap();
+    source
+        .create_entry("github".to_string(), "replacement".to_string())
+        .unwrap();
+    let mut buffer = Vec::new();
+    source
+        .export_to_writer(
+            &mut buffer,
+            "export".to_string(),
+            ExportOptions::default(),
+            None,
+        )
+        .unwrap();
+
+    let result = vault
+        .import_from_reader(
+            buffer.as_slice(),
+            "export".to_string(),
+            false,
+            true,
+            false,
+            None,
+        )
+        .unwrap();
+    assert_eq!(result.skipped, vec!["github"]);
+    assert!(result.updated.is_empty());
+
+    assert!(vault.has_lock_password("github").unwrap());
+    vault.unlock_with_password("github", "open sesame").unwrap();
+    assert_eq!(vault.get_entry("github").unwrap(), "ghp_token");
+}
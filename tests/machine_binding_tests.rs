@@ -0,0 +1,64 @@
+//! Machine Binding Tests
+//!
+//! Tests for `ik init --bind-machine` tamper-evidence.
+
+use ironkey::machine::{MachineBinding, mismatch_warning};
+use ironkey::storage::MemoryBackend;
+use ironkey::vault::VaultBuilder;
+
+fn binding(hostname: &str, machine_id: &str) -> MachineBinding {
+    MachineBinding {
+        hostname: hostname.to_string(),
+        machine_id: machine_id.to_string(),
+    }
+}
+
+#[test]
+fn test_binding_is_stored_and_reloaded() {
+    let backend = MemoryBackend::new();
+    let mut vault = VaultBuilder::new()
+        .backend(Box::new(backend.clone()))
+        .init("master".to_string())
+        .unwrap();
+    assert!(vault.bound_machine().is_none());
+
+    vault.bind_machine(binding("laptop", "abc123")).unwrap();
+    drop(vault);
+
+    let vault = VaultBuilder::new()
+        .backend(Box::new(backend))
+        .unlock("master".to_string())
+        .unwrap();
+    assert_eq!(vault.bound_machine(), Some(&binding("laptop", "abc123")));
+}
+
+#[test]
+fn test_mismatched_machine_warns() {
+    let bound = binding("laptop", "abc123");
+
+    // A different id counts as elsewhere even with the same hostname
+    let warning = mismatch_warning(&bound, &binding("laptop", "def456")).unwrap();
+    assert!(warning.contains("laptop"));
+}
+
+#[test]
+fn test_renamed_host_does_not_warn() {
+    let bound = binding("laptop", "abc123");
+    assert!(mismatch_warning(&bound, &binding("laptop-renamed", "abc123")).is_none());
+}
+
+#[test]
+fn test_matching_machine_does_not_warn() {
+    let bound = binding("laptop", "abc123");
+    assert!(mismatch_warning(&bound, &binding("laptop", "abc123")).is_none());
+}
+
+#[test]
+fn test_current_machine_identity_is_stable() {
+    let first = MachineBinding::current().unwrap();
+    let second = MachineBinding::current().unwrap();
+
+    assert_eq!(first, second);
+    assert_eq!(first.machine_id.len(), 32);
+    assert!(mismatch_warning(&first, &second).is_none());
+}
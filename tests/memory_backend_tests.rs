@@ -0,0 +1,90 @@
+//! In-Memory Backend Tests
+//!
+//! Tests for running the vault against `MemoryBackend` via the `StorageBackend` trait.
+
+use ironkey::error::Error;
+use ironkey::storage::{MemoryBackend, StorageBackend};
+use ironkey::vault::VaultBuilder;
+
+#[test]
+fn test_full_crud_against_memory_backend() {
+    let backend = MemoryBackend::new();
+    let mut vault = VaultBuilder::new()
+        .backend(Box::new(backend.clone()))
+        .init("master".to_string())
+        .unwrap();
+
+    // Create
+    vault
+        .create_entry("github".to_string(), "ghp_token".to_string())
+        .unwrap();
+    vault
+        .create_entry("aws".to_string(), "aws_secret".to_string())
+        .unwrap();
+
+    // Read
+    assert_eq!(vault.get_entry("github").unwrap(), "ghp_token");
+
+    // Update
+    vault
+        .update_entry("github".to_string(), "ghp_new".to_string())
+        .unwrap();
+    assert_eq!(vault.get_entry("github").unwrap(), "ghp_new");
+
+    // Lock / delete
+    assert!(vault.toggle_lock("aws").unwrap());
+    assert!(matches!(
+        vault.delete_entry("aws"),
+        Err(Error::EntryLocked(_))
+    ));
+    vault.toggle_lock("aws").unwrap();
+    vault.delete_entry("aws").unwrap();
+    drop(vault);
+
+    // Changes were persisted to the shared backend
+    let stored = backend.load().unwrap();
+    assert_eq!(stored.entries.len(), 1);
+    assert!(stored.entries.contains_key("github"));
+
+    let vault = VaultBuilder::new()
+        .backend(Box::new(backend))
+        .unlock("master".to_string())
+        .unwrap();
+    assert_eq!(vault.get_entry("github").unwrap(), "ghp_new");
+    assert!(vault.get_entry("aws").is_err());
+}
+
+#[test]
+fn test_memory_backend_wrong_password_and_double_init() {
+    let backend = MemoryBackend::new();
+    assert!(!backend.exists().unwrap());
+
+    drop(
+        VaultBuilder::new()
+            .backend(Box::new(backend.clone()))
+            .init("master".to_string())
+            .unwrap(),
+    );
+    assert!(backend.exists().unwrap());
+
+    assert!(matches!(
+        VaultBuilder::new()
+            .backend(Box::new(backend.clone()))
+            .unlock("wrong".to_string()),
+        Err(Error::InvalidMasterPassword)
+    ));
+    assert!(matches!(
+        VaultBuilder::new()
+            .backend(Box::new(backend))
+            .init("again".to_string()),
+        Err(Error::MasterKeyAlreadyExists)
+    ));
+}
+
+#[test]
+fn test_unlock_empty_memory_backend() {
+    let result = VaultBuilder::new()
+        .backend(Box::new(MemoryBackend::new()))
+        .unlock("master".to_string());
+    assert!(matches!(result, Err(Error::DatabaseNotFound)));
+}
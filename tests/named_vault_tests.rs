@@ -0,0 +1,162 @@
+//! Named Vault Tests
+//!
+//! Tests for `--vault` name handling: validation, where each vault's files live,
+//! listing and deleting vaults. `IRONKEY_DB_PATH` is process-wide, so this binary
+//! points it at a temporary directory once and named vaults go in `vaults/` there.
+//! NOTE: These tests must run serially because they share that directory.
+
+use ironkey::error::Error;
+use ironkey::storage::{self, DB_PATH_ENV, DEFAULT_VAULT, JsonFileBackend};
+use ironkey::vault::{Vault, VaultBuilder};
+use std::path::PathBuf;
+
+mod common;
+
+fn isolated_dir() -> PathBuf {
+    common::use_isolated_database();
+    PathBuf::from(std::env::var_os(DB_PATH_ENV).unwrap())
+        .parent()
+        .unwrap()
+        .to_path_buf()
+}
+
+fn init_vault(name: &str, password: &str) -> Vault {
+    let path = storage::vault_database_path(name, "json").unwrap();
+    VaultBuilder::new()
+        .backend(Box::new(JsonFileBackend::new(path)))
+        .init(password.to_string())
+        .unwrap()
+}
+
+#[test]
+fn test_vault_name_validation() {
+    for name in ["default", "work", "personal-2", "team_a", "v1.2"] {
+        assert!(storage::validate_vault_name(name).is_ok(), "{name}");
+    }
+
+    let too_long = "v".repeat(storage::MAX_VAULT_NAME_LENGTH + 1);
+    for name in [
+        "",
+        ".hidden",
+        "../escape",
+        "a/b",
+        "a\\b",
+        "with space",
+        &too_long,
+    ] {
+        assert!(
+            matches!(
+                storage::validate_vault_name(name),
+                Err(Error::InvalidInput(_))
+            ),
+            "{name:?} should be rejected"
+        );
+    }
+    assert!(storage::vault_database_path("../escape", "json").is_err());
+}
+
+#[test]
+fn test_named_vaults_live_in_vaults_dir() {
+    let dir = isolated_dir();
+
+    assert_eq!(
+        storage::vault_database_path("work", "json").unwrap(),
+        dir.join("vaults").join("work.json")
+    );
+    assert_eq!(
+        storage::vault_database_path("work", "db").unwrap(),
+        dir.join("vaults").join("work.db")
+    );
+    // IRONKEY_DB_PATH stays the default vault's JSON file
+    assert_eq!(
+        storage::vault_database_path(DEFAULT_VAULT, "json").unwrap(),
+        std::env::var_os(DB_PATH_ENV).unwrap()
+    );
+}
+
+#[test]
+fn test_vaults_have_separate_passwords_and_entries() {
+    isolated_dir();
+    let mut work = init_vault("sep-work", "work-master");
+    work.create_entry("token".to_string(), "work-token".to_string())
+        .unwrap();
+    let mut personal = init_vault("sep-personal", "personal-master");
+    personal
+        .create_entry("token".to_string(), "personal-token".to_string())
+        .unwrap();
+
+    let open = |name: &str, password: &str| {
+        VaultBuilder::new()
+            .backend(Box::new(JsonFileBackend::new(
+                storage::vault_database_path(name, "json").unwrap(),
+            )))
+            .unlock(password.to_string())
+    };
+
+    assert_eq!(
+        open("sep-work", "work-master")
+            .unwrap()
+            .get_entry("token")
+            .unwrap(),
+        "work-token"
+    );
+    assert_eq!(
+        open("sep-personal", "personal-master")
+            .unwrap()
+            .get_entry("token")
+            .unwrap(),
+        "personal-token"
+    );
+    assert!(matches!(
+        open("sep-work", "personal-master"),
+        Err(Error::InvalidMasterPassword)
+    ));
+}
+
+#[test]
+fn test_list_and_remove_vaults() {
+    isolated_dir();
+    init_vault("list-a", "master");
+    init_vault("list-b", "master");
+
+    let vaults = storage::list_vaults().unwrap();
+    assert!(vaults.contains(&"list-a".to_string()));
+    assert!(vaults.contains(&"list-b".to_string()));
+    assert!(vaults.is_sorted());
+
+    assert!(storage::remove_vault("list-a").unwrap());
+    assert!(
+        !storage::vault_database_path("list-a", "json")
+            .unwrap()
+            .exists()
+    );
+    assert!(
+        !storage::list_vaults()
+            .unwrap()
+            .contains(&"list-a".to_string())
+    );
+    assert!(
+        storage::list_vaults()
+            .unwrap()
+            .contains(&"list-b".to_string())
+    );
+
+    // Nothing left to remove
+    assert!(!storage::remove_vault("list-a").unwrap());
+}
+
+#[test]
+fn test_select_vault_routes_default_paths() {
+    let dir = isolated_dir();
+    assert!(storage::select_vault("../nope").is_err());
+
+    storage::select_vault("selected").unwrap();
+    assert_eq!(storage::selected_vault(), "selected");
+    assert_eq!(
+        storage::get_database_path().unwrap(),
+        dir.join("vaults").join("selected.json")
+    );
+
+    // Only one vault per process
+    assert!(storage::select_vault("other").is_err());
+}
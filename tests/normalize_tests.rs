@@ -0,0 +1,81 @@
+//! Database Normalization Tests
+//!
+//! Tests for deterministic (sorted) database serialization and `ik normalize`.
+//! NOTE: These tests must run serially because they share the same database file.
+
+use ironkey::storage::{self, Database, Entry};
+use ironkey::vault::Vault;
+use std::fs;
+
+mod common;
+
+fn cleanup() {
+    common::use_isolated_database();
+    if let Ok(db_path) = storage::get_database_path() {
+        let _ = fs::remove_file(db_path);
+    }
+}
+
+#[test]
+fn test_serialization_is_independent_of_insertion_order() {
+    let keys = ["zeta", "alpha", "mike", "bravo", "yankee"];
+
+    let mut forward = Database::new(vec![1, 2, 3], vec![4, 5, 6], 100_000);
+    for key in keys {
+        forward
+            .entries
+            .insert(key.to_string(), Entry::new(vec![1], vec![2], false));
+    }
+
+    let mut backward = Database::new(vec![1, 2, 3], vec![4, 5, 6], 100_000);
+    for key in keys.iter().rev() {
+        backward
+            .entries
+            .insert(key.to_string(), Entry::new(vec![1], vec![2], false));
+    }
+
+    let forward_json = serde_json::to_string_pretty(&forward).unwrap();
+    let backward_json = serde_json::to_string_pretty(&backward).unwrap();
+    assert_eq!(forward_json, backward_json);
+
+    // Keys appear in sorted order
+    let positions: Vec<usize> = ["alpha", "bravo", "mike", "yankee", "zeta"]
+        .iter()
+        .map(|k| forward_json.find(&format!("\"{k}\"")).unwrap())
+        .collect();
+    assert!(positions.windows(2).all(|w| w[0] < w[1]));
+}
+
+#[test]
+fn test_normalize_twice_produces_identical_files() {
+    cleanup();
+    let mut vault = Vault::init("master".to_string()).unwrap();
+    for key in ["delta", "alpha", "charlie", "bravo"] {
+        vault
+            .create_entry(key.to_string(), format!("{key}_value"))
+            .unwrap();
+    }
+    drop(vault);
+
+    let db_path = storage::get_database_path().unwrap();
+
+    assert_eq!(storage::normalize().unwrap(), 4);
+    let first = fs::read(&db_path).unwrap();
+
+    assert_eq!(storage::normalize().unwrap(), 4);
+    let second = fs::read(&db_path).unwrap();
+
+    assert_eq!(first, second);
+
+    // Normalization doesn't affect the data
+    let vault = Vault::unlock("master".to_string()).unwrap();
+    assert_eq!(vault.get_entry("charlie").unwrap(), "charlie_value");
+
+    cleanup();
+}
+
+#[test]
+fn test_normalize_without_database_fails() {
+    cleanup();
+    assert!(storage::normalize().is_err());
+}
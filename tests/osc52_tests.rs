@@ -0,0 +1,96 @@
+//! OSC 52 Clipboard Tests
+//!
+//! Tests for the OSC 52 escape sequence used to copy through the terminal over
+//! SSH, and for parsing `--osc52` on the copy paths.
+
+use base64::{Engine as _, engine::general_purpose};
+use clap::Parser;
+use ironkey::cli::{CliArgs, Commands};
+use ironkey::clipboard::{osc52_sequence, prefers_osc52};
+
+fn parse(args: &[&str]) -> Result<Commands, clap::Error> {
+    CliArgs::try_parse_from(std::iter::once("ik").chain(args.iter().copied()))
+        .map(|cli| cli.command.expect("subcommand"))
+}
+
+#[test]
+fn test_sequence_carries_base64_value() {
+    let sequence = osc52_sequence("p@ss wörd", false);
+
+    let payload = sequence
+        .strip_prefix("\x1b]52;c;")
+        .and_then(|rest| rest.strip_suffix('\x07'))
+        .expect("OSC 52 framing");
+    let decoded = general_purpose::STANDARD.decode(payload).unwrap();
+    assert_eq!(decoded, "p@ss wörd".as_bytes());
+}
+
+#[test]
+fn test_tmux_sequence_is_wrapped_in_passthrough() {
+    let plain = osc52_sequence("secret", false);
+    let wrapped = osc52_sequence("secret", true);
+
+    assert!(wrapped.starts_with("\x1bPtmux;\x1b"));
+    assert!(wrapped.ends_with("\x1b\\"));
+    assert!(wrapped.contains(plain.trim_start_matches('\x1b')));
+}
+
+#[test]
+fn test_empty_value() {
+    assert_eq!(osc52_sequence("", false).as_str(), "\x1b]52;c;\x07");
+}
+
+#[test]
+fn test_requested_always_prefers_osc52() {
+    assert!(prefers_osc52(true));
+}
+
+#[test]
+fn test_osc52_on_copy_paths() {
+    assert!(matches!(
+        parse(&["get", "-k", "token", "--copy", "--osc52"]).unwrap(),
+        Commands::Get { osc52: true, .. }
+    ));
+    assert!(matches!(
+        parse(&["copy", "-k", "token", "--osc52"]).unwrap(),
+        Commands::Copy { osc52: true, .. }
+    ));
+    assert!(matches!(
+        parse(&["totp", "-k", "github-2fa", "--copy", "--osc52"]).unwrap(),
+        Commands::Totp { osc52: true, .. }
+    ));
+    assert!(matches!(
+        parse(&["generate", "--copy", "--osc52"]).unwrap(),
+        Commands::Generate { osc52: true, .. }
+    ));
+}
+
+#[test]
+fn test_osc52_requires_copy() {
+    let err = parse(&["get", "-k", "token", "--osc52"])
+        .err()
+        .expect("--osc52 without --copy should be rejected");
+    assert_eq!(err.kind(), clap::error::ErrorKind::MissingRequiredArgument);
+}
+
+#[test]
+fn test_osc52_conflicts_with_system_clipboard_options() {
+    for args in [
+        &["get", "-k", "token", "--copy", "--osc52", "--primary"][..],
+        &[
+            "get",
+            "-k",
+            "token",
+            "--copy",
+            "--osc52",
+            "--then-run",
+            "true",
+        ],
+        &["copy", "-k", "token", "--osc52", "--primary"],
+    ] {
+        let err = parse(args)
+            .err()
+            .expect("conflicting flags should be rejected");
+        assert_eq!(err.kind(), clap::error::ErrorKind::ArgumentConflict);
+    }
+}
@@ -0,0 +1,141 @@
+//! Parallel Re-encryption Tests
+//!
+//! Tests that importing and changing the master password still round-trip now that
+//! entries are encrypted in parallel, with deterministic `ImportResult` ordering.
+
+use ironkey::storage::MemoryBackend;
+use ironkey::vault::{Vault, VaultBuilder};
+use std::fs;
+use tempfile::TempDir;
+
+const ENTRY_COUNT: usize = 1000;
+
+fn key(i: usize) -> String {
+    format!("service_{i:04}")
+}
+
+fn value(i: usize) -> String {
+    format!("secret-value-{i}")
+}
+
+fn new_vault() -> Vault {
+    VaultBuilder::new()
+        .backend(Box::new(MemoryBackend::new()))
+        .init("master".to_string())
+        .unwrap()
+}
+
+/// Write `ENTRY_COUNT` entries as a plaintext JSON import file, in reverse key order
+fn write_plain_entries(dir: &TempDir) -> std::path::PathBuf {
+    let entries: Vec<serde_json::Value> = (0..ENTRY_COUNT)
+        .rev()
+        .map(|i| serde_json::json!({"key": key(i), "value": value(i), "locked": i % 10 == 0}))
+        .collect();
+    let path = dir.path().join("entries.json");
+    fs::write(&path, serde_json::to_string(&entries).unwrap()).unwrap();
+    path
+}
+
+#[test]
+fn test_thousand_entries_round_trip_through_export() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut source = new_vault();
+    source
+        .import_from_plain_json(&write_plain_entries(&temp_dir), true, false, false)
+        .unwrap();
+
+    let export_path = temp_dir.path().join("backup.ik");
+    source
+        .export_to_file(&export_path, "export".to_string())
+        .unwrap();
+
+    let mut target = new_vault();
+    target.create_entry(key(5), "old".to_string()).unwrap();
+    let result = target
+        .import_from_file(&export_path, "export".to_string(), false, true, false)
+        .unwrap();
+
+    assert_eq!(result.added.len(), ENTRY_COUNT - 1);
+    assert_eq!(result.updated, vec![key(5)]);
+    assert!(result.added.is_sorted());
+
+    for i in 0..ENTRY_COUNT {
+        assert_eq!(target.get_entry_unlocked(&key(i)).unwrap(), value(i));
+    }
+    assert_eq!(target.list_entries(None, None).unwrap().len(), ENTRY_COUNT);
+}
+
+#[test]
+fn test_import_result_is_sorted() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut vault = new_vault();
+    for i in [3, 1, 2] {
+        vault.create_entry(key(i), "existing".to_string()).unwrap();
+    }
+
+    let result = vault
+        .import_from_plain_json(&write_plain_entries(&temp_dir), true, false, false)
+        .unwrap();
+
+    assert_eq!(result.skipped, vec![key(1), key(2), key(3)]);
+    assert_eq!(result.added.len(), ENTRY_COUNT - 3);
+    assert!(result.added.is_sorted());
+}
+
+#[test]
+fn test_repeated_key_resolves_like_sequential_import() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("dupes.json");
+    fs::write(
+        &path,
+        r#"[
+            {"key": "api", "value": "first", "locked": false},
+            {"key": "api", "value": "second", "locked": false}
+        ]"#,
+    )
+    .unwrap();
+
+    // Replace: the later value wins and counts as an update of the first
+    let mut vault = new_vault();
+    let result = vault
+        .import_from_plain_json(&path, false, true, false)
+        .unwrap();
+    assert_eq!(result.added, vec!["api"]);
+    assert_eq!(result.updated, vec!["api"]);
+    assert_eq!(vault.get_entry("api").unwrap(), "second");
+
+    // Merge: the first value is kept and the repeat is skipped
+    let mut vault = new_vault();
+    let result = vault
+        .import_from_plain_json(&path, true, false, false)
+        .unwrap();
+    assert_eq!(result.added, vec!["api"]);
+    assert_eq!(result.skipped, vec!["api"]);
+    assert_eq!(vault.get_entry("api").unwrap(), "first");
+}
+
+#[test]
+fn test_change_master_password_with_many_entries() {
+    let temp_dir = TempDir::new().unwrap();
+    let backend = MemoryBackend::new();
+    let mut vault = VaultBuilder::new()
+        .backend(Box::new(backend.clone()))
+        .init("master".to_string())
+        .unwrap();
+    vault
+        .import_from_plain_json(&write_plain_entries(&temp_dir), true, false, false)
+        .unwrap();
+
+    vault
+        .change_master_password("master".to_string(), "new-master".to_string())
+        .unwrap();
+    drop(vault);
+
+    let vault = VaultBuilder::new()
+        .backend(Box::new(backend))
+        .unlock("new-master".to_string())
+        .unwrap();
+    for i in 0..ENTRY_COUNT {
+        assert_eq!(vault.get_entry_unlocked(&key(i)).unwrap(), value(i));
+    }
+}
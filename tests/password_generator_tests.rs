@@ -207,3 +207,292 @@ fn test_character_set_building() {
     // None
     assert_eq!(charset_none.len(), 0, "Empty charset should be empty");
 }
+
+#[test]
+fn test_generate_pronounceable_length_and_classes() {
+    for length in [1, 2, 8, 12, 16, 32] {
+        let password = password_generator::generate_pronounceable(length, true)
+            .expect("Failed to generate password");
+        assert_eq!(password.len(), length);
+
+        let digits = password.chars().filter(|c| c.is_ascii_digit()).count();
+        if length > 1 {
+            assert_eq!(digits, (length / 8).max(1));
+        }
+
+        // Without the digits, letters alternate consonant / vowel
+        let letters: Vec<char> = password.chars().filter(|c| !c.is_ascii_digit()).collect();
+        for (i, c) in letters.iter().enumerate() {
+            assert!(c.is_ascii_lowercase());
+            assert_eq!(
+                "aeiou".contains(*c),
+                i % 2 == 1,
+                "unexpected letter in {password}"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_generate_pronounceable_without_digits() {
+    let password = password_generator::generate_pronounceable(20, false).unwrap();
+    assert_eq!(password.len(), 20);
+    assert!(password.chars().all(|c| c.is_ascii_lowercase()));
+
+    assert!(password_generator::generate_pronounceable(0, true).is_err());
+}
+
+#[test]
+fn test_pronounceable_entropy_is_lower_than_random() {
+    let pronounceable = password_generator::pronounceable_entropy_bits(16, true);
+    let random = password_generator::entropy_bits(16, 88);
+
+    assert!(pronounceable > 0.0);
+    assert!(pronounceable < random);
+
+    // One consonant and one vowel
+    let two_letters = password_generator::pronounceable_entropy_bits(2, false);
+    assert!((two_letters - (17f64.log2() + 5f64.log2())).abs() < 1e-9);
+}
+
+#[test]
+fn test_exclude_ambiguous_characters() {
+    let options = password_generator::GenerateOptions {
+        exclude_ambiguous: true,
+        ..Default::default()
+    };
+    for _ in 0..20 {
+        let password = password_generator::generate_with(64, &options).unwrap();
+        assert_eq!(password.len(), 64);
+        assert!(
+            !password
+                .chars()
+                .any(|c| password_generator::AMBIGUOUS.contains(c)),
+            "Password should contain no ambiguous characters: {password}"
+        );
+    }
+}
+
+#[test]
+fn test_exclude_ambiguous_numbers_only() {
+    let options = password_generator::GenerateOptions {
+        lowercase: false,
+        uppercase: false,
+        symbols: false,
+        exclude_ambiguous: true,
+        ..Default::default()
+    };
+    let password = password_generator::generate_with(32, &options).unwrap();
+    assert!(password.chars().all(|c| "34679".contains(c)));
+}
+
+#[test]
+fn test_remove_ambiguous() {
+    assert_eq!(password_generator::remove_ambiguous("0123456789"), "34679");
+    assert_eq!(password_generator::remove_ambiguous("l1IO0oB8S5Z2"), "");
+}
+
+#[test]
+fn test_exclude_ambiguous_empty_charset_errors() {
+    let options = password_generator::GenerateOptions {
+        lowercase: false,
+        uppercase: false,
+        numbers: false,
+        symbols: false,
+        exclude_ambiguous: true,
+        ..Default::default()
+    };
+    let result = password_generator::generate_with(16, &options);
+    assert!(matches!(
+        result,
+        Err(ironkey::error::Error::InvalidInput(_))
+    ));
+}
+
+#[test]
+fn test_custom_symbol_set() {
+    let options = password_generator::GenerateOptions {
+        lowercase: false,
+        uppercase: false,
+        numbers: false,
+        custom_symbols: Some("!@#$%"),
+        ..Default::default()
+    };
+    for _ in 0..20 {
+        let password = password_generator::generate_with(64, &options).unwrap();
+        assert_eq!(password.len(), 64);
+        assert!(password.chars().all(|c| "!@#$%".contains(c)));
+    }
+}
+
+#[test]
+fn test_custom_symbols_replace_builtin_set() {
+    let options = password_generator::GenerateOptions {
+        custom_symbols: Some("#"),
+        ..Default::default()
+    };
+    let password = password_generator::generate_with(200, &options).unwrap();
+    assert!(
+        password
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '#')
+    );
+}
+
+#[test]
+fn test_custom_symbols_validation() {
+    use ironkey::error::Error;
+
+    for invalid in ["", "€", "ab\tc", "!\n"] {
+        assert!(
+            matches!(
+                password_generator::generate_with(
+                    16,
+                    &password_generator::GenerateOptions {
+                        custom_symbols: Some(invalid),
+                        ..Default::default()
+                    }
+                ),
+                Err(Error::InvalidInput(_))
+            ),
+            "{invalid:?} should be rejected"
+        );
+    }
+
+    assert_eq!(password_generator::validate_symbols("!!@@!").unwrap(), "!@");
+    assert_eq!(password_generator::validate_symbols(" ~").unwrap(), " ~");
+}
+
+#[test]
+fn test_build_charset_with_symbols() {
+    assert_eq!(
+        password_generator::build_charset_with_symbols(false, false, true, true, "!?"),
+        "0123456789!?"
+    );
+    assert_eq!(
+        password_generator::build_charset_with_symbols(
+            true,
+            true,
+            true,
+            true,
+            "!@#$%^&*()_+-=[]{}|;:,.<>?"
+        ),
+        password_generator::build_charset(true, true, true, true)
+    );
+}
+
+#[test]
+fn test_enforced_classes_at_minimum_length() {
+    // With exactly one slot per class, each class must appear exactly once
+    for _ in 0..50 {
+        let password = password_generator::generate(4, true, true, true, true).unwrap();
+        assert_eq!(
+            password.chars().filter(|c| c.is_ascii_lowercase()).count(),
+            1
+        );
+        assert_eq!(
+            password.chars().filter(|c| c.is_ascii_uppercase()).count(),
+            1
+        );
+        assert_eq!(password.chars().filter(|c| c.is_ascii_digit()).count(), 1);
+        assert_eq!(
+            password
+                .chars()
+                .filter(|c| !c.is_ascii_alphanumeric())
+                .count(),
+            1
+        );
+    }
+}
+
+#[test]
+fn test_enforced_class_positions_are_shuffled() {
+    // The guaranteed characters shouldn't always sit at the start
+    let first_is_lowercase = (0..50)
+        .map(|_| password_generator::generate(4, true, true, true, true).unwrap())
+        .filter(|p| p.starts_with(|c: char| c.is_ascii_lowercase()))
+        .count();
+    assert!(first_is_lowercase < 50);
+}
+
+#[test]
+fn test_length_shorter_than_class_count_errors() {
+    use ironkey::error::Error;
+
+    assert!(matches!(
+        password_generator::generate(3, true, true, true, true),
+        Err(Error::InvalidInput(_))
+    ));
+    assert!(password_generator::generate(2, true, false, true, false).is_ok());
+}
+
+#[test]
+fn test_enforce_classes_can_be_disabled() {
+    use password_generator::GenerateOptions;
+
+    let options = GenerateOptions {
+        enforce_classes: false,
+        ..GenerateOptions::default()
+    };
+    let password = password_generator::generate_with(2, &options).unwrap();
+    assert_eq!(password.len(), 2);
+}
+
+#[test]
+fn test_enforced_class_emptied_by_ambiguous_exclusion_errors() {
+    use password_generator::GenerateOptions;
+
+    let options = GenerateOptions {
+        exclude_ambiguous: true,
+        custom_symbols: Some("0O"),
+        ..GenerateOptions::default()
+    };
+    assert!(matches!(
+        password_generator::generate_with(16, &options),
+        Err(ironkey::error::Error::InvalidInput(_))
+    ));
+
+    // Without enforcement the other classes are enough
+    let options = GenerateOptions {
+        enforce_classes: false,
+        ..options
+    };
+    assert!(password_generator::generate_with(16, &options).is_ok());
+}
+
+#[test]
+fn test_entropy_bits_known_values() {
+    assert_eq!(password_generator::entropy_bits(16, 64), 96.0);
+    assert_eq!(password_generator::entropy_bits(8, 2), 8.0);
+    assert_eq!(password_generator::entropy_bits(10, 1), 0.0);
+    assert_eq!(password_generator::entropy_bits(0, 88), 0.0);
+
+    // 12 lowercase letters: 12 * log2(26)
+    let bits = password_generator::entropy_bits(12, 26);
+    assert!((bits - 56.4053).abs() < 1e-3);
+}
+
+#[test]
+fn test_entropy_bits_accounts_for_excluded_ambiguous_characters() {
+    let full = password_generator::build_charset(true, true, true, true);
+    let unambiguous = password_generator::remove_ambiguous(&full);
+
+    let full_bits = password_generator::entropy_bits(20, full.len());
+    let unambiguous_bits = password_generator::entropy_bits(20, unambiguous.len());
+
+    assert!(unambiguous_bits < full_bits);
+    assert!((unambiguous_bits - 20.0 * (unambiguous.len() as f64).log2()).abs() < 1e-9);
+}
+
+#[test]
+fn test_entropy_rating_thresholds() {
+    use password_generator::EntropyRating;
+
+    assert_eq!(EntropyRating::from_bits(0.0), EntropyRating::Weak);
+    assert_eq!(EntropyRating::from_bits(49.9), EntropyRating::Weak);
+    assert_eq!(EntropyRating::from_bits(50.0), EntropyRating::Fair);
+    assert_eq!(EntropyRating::from_bits(70.0), EntropyRating::Strong);
+    assert_eq!(EntropyRating::from_bits(99.9), EntropyRating::Strong);
+    assert_eq!(EntropyRating::from_bits(100.0), EntropyRating::VeryStrong);
+    assert_eq!(EntropyRating::VeryStrong.to_string(), "very strong");
+}
@@ -0,0 +1,57 @@
+//! Password Strength Tests
+//!
+//! Tests for `password_generator::estimate_strength` (weak value warnings on create/update).
+
+use ironkey::password_generator::{self, WEAK_SCORE, estimate_strength};
+
+#[test]
+fn test_common_passwords_are_weak() {
+    for password in ["password", "123456", "qwerty", "letmein"] {
+        let report = estimate_strength(password);
+        assert!(
+            report.score < WEAK_SCORE,
+            "{password} scored {}",
+            report.score
+        );
+        assert!(report.is_weak());
+    }
+}
+
+#[test]
+fn test_generated_passwords_are_strong() {
+    let password = password_generator::generate(20, true, true, true, true).unwrap();
+    let report = estimate_strength(&password);
+
+    assert_eq!(report.score, 4);
+    assert!(!report.is_weak());
+    assert!(report.entropy_bits > 40.0);
+}
+
+#[test]
+fn test_score_is_in_range() {
+    for password in ["", "a", "correct horse battery staple", "Tr0ub4dor&3"] {
+        assert!(estimate_strength(password).score <= 4);
+    }
+}
+
+#[test]
+fn test_empty_password() {
+    let report = estimate_strength("");
+    assert_eq!(report.score, 0);
+    assert_eq!(report.entropy_bits, 0.0);
+    assert!(report.is_weak());
+}
+
+#[test]
+fn test_weak_password_reports_crack_time_and_warning() {
+    let report = estimate_strength("password");
+    assert!(!report.crack_time.is_empty());
+    assert!(report.warning.is_some());
+}
+
+#[test]
+fn test_longer_passwords_have_more_entropy() {
+    let short = estimate_strength("x7#Qp");
+    let long = estimate_strength("x7#Qp9$Lm2@Wz5!K");
+    assert!(long.entropy_bits > short.entropy_bits);
+}
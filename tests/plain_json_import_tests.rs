@@ -0,0 +1,99 @@
+//! Plaintext JSON Import Tests
+//!
+//! Tests for `ik import --format json-plain` (unencrypted `ExportEntry` arrays).
+//! NOTE: These tests must run serially because they share the same database file.
+
+use ironkey::storage;
+use ironkey::vault::Vault;
+use std::fs;
+use tempfile::TempDir;
+
+mod common;
+
+fn cleanup() {
+    common::use_isolated_database();
+    if let Ok(db_path) = storage::get_database_path() {
+        let _ = fs::remove_file(db_path);
+    }
+}
+
+#[test]
+fn test_import_plain_json_merge_mode() {
+    cleanup();
+    let mut vault = Vault::init("master".to_string()).unwrap();
+    vault
+        .create_entry("existing".to_string(), "keep_me".to_string())
+        .unwrap();
+
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("entries.json");
+    fs::write(
+        &path,
+        r#"[
+            {"key": "api_key", "value": "sk-123", "locked": false},
+            {"key": "db_pass", "value": "hunter2", "locked": true}
+        ]"#,
+    )
+    .unwrap();
+
+    let result = vault
+        .import_from_plain_json(&path, true, false, false)
+        .unwrap();
+    assert_eq!(result.total_in_export, 2);
+    assert_eq!(result.added.len(), 2);
+    assert!(result.skipped.is_empty());
+    drop(vault);
+
+    // Entries are encrypted under the master key and persisted
+    let vault = Vault::unlock("master".to_string()).unwrap();
+    assert_eq!(vault.get_entry("api_key").unwrap(), "sk-123");
+    assert_eq!(vault.get_entry("existing").unwrap(), "keep_me");
+    let locked = vault.list_entries(None, Some(true)).unwrap();
+    assert_eq!(locked, vec![(&"db_pass".to_string(), true)]);
+
+    cleanup();
+}
+
+#[test]
+fn test_import_plain_json_merge_skips_existing() {
+    cleanup();
+    let mut vault = Vault::init("master".to_string()).unwrap();
+    vault
+        .create_entry("api_key".to_string(), "original".to_string())
+        .unwrap();
+
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("entries.json");
+    fs::write(
+        &path,
+        r#"[{"key": "api_key", "value": "changed", "locked": false}]"#,
+    )
+    .unwrap();
+
+    let result = vault
+        .import_from_plain_json(&path, true, false, false)
+        .unwrap();
+    assert_eq!(result.skipped, vec!["api_key".to_string()]);
+    assert_eq!(vault.get_entry("api_key").unwrap(), "original");
+
+    cleanup();
+}
+
+#[test]
+fn test_import_plain_json_rejects_malformed() {
+    cleanup();
+    let mut vault = Vault::init("master".to_string()).unwrap();
+
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("entries.json");
+    fs::write(&path, r#"{"key": "not_an_array"}"#).unwrap();
+
+    assert!(
+        vault
+            .import_from_plain_json(&path, true, false, false)
+            .is_err()
+    );
+    assert!(vault.list_entries(None, None).unwrap().is_empty());
+
+    cleanup();
+}
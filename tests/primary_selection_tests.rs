@@ -0,0 +1,68 @@
+//! Primary Selection Tests
+//!
+//! Tests for parsing `--primary` on the copy paths, and that `copy_to_primary`
+//! fails with an error rather than panicking when no display is available.
+
+use clap::Parser;
+use ironkey::cli::{CliArgs, Commands};
+
+fn parse(args: &[&str]) -> Result<Commands, clap::Error> {
+    CliArgs::try_parse_from(std::iter::once("ik").chain(args.iter().copied()))
+        .map(|cli| cli.command.expect("subcommand"))
+}
+
+#[test]
+fn test_primary_defaults_to_off() {
+    assert!(matches!(
+        parse(&["get", "-k", "token", "--copy"]).unwrap(),
+        Commands::Get { primary: false, .. }
+    ));
+    assert!(matches!(
+        parse(&["copy", "-k", "token"]).unwrap(),
+        Commands::Copy { primary: false, .. }
+    ));
+}
+
+#[test]
+fn test_primary_on_copy_paths() {
+    assert!(matches!(
+        parse(&["get", "-k", "token", "--copy", "--primary"]).unwrap(),
+        Commands::Get { primary: true, .. }
+    ));
+    assert!(matches!(
+        parse(&["copy", "-k", "token", "--primary"]).unwrap(),
+        Commands::Copy { primary: true, .. }
+    ));
+    assert!(matches!(
+        parse(&["totp", "-k", "github-2fa", "--copy", "--primary"]).unwrap(),
+        Commands::Totp { primary: true, .. }
+    ));
+    assert!(matches!(
+        parse(&["generate", "--copy", "--primary"]).unwrap(),
+        Commands::Generate { primary: true, .. }
+    ));
+}
+
+#[test]
+fn test_primary_requires_copy() {
+    for args in [
+        &["get", "-k", "token", "--primary"][..],
+        &["totp", "-k", "github-2fa", "--primary"],
+        &["generate", "--primary"],
+    ] {
+        let err = parse(args)
+            .err()
+            .expect("--primary without --copy should be rejected");
+        assert_eq!(err.kind(), clap::error::ErrorKind::MissingRequiredArgument);
+    }
+}
+
+#[test]
+fn test_copy_to_primary_without_display_is_an_error() {
+    // Only meaningful where there is no X11/Wayland display to talk to
+    if std::env::var_os("DISPLAY").is_some() || std::env::var_os("WAYLAND_DISPLAY").is_some() {
+        return;
+    }
+
+    assert!(ironkey::clipboard::copy_to_primary("secret").is_err());
+}
@@ -0,0 +1,37 @@
+//! QR Code Tests
+//!
+//! Tests for `display::render_qr` (`ik get --qr`).
+
+use ironkey::display::render_qr;
+
+#[test]
+fn test_render_is_deterministic() {
+    assert_eq!(render_qr("hello").unwrap(), render_qr("hello").unwrap());
+}
+
+#[test]
+fn test_small_value_renders_version_1_matrix() {
+    let rendered = render_qr("hello").unwrap();
+    let lines: Vec<&str> = rendered.lines().collect();
+
+    // 21 modules plus a 4-module quiet zone on each side, two rows per line
+    assert_eq!(lines.len(), 15);
+    assert!(lines.iter().all(|line| line.chars().count() == 29));
+}
+
+#[test]
+fn test_different_values_render_differently() {
+    assert_ne!(render_qr("hello").unwrap(), render_qr("world").unwrap());
+}
+
+#[test]
+fn test_rendering_does_not_contain_plaintext() {
+    let rendered = render_qr("WIFI:S:home;T:WPA;P:hunter2;;").unwrap();
+    assert!(!rendered.contains("hunter2"));
+}
+
+#[test]
+fn test_value_too_long_is_rejected() {
+    let value = "x".repeat(5000);
+    assert!(render_qr(&value).is_err());
+}
@@ -0,0 +1,115 @@
+//! Database Recovery Tests
+//!
+//! Tests for restoring a corrupted database from its rotating `.bak.N` backups or `.tmp` copy.
+//! NOTE: These tests must run serially because they share the same database file.
+
+use ironkey::error::Error;
+use ironkey::storage::{self, BACKUP_COUNT, JsonFileBackend, StorageBackend};
+use ironkey::vault::Vault;
+use std::fs;
+
+mod common;
+
+fn cleanup() {
+    common::use_isolated_database();
+    let backend = JsonFileBackend::default_location().unwrap();
+    for suffix in ["", ".tmp", ".corrupt"] {
+        let _ = fs::remove_file(backend.sibling_path(suffix));
+    }
+    for index in 1..=BACKUP_COUNT {
+        let _ = fs::remove_file(backend.backup_path(index));
+    }
+}
+
+/// Create a vault with two entries; every save rotates the previous file into `.bak.1`
+fn setup_with_backup() -> JsonFileBackend {
+    cleanup();
+    let mut vault = Vault::init("master".to_string()).unwrap();
+    vault
+        .create_entry("github".to_string(), "ghp_token".to_string())
+        .unwrap();
+    vault
+        .create_entry("aws".to_string(), "aws_secret".to_string())
+        .unwrap();
+    drop(vault);
+
+    JsonFileBackend::default_location().unwrap()
+}
+
+#[test]
+fn test_recover_corrupted_primary_from_backup() {
+    let backend = setup_with_backup();
+    let db_path = storage::get_database_path().unwrap();
+
+    // Simulate a partial write
+    fs::write(&db_path, "{\"master_salt\": \"abc\", \"entr").unwrap();
+
+    // Loading fails but points at the recovery command
+    match storage::load() {
+        Err(Error::DatabaseLoadFailed(msg)) => assert!(msg.contains("ik recover-db")),
+        other => panic!("expected DatabaseLoadFailed, got {other:?}"),
+    }
+
+    // `.bak.1` is the database as it was before the last save (adding "aws")
+    let (candidate, database) = backend.find_recoverable().unwrap();
+    assert_eq!(candidate, backend.backup_path(1));
+    assert_eq!(database.entries.len(), 1);
+
+    assert_eq!(backend.recover_from(&candidate).unwrap(), 1);
+    assert!(backend.sibling_path(".corrupt").exists());
+
+    let vault = Vault::unlock("master".to_string()).unwrap();
+    assert_eq!(vault.get_entry("github").unwrap(), "ghp_token");
+    assert!(vault.get_entry("aws").is_err());
+
+    cleanup();
+}
+
+#[test]
+fn test_invalid_backup_is_not_offered() {
+    let backend = setup_with_backup();
+    for index in 1..=BACKUP_COUNT {
+        fs::write(backend.backup_path(index), "not json").unwrap();
+    }
+    fs::write(storage::get_database_path().unwrap(), "also not json").unwrap();
+
+    assert!(backend.find_recoverable().is_none());
+    match backend.load() {
+        Err(Error::DatabaseLoadFailed(msg)) => assert!(!msg.contains("recover-db")),
+        other => panic!("expected DatabaseLoadFailed, got {other:?}"),
+    }
+
+    cleanup();
+}
+
+#[test]
+fn test_staging_file_is_a_recovery_candidate() {
+    let backend = setup_with_backup();
+    fs::copy(
+        storage::get_database_path().unwrap(),
+        backend.sibling_path(".tmp"),
+    )
+    .unwrap();
+    fs::remove_file(storage::get_database_path().unwrap()).unwrap();
+
+    let (candidate, _) = backend.find_recoverable().unwrap();
+    assert_eq!(candidate, backend.sibling_path(".tmp"));
+    backend.recover_from(&candidate).unwrap();
+    assert!(backend.exists().unwrap());
+
+    cleanup();
+}
+
+#[test]
+fn test_falls_back_to_older_backup() {
+    let backend = setup_with_backup();
+    fs::write(backend.backup_path(1), "not json").unwrap();
+    fs::write(storage::get_database_path().unwrap(), "not json either").unwrap();
+
+    // `.bak.2` predates "github", so it holds the empty vault
+    let (candidate, database) = backend.find_recoverable().unwrap();
+    assert_eq!(candidate, backend.backup_path(2));
+    assert!(database.entries.is_empty());
+
+    cleanup();
+}
@@ -0,0 +1,57 @@
+//! Re-encryption Plan Tests
+//!
+//! Tests for `Vault::reencrypt_plan`, the dry-run report behind `--plan`.
+//! NOTE: These tests must run serially because they share the same database file.
+
+use ironkey::storage;
+use ironkey::vault::Vault;
+use std::fs;
+
+mod common;
+
+fn cleanup() {
+    common::use_isolated_database();
+    if let Ok(db_path) = storage::get_database_path() {
+        let _ = fs::remove_file(db_path);
+    }
+}
+
+#[test]
+fn test_plan_reports_entry_count_without_changes() {
+    cleanup();
+    let mut vault = Vault::init("master".to_string()).unwrap();
+    for key in ["a", "b", "c"] {
+        vault
+            .create_entry(key.to_string(), format!("value_{key}"))
+            .unwrap();
+    }
+    vault.toggle_lock("b").unwrap();
+
+    let db_path = storage::get_database_path().unwrap();
+    let before = fs::read_to_string(&db_path).unwrap();
+
+    let plan = vault.reencrypt_plan(None).unwrap();
+    assert_eq!(plan.entry_count, 3);
+    assert_eq!(plan.locked_count, 1);
+    assert_eq!(plan.iterations, ironkey::crypto::default_iterations());
+    assert!(plan.estimated_time >= plan.kdf_time);
+
+    // Nothing was written and entries still decrypt with the old key
+    assert_eq!(fs::read_to_string(&db_path).unwrap(), before);
+    assert_eq!(vault.get_entry("a").unwrap(), "value_a");
+
+    cleanup();
+}
+
+#[test]
+fn test_plan_with_new_iterations_and_empty_vault() {
+    cleanup();
+    let vault = Vault::init("master".to_string()).unwrap();
+
+    let plan = vault.reencrypt_plan(Some(200_000)).unwrap();
+    assert_eq!(plan.entry_count, 0);
+    assert_eq!(plan.iterations, 200_000);
+    assert_eq!(plan.estimated_time, plan.kdf_time);
+
+    cleanup();
+}
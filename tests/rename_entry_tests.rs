@@ -0,0 +1,153 @@
+//! Entry Rename Tests
+//!
+//! Tests for `Vault::rename_entry` (`ik rename --from old --to new`).
+
+use ironkey::crypto;
+use ironkey::error::Error;
+use ironkey::storage::{Entry, KeyScheme, MemoryBackend, StorageBackend};
+use ironkey::vault::VaultBuilder;
+
+mod common;
+
+#[test]
+fn test_rename_entry_keeps_value_and_metadata() {
+    let backend = MemoryBackend::new();
+    let mut vault =
+        common::vault_with_entries(&backend, &[("github", "ghp_token"), ("other", "x")]);
+    vault
+        .set_label("github", Some("work account".to_string()))
+        .unwrap();
+    let created = backend.load().unwrap().entries["github"].created_at.clone();
+
+    vault.rename_entry("github", "github_work").unwrap();
+    assert_eq!(vault.get_entry("github_work").unwrap(), "ghp_token");
+    drop(vault);
+
+    let db = backend.load().unwrap();
+    assert!(!db.entries.contains_key("github"));
+    assert_eq!(db.entries["github_work"].created_at, created);
+
+    let vault = common::reopen(&backend, "master");
+    assert_eq!(vault.get_entry("github_work").unwrap(), "ghp_token");
+    assert_eq!(
+        vault.get_label("github_work").unwrap(),
+        Some("work account")
+    );
+    assert_eq!(vault.get_entry("other").unwrap(), "x");
+    assert!(matches!(
+        vault.get_entry("github"),
+        Err(Error::EntryNotFound(_))
+    ));
+}
+
+#[test]
+fn test_rename_locked_entry_stays_locked() {
+    let backend = MemoryBackend::new();
+    let mut vault = common::vault_with_entries(&backend, &[("prod", "secret")]);
+    vault.toggle_lock("prod").unwrap();
+
+    vault.rename_entry("prod", "production").unwrap();
+    drop(vault);
+
+    let mut vault = common::reopen(&backend, "master");
+    assert!(matches!(
+        vault.get_entry("production"),
+        Err(Error::EntryLocked(_))
+    ));
+    vault.toggle_lock("production").unwrap();
+    assert_eq!(vault.get_entry("production").unwrap(), "secret");
+}
+
+#[test]
+fn test_rename_missing_entry() {
+    let backend = MemoryBackend::new();
+    let mut vault = common::vault_with_entries(&backend, &[]);
+
+    assert!(matches!(
+        vault.rename_entry("missing", "new"),
+        Err(Error::EntryNotFound(_))
+    ));
+}
+
+#[test]
+fn test_rename_onto_existing_key_changes_nothing() {
+    let backend = MemoryBackend::new();
+    let mut vault = common::vault_with_entries(&backend, &[("a", "one"), ("b", "two")]);
+    let before = serde_json::to_string(&backend.load().unwrap()).unwrap();
+
+    assert!(matches!(
+        vault.rename_entry("a", "b"),
+        Err(Error::EntryAlreadyExists(_))
+    ));
+    assert!(matches!(
+        vault.rename_entry("a", "a"),
+        Err(Error::EntryAlreadyExists(_))
+    ));
+
+    assert_eq!(
+        serde_json::to_string(&backend.load().unwrap()).unwrap(),
+        before
+    );
+    assert_eq!(vault.get_entry("a").unwrap(), "one");
+    assert_eq!(vault.get_entry("b").unwrap(), "two");
+}
+
+#[test]
+fn test_rename_legacy_entry_keeps_ciphertext() {
+    let backend = MemoryBackend::new();
+    drop(common::vault_with_entries(&backend, &[]));
+
+    // Rewrite as a pre-HKDF vault, where ciphertext doesn't depend on the key name
+    let mut db = backend.load().unwrap();
+    let master = crypto::derive_key("master", &db.get_salt().unwrap(), db.iterations).unwrap();
+    db.key_scheme = KeyScheme::Legacy;
+    db.mac = None;
+    let encrypted = crypto::encrypt(b"legacy_secret", &master).unwrap();
+    db.entries.insert(
+        "old".to_string(),
+        Entry::new(encrypted.ciphertext, encrypted.nonce, false),
+    );
+    backend.save(&db).unwrap();
+    let ciphertext = db.entries["old"].encrypted_value.clone();
+
+    let mut vault = common::reopen(&backend, "master");
+    vault.rename_entry("old", "new").unwrap();
+    drop(vault);
+
+    assert_eq!(
+        backend.load().unwrap().entries["new"].encrypted_value,
+        ciphertext
+    );
+    assert_eq!(
+        common::reopen(&backend, "master").get_entry("new").unwrap(),
+        "legacy_secret"
+    );
+}
+
+#[test]
+fn test_rename_removes_old_row_from_sqlite() {
+    use ironkey::sqlite_backend::SqliteBackend;
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let path = temp_dir.path().join("ironkey.db");
+
+    let mut vault = VaultBuilder::new()
+        .backend(Box::new(SqliteBackend::open(&path).unwrap()))
+        .init("master".to_string())
+        .unwrap();
+    vault
+        .create_entry("github".to_string(), "ghp_token".to_string())
+        .unwrap();
+    vault.rename_entry("github", "github_work").unwrap();
+    drop(vault);
+
+    let backend = SqliteBackend::open(&path).unwrap();
+    let keys: Vec<String> = backend.load().unwrap().entries.into_keys().collect();
+    assert_eq!(keys, vec!["github_work".to_string()]);
+
+    let vault = VaultBuilder::new()
+        .backend(Box::new(backend))
+        .unlock("master".to_string())
+        .unwrap();
+    assert_eq!(vault.get_entry("github_work").unwrap(), "ghp_token");
+}
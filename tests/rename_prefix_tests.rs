@@ -0,0 +1,161 @@
+//! Prefix Rename Tests
+//!
+//! Tests for `Vault::rename_prefix` (`ik rename --prefix old_ --to new_`).
+
+use ironkey::crypto;
+use ironkey::error::Error;
+use ironkey::storage::{Entry, KeyScheme, MemoryBackend, StorageBackend};
+use ironkey::vault::VaultBuilder;
+
+mod common;
+
+fn sorted_keys(backend: &MemoryBackend) -> Vec<String> {
+    let mut keys: Vec<String> = backend.load().unwrap().entries.into_keys().collect();
+    keys.sort();
+    keys
+}
+
+#[test]
+fn test_rename_two_keys_by_prefix() {
+    let backend = MemoryBackend::new();
+    let mut vault = common::vault_with_entries(
+        &backend,
+        &[
+            ("old_project_db", "db_secret"),
+            ("old_project_api", "api_secret"),
+            ("other", "untouched"),
+        ],
+    );
+    vault
+        .set_label("old_project_db", Some("primary".to_string()))
+        .unwrap();
+
+    let renamed = vault.rename_prefix("old_project_", "new_project_").unwrap();
+    assert_eq!(
+        renamed,
+        vec![
+            ("old_project_api".to_string(), "new_project_api".to_string()),
+            ("old_project_db".to_string(), "new_project_db".to_string()),
+        ]
+    );
+    drop(vault);
+
+    assert_eq!(
+        sorted_keys(&backend),
+        vec!["new_project_api", "new_project_db", "other"]
+    );
+
+    // Values still decrypt under the new names and metadata came along
+    let vault = common::reopen(&backend, "master");
+    assert_eq!(vault.get_entry("new_project_db").unwrap(), "db_secret");
+    assert_eq!(vault.get_entry("new_project_api").unwrap(), "api_secret");
+    assert_eq!(vault.get_entry("other").unwrap(), "untouched");
+    assert_eq!(vault.get_label("new_project_db").unwrap(), Some("primary"));
+}
+
+#[test]
+fn test_collision_leaves_vault_untouched() {
+    let backend = MemoryBackend::new();
+    let mut vault = common::vault_with_entries(
+        &backend,
+        &[("old_a", "a"), ("old_b", "b"), ("new_b", "already here")],
+    );
+    let before = sorted_keys(&backend);
+
+    let err = vault.rename_prefix("old_", "new_").unwrap_err();
+    assert!(matches!(err, Error::EntryAlreadyExists(ref key) if key == "new_b"));
+
+    // Nothing was renamed, in memory or on disk
+    assert_eq!(sorted_keys(&backend), before);
+    assert_eq!(vault.get_entry("old_a").unwrap(), "a");
+    assert!(matches!(
+        vault.get_entry("new_a"),
+        Err(Error::EntryNotFound(_))
+    ));
+    assert_eq!(vault.get_entry("new_b").unwrap(), "already here");
+}
+
+#[test]
+fn test_locked_entry_blocks_rename() {
+    let backend = MemoryBackend::new();
+    let mut vault = common::vault_with_entries(&backend, &[("old_a", "a"), ("old_b", "b")]);
+    vault.toggle_lock("old_b").unwrap();
+    let before = sorted_keys(&backend);
+
+    assert!(matches!(
+        vault.rename_prefix("old_", "new_"),
+        Err(Error::EntryLocked(ref key)) if key == "old_b"
+    ));
+    assert_eq!(sorted_keys(&backend), before);
+}
+
+#[test]
+fn test_overlapping_prefixes_are_not_collisions() {
+    // "a_x" -> "a_a_x" while "a_a_x" itself moves to "a_a_a_x"
+    let backend = MemoryBackend::new();
+    let mut vault = common::vault_with_entries(&backend, &[("a_x", "one"), ("a_a_x", "two")]);
+
+    vault.rename_prefix("a_", "a_a_").unwrap();
+    drop(vault);
+
+    let vault = common::reopen(&backend, "master");
+    assert_eq!(vault.get_entry("a_a_x").unwrap(), "one");
+    assert_eq!(vault.get_entry("a_a_a_x").unwrap(), "two");
+}
+
+#[test]
+fn test_no_match_and_invalid_prefixes() {
+    let backend = MemoryBackend::new();
+    let mut vault = common::vault_with_entries(&backend, &[("github", "token")]);
+
+    assert!(vault.rename_prefix("old_", "new_").unwrap().is_empty());
+    assert!(matches!(
+        vault.rename_prefix("", "new_"),
+        Err(Error::InvalidInput(_))
+    ));
+    assert!(matches!(
+        vault.rename_prefix("git", "git"),
+        Err(Error::InvalidInput(_))
+    ));
+}
+
+#[test]
+fn test_legacy_vault_keeps_ciphertext() {
+    let backend = MemoryBackend::new();
+    drop(
+        VaultBuilder::new()
+            .backend(Box::new(backend.clone()))
+            .init("master".to_string())
+            .unwrap(),
+    );
+
+    // Rewrite as a pre-HKDF vault, where the key name isn't bound into the ciphertext
+    let mut db = backend.load().unwrap();
+    let master = crypto::derive_key("master", &db.get_salt().unwrap(), db.iterations).unwrap();
+    db.key_scheme = KeyScheme::Legacy;
+    db.mac = None;
+    let encrypted = crypto::encrypt(b"legacy_secret", &master).unwrap();
+    db.entries.insert(
+        "old_key".to_string(),
+        Entry::new(encrypted.ciphertext, encrypted.nonce, false),
+    );
+    backend.save(&db).unwrap();
+    let before = backend.load().unwrap().entries["old_key"]
+        .encrypted_value
+        .clone();
+
+    let mut vault = common::reopen(&backend, "master");
+    vault.rename_prefix("old_", "new_").unwrap();
+    drop(vault);
+
+    assert_eq!(
+        backend.load().unwrap().entries["new_key"].encrypted_value,
+        before
+    );
+    assert_eq!(
+        common::reopen(&backend, "master")
+            .get_entry("new_key")
+            .unwrap(),
+        "legacy_secret"
+    );
+}
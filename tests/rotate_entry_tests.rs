@@ -0,0 +1,71 @@
+//! Rotate Entry Tests
+//!
+//! Tests for `Vault::rotate_entry` (`ik generate --key <name> --rotate`).
+
+use ironkey::error::Error;
+use ironkey::storage::MemoryBackend;
+use ironkey::vault::{Vault, VaultBuilder};
+
+fn new_vault() -> Vault {
+    VaultBuilder::new()
+        .backend(Box::new(MemoryBackend::new()))
+        .init("master".to_string())
+        .unwrap()
+}
+
+#[test]
+fn test_rotate_replaces_existing_value() {
+    let mut vault = new_vault();
+    vault
+        .create_entry("github".to_string(), "old-token".to_string())
+        .unwrap();
+
+    let old_len = vault
+        .rotate_entry("github".to_string(), "fresh-generated-pw".to_string())
+        .unwrap();
+
+    assert_eq!(old_len, Some(9));
+    assert_eq!(vault.get_entry("github").unwrap(), "fresh-generated-pw");
+}
+
+#[test]
+fn test_rotate_keeps_old_value_in_history() {
+    let mut vault = new_vault();
+    vault
+        .create_entry("github".to_string(), "old-token".to_string())
+        .unwrap();
+
+    vault
+        .rotate_entry("github".to_string(), "new-token".to_string())
+        .unwrap();
+
+    let history = vault.get_history("github").unwrap();
+    assert_eq!(history[0].value, "old-token");
+}
+
+#[test]
+fn test_rotate_creates_missing_entry() {
+    let mut vault = new_vault();
+
+    let old_len = vault
+        .rotate_entry("new_key".to_string(), "value".to_string())
+        .unwrap();
+
+    assert_eq!(old_len, None);
+    assert_eq!(vault.get_entry("new_key").unwrap(), "value");
+}
+
+#[test]
+fn test_rotate_locked_entry_fails() {
+    let mut vault = new_vault();
+    vault
+        .create_entry("prod".to_string(), "secret".to_string())
+        .unwrap();
+    vault.toggle_lock("prod").unwrap();
+
+    let result = vault.rotate_entry("prod".to_string(), "replacement".to_string());
+
+    assert!(matches!(result, Err(Error::EntryLocked(_))));
+    vault.toggle_lock("prod").unwrap();
+    assert_eq!(vault.get_entry("prod").unwrap(), "secret");
+}
@@ -0,0 +1,162 @@
+//! Rotating Backup Tests
+//!
+//! Tests for the `.bak.1`..`.bak.N` copies kept on every save and `ik restore --backup <n>`.
+
+use ironkey::error::Error;
+use ironkey::storage::{BACKUP_COUNT, JsonFileBackend, StorageBackend};
+use ironkey::vault::VaultBuilder;
+use std::fs;
+use tempfile::TempDir;
+
+fn temp_backend(dir: &TempDir) -> JsonFileBackend {
+    JsonFileBackend::new(dir.path().join("ironkey.json"))
+}
+
+fn entry_count(backend: &JsonFileBackend, index: usize) -> usize {
+    let content = fs::read_to_string(backend.backup_path(index)).unwrap();
+    let db: ironkey::storage::Database = serde_json::from_str(&content).unwrap();
+    db.entries.len()
+}
+
+#[test]
+fn test_first_init_creates_no_backup() {
+    let dir = TempDir::new().unwrap();
+    let backend = temp_backend(&dir);
+    drop(
+        VaultBuilder::new()
+            .backend(Box::new(backend.clone()))
+            .init("master".to_string())
+            .unwrap(),
+    );
+
+    assert!(backend.exists().unwrap());
+    assert!(!backend.backup_path(1).exists());
+}
+
+#[test]
+fn test_backups_rotate_and_are_capped() {
+    let dir = TempDir::new().unwrap();
+    let backend = temp_backend(&dir);
+    let mut vault = VaultBuilder::new()
+        .backend(Box::new(backend.clone()))
+        .init("master".to_string())
+        .unwrap();
+
+    for i in 0..5 {
+        vault
+            .create_entry(format!("key_{i}"), "x".to_string())
+            .unwrap();
+    }
+
+    // Current has 5 entries; .bak.1 is the state before the last save, and so on
+    assert_eq!(backend.load().unwrap().entries.len(), 5);
+    for index in 1..=BACKUP_COUNT {
+        assert_eq!(entry_count(&backend, index), 5 - index);
+    }
+    assert!(!backend.backup_path(BACKUP_COUNT + 1).exists());
+}
+
+#[test]
+fn test_restore_backup() {
+    let dir = TempDir::new().unwrap();
+    let backend = temp_backend(&dir);
+    let mut vault = VaultBuilder::new()
+        .backend(Box::new(backend.clone()))
+        .init("master".to_string())
+        .unwrap();
+    vault
+        .create_entry("github".to_string(), "ghp_token".to_string())
+        .unwrap();
+    vault.delete_entry("github").unwrap();
+    drop(vault);
+
+    assert_eq!(backend.restore_backup(1).unwrap(), 1);
+    let vault = VaultBuilder::new()
+        .backend(Box::new(backend.clone()))
+        .unlock("master".to_string())
+        .unwrap();
+    assert_eq!(vault.get_entry("github").unwrap(), "ghp_token");
+
+    // The database that was replaced is now the newest backup, so the restore can be undone
+    assert_eq!(entry_count(&backend, 1), 0);
+}
+
+#[test]
+fn test_restore_backup_invalid_index() {
+    let dir = TempDir::new().unwrap();
+    let backend = temp_backend(&dir);
+    drop(
+        VaultBuilder::new()
+            .backend(Box::new(backend.clone()))
+            .init("master".to_string())
+            .unwrap(),
+    );
+
+    for index in [0, 1, BACKUP_COUNT + 1] {
+        assert!(matches!(
+            backend.restore_backup(index),
+            Err(Error::InvalidInput(_))
+        ));
+    }
+}
+
+#[test]
+fn test_corrupt_backup_is_not_restored() {
+    let dir = TempDir::new().unwrap();
+    let backend = temp_backend(&dir);
+    let mut vault = VaultBuilder::new()
+        .backend(Box::new(backend.clone()))
+        .init("master".to_string())
+        .unwrap();
+    vault
+        .create_entry("github".to_string(), "ghp_token".to_string())
+        .unwrap();
+    drop(vault);
+
+    fs::write(backend.backup_path(1), "not json").unwrap();
+    let before = fs::read(dir.path().join("ironkey.json")).unwrap();
+
+    assert!(backend.restore_backup(1).is_err());
+    assert_eq!(fs::read(dir.path().join("ironkey.json")).unwrap(), before);
+}
+
+#[test]
+fn test_remove_deletes_backups() {
+    let dir = TempDir::new().unwrap();
+    let backend = temp_backend(&dir);
+    let mut vault = VaultBuilder::new()
+        .backend(Box::new(backend.clone()))
+        .init("master".to_string())
+        .unwrap();
+    vault
+        .create_entry("github".to_string(), "ghp_token".to_string())
+        .unwrap();
+    drop(vault);
+    assert!(backend.backup_path(1).exists());
+
+    backend.remove().unwrap();
+    assert!(!backend.exists().unwrap());
+    assert!(!backend.backup_path(1).exists());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_backups_are_private() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = TempDir::new().unwrap();
+    let backend = temp_backend(&dir);
+    let mut vault = VaultBuilder::new()
+        .backend(Box::new(backend.clone()))
+        .init("master".to_string())
+        .unwrap();
+    vault
+        .create_entry("github".to_string(), "ghp_token".to_string())
+        .unwrap();
+
+    let mode = fs::metadata(backend.backup_path(1))
+        .unwrap()
+        .permissions()
+        .mode();
+    assert_eq!(mode & 0o777, 0o600);
+}
@@ -0,0 +1,134 @@
+//! Safe Save Tests
+//!
+//! Tests for `--safe-save` (fsync plus read-back verification after each JSON save).
+
+use ironkey::config::Config;
+use ironkey::error::Error;
+use ironkey::storage::{JsonFileBackend, StorageBackend};
+use ironkey::vault::VaultBuilder;
+use std::fs;
+use tempfile::TempDir;
+
+#[test]
+fn test_safe_save_round_trip() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("nested").join("ironkey.json");
+    let backend = JsonFileBackend::new(path).with_safe_save(true);
+
+    let mut vault = VaultBuilder::new()
+        .backend(Box::new(backend.clone()))
+        .init("master".to_string())
+        .unwrap();
+    vault
+        .create_entry("github".to_string(), "ghp_token".to_string())
+        .unwrap();
+    vault
+        .update_entry("github".to_string(), "ghp_new".to_string())
+        .unwrap();
+    drop(vault);
+
+    let vault = VaultBuilder::new()
+        .backend(Box::new(backend))
+        .unlock("master".to_string())
+        .unwrap();
+    assert_eq!(vault.get_entry("github").unwrap(), "ghp_new");
+}
+
+#[test]
+fn test_safe_save_writes_same_bytes_as_plain_save() {
+    let temp_dir = TempDir::new().unwrap();
+    let plain_path = temp_dir.path().join("plain.json");
+    let safe_path = temp_dir.path().join("safe.json");
+
+    let source = JsonFileBackend::new(plain_path.clone());
+    drop(
+        VaultBuilder::new()
+            .backend(Box::new(source.clone()))
+            .init("master".to_string())
+            .unwrap(),
+    );
+    let db = source.load().unwrap();
+
+    JsonFileBackend::new(safe_path.clone())
+        .with_safe_save(true)
+        .save(&db)
+        .unwrap();
+
+    assert_eq!(fs::read(plain_path).unwrap(), fs::read(safe_path).unwrap());
+}
+
+#[test]
+fn test_verify_written_accepts_matching_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("ironkey.json");
+    drop(
+        VaultBuilder::new()
+            .backend(Box::new(JsonFileBackend::new(path.clone())))
+            .init("master".to_string())
+            .unwrap(),
+    );
+
+    let written = fs::read(&path).unwrap();
+    JsonFileBackend::verify_written(&path, &written).unwrap();
+}
+
+#[test]
+fn test_verify_written_detects_mismatch() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("ironkey.json");
+    drop(
+        VaultBuilder::new()
+            .backend(Box::new(JsonFileBackend::new(path.clone())))
+            .init("master".to_string())
+            .unwrap(),
+    );
+    let expected = fs::read(&path).unwrap();
+
+    // Simulate the disk returning different bytes than were written
+    let mut corrupted = expected.clone();
+    let last = corrupted.len() - 2;
+    corrupted[last] ^= 0x01;
+    fs::write(&path, &corrupted).unwrap();
+
+    assert!(matches!(
+        JsonFileBackend::verify_written(&path, &expected),
+        Err(Error::DatabaseSaveFailed(_))
+    ));
+
+    // A truncated write is caught the same way
+    fs::write(&path, &expected[..expected.len() / 2]).unwrap();
+    assert!(matches!(
+        JsonFileBackend::verify_written(&path, &expected),
+        Err(Error::DatabaseSaveFailed(_))
+    ));
+}
+
+#[test]
+fn test_verify_written_rejects_unparseable_content() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("ironkey.json");
+    fs::write(&path, b"{ not json").unwrap();
+
+    // Bytes match but don't form a database
+    assert!(matches!(
+        JsonFileBackend::verify_written(&path, b"{ not json"),
+        Err(Error::DatabaseSaveFailed(_))
+    ));
+}
+
+#[test]
+fn test_verify_written_missing_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("missing.json");
+
+    assert!(matches!(
+        JsonFileBackend::verify_written(&path, b"{}"),
+        Err(Error::DatabaseSaveFailed(_))
+    ));
+}
+
+#[test]
+fn test_config_safe_save() {
+    assert!(!Config::from_toml_str("").unwrap().safe_save);
+    assert!(Config::from_toml_str("safe_save = true").unwrap().safe_save);
+}
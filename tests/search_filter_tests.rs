@@ -9,9 +9,12 @@ use ironkey::storage;
 use ironkey::vault::Vault;
 use std::fs;
 
+mod common;
+
 /// Helper function to create a test vault with sample entries
 /// Note: Tests must run serially due to shared database file
 fn setup_test_vault() -> Result<Vault> {
+    common::use_isolated_database();
     // Clean up any existing test database
     let db_path = storage::get_database_path()?;
     let _ = fs::remove_file(&db_path);
@@ -38,6 +41,7 @@ fn setup_test_vault() -> Result<Vault> {
 
 /// Cleanup function to remove test database
 fn cleanup_test_vault() {
+    common::use_isolated_database();
     if let Ok(db_path) = storage::get_database_path() {
         let _ = fs::remove_file(db_path);
     }
@@ -0,0 +1,90 @@
+//! Search Index Tests
+//!
+//! Tests that the cached lowercase key index gives the same results as a naive scan.
+//! Uses the in-memory backend, so these don't touch the database file.
+
+use ironkey::storage::MemoryBackend;
+use ironkey::vault::{Vault, VaultBuilder};
+
+const KEY_COUNT: usize = 2_000;
+
+fn vault_with_many_keys() -> Vault {
+    let mut vault = VaultBuilder::new()
+        .backend(Box::new(MemoryBackend::new()))
+        .init("master".to_string())
+        .unwrap();
+    for i in 0..KEY_COUNT {
+        let key = match i % 3 {
+            0 => format!("GitHub_Token_{i}"),
+            1 => format!("aws_access_{i}"),
+            _ => format!("Database_PASS_{i}"),
+        };
+        vault.create_entry(key, "v".to_string()).unwrap();
+    }
+    vault.toggle_lock("aws_access_1").unwrap();
+    vault
+}
+
+/// Reference implementation: lowercase every key on every query
+fn naive_search(vault: &Vault, term: &str, lock_filter: Option<bool>) -> Vec<(String, bool)> {
+    let term = term.to_lowercase();
+    let mut results: Vec<(String, bool)> = vault
+        .list_entries(None, lock_filter)
+        .unwrap()
+        .into_iter()
+        .filter(|(key, _)| key.to_lowercase().contains(&term))
+        .map(|(key, locked)| (key.clone(), locked))
+        .collect();
+    results.sort();
+    results
+}
+
+fn indexed_search(vault: &Vault, term: &str, lock_filter: Option<bool>) -> Vec<(String, bool)> {
+    vault
+        .list_entries(Some(term), lock_filter)
+        .unwrap()
+        .into_iter()
+        .map(|(key, locked)| (key.clone(), locked))
+        .collect()
+}
+
+#[test]
+fn test_cached_search_matches_naive_scan() {
+    let vault = vault_with_many_keys();
+    vault.prehash_search_index();
+
+    for term in [
+        "github",
+        "TOKEN",
+        "aws_access_1",
+        "pass_1",
+        "_99",
+        "missing",
+        "",
+    ] {
+        for lock_filter in [None, Some(true), Some(false)] {
+            assert_eq!(
+                indexed_search(&vault, term, lock_filter),
+                naive_search(&vault, term, lock_filter),
+                "mismatch for term {term:?} with lock filter {lock_filter:?}"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_index_is_invalidated_on_mutation() {
+    let mut vault = vault_with_many_keys();
+    assert!(indexed_search(&vault, "brand_new", None).is_empty());
+
+    vault
+        .create_entry("Brand_New_Key".to_string(), "v".to_string())
+        .unwrap();
+    assert_eq!(
+        indexed_search(&vault, "brand_new", None),
+        vec![("Brand_New_Key".to_string(), false)]
+    );
+
+    vault.delete_entry("Brand_New_Key").unwrap();
+    assert!(indexed_search(&vault, "brand_new", None).is_empty());
+}
@@ -0,0 +1,80 @@
+//! Value Search Tests
+//!
+//! Tests for `Vault::search_values` (`ik search --value <text>`).
+
+use ironkey::error::Error;
+use ironkey::storage::MemoryBackend;
+
+mod common;
+
+#[test]
+fn test_matches_value_substring() {
+    let vault = common::vault_with_entries(
+        &MemoryBackend::new(),
+        &[
+            ("github", "user=alice;token=abc"),
+            ("gitlab", "user=bob;token=def"),
+            ("aws", "alice@example.com"),
+        ],
+    );
+
+    let result = vault.search_values("alice", false).unwrap();
+
+    assert_eq!(result.matches, ["aws", "github"]);
+    assert_eq!(result.skipped_locked, 0);
+}
+
+#[test]
+fn test_case_sensitive_by_default() {
+    let vault = common::vault_with_entries(&MemoryBackend::new(), &[("github", "User=Alice")]);
+
+    assert!(
+        vault
+            .search_values("alice", false)
+            .unwrap()
+            .matches
+            .is_empty()
+    );
+    assert_eq!(
+        vault.search_values("alice", true).unwrap().matches,
+        ["github"]
+    );
+}
+
+#[test]
+fn test_does_not_match_keys() {
+    let vault = common::vault_with_entries(&MemoryBackend::new(), &[("alice_token", "xyz")]);
+
+    assert!(
+        vault
+            .search_values("alice", true)
+            .unwrap()
+            .matches
+            .is_empty()
+    );
+}
+
+#[test]
+fn test_skips_and_counts_locked_entries() {
+    let mut vault = common::vault_with_entries(
+        &MemoryBackend::new(),
+        &[("github", "alice"), ("prod", "alice"), ("staging", "alice")],
+    );
+    vault.toggle_lock("prod").unwrap();
+    vault.toggle_lock("staging").unwrap();
+
+    let result = vault.search_values("alice", false).unwrap();
+
+    assert_eq!(result.matches, ["github"]);
+    assert_eq!(result.skipped_locked, 2);
+}
+
+#[test]
+fn test_empty_search_is_rejected() {
+    let vault = common::vault_with_entries(&MemoryBackend::new(), &[("github", "alice")]);
+
+    assert!(matches!(
+        vault.search_values("", false),
+        Err(Error::InvalidInput(_))
+    ));
+}
@@ -0,0 +1,66 @@
+//! Secret String Tests
+//!
+//! Tests for `SecretString` and the vault methods that return it
+//! (`get_entry_secret`, `access_entry_secret`). These use `MemoryBackend`.
+
+use ironkey::error::Error;
+use ironkey::secret::SecretString;
+use ironkey::storage::MemoryBackend;
+
+mod common;
+
+#[test]
+fn test_secret_string_exposes_value() {
+    let secret = SecretString::from("hunter2 ✓".to_string());
+
+    assert_eq!(secret.as_str(), "hunter2 ✓");
+    assert_eq!(secret, "hunter2 ✓");
+    assert_eq!(secret.len(), "hunter2 ✓".len());
+    assert!(secret.starts_with("hunter2"));
+}
+
+#[test]
+fn test_secret_string_debug_is_redacted() {
+    let secret = SecretString::from("hunter2".to_string());
+
+    let debug = format!("{secret:?}");
+    assert!(!debug.contains("hunter2"));
+    assert_eq!(debug, "SecretString(***)");
+}
+
+#[test]
+fn test_get_entry_secret_matches_get_entry() {
+    let vault = common::vault_with_entry(&MemoryBackend::new(), "github", "ghp_token");
+
+    assert_eq!(vault.get_entry_secret("github").unwrap(), "ghp_token");
+    // Served from the value cache the second time
+    assert_eq!(vault.get_entry_secret("github").unwrap(), "ghp_token");
+    assert_eq!(vault.get_entry("github").unwrap(), "ghp_token");
+}
+
+#[test]
+fn test_get_entry_secret_errors() {
+    let mut vault = common::vault_with_entry(&MemoryBackend::new(), "github", "ghp_token");
+    vault.toggle_lock("github").unwrap();
+
+    assert!(matches!(
+        vault.get_entry_secret("github"),
+        Err(Error::EntryLocked(_))
+    ));
+    assert!(matches!(
+        vault.get_entry_secret("missing"),
+        Err(Error::EntryNotFound(_))
+    ));
+}
+
+#[test]
+fn test_access_entry_secret_records_access() {
+    let mut vault = common::vault_with_entry(&MemoryBackend::new(), "github", "ghp_token");
+    vault.set_access_tracking(true);
+
+    assert_eq!(vault.access_entry_secret("github").unwrap(), "ghp_token");
+    assert_eq!(vault.access_entry("github").unwrap(), "ghp_token");
+
+    let stats = vault.access_stats();
+    assert_eq!(stats[0].1, 2);
+}
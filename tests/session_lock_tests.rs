@@ -0,0 +1,181 @@
+//! Session Lock Tests
+//!
+//! Tests for `Vault::lock_session` and `Vault::reunlock` (idle timeouts).
+
+use ironkey::error::Error;
+use ironkey::storage::{MemoryBackend, StorageBackend};
+use ironkey::vault::VaultBuilder;
+
+mod common;
+
+#[test]
+fn test_locked_session_refuses_entry_operations() {
+    let backend = MemoryBackend::new();
+    let mut vault = common::vault_with_entry(&backend, "github", "ghp_token");
+    vault.lock_session();
+    assert!(vault.is_session_locked());
+
+    assert!(matches!(
+        vault.get_entry("github"),
+        Err(Error::InvalidMasterPassword)
+    ));
+    assert!(matches!(
+        vault.create_entry("aws".to_string(), "x".to_string()),
+        Err(Error::InvalidMasterPassword)
+    ));
+    assert!(matches!(
+        vault.update_entry("github".to_string(), "x".to_string()),
+        Err(Error::InvalidMasterPassword)
+    ));
+    assert!(matches!(
+        vault.delete_entry("github"),
+        Err(Error::InvalidMasterPassword)
+    ));
+
+    // Nothing was written while locked
+    let db = backend.load().unwrap();
+    assert_eq!(db.entries.len(), 1);
+}
+
+#[test]
+fn test_reunlock_restores_access() {
+    let backend = MemoryBackend::new();
+    let mut vault = common::vault_with_entry(&backend, "github", "ghp_token");
+    vault.lock_session();
+
+    vault.reunlock("master".to_string()).unwrap();
+
+    assert!(!vault.is_session_locked());
+    assert_eq!(vault.get_entry("github").unwrap(), "ghp_token");
+    vault
+        .create_entry("aws".to_string(), "aws_key".to_string())
+        .unwrap();
+    drop(vault);
+
+    // Saves after re-unlocking are sealed with the right key
+    let vault = VaultBuilder::new()
+        .backend(Box::new(backend))
+        .unlock("master".to_string())
+        .unwrap();
+    assert_eq!(vault.get_entry("aws").unwrap(), "aws_key");
+}
+
+#[test]
+fn test_reunlock_with_wrong_password_stays_locked() {
+    let backend = MemoryBackend::new();
+    let mut vault = common::vault_with_entry(&backend, "github", "ghp_token");
+    vault.lock_session();
+
+    assert!(matches!(
+        vault.reunlock("wrong".to_string()),
+        Err(Error::InvalidMasterPassword)
+    ));
+    assert!(vault.is_session_locked());
+    assert!(vault.get_entry("github").is_err());
+}
+
+#[test]
+fn test_locked_session_refuses_history_and_notes() {
+    let backend = MemoryBackend::new();
+    let mut vault = common::vault_with_entry(&backend, "github", "ghp_token");
+    vault
+        .update_entry("github".to_string(), "ghp_new".to_string())
+        .unwrap();
+    vault
+        .set_note("github", "rotate monthly".to_string())
+        .unwrap();
+    vault.lock_session();
+
+    assert!(matches!(
+        vault.get_history("github"),
+        Err(Error::InvalidMasterPassword)
+    ));
+    assert!(matches!(
+        vault.get_note("github"),
+        Err(Error::InvalidMasterPassword)
+    ));
+}
+
+#[test]
+fn test_locked_session_refuses_metadata_and_lock_changes() {
+    let backend = MemoryBackend::new();
+    let mut vault = common::vault_with_entry(&backend, "github", "ghp_token");
+    vault.lock_session();
+
+    let refused =
+        |result: ironkey::error::Result<()>| matches!(result, Err(Error::InvalidMasterPassword));
+    assert!(refused(vault.rename_entry("github", "gh")));
+    assert!(refused(vault.set_label("github", Some("work".to_string()))));
+    assert!(refused(vault.add_tag("github", "work").map(|_| ())));
+    assert!(refused(vault.remove_tag("github", "work").map(|_| ())));
+    assert!(refused(vault.toggle_lock("github").map(|_| ())));
+    assert!(refused(
+        vault
+            .lock_for("github", chrono::Duration::minutes(5))
+            .map(|_| ())
+    ));
+    assert!(refused(vault.lock_with_password("github", "phrase")));
+
+    // Nothing was written while locked
+    let db = backend.load().unwrap();
+    let entry = &db.entries["github"];
+    assert!(!entry.is_locked);
+    assert!(entry.label.is_none());
+    assert!(entry.tags.is_empty());
+}
+
+#[test]
+fn test_locked_session_refuses_notes_and_imports() {
+    let backend = MemoryBackend::new();
+    let mut vault = common::vault_with_entry(&backend, "github", "ghp_token");
+    let mut export = Vec::new();
+    vault
+        .export_to_writer(
+            &mut export,
+            "export_pw".to_string(),
+            Default::default(),
+            None,
+        )
+        .unwrap();
+    vault.delete_entry("github").unwrap();
+    vault
+        .create_entry("aws".to_string(), "aws_key".to_string())
+        .unwrap();
+    vault.lock_session();
+
+    assert!(matches!(
+        vault.set_note("aws", "rotate monthly".to_string()),
+        Err(Error::InvalidMasterPassword)
+    ));
+    assert!(matches!(
+        vault.import_from_reader(
+            export.as_slice(),
+            "export_pw".to_string(),
+            true,
+            false,
+            false,
+            None
+        ),
+        Err(Error::InvalidMasterPassword)
+    ));
+    assert!(matches!(
+        vault.find_duplicates(),
+        Err(Error::InvalidMasterPassword)
+    ));
+    assert!(matches!(
+        vault.verify_integrity(),
+        Err(Error::InvalidMasterPassword)
+    ));
+
+    // Nothing was written, and nothing was staged to be written after re-unlocking
+    vault.reunlock("master".to_string()).unwrap();
+    assert!(vault.get_note("aws").unwrap().is_none());
+    assert!(matches!(
+        vault.get_entry("github"),
+        Err(Error::EntryNotFound(_))
+    ));
+    vault.save().unwrap();
+    let db = backend.load().unwrap();
+    assert_eq!(db.entries.len(), 1);
+    assert!(db.entries["aws"].notes.is_none());
+}
@@ -0,0 +1,69 @@
+//! Shell Tests
+//!
+//! Tests for parsing the lines typed at the `ik shell` prompt.
+
+use clap::Parser;
+use ironkey::cli::{Commands, ShellLine};
+
+fn parse(line: &str) -> Result<Commands, clap::Error> {
+    let words = shlex::split(line).expect("balanced quotes");
+    ShellLine::try_parse_from(words).map(|parsed| parsed.command)
+}
+
+#[test]
+fn test_parses_subcommand_without_binary_name() {
+    let command = parse("get -k github").unwrap();
+    assert!(matches!(command, Commands::Get { ref key, .. } if key == "github"));
+}
+
+#[test]
+fn test_quoted_values_stay_one_argument() {
+    let command = parse(r#"label -k prod -t "do not delete""#).unwrap();
+    match command {
+        Commands::Label { key, text, .. } => {
+            assert_eq!(key, "prod");
+            assert_eq!(text.as_deref(), Some("do not delete"));
+        }
+        _ => panic!("expected label"),
+    }
+}
+
+#[test]
+fn test_unknown_command_is_an_error() {
+    assert!(parse("frobnicate").is_err());
+}
+
+#[test]
+fn test_secret_values_are_kept_out_of_history() {
+    assert!(
+        parse("create -k api -v s3cret")
+            .unwrap()
+            .has_secret_argument()
+    );
+    assert!(
+        parse("update -k api -v s3cret")
+            .unwrap()
+            .has_secret_argument()
+    );
+    assert!(
+        parse("note -k api --set pin-1234")
+            .unwrap()
+            .has_secret_argument()
+    );
+}
+
+#[test]
+fn test_prompted_values_are_recorded_in_history() {
+    assert!(!parse("create -k api").unwrap().has_secret_argument());
+    assert!(!parse("get -k api").unwrap().has_secret_argument());
+    assert!(!parse("list --search api").unwrap().has_secret_argument());
+}
+
+#[test]
+fn test_vault_level_commands_do_not_run_in_shell() {
+    assert!(parse("list").unwrap().runs_in_shell());
+    assert!(parse("delete -k api").unwrap().runs_in_shell());
+    assert!(!parse("init").unwrap().runs_in_shell());
+    assert!(!parse("change-master").unwrap().runs_in_shell());
+    assert!(!parse("shell").unwrap().runs_in_shell());
+}
@@ -0,0 +1,152 @@
+//! SQLite Backend Tests
+//!
+//! Tests for `SqliteBackend` (one row per entry).
+//! Each test uses its own temporary database, so they don't touch the default vault.
+
+use ironkey::sqlite_backend::SqliteBackend;
+use ironkey::storage::StorageBackend;
+use ironkey::vault::VaultBuilder;
+use rusqlite::Connection;
+use std::path::Path;
+use tempfile::TempDir;
+
+fn open(path: &Path) -> Box<SqliteBackend> {
+    Box::new(SqliteBackend::open(path).unwrap())
+}
+
+/// Record every row-level write to `entries` in a `writes` table
+fn install_write_audit(path: &Path) -> Connection {
+    let conn = Connection::open(path).unwrap();
+    conn.execute_batch(
+        "CREATE TABLE writes (op TEXT, key TEXT);
+         CREATE TRIGGER audit_insert AFTER INSERT ON entries
+             BEGIN INSERT INTO writes VALUES ('insert', NEW.key); END;
+         CREATE TRIGGER audit_update AFTER UPDATE ON entries
+             BEGIN INSERT INTO writes VALUES ('update', NEW.key); END;
+         CREATE TRIGGER audit_delete AFTER DELETE ON entries
+             BEGIN INSERT INTO writes VALUES ('delete', OLD.key); END;",
+    )
+    .unwrap();
+    conn
+}
+
+fn take_writes(conn: &Connection) -> Vec<(String, String)> {
+    let mut stmt = conn.prepare("SELECT op, key FROM writes").unwrap();
+    let writes = stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+        .unwrap()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    conn.execute("DELETE FROM writes", []).unwrap();
+    writes
+}
+
+#[test]
+fn test_crud_through_sqlite_backend() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("vault.db");
+
+    let mut vault = VaultBuilder::new()
+        .backend(open(&path))
+        .init("master".to_string())
+        .unwrap();
+    vault
+        .create_entry("github".to_string(), "ghp_token".to_string())
+        .unwrap();
+    vault
+        .create_entry("aws".to_string(), "aws_secret".to_string())
+        .unwrap();
+    vault
+        .update_entry("github".to_string(), "ghp_new".to_string())
+        .unwrap();
+    vault.toggle_lock("aws").unwrap();
+    vault
+        .create_entry("temp".to_string(), "x".to_string())
+        .unwrap();
+    vault.delete_entry("temp").unwrap();
+    drop(vault);
+
+    // Reopen from disk
+    let vault = VaultBuilder::new()
+        .backend(open(&path))
+        .unlock("master".to_string())
+        .unwrap();
+    assert_eq!(vault.get_entry("github").unwrap(), "ghp_new");
+    assert!(vault.get_entry("temp").is_err());
+    assert_eq!(
+        vault.list_entries(None, Some(true)).unwrap(),
+        vec![(&"aws".to_string(), true)]
+    );
+    assert!(vault.last_modified().is_some());
+
+    assert!(
+        VaultBuilder::new()
+            .backend(open(&path))
+            .unlock("wrong".to_string())
+            .is_err()
+    );
+}
+
+#[test]
+fn test_single_operations_touch_only_their_row() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("vault.db");
+
+    let mut vault = VaultBuilder::new()
+        .backend(open(&path))
+        .init("master".to_string())
+        .unwrap();
+    for key in ["a", "b", "c"] {
+        vault
+            .create_entry(key.to_string(), format!("value_{key}"))
+            .unwrap();
+    }
+
+    let audit = install_write_audit(&path);
+
+    vault
+        .update_entry("b".to_string(), "changed".to_string())
+        .unwrap();
+    assert_eq!(take_writes(&audit), vec![("update".into(), "b".into())]);
+
+    vault
+        .create_entry("d".to_string(), "new".to_string())
+        .unwrap();
+    assert_eq!(take_writes(&audit), vec![("insert".into(), "d".into())]);
+
+    vault.delete_entry("a").unwrap();
+    assert_eq!(take_writes(&audit), vec![("delete".into(), "a".into())]);
+
+    // A full save leaves unchanged rows alone
+    vault.save().unwrap();
+    assert!(take_writes(&audit).is_empty());
+}
+
+#[test]
+fn test_sqlite_backend_roundtrips_metadata() {
+    let temp_dir = TempDir::new().unwrap();
+    let backend = SqliteBackend::open(&temp_dir.path().join("vault.db")).unwrap();
+    assert!(!backend.exists().unwrap());
+
+    let mut vault = VaultBuilder::new()
+        .backend(Box::new(
+            SqliteBackend::open(&temp_dir.path().join("vault.db")).unwrap(),
+        ))
+        .init("master".to_string())
+        .unwrap();
+    vault
+        .create_entry("github".to_string(), "token".to_string())
+        .unwrap();
+    vault.set_access_tracking(true);
+    vault.access_entry("github").unwrap();
+    drop(vault);
+
+    assert!(backend.exists().unwrap());
+    let db = backend.load().unwrap();
+    assert_eq!(db.entries["github"].access_count, 1);
+    assert!(db.entries["github"].last_accessed.is_some());
+    assert!(db.last_modified.is_some());
+
+    backend.remove().unwrap();
+    assert!(!backend.exists().unwrap());
+}
@@ -0,0 +1,64 @@
+//! Stale Vault Tests
+//!
+//! Tests for the `--max-age` staleness decision and last-modified tracking.
+//! NOTE: These tests must run serially because they share the same database file.
+
+use chrono::{Duration, Utc};
+use ironkey::config::Config;
+use ironkey::storage;
+use ironkey::vault::{Vault, is_stale};
+use std::fs;
+
+mod common;
+
+fn cleanup() {
+    common::use_isolated_database();
+    if let Ok(db_path) = storage::get_database_path() {
+        let _ = fs::remove_file(db_path);
+    }
+}
+
+#[test]
+fn test_recent_vault_is_not_stale() {
+    let now = Utc::now();
+    assert!(!is_stale(now - Duration::days(3), now, 180));
+    assert!(!is_stale(now - Duration::days(180), now, 180));
+}
+
+#[test]
+fn test_old_vault_is_stale() {
+    let now = Utc::now();
+    assert!(is_stale(now - Duration::days(181), now, 180));
+    assert!(is_stale(now - Duration::days(400), now, 30));
+}
+
+#[test]
+fn test_modifications_update_last_modified() {
+    cleanup();
+    let mut vault = Vault::init("master".to_string()).unwrap();
+    let created = vault.last_modified().unwrap();
+
+    vault
+        .create_entry("github".to_string(), "token".to_string())
+        .unwrap();
+    let after_create = vault.last_modified().unwrap();
+    assert!(after_create >= created);
+    drop(vault);
+
+    // Persisted across unlocks
+    let vault = Vault::unlock("master".to_string()).unwrap();
+    assert_eq!(vault.last_modified().unwrap(), after_create);
+
+    cleanup();
+}
+
+#[test]
+fn test_config_max_age_days() {
+    assert_eq!(Config::from_toml_str("").unwrap().max_age_days, None);
+    assert_eq!(
+        Config::from_toml_str("max_age_days = 180")
+            .unwrap()
+            .max_age_days,
+        Some(180)
+    );
+}
@@ -0,0 +1,101 @@
+//! TOTP Tests
+//!
+//! Tests for `totp::generate_totp` and `Totp::parse` against the RFC 6238
+//! appendix B test vectors.
+
+use ironkey::totp::{Totp, TotpAlgorithm, generate_totp};
+
+const SEED_SHA1: &[u8] = b"12345678901234567890";
+const SEED_SHA256: &[u8] = b"12345678901234567890123456789012";
+const SEED_SHA512: &[u8] = b"1234567890123456789012345678901234567890123456789012345678901234";
+
+/// (time, SHA-1 code, SHA-256 code, SHA-512 code)
+const VECTORS: [(u64, &str, &str, &str); 6] = [
+    (59, "94287082", "46119246", "90693936"),
+    (1111111109, "07081804", "68084774", "25091201"),
+    (1111111111, "14050471", "67062674", "99943326"),
+    (1234567890, "89005924", "91819424", "93441116"),
+    (2000000000, "69279037", "90698825", "38618901"),
+    (20000000000, "65353130", "77737706", "47863826"),
+];
+
+fn encode(seed: &[u8]) -> String {
+    base32::encode(base32::Alphabet::Rfc4648 { padding: false }, seed)
+}
+
+fn uri(seed: &[u8], algorithm: &str) -> String {
+    format!(
+        "otpauth://totp/Example:alice@example.com?secret={}&issuer=Example&digits=8&algorithm={algorithm}",
+        encode(seed)
+    )
+}
+
+#[test]
+fn test_rfc6238_sha1_vectors() {
+    let secret = uri(SEED_SHA1, "SHA1");
+    for (time, expected, _, _) in VECTORS {
+        assert_eq!(generate_totp(&secret, time).unwrap(), expected, "t={time}");
+    }
+}
+
+#[test]
+fn test_rfc6238_sha256_vectors() {
+    let secret = uri(SEED_SHA256, "SHA256");
+    for (time, _, expected, _) in VECTORS {
+        assert_eq!(generate_totp(&secret, time).unwrap(), expected, "t={time}");
+    }
+}
+
+#[test]
+fn test_rfc6238_sha512_vectors() {
+    let secret = uri(SEED_SHA512, "SHA512");
+    for (time, _, _, expected) in VECTORS {
+        assert_eq!(generate_totp(&secret, time).unwrap(), expected, "t={time}");
+    }
+}
+
+#[test]
+fn test_bare_base32_secret_uses_six_digit_sha1() {
+    let secret = encode(SEED_SHA1);
+    assert_eq!(generate_totp(&secret, 59).unwrap(), "287082");
+
+    let totp = Totp::parse(&secret).unwrap();
+    assert_eq!(totp.digits, 6);
+    assert_eq!(totp.period, 30);
+    assert_eq!(totp.algorithm, TotpAlgorithm::Sha1);
+}
+
+#[test]
+fn test_secret_formatting_is_ignored() {
+    // Lowercase with spaces, as many sites display the seed
+    let secret = encode(SEED_SHA1).to_lowercase();
+    let spaced: Vec<&str> = secret
+        .as_bytes()
+        .chunks(4)
+        .map(|chunk| std::str::from_utf8(chunk).unwrap())
+        .collect();
+    assert_eq!(generate_totp(&spaced.join(" "), 59).unwrap(), "287082");
+}
+
+#[test]
+fn test_uri_period_and_seconds_remaining() {
+    let totp = Totp::parse(&format!(
+        "otpauth://totp/x?secret={}&period=60",
+        encode(SEED_SHA1)
+    ))
+    .unwrap();
+
+    assert_eq!(totp.period, 60);
+    assert_eq!(totp.seconds_remaining(120), 60);
+    assert_eq!(totp.seconds_remaining(179), 1);
+}
+
+#[test]
+fn test_invalid_secrets_are_rejected() {
+    assert!(generate_totp("", 59).is_err());
+    assert!(generate_totp("not base32!", 59).is_err());
+    assert!(generate_totp("otpauth://totp/x?issuer=Example", 59).is_err());
+    assert!(generate_totp("otpauth://hotp/x?secret=GEZDGNBV", 59).is_err());
+    assert!(generate_totp("otpauth://totp/x?secret=GEZDGNBV&algorithm=MD5", 59).is_err());
+    assert!(generate_totp("otpauth://totp/x?secret=GEZDGNBV&digits=4", 59).is_err());
+}
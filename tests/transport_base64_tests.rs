@@ -0,0 +1,92 @@
+//! Base64 Transport Tests
+//!
+//! Tests for `ik export --stdout-base64` / `ik import --stdin-base64` round-trips.
+//! NOTE: These tests must run serially because they share the same database file.
+
+use ironkey::storage;
+use ironkey::vault::Vault;
+use std::fs;
+
+mod common;
+
+/// Cleanup function
+fn cleanup() {
+    common::use_isolated_database();
+    if let Ok(db_path) = storage::get_database_path() {
+        let _ = fs::remove_file(db_path);
+    }
+}
+
+#[test]
+fn test_base64_export_import_roundtrip() {
+    // Phase 1: Export source vault as base64
+    let encoded = {
+        cleanup();
+        let mut vault = Vault::init("source_master".to_string()).unwrap();
+        vault
+            .create_entry("github".to_string(), "ghp_token123".to_string())
+            .unwrap();
+        vault
+            .create_entry("aws".to_string(), "aws=secret/456+".to_string())
+            .unwrap();
+        vault.toggle_lock("aws").unwrap();
+
+        vault.export_to_base64("transport_pw".to_string()).unwrap()
+    };
+
+    // Output must be a single line of base64
+    assert!(!encoded.contains('\n'));
+    assert!(
+        encoded
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '+' || c == '/' || c == '=')
+    );
+
+    // Phase 2: Pipe the output into a fresh vault (simulate trailing newline from stdin)
+    {
+        cleanup();
+        let mut vault = Vault::init("dest_master".to_string()).unwrap();
+        let piped = format!("{encoded}\n");
+
+        let result = vault
+            .import_from_base64(&piped, "transport_pw".to_string(), true, false, false)
+            .unwrap();
+
+        assert_eq!(result.total_in_export, 2);
+        assert_eq!(result.added.len(), 2);
+        assert_eq!(vault.get_entry("github").unwrap(), "ghp_token123");
+
+        // Lock status preserved
+        let locked = vault.list_entries(None, Some(true)).unwrap();
+        assert_eq!(locked.len(), 1);
+        assert_eq!(locked[0].0, "aws");
+    }
+
+    cleanup();
+}
+
+#[test]
+fn test_base64_import_wrong_password_fails() {
+    cleanup();
+    let mut vault = Vault::init("master".to_string()).unwrap();
+    vault
+        .create_entry("key".to_string(), "value".to_string())
+        .unwrap();
+    let encoded = vault.export_to_base64("right_pw".to_string()).unwrap();
+
+    let result = vault.import_from_base64(&encoded, "wrong_pw".to_string(), true, false, false);
+    assert!(result.is_err());
+
+    cleanup();
+}
+
+#[test]
+fn test_base64_import_rejects_garbage() {
+    cleanup();
+    let mut vault = Vault::init("master".to_string()).unwrap();
+
+    let result = vault.import_from_base64("not base64!!", "pw".to_string(), true, false, false);
+    assert!(result.is_err());
+
+    cleanup();
+}
@@ -0,0 +1,26 @@
+//! Unlock Retry Tests
+//!
+//! Tests for the attempt limit and growing delay used when the master password
+//! prompt is retried after a wrong password.
+
+use ironkey::vault::{MAX_UNLOCK_ATTEMPTS, unlock_retry_delay};
+use std::time::Duration;
+
+#[test]
+fn test_three_attempts() {
+    assert_eq!(MAX_UNLOCK_ATTEMPTS, 3);
+}
+
+#[test]
+fn test_delay_grows_with_each_failure() {
+    let delays: Vec<Duration> = (1..MAX_UNLOCK_ATTEMPTS).map(unlock_retry_delay).collect();
+
+    assert!(delays.windows(2).all(|pair| pair[0] < pair[1]));
+    assert!(delays.iter().all(|delay| !delay.is_zero()));
+}
+
+#[test]
+fn test_delays_stay_short() {
+    let total: Duration = (1..MAX_UNLOCK_ATTEMPTS).map(unlock_retry_delay).sum();
+    assert!(total <= Duration::from_secs(5));
+}
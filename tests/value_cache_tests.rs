@@ -0,0 +1,66 @@
+//! Decrypted Value Cache Tests
+//!
+//! Tests for the per-process cache behind `Vault::get_entry`.
+
+use ironkey::error::Error;
+use ironkey::storage::MemoryBackend;
+
+mod common;
+
+#[test]
+fn test_repeated_reads_are_cached_and_equal() {
+    let vault = common::vault_with_entry(&MemoryBackend::new(), "github", "ghp_token");
+    assert!(!vault.is_value_cached("github"));
+
+    let first = vault.get_entry("github").unwrap();
+    assert!(vault.is_value_cached("github"));
+    let second = vault.get_entry("github").unwrap();
+
+    assert_eq!(first, "ghp_token");
+    assert_eq!(first, second);
+}
+
+#[test]
+fn test_update_invalidates_cached_value() {
+    let mut vault = common::vault_with_entry(&MemoryBackend::new(), "github", "ghp_token");
+    vault.get_entry("github").unwrap();
+
+    vault
+        .update_entry("github".to_string(), "ghp_new".to_string())
+        .unwrap();
+    assert!(!vault.is_value_cached("github"));
+    assert_eq!(vault.get_entry("github").unwrap(), "ghp_new");
+}
+
+#[test]
+fn test_lock_and_delete_invalidate_cached_value() {
+    let mut vault = common::vault_with_entry(&MemoryBackend::new(), "github", "ghp_token");
+    vault.get_entry("github").unwrap();
+
+    // A cached value must not bypass the lock
+    vault.toggle_lock("github").unwrap();
+    assert!(!vault.is_value_cached("github"));
+    assert!(matches!(
+        vault.get_entry("github"),
+        Err(Error::EntryLocked(_))
+    ));
+
+    vault.toggle_lock("github").unwrap();
+    vault.get_entry("github").unwrap();
+    vault.delete_entry("github").unwrap();
+    assert!(!vault.is_value_cached("github"));
+    assert!(matches!(
+        vault.get_entry("github"),
+        Err(Error::EntryNotFound(_))
+    ));
+}
+
+#[test]
+fn test_clear_value_cache() {
+    let vault = common::vault_with_entry(&MemoryBackend::new(), "github", "ghp_token");
+    vault.get_entry("github").unwrap();
+
+    vault.clear_value_cache();
+    assert!(!vault.is_value_cached("github"));
+    assert_eq!(vault.get_entry("github").unwrap(), "ghp_token");
+}
@@ -0,0 +1,139 @@
+//! Vault Builder Tests
+//!
+//! Tests that `VaultBuilder` creates and unlocks vaults at an explicit path with
+//! explicit KDF settings, independent of the CLI's default database location.
+
+use ironkey::crypto::{KdfAlgorithm, KdfParams};
+use ironkey::error::Error;
+use ironkey::storage::{JsonFileBackend, MemoryBackend, StorageBackend};
+use ironkey::vault::VaultBuilder;
+use tempfile::TempDir;
+
+fn stored_kdf(path: &std::path::Path) -> KdfParams {
+    JsonFileBackend::new(path.to_path_buf())
+        .load()
+        .unwrap()
+        .kdf_params()
+        .unwrap()
+}
+
+#[test]
+fn test_init_and_unlock_at_explicit_path() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("gui-vault.json");
+
+    let mut vault = VaultBuilder::new()
+        .path(&path)
+        .init("master".to_string())
+        .unwrap();
+    vault
+        .create_entry("github".to_string(), "gh-token".to_string())
+        .unwrap();
+    drop(vault);
+    assert!(path.exists());
+
+    let vault = VaultBuilder::new()
+        .path(&path)
+        .unlock("master".to_string())
+        .unwrap();
+    assert_eq!(vault.get_entry_unlocked("github").unwrap(), "gh-token");
+}
+
+#[test]
+fn test_iterations_are_stored() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("vault.json");
+
+    VaultBuilder::new()
+        .path(&path)
+        .iterations(150_000)
+        .init("master".to_string())
+        .unwrap();
+
+    assert_eq!(stored_kdf(&path), KdfParams::pbkdf2(150_000));
+}
+
+#[test]
+fn test_iterations_override_kdf_default() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("vault.json");
+
+    VaultBuilder::new()
+        .path(&path)
+        .kdf(KdfParams::default_for(KdfAlgorithm::Argon2id))
+        .iterations(2)
+        .init("master".to_string())
+        .unwrap();
+
+    let kdf = stored_kdf(&path);
+    assert_eq!(kdf.algorithm, KdfAlgorithm::Argon2id);
+    assert_eq!(kdf.iterations, 2);
+}
+
+#[test]
+fn test_defaults_match_vault_init() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("vault.json");
+
+    VaultBuilder::new()
+        .path(&path)
+        .init("master".to_string())
+        .unwrap();
+
+    assert_eq!(
+        stored_kdf(&path),
+        KdfParams::default_for(KdfAlgorithm::Pbkdf2)
+    );
+}
+
+#[test]
+fn test_too_few_iterations_rejected() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("vault.json");
+
+    let result = VaultBuilder::new()
+        .path(&path)
+        .iterations(10)
+        .init("master".to_string());
+
+    assert!(matches!(result, Err(Error::InvalidInput(_))));
+    assert!(!path.exists());
+}
+
+#[test]
+fn test_init_twice_at_same_path_fails() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("vault.json");
+
+    VaultBuilder::new()
+        .path(&path)
+        .init("master".to_string())
+        .unwrap();
+    let result = VaultBuilder::new().path(&path).init("other".to_string());
+
+    assert!(matches!(result, Err(Error::MasterKeyAlreadyExists)));
+}
+
+#[test]
+fn test_unlock_with_wrong_password_fails() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("vault.json");
+
+    VaultBuilder::new()
+        .path(&path)
+        .init("master".to_string())
+        .unwrap();
+    let result = VaultBuilder::new().path(&path).unlock("wrong".to_string());
+
+    assert!(matches!(result, Err(Error::InvalidMasterPassword)));
+}
+
+#[test]
+fn test_custom_backend() {
+    let vault = VaultBuilder::new()
+        .backend(Box::new(MemoryBackend::new()))
+        .init("master".to_string())
+        .unwrap();
+
+    assert!(vault.list_entries(None, None).unwrap().is_empty());
+}
@@ -0,0 +1,67 @@
+//! Verbose Timing Tests
+//!
+//! Tests for `Verbosity` (the `--verbose` flag) and the vault constructors that take it.
+//! Timings only go to stderr, so these check that results pass through unchanged.
+
+use ironkey::crypto::{KdfAlgorithm, KdfParams};
+use ironkey::error::Error;
+use ironkey::storage::MemoryBackend;
+use ironkey::timing::Verbosity;
+use ironkey::vault::VaultBuilder;
+
+#[test]
+fn test_default_is_quiet() {
+    assert_eq!(Verbosity::default(), Verbosity::Quiet);
+    assert!(!Verbosity::default().is_verbose());
+}
+
+#[test]
+fn test_from_flag() {
+    assert_eq!(Verbosity::from_flag(true), Verbosity::Verbose);
+    assert_eq!(Verbosity::from_flag(false), Verbosity::Quiet);
+}
+
+#[test]
+fn test_time_returns_the_result() {
+    assert_eq!(Verbosity::Quiet.time("op", || 42), 42);
+    assert_eq!(Verbosity::Verbose.time("op", || 42), 42);
+
+    let failed: Result<(), &str> = Verbosity::Verbose.time("op", || Err("boom"));
+    assert_eq!(failed, Err("boom"));
+}
+
+#[test]
+fn test_time_runs_the_operation_once() {
+    let mut calls = 0;
+    Verbosity::Verbose.time("op", || calls += 1);
+    Verbosity::Quiet.time("op", || calls += 1);
+    assert_eq!(calls, 2);
+}
+
+#[test]
+fn test_verbose_vault_round_trip() {
+    let backend = MemoryBackend::new();
+    let mut vault = VaultBuilder::new()
+        .backend(Box::new(backend.clone()))
+        .kdf(KdfParams::default_for(KdfAlgorithm::Pbkdf2))
+        .verbosity(Verbosity::Verbose)
+        .init("master".to_string())
+        .unwrap();
+    vault
+        .create_entry("github".to_string(), "ghp_token".to_string())
+        .unwrap();
+    drop(vault);
+
+    let vault = VaultBuilder::new()
+        .backend(Box::new(backend.clone()))
+        .verbosity(Verbosity::Verbose)
+        .unlock("master".to_string())
+        .unwrap();
+    assert_eq!(vault.get_entry("github").unwrap(), "ghp_token");
+
+    let result = VaultBuilder::new()
+        .backend(Box::new(backend))
+        .verbosity(Verbosity::Verbose)
+        .unlock("wrong".to_string());
+    assert!(matches!(result, Err(Error::InvalidMasterPassword)));
+}
@@ -0,0 +1,130 @@
+//! Create/Update Guard Tests
+//!
+//! Tests for `--fail-if-exists` / `--ignore-if-exists` on create and
+//! `--fail-if-missing` / `--create-if-missing` on update.
+//! NOTE: These tests must run serially because they share the same database file.
+
+use ironkey::error::Error;
+use ironkey::storage;
+use ironkey::vault::{OnExists, OnMissing, Vault, WriteOutcome};
+use std::fs;
+
+mod common;
+
+/// Create a fresh vault containing a single entry `existing = old`
+fn setup() -> Vault {
+    common::use_isolated_database();
+    if let Ok(db_path) = storage::get_database_path() {
+        let _ = fs::remove_file(db_path);
+    }
+    let mut vault = Vault::init("master".to_string()).unwrap();
+    vault
+        .create_entry("existing".to_string(), "old".to_string())
+        .unwrap();
+    vault
+}
+
+fn cleanup() {
+    common::use_isolated_database();
+    if let Ok(db_path) = storage::get_database_path() {
+        let _ = fs::remove_file(db_path);
+    }
+}
+
+#[test]
+fn test_create_new_key_with_either_policy() {
+    let mut vault = setup();
+
+    let outcome = vault
+        .create_entry_guarded("a".to_string(), "1".to_string(), OnExists::Fail)
+        .unwrap();
+    assert_eq!(outcome, WriteOutcome::Created);
+
+    let outcome = vault
+        .create_entry_guarded("b".to_string(), "2".to_string(), OnExists::Ignore)
+        .unwrap();
+    assert_eq!(outcome, WriteOutcome::Created);
+    assert_eq!(vault.get_entry("b").unwrap(), "2");
+
+    cleanup();
+}
+
+#[test]
+fn test_create_existing_key_fail_if_exists() {
+    let mut vault = setup();
+
+    let result =
+        vault.create_entry_guarded("existing".to_string(), "new".to_string(), OnExists::Fail);
+    assert!(matches!(result, Err(Error::EntryAlreadyExists(_))));
+    assert_eq!(vault.get_entry("existing").unwrap(), "old");
+
+    cleanup();
+}
+
+#[test]
+fn test_create_existing_key_ignore_if_exists() {
+    let mut vault = setup();
+
+    let outcome = vault
+        .create_entry_guarded("existing".to_string(), "new".to_string(), OnExists::Ignore)
+        .unwrap();
+    assert_eq!(outcome, WriteOutcome::Unchanged);
+    assert_eq!(vault.get_entry("existing").unwrap(), "old");
+
+    cleanup();
+}
+
+#[test]
+fn test_update_existing_key_with_either_policy() {
+    let mut vault = setup();
+
+    let outcome = vault
+        .update_entry_guarded("existing".to_string(), "v2".to_string(), OnMissing::Fail)
+        .unwrap();
+    assert_eq!(outcome, WriteOutcome::Updated);
+
+    let outcome = vault
+        .update_entry_guarded("existing".to_string(), "v3".to_string(), OnMissing::Create)
+        .unwrap();
+    assert_eq!(outcome, WriteOutcome::Updated);
+    assert_eq!(vault.get_entry("existing").unwrap(), "v3");
+
+    cleanup();
+}
+
+#[test]
+fn test_update_missing_key_fail_if_missing() {
+    let mut vault = setup();
+
+    let result =
+        vault.update_entry_guarded("missing".to_string(), "v".to_string(), OnMissing::Fail);
+    assert!(matches!(result, Err(Error::EntryNotFound(_))));
+    assert!(vault.get_entry("missing").is_err());
+
+    cleanup();
+}
+
+#[test]
+fn test_update_missing_key_create_if_missing() {
+    let mut vault = setup();
+
+    let outcome = vault
+        .update_entry_guarded("missing".to_string(), "v".to_string(), OnMissing::Create)
+        .unwrap();
+    assert_eq!(outcome, WriteOutcome::Created);
+    assert_eq!(vault.get_entry("missing").unwrap(), "v");
+
+    cleanup();
+}
+
+#[test]
+fn test_upsert_respects_entry_lock() {
+    let mut vault = setup();
+    vault.toggle_lock("existing").unwrap();
+
+    let result =
+        vault.update_entry_guarded("existing".to_string(), "v".to_string(), OnMissing::Create);
+    assert!(matches!(result, Err(Error::EntryLocked(_))));
+
+    cleanup();
+}